@@ -5,14 +5,27 @@ use crate::constants::games;
 pub(super) const ARG_GAME: &str = "game";
 pub(super) const ARG_RULES: &str = "rules";
 pub(super) const ARG_TEST: &str = "graphicstest";
+pub(super) const ARG_LOAD: &str = "load";
+pub(super) const ARG_PROBLEMS: &str = "problems";
+pub(super) const ARG_USI: &str = "usi";
+pub(super) const ARG_GTP: &str = "gtp";
+pub(super) const ARG_DXP_SERVER: &str = "dxp-server";
+pub(super) const ARG_DXP_CONNECT: &str = "dxp-connect";
 
-const GAMES: [&str; 18] = [
+const GAMES: [&str; 34] = [
     games::TICTACTOE,
-    games::MANCALA,
+    games::MANCALA_KALAH,
+    games::MANCALA_OWARE,
+    games::MANCALA_CONGKAK,
     games::DRAUGHTS_BRAZILIAN,
     games::DRAUGHTS_CANADIAN,
     games::DRAUGHTS_INTERNATIONAL,
     games::DRAUGHTS_ENGLISH,
+    games::DRAUGHTS_RUSSIAN,
+    games::DRAUGHTS_ITALIAN,
+    games::DRAUGHTS_SPANISH,
+    games::DRAUGHTS_TURKISH,
+    games::DRAUGHTS_FRISIAN,
     games::CHESS_CAPABLANCA,
     games::CHESS_PROGRESSIVE,
     games::CHESS_MODERN,
@@ -22,18 +35,20 @@ const GAMES: [&str; 18] = [
     games::CHESS_GRAND,
     games::CHESS_MINI,
     games::CHESS_STANDARD,
-    // games::UR,
-    // games::SHOGI_STANDARD,
-    // games::SHOGI_MINI,
-    // games::SHOGI_MEDIUM,
+    games::UR,
+    games::SHOGI_STANDARD,
+    games::SHOGI_MINI,
+    games::SHOGI_MEDIUM,
     // games::SHOGI_LARGE,
     // games::SHOGI_HUGE,
     games::ORDERCHAOS,
-    // games::BLACKHOLE,
+    games::BLACKHOLE,
     games::SENET,
     games::TABLUT,
-    // games::GO,
-    // games::RITHMOMANCHY,
+    games::GO_SMALL,
+    games::GO_MEDIUM,
+    games::GO_STANDARD,
+    games::RITHMOMANCHY,
 ];
 
 pub(super) fn args_matches() -> ArgMatches {
@@ -53,6 +68,54 @@ pub(super) fn args_matches() -> ArgMatches {
                 .requires("game")
                 .action(ArgAction::SetTrue)
         )
+        .arg(
+            Arg::new(ARG_LOAD)
+                .short('l')
+                .long("load")
+                .help("Continue a game from a record file (KIF or CSA for shogi, SGF for go, PDN for draughts)")
+                .num_args(1)
+                .requires("game")
+                .conflicts_with(ARG_RULES)
+        )
+        .arg(
+            Arg::new(ARG_PROBLEMS)
+                .short('p')
+                .long("problems")
+                .help("Solve the problems in a PDN file, each needs a FEN position and the solution as its moves (draughts only)")
+                .num_args(1)
+                .requires("game")
+                .conflicts_with_all(vec![ARG_RULES, ARG_LOAD])
+        )
+        .arg(
+            Arg::new(ARG_USI)
+                .long("usi")
+                .help("Run the shogi AI as a USI engine over stdin/stdout")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(vec![ARG_RULES, ARG_GAME, ARG_LOAD])
+        )
+        .arg(
+            Arg::new(ARG_GTP)
+                .long("gtp")
+                .help("Run the go AI as a GTP engine over stdin/stdout")
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(vec![ARG_RULES, ARG_GAME, ARG_LOAD, ARG_USI])
+        )
+        .arg(
+            Arg::new(ARG_DXP_SERVER)
+                .long("dxp-server")
+                .value_name("address")
+                .help("Wait for DXP connections (e.g. 127.0.0.1:27531) and play international draughts")
+                .num_args(1)
+                .conflicts_with_all(vec![ARG_RULES, ARG_GAME, ARG_LOAD, ARG_USI, ARG_GTP])
+        )
+        .arg(
+            Arg::new(ARG_DXP_CONNECT)
+                .long("dxp-connect")
+                .value_name("address")
+                .help("Connect to a DXP engine and play international draughts as white")
+                .num_args(1)
+                .conflicts_with_all(vec![ARG_RULES, ARG_GAME, ARG_LOAD, ARG_USI, ARG_GTP, ARG_DXP_SERVER])
+        )
         .arg(
             Arg::new(ARG_TEST)
                 .long("graphicstest")
@@ -0,0 +1,142 @@
+use crate::blackhole::rules::{adjacent_totals, empty_cells, next_tile, result, winner};
+use crate::blackhole::Board;
+use crate::system::Player;
+
+//Once this few cells are empty the rest of the game is solved exactly
+const SOLVE_EMPTIES: usize = 9;
+const SEARCH_DEPTH: usize = 4;
+const WIN: i32 = 10000;
+//Totals are scaled so the heuristic can use fractions of a point
+const SCALE: i32 = 10;
+
+//From the point of view of player, the opponent's total minus theirs is how much they win by
+fn margin(human: usize, computer: usize, player: Player) -> i32 {
+    let diff = human as i32 - computer as i32;
+    match player {
+        Player::Human => -diff,
+        Player::Computer => diff,
+    }
+}
+
+//Average margin over every cell that could still be the hole
+fn evaluate(board: &Board, player: Player) -> i32 {
+    let empty = empty_cells(board);
+    let total: i32 = empty
+        .iter()
+        .map(|idx| {
+            let (human, computer) = adjacent_totals(board, *idx);
+            margin(human, computer, player)
+        })
+        .sum();
+    total * SCALE / empty.len().max(1) as i32
+}
+
+fn negamax(board: &mut Board, to_move: Player, depth: usize, mut alpha: i32, beta: i32) -> i32 {
+    if let Some(result) = result(board) {
+        let margin = margin(result.human, result.computer, to_move) * SCALE;
+        return match winner(&result) {
+            Some(player) if player == to_move => WIN + margin,
+            Some(_) => -WIN + margin,
+            None => 0,
+        };
+    }
+    if depth == 0 {
+        return evaluate(board, to_move);
+    }
+    let tile = next_tile(board, to_move).expect("No tiles left");
+    let mut best = i32::MIN + 1;
+    for idx in empty_cells(board) {
+        board[idx] = Some(tile);
+        let value = -negamax(board, to_move.opposite(), depth - 1, -beta, -alpha);
+        board[idx] = None;
+        best = best.max(value);
+        alpha = alpha.max(value);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+//Cell to place the player's next tile in
+pub(super) fn choose_move(board: &Board, player: Player) -> Option<usize> {
+    let tile = next_tile(board, player)?;
+    let empty = empty_cells(board);
+    let depth = if empty.len() <= SOLVE_EMPTIES {
+        empty.len()
+    } else {
+        SEARCH_DEPTH
+    };
+    let mut board = *board;
+    let mut best = None;
+    let mut alpha = i32::MIN + 1;
+    for idx in empty {
+        board[idx] = Some(tile);
+        let value = -negamax(
+            &mut board,
+            player.opposite(),
+            depth - 1,
+            i32::MIN + 1,
+            -alpha,
+        );
+        board[idx] = None;
+        if best.is_none() || value > alpha {
+            alpha = value;
+            best = Some(idx);
+        }
+    }
+    debug_log!("Placing {} at {:?} ({})", tile.value, best, alpha);
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::blackhole::{Tile, CELLS};
+
+    fn tile(player: Player, value: usize) -> Option<Tile> {
+        Some(Tile { player, value })
+    }
+
+    //Fills the cells taking turns, starting with the human
+    fn fill(cells: &[usize]) -> Board {
+        let mut board: Board = [None; CELLS];
+        for (i, idx) in cells.iter().enumerate() {
+            let player = if i % 2 == 0 {
+                Player::Human
+            } else {
+                Player::Computer
+            };
+            board[*idx] = next_tile(&board, player);
+        }
+        board
+    }
+
+    #[test]
+    fn test_last_tile() {
+        //Cells 0 and 20 are left, the computer's last tile is a 10
+        let cells: Vec<usize> = (1..20).collect();
+        let board = fill(&cells);
+        assert_eq!(
+            next_tile(&board, Player::Computer),
+            tile(Player::Computer, 10)
+        );
+        //Cell 0 is next to 1 (human 1) and 2 (computer 1), cell 20 is next to 14 (computer 7)
+        //and 19 (human 10). Placing at 0 leaves 20 as the hole which the computer wins 7 to 10,
+        //placing at 20 leaves 0 as the hole which is a draw
+        assert_eq!(choose_move(&board, Player::Computer), Some(0));
+    }
+
+    #[test]
+    fn test_solves_endgame() {
+        let cells: Vec<usize> = (0..16).collect();
+        let mut board = fill(&cells);
+        //Five cells left for the human's and computer's 9s and 10s
+        let idx = choose_move(&board, Player::Human).unwrap();
+        assert!(idx >= 16);
+        board[idx] = next_tile(&board, Player::Human);
+        let idx = choose_move(&board, Player::Computer).unwrap();
+        assert!(board[idx].is_none());
+        assert!(choose_move(&fill(&(0..21).collect::<Vec<usize>>()), Player::Human).is_none());
+    }
+}
@@ -0,0 +1,96 @@
+use crate::blackhole::ai::choose_move;
+use crate::blackhole::renderer::render;
+use crate::blackhole::rules::{next_tile, result, winner};
+use crate::blackhole::{State, CELLS, ROWS};
+use crate::boards::tri_cursor::TriCursor;
+use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::PlayState::{ComputerWin, Draw, HumanWin};
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player, Scene, Turn, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
+use ggez::event::KeyCode;
+use ggez::{Context, GameResult};
+
+pub struct Controller {
+    state: State,
+}
+
+impl Controller {
+    pub fn new() -> Self {
+        Controller {
+            state: State {
+                play_state: NEW_TURN_HUMAN,
+                board: [None; CELLS],
+                cursor: TriCursor::new(ROWS),
+                last_human_cursor_pos: 0,
+                next_move_time: AI_MOVE_DELAY,
+                result: None,
+            },
+        }
+    }
+}
+
+impl Controller {
+    fn place_tile(&mut self, player: Player) {
+        let idx = self.state.cursor.idx;
+        let tile = next_tile(&self.state.board, player);
+        debug_log!("Placing {:?} at {}", tile, idx);
+        self.state.board[idx] = tile;
+        if let Some(result) = result(&self.state.board) {
+            debug_log!("Game over: {:?}", result);
+            self.state.result = Some(result);
+            self.state.play_state = match winner(&result) {
+                Some(Player::Human) => HumanWin,
+                Some(Player::Computer) => ComputerWin,
+                None => Draw,
+            };
+            return;
+        }
+        self.state.next_move_time = AI_MOVE_DELAY;
+        self.state.play_state = match player {
+            Player::Human => NEW_TURN_COMPUTER,
+            Player::Computer => NEW_TURN_HUMAN,
+        };
+    }
+}
+
+impl Scene for Controller {
+    fn on_key_down(&mut self, key: KeyCode) {
+        if self.state.play_state.is_human(SelectingPiece)
+            && !self.state.cursor.handle_input(key)
+            && key == KeyCode::Return
+            && self.state.board[self.state.cursor.idx].is_none()
+        {
+            self.place_tile(Player::Human);
+        }
+    }
+
+    fn update(&mut self, delta: f64) -> GameResult<()> {
+        if self.state.play_state.is_computer(SelectingPiece) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                self.state.last_human_cursor_pos = self.state.cursor.idx;
+                if let Some(idx) = choose_move(&self.state.board, Player::Computer) {
+                    self.state.cursor.idx = idx;
+                }
+                self.state.next_move_time = ANIMATION_DURATION;
+                self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove));
+            }
+        } else if self.state.play_state.is_computer(SelectingMove) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                self.place_tile(Player::Computer);
+                self.state.cursor.idx = self.state.last_human_cursor_pos;
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut Context, mesh_helper: &mut MeshHelper) -> GameResult<()> {
+        render(ctx, mesh_helper, &self.state)
+    }
+
+    fn play_state(&self) -> PlayState {
+        self.state.play_state
+    }
+}
@@ -0,0 +1,37 @@
+use crate::boards::tri_cursor::TriCursor;
+use crate::system::{PlayState, Player};
+
+mod ai;
+pub mod controller;
+mod renderer;
+mod rules;
+
+const ROWS: usize = 6;
+const CELLS: usize = 21;
+//Each player places their tiles in order from 1 to TILES
+const TILES: usize = 10;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Tile {
+    player: Player,
+    value: usize,
+}
+
+type Board = [Option<Tile>; CELLS];
+
+//Where the black hole ended up and the total of each player's tiles next to it
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Result {
+    hole: usize,
+    human: usize,
+    computer: usize,
+}
+
+struct State {
+    play_state: PlayState,
+    board: Board,
+    cursor: TriCursor,
+    last_human_cursor_pos: usize,
+    next_move_time: f64,
+    result: Option<Result>,
+}
@@ -0,0 +1,95 @@
+use crate::blackhole::rules::{neighbours, next_tile};
+use crate::blackhole::{State, CELLS, ROWS};
+use crate::boards::tri_coord::TriCoord;
+use crate::constants::colors::{
+    BLACK, LIGHT_BLUE, LIGHT_GRAY, PIECE_COMPUTER, PIECE_HUMAN, RED, WHITE,
+};
+use crate::system::math::{pt, Offset, OffsetTuple};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::Player;
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use ggez::graphics::DrawMode;
+use ggez::{Context, GameResult};
+
+pub(super) fn render(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let cell_size = mesh_helper.calc_height(0.12);
+    let board_start = pt(mesh_helper.calc_width(0.05), mesh_helper.calc_height(0.1));
+    let info_start = pt(mesh_helper.calc_width(0.65), mesh_helper.calc_height(0.2));
+
+    let outline =
+        mesh_helper.make_circle(ctx, cell_size, cell_size * 0.45, DrawMode::stroke(2.))?;
+    let tile = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.42, DrawMode::fill())?;
+    let hole = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.44, DrawMode::fill())?;
+
+    let sucked_in = state
+        .result
+        .map(|result| neighbours(result.hole))
+        .unwrap_or_default();
+
+    for idx in 0..CELLS {
+        let pos = TriCoord::from(idx)
+            .point(ROWS, cell_size)
+            .offset_point(board_start);
+        let ring = if sucked_in.contains(&idx) {
+            RED
+        } else {
+            LIGHT_GRAY
+        };
+        mesh_helper.draw_coloured_mesh(ctx, outline.as_ref(), pos, ring);
+        if let Some(placed) = state.board[idx] {
+            let (colour, text_colour) = match placed.player {
+                Player::Human => (PIECE_HUMAN, BLACK),
+                Player::Computer => (PIECE_COMPUTER, WHITE),
+            };
+            mesh_helper.draw_coloured_mesh(ctx, tile.as_ref(), pos, colour);
+            mesh_helper.draw_text(
+                ctx,
+                &placed.value.to_string(),
+                pos.offset(cell_size * 0.5, cell_size * 0.35),
+                text_colour,
+                24.,
+                true,
+            );
+        } else if state.result.map(|result| result.hole) == Some(idx) {
+            mesh_helper.draw_coloured_mesh(ctx, hole.as_ref(), pos, BLACK);
+            mesh_helper.draw_coloured_mesh(ctx, outline.as_ref(), pos, RED);
+        }
+    }
+
+    if state.play_state.is_human(SelectingPiece) || state.play_state.is_computer(SelectingMove) {
+        state
+            .cursor
+            .render(ctx, mesh_helper, board_start, cell_size)?;
+    }
+
+    let mut lines = vec![];
+    for (player, name) in [(Player::Human, "Your"), (Player::Computer, "Computer's")] {
+        if let Some(next) = next_tile(&state.board, player) {
+            lines.push((format!("{} next tile: {}", name, next.value), WHITE));
+        }
+    }
+    if let Some(result) = state.result {
+        lines.push((
+            format!("Next to the black hole: you {}", result.human),
+            LIGHT_BLUE,
+        ));
+        lines.push((format!("Computer {}", result.computer), LIGHT_BLUE));
+        lines.push((String::from("Lowest total wins"), LIGHT_GRAY));
+    }
+    for (i, (line, colour)) in lines.iter().enumerate() {
+        mesh_helper.draw_text(
+            ctx,
+            line,
+            info_start.offset(0., 30. * i as f32),
+            *colour,
+            20.,
+            false,
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,119 @@
+use crate::blackhole::{Board, Result, Tile, ROWS, TILES};
+use crate::boards::tri_coord::TriCoord;
+use crate::system::Player;
+
+pub(super) fn neighbours(idx: usize) -> Vec<usize> {
+    TriCoord::from(idx)
+        .neighbours(ROWS)
+        .iter()
+        .map(|coord| coord.idx())
+        .collect()
+}
+
+pub(super) fn empty_cells(board: &Board) -> Vec<usize> {
+    (0..board.len())
+        .filter(|idx| board[*idx].is_none())
+        .collect()
+}
+
+//Value of the tile the player places next, None once all of them are placed
+pub(super) fn next_tile(board: &Board, player: Player) -> Option<Tile> {
+    let placed = board
+        .iter()
+        .flatten()
+        .filter(|tile| tile.player == player)
+        .count();
+    if placed < TILES {
+        Some(Tile {
+            player,
+            value: placed + 1,
+        })
+    } else {
+        None
+    }
+}
+
+//Total of each player's tiles next to the cell, as (human, computer)
+pub(super) fn adjacent_totals(board: &Board, idx: usize) -> (usize, usize) {
+    neighbours(idx)
+        .iter()
+        .filter_map(|idx| board[*idx])
+        .fold((0, 0), |(human, computer), tile| match tile.player {
+            Player::Human => (human + tile.value, computer),
+            Player::Computer => (human, computer + tile.value),
+        })
+}
+
+//The game ends when every tile is placed, leaving one empty cell as the black hole
+pub(super) fn result(board: &Board) -> Option<Result> {
+    match empty_cells(board).as_slice() {
+        [hole] => {
+            let (human, computer) = adjacent_totals(board, *hole);
+            Some(Result {
+                hole: *hole,
+                human,
+                computer,
+            })
+        }
+        _ => None,
+    }
+}
+
+//The player with the lowest total next to the hole wins
+pub(super) fn winner(result: &Result) -> Option<Player> {
+    if result.human < result.computer {
+        Some(Player::Human)
+    } else if result.computer < result.human {
+        Some(Player::Computer)
+    } else {
+        None
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::blackhole::CELLS;
+
+    fn tile(player: Player, value: usize) -> Option<Tile> {
+        Some(Tile { player, value })
+    }
+
+    #[test]
+    fn test_next_tile() {
+        let mut board: Board = [None; CELLS];
+        assert_eq!(next_tile(&board, Player::Human), tile(Player::Human, 1));
+        board[4] = tile(Player::Human, 1);
+        board[5] = tile(Player::Computer, 1);
+        assert_eq!(next_tile(&board, Player::Human), tile(Player::Human, 2));
+        assert_eq!(
+            next_tile(&board, Player::Computer),
+            tile(Player::Computer, 2)
+        );
+    }
+
+    #[test]
+    fn test_scoring() {
+        let mut board: Board = [None; CELLS];
+        //Fill all but cell 4 alternating players in order
+        let mut values = [0, 0];
+        for idx in (0..CELLS).filter(|idx| *idx != 4) {
+            let player = if idx % 2 == 0 {
+                Player::Human
+            } else {
+                Player::Computer
+            };
+            let count = &mut values[idx % 2];
+            *count += 1;
+            board[idx] = tile(player, *count);
+        }
+        assert_eq!(next_tile(&board, Player::Human), None);
+        assert_eq!(next_tile(&board, Player::Computer), None);
+        //Neighbours of 4 are 1, 2, 3, 5, 7 and 8
+        let result = result(&board).unwrap();
+        assert_eq!(result.hole, 4);
+        assert_eq!(result.human, 2 + 4);
+        assert_eq!(result.computer, 1 + 2 + 3 + 4);
+        assert_eq!(winner(&result), Some(Player::Human));
+    }
+}
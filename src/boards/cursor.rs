@@ -1,18 +1,38 @@
 use crate::boards::idx_coord::BoardCoord;
 use crate::boards::{board_cols, board_rows};
-use crate::constants::colors::{BLUE, LIGHT_BLUE};
+use crate::constants::colors::{alpha, BLUE, LIGHT_BLUE, LIGHT_GREEN};
 use crate::constants::Direction;
+use crate::system::find_nearest::find_nearest_to;
 use crate::system::ggez_ext::keycode_to_direction;
 use crate::system::math::{Offset, OffsetTuple, Point};
 use crate::system::mesh_helper::MeshHelper;
 use ggez::event::KeyCode;
 use ggez::graphics::{Color, DrawMode};
 use ggez::{Context, GameResult};
+use std::collections::HashMap;
+
+//Switches the arrow keys between jumping between pieces that can move, which are also
+//highlighted, and moving a square at a time
+pub const JUMP_MODE_KEY: KeyCode = KeyCode::M;
+//Cycles through the pieces that can move
+pub const NEXT_PIECE_KEY: KeyCode = KeyCode::Tab;
+
+//Squares with a piece that can move, for handle_movable_input and render_movable
+pub fn movable<M>(moves: &HashMap<usize, Vec<M>>) -> Vec<usize> {
+    let mut squares: Vec<usize> = moves
+        .iter()
+        .filter(|(_, moves)| !moves.is_empty())
+        .map(|(idx, _)| *idx)
+        .collect();
+    squares.sort_unstable();
+    squares
+}
 
 #[derive(Debug, Clone)]
 pub struct Cursor {
     pub idx: usize,
     pub invalid_squares: Vec<usize>,
+    pub jump_mode: bool,
 }
 
 impl Cursor {
@@ -20,6 +40,7 @@ impl Cursor {
         Cursor {
             idx: 0,
             invalid_squares: vec![],
+            jump_mode: true,
         }
     }
 
@@ -27,6 +48,7 @@ impl Cursor {
         Cursor {
             idx: 0,
             invalid_squares,
+            jump_mode: true,
         }
     }
 }
@@ -75,6 +97,38 @@ impl Cursor {
         }
     }
 
+    //As handle_input but also supports JUMP_MODE_KEY and NEXT_PIECE_KEY, movable is
+    //every square with a piece that has a legal move
+    pub fn handle_movable_input(&mut self, key: KeyCode, movable: &[usize]) -> bool {
+        if key == JUMP_MODE_KEY {
+            self.jump_mode = !self.jump_mode;
+            return true;
+        }
+        if key == NEXT_PIECE_KEY {
+            let next = movable
+                .iter()
+                .filter(|idx| **idx > self.idx)
+                .min()
+                .or_else(|| movable.iter().min());
+            if let Some(next) = next {
+                self.idx = *next;
+            }
+            return true;
+        }
+        match keycode_to_direction(key) {
+            Some(dir) if self.jump_mode && !movable.is_empty() => {
+                let nearest = find_nearest_to(movable, BoardCoord::from(self.idx), dir, &|idx| {
+                    BoardCoord::from(*idx)
+                });
+                if let Some(nearest) = nearest {
+                    self.idx = movable[nearest];
+                }
+                true
+            }
+            _ => self.handle_input(key),
+        }
+    }
+
     pub fn point(&self, board_start: Point, cell_size: f32) -> Point {
         Point::from(BoardCoord::from(self.idx))
             .multiply(cell_size, cell_size)
@@ -119,4 +173,71 @@ impl Cursor {
     ) -> GameResult<()> {
         self.draw(ctx, mesh_helper, board_start, cell_size, BLUE)
     }
+
+    //Highlights the pieces that can move while in jump mode
+    pub fn render_movable(
+        &self,
+        ctx: &mut Context,
+        mesh_helper: &mut MeshHelper,
+        board_start: Point,
+        cell_size: f32,
+        movable: &[usize],
+    ) -> GameResult<()> {
+        if !self.jump_mode {
+            return Ok(());
+        }
+        let highlight =
+            mesh_helper.make_rect(ctx, cell_size - 8., cell_size - 8., DrawMode::stroke(2.))?;
+        for idx in movable {
+            let xy = Point::from(BoardCoord::from(*idx))
+                .multiply(cell_size, cell_size)
+                .offset_point(board_start)
+                .offset(4., 4.);
+            mesh_helper.draw_coloured_mesh(ctx, highlight.as_ref(), xy, alpha(LIGHT_GREEN, 0.7));
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_jump_mode() {
+        set_board_size((8, 8));
+        let movable = vec![40, 42, 46, 17];
+        let mut cursor = Cursor::new();
+        cursor.idx = 41;
+
+        //On by default so the arrows jump between movable pieces
+        assert!(cursor.jump_mode);
+        assert!(cursor.handle_movable_input(KeyCode::Right, &movable));
+        assert_eq!(cursor.idx, 42);
+        cursor.handle_movable_input(KeyCode::Right, &movable);
+        assert_eq!(cursor.idx, 46);
+        cursor.handle_movable_input(KeyCode::Up, &movable);
+        assert_eq!(cursor.idx, 17);
+        //Nothing further up so it stays put
+        cursor.handle_movable_input(KeyCode::Up, &movable);
+        assert_eq!(cursor.idx, 17);
+
+        //Turned off the arrows move a square at a time
+        cursor.handle_movable_input(JUMP_MODE_KEY, &movable);
+        assert!(!cursor.jump_mode);
+        cursor.handle_movable_input(KeyCode::Right, &movable);
+        assert_eq!(cursor.idx, 18);
+
+        cursor.handle_movable_input(NEXT_PIECE_KEY, &movable);
+        assert_eq!(cursor.idx, 40);
+        cursor.handle_movable_input(NEXT_PIECE_KEY, &movable);
+        assert_eq!(cursor.idx, 42);
+        cursor.idx = 46;
+        cursor.handle_movable_input(NEXT_PIECE_KEY, &movable);
+        assert_eq!(cursor.idx, 17);
+        assert!(!cursor.handle_movable_input(KeyCode::Return, &movable));
+    }
 }
@@ -2,7 +2,12 @@ pub mod chessboard;
 pub mod cursor;
 pub mod idx_coord;
 pub mod is_in_board;
+pub mod move_entry;
+pub mod multi_char_board_converter;
 pub mod single_char_board_converter;
+pub mod tri_coord;
+pub mod tri_cursor;
+pub mod viewport;
 
 use std::sync::atomic::{AtomicUsize, Ordering};
 
@@ -0,0 +1,238 @@
+use crate::boards::chessboard::ChessBoard;
+use crate::constants::colors::{LIGHT_GRAY, LIGHT_RED, WHITE};
+use crate::system::math::{pt, Offset, Point};
+use crate::system::mesh_helper::MeshHelper;
+use ggez::graphics::{Color, PxScale, Text, TextFragment};
+use ggez::Context;
+
+const TEXT_SIZE: f32 = 18.;
+
+//What a typed entry does once Return is pressed
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum EntryAction<M> {
+    //Puts the cursor on the piece so the player can choose a move for it
+    SelectPiece(usize),
+    PlayMove(M),
+}
+
+//Every text the player can type and what it does, games list each legal move
+//(possibly in more than one notation) and the square of each piece that can move
+pub type EntryOptions<M> = Vec<(String, EntryAction<M>)>;
+
+//Moves typed by the player, e.g. e2e4 or 12-16
+#[derive(Debug, Default, Clone)]
+pub struct MoveEntry {
+    pub text: String,
+    //Rest of the only option the text could become
+    pub completion: Option<String>,
+    pub error: Option<String>,
+}
+
+impl MoveEntry {
+    pub fn new() -> Self {
+        MoveEntry::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.text.clear();
+        self.completion = None;
+        self.error = None;
+    }
+
+    //Adds the character if it can still lead to an option, returns false if it was ignored
+    //(the first character is ignored silently as it may be for a shortcut key)
+    pub fn push<M>(&mut self, chr: char, options: &[(String, EntryAction<M>)]) -> bool {
+        if !(chr.is_ascii_alphanumeric() || chr == '-') {
+            return false;
+        }
+        let text = format!("{}{}", self.text, chr.to_ascii_lowercase());
+        if matching(&text, options).is_empty() {
+            if !self.text.is_empty() {
+                self.error = Some(format!("Nothing starts with {}", text));
+            }
+            return false;
+        }
+        self.text = text;
+        self.error = None;
+        self.update_completion(options);
+        true
+    }
+
+    pub fn pop<M>(&mut self, options: &[(String, EntryAction<M>)]) {
+        self.text.pop();
+        self.error = None;
+        self.update_completion(options);
+    }
+
+    fn update_completion<M>(&mut self, options: &[(String, EntryAction<M>)]) {
+        let matches = matching(&self.text, options);
+        self.completion = match matches.as_slice() {
+            [(option, _)] if !self.text.is_empty() => Some(option[self.text.len()..].to_string()),
+            _ => None,
+        };
+    }
+
+    //Returns the action for the text, if it matches one exactly or only one could be meant,
+    //otherwise explains what's wrong
+    pub fn submit<'a, M>(
+        &mut self,
+        options: &'a [(String, EntryAction<M>)],
+    ) -> Option<&'a EntryAction<M>> {
+        if let Some((_, action)) = options.iter().find(|(option, _)| *option == self.text) {
+            self.clear();
+            return Some(action);
+        }
+        let matches = matching(&self.text, options);
+        match matches.as_slice() {
+            [] => self.error = Some(format!("{} is not a legal move", self.text)),
+            [(_, action)] => {
+                self.clear();
+                return Some(action);
+            }
+            _ => {
+                self.error = Some(format!(
+                    "{} could be {}",
+                    self.text,
+                    matches
+                        .iter()
+                        .map(|(option, _)| option.as_str())
+                        .collect::<Vec<&str>>()
+                        .join(", ")
+                ))
+            }
+        }
+        None
+    }
+
+    //Draws the typed text with the completion after it and any error below
+    pub fn render(&self, ctx: &mut Context, mesh_helper: &mut MeshHelper, xy: Point) {
+        let fragment = |text: String, color: Color| TextFragment {
+            text,
+            color: Some(color),
+            scale: Some(PxScale::from(TEXT_SIZE)),
+            ..TextFragment::default()
+        };
+        let mut line = Text::new(fragment(format!("Move: {}", self.text), WHITE));
+        if let Some(completion) = &self.completion {
+            line.add(fragment(completion.clone(), LIGHT_GRAY));
+        }
+        mesh_helper.draw_mesh(ctx, &line, xy);
+        if let Some(error) = &self.error {
+            mesh_helper.draw_text(ctx, error, xy.offsety(22.), LIGHT_RED, 14., false);
+        }
+    }
+}
+
+//Draws the file letters under the board and the rank numbers to its left, for boards
+//that don't normally show coordinates
+pub fn render_coord_labels(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    coords: &ChessBoard,
+    board_start: Point,
+    cell_size: f32,
+) {
+    let last_row = (coords.rows - 1) * coords.cols;
+    for col in 0..coords.cols {
+        if let Some(name) = coords.idx_to_coord(last_row + col) {
+            let file: String = name
+                .chars()
+                .take_while(|chr| chr.is_ascii_alphabetic())
+                .collect();
+            let xy = board_start.offset(
+                cell_size * (col as f32 + 0.5),
+                cell_size * coords.rows as f32 + 4.,
+            );
+            mesh_helper.draw_text(ctx, &file, xy, LIGHT_GRAY, 12., true);
+        }
+    }
+    for row in 0..coords.rows {
+        if let Some(name) = coords.idx_to_coord(row * coords.cols) {
+            let rank: String = name
+                .chars()
+                .skip_while(|chr| chr.is_ascii_alphabetic())
+                .collect();
+            let xy = pt(
+                board_start.x - 16.,
+                board_start.y + cell_size * (row as f32 + 0.5) - 6.,
+            );
+            mesh_helper.draw_text(ctx, &rank, xy, LIGHT_GRAY, 12., true);
+        }
+    }
+}
+
+//Options that start with the text
+fn matching<'a, M>(
+    text: &str,
+    options: &'a [(String, EntryAction<M>)],
+) -> Vec<&'a (String, EntryAction<M>)> {
+    options
+        .iter()
+        .filter(|(option, _)| option.starts_with(text))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::move_entry::EntryAction::*;
+
+    fn options() -> EntryOptions<usize> {
+        vec![
+            (String::from("e2"), SelectPiece(52)),
+            (String::from("e2e3"), PlayMove(1)),
+            (String::from("e2e4"), PlayMove(2)),
+            (String::from("g1"), SelectPiece(62)),
+            (String::from("g1f3"), PlayMove(3)),
+        ]
+    }
+
+    #[test]
+    fn test_typing() {
+        let options = options();
+        let mut entry = MoveEntry::new();
+        assert!(!entry.push('z', &options));
+        assert_eq!(entry.error, None);
+        assert!(entry.is_empty());
+
+        assert!(entry.push('G', &options));
+        assert_eq!(entry.text, "g");
+        assert_eq!(entry.completion, None);
+        entry.push('1', &options);
+        entry.push('f', &options);
+        assert_eq!(entry.completion, Some(String::from("3")));
+        assert!(!entry.push('z', &options));
+        assert_eq!(entry.error, Some(String::from("Nothing starts with g1fz")));
+        entry.pop(&options);
+        assert_eq!(entry.text, "g1");
+        assert!(!entry.push(' ', &options));
+    }
+
+    #[test]
+    fn test_submit() {
+        let options = options();
+        let mut entry = MoveEntry::new();
+        entry.push('e', &options);
+        entry.push('2', &options);
+        assert_eq!(entry.submit(&options), Some(&SelectPiece(52)));
+        assert!(entry.is_empty());
+
+        "e2e".chars().for_each(|chr| {
+            entry.push(chr, &options);
+        });
+        assert_eq!(entry.submit(&options), None);
+        assert_eq!(entry.error, Some(String::from("e2e could be e2e3, e2e4")));
+        entry.push('4', &options);
+        assert_eq!(entry.submit(&options), Some(&PlayMove(2)));
+
+        //Only one move can be meant so it doesn't have to be typed in full
+        entry.push('g', &options);
+        entry.push('1', &options);
+        entry.push('f', &options);
+        assert_eq!(entry.submit(&options), Some(&PlayMove(3)));
+    }
+}
@@ -0,0 +1,357 @@
+use crate::boards::CoordIdxConverter;
+use crate::system::math::idx_to_coord;
+
+const LETTERS: usize = 26;
+
+//This treats the board as starting with 0 in the top left corner
+//Ranks can have any number of digits and after z the files continue aa, ab, ... az, ba, etc
+#[derive(Debug)]
+pub struct MultiCharBoardConverter {
+    rows: usize,
+    cols: usize,
+}
+
+impl MultiCharBoardConverter {
+    pub fn new(rows: usize, cols: usize) -> Self {
+        MultiCharBoardConverter { rows, cols }
+    }
+}
+
+impl CoordIdxConverter for MultiCharBoardConverter {
+    fn is_valid_coord(&self, alpha: &str, num: &str) -> bool {
+        let file = match file_to_col(alpha) {
+            Some(file) => file,
+            None => return false,
+        };
+        //Leading zeros would give each square more than one name
+        if num.is_empty() || num.starts_with('0') || !num.chars().all(|chr| chr.is_ascii_digit()) {
+            return false;
+        }
+        match num.parse::<usize>() {
+            Ok(rank) => file < self.cols && rank > 0 && rank <= self.rows,
+            Err(_) => false,
+        }
+    }
+
+    fn coord_to_idx(&self, alpha: &str, num: &str) -> usize {
+        let col = file_to_col(alpha).expect("Invalid file");
+        let rank: usize = num.parse().expect("Invalid rank");
+        col + (self.rows - rank) * self.cols
+    }
+
+    fn idx_to_coord(&self, idx: usize) -> (String, String) {
+        let (x, y) = idx_to_coord(idx, self.cols);
+        (col_to_file(x), (self.rows - y).to_string())
+    }
+}
+
+//Files are numbered like spreadsheet columns, a is 0, z is 25 and aa is 26
+fn file_to_col(alpha: &str) -> Option<usize> {
+    if alpha.is_empty() || !alpha.chars().all(|chr| chr.is_ascii_alphabetic()) {
+        return None;
+    }
+    alpha
+        .to_ascii_lowercase()
+        .bytes()
+        .try_fold(0_usize, |value, chr| {
+            value
+                .checked_mul(LETTERS)?
+                .checked_add((chr - b'a') as usize + 1)
+        })
+        .map(|value| value - 1)
+}
+
+fn col_to_file(col: usize) -> String {
+    let mut file = vec![];
+    let mut value = col + 1;
+    while value > 0 {
+        value -= 1;
+        file.push((b'a' + (value % LETTERS) as u8) as char);
+        value /= LETTERS;
+    }
+    file.iter().rev().collect()
+}
+
+#[allow(non_snake_case)]
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    lazy_static! {
+        static ref CONVERTER_10: MultiCharBoardConverter = MultiCharBoardConverter::new(10, 10);
+        static ref CONVERTER_12: MultiCharBoardConverter = MultiCharBoardConverter::new(12, 12);
+        static ref CONVERTER_19: MultiCharBoardConverter = MultiCharBoardConverter::new(19, 19);
+        static ref CONVERTER_30: MultiCharBoardConverter = MultiCharBoardConverter::new(3, 30);
+    }
+
+    #[test]
+    fn test_files() {
+        let files = vec![
+            (0, "a"),
+            (8, "i"),
+            (25, "z"),
+            (26, "aa"),
+            (27, "ab"),
+            (51, "az"),
+            (52, "ba"),
+            (701, "zz"),
+            (702, "aaa"),
+        ];
+
+        for (col, file) in files {
+            assert_eq!(col_to_file(col), file);
+            assert_eq!(file_to_col(file), Some(col), "{}", file);
+        }
+        assert_eq!(file_to_col("AB"), Some(27));
+        assert_eq!(file_to_col(""), None);
+        assert_eq!(file_to_col("a1"), None);
+    }
+
+    //
+    //CONVERTER_10
+    //
+
+    #[test]
+    fn test_valid_coords_10() {
+        let coords = vec![
+            ("a", "1"),
+            ("a", "9"),
+            ("a", "10"),
+            ("j", "1"),
+            ("j", "10"),
+            ("e", "5"),
+            ("A", "10"),
+            ("J", "1"),
+        ];
+
+        for coord in coords {
+            assert!(CONVERTER_10.is_valid_coord(coord.0, coord.1), "{:?}", coord);
+        }
+    }
+
+    #[test]
+    fn test_invalid_coords_10() {
+        let coords = vec![
+            ("a", "0"),
+            ("a", "11"),
+            ("a", "01"),
+            ("a", ""),
+            ("k", "1"),
+            ("aa", "5"),
+            ("Ä", "6"),
+            ("", "3"),
+            ("a", "1a"),
+        ];
+
+        for coord in coords {
+            assert!(
+                !CONVERTER_10.is_valid_coord(coord.0, coord.1),
+                "{:?}",
+                coord
+            );
+        }
+    }
+
+    #[test]
+    fn test_coord_to_idx_10() {
+        assert_eq!(CONVERTER_10.coord_to_idx("a", "10"), 0);
+        assert_eq!(CONVERTER_10.coord_to_idx("a", "1"), 90);
+        assert_eq!(CONVERTER_10.coord_to_idx("j", "10"), 9);
+        assert_eq!(CONVERTER_10.coord_to_idx("j", "1"), 99);
+        assert_eq!(CONVERTER_10.coord_to_idx("c", "5"), 52);
+    }
+
+    #[test]
+    fn test_idx_to_coord_10() {
+        assert_eq!(
+            CONVERTER_10.idx_to_coord(0),
+            (String::from("a"), String::from("10"))
+        );
+        assert_eq!(
+            CONVERTER_10.idx_to_coord(90),
+            (String::from("a"), String::from("1"))
+        );
+        assert_eq!(
+            CONVERTER_10.idx_to_coord(9),
+            (String::from("j"), String::from("10"))
+        );
+        assert_eq!(
+            CONVERTER_10.idx_to_coord(99),
+            (String::from("j"), String::from("1"))
+        );
+        assert_eq!(
+            CONVERTER_10.idx_to_coord(52),
+            (String::from("c"), String::from("5"))
+        );
+    }
+
+    //
+    //CONVERTER_12
+    //
+
+    #[test]
+    fn test_valid_coords_12() {
+        let coords = vec![
+            ("a", "1"),
+            ("a", "12"),
+            ("l", "1"),
+            ("l", "12"),
+            ("f", "11"),
+            ("L", "10"),
+        ];
+
+        for coord in coords {
+            assert!(CONVERTER_12.is_valid_coord(coord.0, coord.1), "{:?}", coord);
+        }
+    }
+
+    #[test]
+    fn test_invalid_coords_12() {
+        let coords = vec![
+            ("a", "0"),
+            ("a", "13"),
+            ("m", "1"),
+            ("l", "012"),
+            ("ll", "12"),
+        ];
+
+        for coord in coords {
+            assert!(
+                !CONVERTER_12.is_valid_coord(coord.0, coord.1),
+                "{:?}",
+                coord
+            );
+        }
+    }
+
+    #[test]
+    fn test_coord_to_idx_12() {
+        assert_eq!(CONVERTER_12.coord_to_idx("a", "12"), 0);
+        assert_eq!(CONVERTER_12.coord_to_idx("a", "1"), 132);
+        assert_eq!(CONVERTER_12.coord_to_idx("l", "12"), 11);
+        assert_eq!(CONVERTER_12.coord_to_idx("l", "1"), 143);
+        assert_eq!(CONVERTER_12.coord_to_idx("f", "11"), 17);
+    }
+
+    #[test]
+    fn test_idx_to_coord_12() {
+        assert_eq!(
+            CONVERTER_12.idx_to_coord(0),
+            (String::from("a"), String::from("12"))
+        );
+        assert_eq!(
+            CONVERTER_12.idx_to_coord(132),
+            (String::from("a"), String::from("1"))
+        );
+        assert_eq!(
+            CONVERTER_12.idx_to_coord(143),
+            (String::from("l"), String::from("1"))
+        );
+        assert_eq!(
+            CONVERTER_12.idx_to_coord(17),
+            (String::from("f"), String::from("11"))
+        );
+    }
+
+    //
+    //CONVERTER_19
+    //
+
+    #[test]
+    fn test_valid_coords_19() {
+        let coords = vec![
+            ("a", "1"),
+            ("a", "19"),
+            ("s", "1"),
+            ("s", "19"),
+            ("k", "10"),
+        ];
+
+        for coord in coords {
+            assert!(CONVERTER_19.is_valid_coord(coord.0, coord.1), "{:?}", coord);
+        }
+    }
+
+    #[test]
+    fn test_invalid_coords_19() {
+        let coords = vec![("a", "0"), ("a", "20"), ("t", "1"), ("aa", "1")];
+
+        for coord in coords {
+            assert!(
+                !CONVERTER_19.is_valid_coord(coord.0, coord.1),
+                "{:?}",
+                coord
+            );
+        }
+    }
+
+    #[test]
+    fn test_coord_to_idx_19() {
+        assert_eq!(CONVERTER_19.coord_to_idx("a", "19"), 0);
+        assert_eq!(CONVERTER_19.coord_to_idx("a", "1"), 342);
+        assert_eq!(CONVERTER_19.coord_to_idx("s", "1"), 360);
+        assert_eq!(CONVERTER_19.coord_to_idx("k", "10"), 181);
+    }
+
+    #[test]
+    fn test_idx_to_coord_19() {
+        assert_eq!(
+            CONVERTER_19.idx_to_coord(0),
+            (String::from("a"), String::from("19"))
+        );
+        assert_eq!(
+            CONVERTER_19.idx_to_coord(360),
+            (String::from("s"), String::from("1"))
+        );
+        assert_eq!(
+            CONVERTER_19.idx_to_coord(181),
+            (String::from("k"), String::from("10"))
+        );
+    }
+
+    //
+    //CONVERTER_30
+    //
+
+    #[test]
+    fn test_valid_coords_30() {
+        let coords = vec![("a", "1"), ("z", "3"), ("aa", "1"), ("AD", "3")];
+
+        for coord in coords {
+            assert!(CONVERTER_30.is_valid_coord(coord.0, coord.1), "{:?}", coord);
+        }
+    }
+
+    #[test]
+    fn test_invalid_coords_30() {
+        let coords = vec![("ae", "1"), ("ba", "1"), ("a", "4"), ("a-", "1")];
+
+        for coord in coords {
+            assert!(
+                !CONVERTER_30.is_valid_coord(coord.0, coord.1),
+                "{:?}",
+                coord
+            );
+        }
+    }
+
+    #[test]
+    fn test_coord_to_idx_30() {
+        assert_eq!(CONVERTER_30.coord_to_idx("a", "3"), 0);
+        assert_eq!(CONVERTER_30.coord_to_idx("z", "3"), 25);
+        assert_eq!(CONVERTER_30.coord_to_idx("aa", "3"), 26);
+        assert_eq!(CONVERTER_30.coord_to_idx("ad", "1"), 89);
+    }
+
+    #[test]
+    fn test_idx_to_coord_30() {
+        assert_eq!(
+            CONVERTER_30.idx_to_coord(26),
+            (String::from("aa"), String::from("3"))
+        );
+        assert_eq!(
+            CONVERTER_30.idx_to_coord(89),
+            (String::from("ad"), String::from("1"))
+        );
+    }
+}
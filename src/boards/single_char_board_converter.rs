@@ -5,19 +5,22 @@ const ASCII_A: u8 = 97;
 const ASCII_0: u8 = 48;
 
 //This treats the board as starting with 0 in the top left corner
-//It only supports up to a 9x9 board (i.e. 1-9 and a-i)
+//It only supports up to 9 rows and 26 columns (i.e. 1-9 and a-z), bigger boards
+//need MultiCharBoardConverter
 #[derive(Debug)]
 pub struct SingleCharBoardConverter {
     rows: u8,
     cols: u8,
 }
 
-//TODO Implement properly to support any simple board
 impl SingleCharBoardConverter {
     pub fn new(rows: usize, cols: usize) -> Self {
-        // if rows > 9 || cols > 9 {
-        //     panic!("SingleCharBoardConverter only supports up to 9x9");
-        // }
+        if rows > 9 || cols > 26 {
+            panic!(
+                "SingleCharBoardConverter only supports up to 9 rows and 26 columns, not {}x{}",
+                rows, cols
+            );
+        }
         SingleCharBoardConverter {
             rows: rows as u8,
             cols: cols as u8,
@@ -0,0 +1,96 @@
+//Coords for triangular boards made of circles, row y has y + 1 cells
+//The single dimen index counts along each row from the top:
+//      0
+//     1 2
+//    3 4 5
+//   6 7 8 9
+//Each cell touches up to six others, two in its row, two above and two below
+
+use crate::system::math::{pt, Point};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct TriCoord(pub usize, pub usize);
+
+//Number of cells on a board with this many rows
+pub fn tri_cell_count(rows: usize) -> usize {
+    rows * (rows + 1) / 2
+}
+
+impl TriCoord {
+    pub(crate) fn idx(&self) -> usize {
+        tri_cell_count(self.1) + self.0
+    }
+
+    pub(crate) fn is_valid(&self, rows: usize) -> bool {
+        self.1 < rows && self.0 <= self.1
+    }
+
+    pub(crate) fn neighbours(&self, rows: usize) -> Vec<TriCoord> {
+        let (x, y) = (self.0 as isize, self.1 as isize);
+        [
+            (x - 1, y - 1),
+            (x, y - 1),
+            (x - 1, y),
+            (x + 1, y),
+            (x, y + 1),
+            (x + 1, y + 1),
+        ]
+        .iter()
+        .filter(|(x, y)| *x >= 0 && *y >= 0)
+        .map(|(x, y)| TriCoord(*x as usize, *y as usize))
+        .filter(|coord| coord.is_valid(rows))
+        .collect()
+    }
+
+    //Top left of the cell, rows are centred and spaced so the circles touch
+    pub(crate) fn point(&self, rows: usize, cell_size: f32) -> Point {
+        let indent = (rows - 1 - self.1) as f32 * cell_size * 0.5;
+        pt(
+            indent + self.0 as f32 * cell_size,
+            self.1 as f32 * cell_size * 0.87,
+        )
+    }
+}
+
+impl From<usize> for TriCoord {
+    fn from(idx: usize) -> Self {
+        let mut y = 0;
+        while tri_cell_count(y + 1) <= idx {
+            y += 1;
+        }
+        TriCoord(idx - tri_cell_count(y), y)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_idx() {
+        assert_eq!(tri_cell_count(6), 21);
+        assert_eq!(TriCoord::from(0), TriCoord(0, 0));
+        assert_eq!(TriCoord::from(2), TriCoord(1, 1));
+        assert_eq!(TriCoord::from(3), TriCoord(0, 2));
+        assert_eq!(TriCoord::from(20), TriCoord(5, 5));
+        for idx in 0..21 {
+            assert_eq!(TriCoord::from(idx).idx(), idx);
+        }
+        assert!(TriCoord(2, 2).is_valid(6));
+        assert!(!TriCoord(3, 2).is_valid(6));
+        assert!(!TriCoord(0, 6).is_valid(6));
+    }
+
+    #[test]
+    fn test_neighbours() {
+        let idxs = |coord: TriCoord| -> Vec<usize> {
+            let mut list: Vec<usize> = coord.neighbours(6).iter().map(|c| c.idx()).collect();
+            list.sort_unstable();
+            list
+        };
+        assert_eq!(idxs(TriCoord(0, 0)), vec![1, 2]);
+        assert_eq!(idxs(TriCoord(1, 2)), vec![1, 2, 3, 5, 7, 8]);
+        assert_eq!(idxs(TriCoord(5, 5)), vec![14, 19]);
+        assert_eq!(idxs(TriCoord(0, 5)), vec![10, 16]);
+    }
+}
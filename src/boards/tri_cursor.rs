@@ -0,0 +1,96 @@
+use crate::boards::tri_coord::TriCoord;
+use crate::constants::colors::LIGHT_BLUE;
+use crate::constants::Direction;
+use crate::system::ggez_ext::keycode_to_direction;
+use crate::system::math::{OffsetTuple, Point};
+use crate::system::mesh_helper::MeshHelper;
+use ggez::event::KeyCode;
+use ggez::graphics::DrawMode;
+use ggez::{Context, GameResult};
+
+//Cursor for triangular boards, left and right move along the row (wrapping), up and down move
+//between rows keeping the column where possible
+#[derive(Debug, Clone)]
+pub struct TriCursor {
+    pub idx: usize,
+    rows: usize,
+}
+
+impl TriCursor {
+    pub fn new(rows: usize) -> Self {
+        TriCursor { idx: 0, rows }
+    }
+}
+
+impl TriCursor {
+    fn move_selection(&mut self, dir: Direction) {
+        let TriCoord(x, y) = TriCoord::from(self.idx);
+        let coord = match dir {
+            Direction::Up if y == 0 => TriCoord(x, self.rows - 1),
+            Direction::Up => TriCoord(x.min(y - 1), y - 1),
+            Direction::Down if y == self.rows - 1 => TriCoord(0, 0),
+            Direction::Down => TriCoord(x, y + 1),
+            Direction::Left if x == 0 => TriCoord(y, y),
+            Direction::Left => TriCoord(x - 1, y),
+            Direction::Right if x == y => TriCoord(0, y),
+            Direction::Right => TriCoord(x + 1, y),
+        };
+        self.idx = coord.idx();
+    }
+
+    pub fn handle_input(&mut self, key: KeyCode) -> bool {
+        if let Some(dir) = keycode_to_direction(key) {
+            self.move_selection(dir);
+            true
+        } else {
+            false
+        }
+    }
+
+    pub fn point(&self, board_start: Point, cell_size: f32) -> Point {
+        TriCoord::from(self.idx)
+            .point(self.rows, cell_size)
+            .offset_point(board_start)
+    }
+
+    pub fn render(
+        &self,
+        ctx: &mut Context,
+        mesh_helper: &mut MeshHelper,
+        board_start: Point,
+        cell_size: f32,
+    ) -> GameResult<()> {
+        let selection =
+            mesh_helper.make_circle(ctx, cell_size, cell_size * 0.48, DrawMode::stroke(4.))?;
+        mesh_helper.draw_coloured_mesh(
+            ctx,
+            selection.as_ref(),
+            self.point(board_start, cell_size),
+            LIGHT_BLUE,
+        );
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_movement() {
+        let mut cursor = TriCursor::new(6);
+        assert!(cursor.handle_input(KeyCode::Up));
+        assert_eq!(cursor.idx, 15);
+        cursor.handle_input(KeyCode::Left);
+        assert_eq!(cursor.idx, 20);
+        cursor.handle_input(KeyCode::Up);
+        assert_eq!(cursor.idx, 14);
+        cursor.handle_input(KeyCode::Right);
+        assert_eq!(cursor.idx, 10);
+        cursor.handle_input(KeyCode::Down);
+        assert_eq!(cursor.idx, 15);
+        cursor.handle_input(KeyCode::Down);
+        assert_eq!(cursor.idx, 0);
+        assert!(!cursor.handle_input(KeyCode::Return));
+    }
+}
@@ -0,0 +1,143 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::{board_cols, board_rows};
+use ggez::event::KeyCode;
+
+pub const ZOOM_IN_KEY: KeyCode = KeyCode::Equals;
+pub const ZOOM_OUT_KEY: KeyCode = KeyCode::Minus;
+//Fewest cells shown along each side when zoomed in
+const MIN_VISIBLE: usize = 5;
+
+//The part of a board that is drawn, for boards too large to show at a readable size
+//x and y are the top left visible cell
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Viewport {
+    pub x: usize,
+    pub y: usize,
+    visible: usize,
+}
+
+impl Viewport {
+    //Shows up to max_visible cells along each side, starting in the top left
+    pub fn new(max_visible: usize) -> Self {
+        Viewport {
+            x: 0,
+            y: 0,
+            visible: max_visible.max(MIN_VISIBLE),
+        }
+    }
+
+    pub fn cols(&self) -> usize {
+        self.visible.min(board_cols())
+    }
+
+    pub fn rows(&self) -> usize {
+        self.visible.min(board_rows())
+    }
+
+    //Cells along the longest visible side, used to pick the cell size
+    pub fn span(&self) -> usize {
+        self.cols().max(self.rows())
+    }
+
+    pub fn is_whole_board(&self) -> bool {
+        self.cols() == board_cols() && self.rows() == board_rows()
+    }
+
+    pub fn contains(&self, idx: usize) -> bool {
+        let BoardCoord(x, y) = BoardCoord::from(idx);
+        x >= self.x && x < self.x + self.cols() && y >= self.y && y < self.y + self.rows()
+    }
+
+    //Scrolls the least amount needed to show idx
+    pub fn follow(&mut self, idx: usize) {
+        let BoardCoord(x, y) = BoardCoord::from(idx);
+        self.x = scroll(self.x, self.cols(), x);
+        self.y = scroll(self.y, self.rows(), y);
+    }
+
+    pub fn zoom_in(&mut self, focus: usize) {
+        self.visible = (self.span() - 1).max(MIN_VISIBLE);
+        self.clamp(focus);
+    }
+
+    pub fn zoom_out(&mut self, focus: usize) {
+        self.visible = (self.span() + 1).min(board_cols().max(board_rows()));
+        self.clamp(focus);
+    }
+
+    //Returns true if the key was used, focus is kept visible
+    pub fn handle_input(&mut self, key: KeyCode, focus: usize) -> bool {
+        match key {
+            ZOOM_IN_KEY => self.zoom_in(focus),
+            ZOOM_OUT_KEY => self.zoom_out(focus),
+            _ => return false,
+        }
+        true
+    }
+
+    fn clamp(&mut self, focus: usize) {
+        self.x = self.x.min(board_cols() - self.cols());
+        self.y = self.y.min(board_rows() - self.rows());
+        self.follow(focus);
+    }
+}
+
+fn scroll(start: usize, len: usize, target: usize) -> usize {
+    if target < start {
+        target
+    } else if target >= start + len {
+        target + 1 - len
+    } else {
+        start
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_small_board_is_all_visible() {
+        set_board_size((9, 9));
+        let viewport = Viewport::new(12);
+        assert!(viewport.is_whole_board());
+        assert_eq!(viewport.span(), 9);
+        assert!((0..81).all(|idx| viewport.contains(idx)));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_follow() {
+        set_board_size((25, 25));
+        let mut viewport = Viewport::new(10);
+        assert!(!viewport.is_whole_board());
+        assert!(viewport.contains(BoardCoord(9, 9).idx()));
+        assert!(!viewport.contains(BoardCoord(10, 0).idx()));
+        viewport.follow(BoardCoord(12, 3).idx());
+        assert_eq!((viewport.x, viewport.y), (3, 0));
+        viewport.follow(BoardCoord(0, 24).idx());
+        assert_eq!((viewport.x, viewport.y), (0, 15));
+        assert!(viewport.contains(BoardCoord(0, 24).idx()));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_zoom() {
+        set_board_size((25, 25));
+        let mut viewport = Viewport::new(6);
+        let corner = BoardCoord(24, 24).idx();
+        viewport.follow(corner);
+        viewport.zoom_in(corner);
+        viewport.zoom_in(corner);
+        assert_eq!(viewport.span(), MIN_VISIBLE);
+        assert!(viewport.contains(corner));
+        for _ in 0..30 {
+            viewport.zoom_out(corner);
+        }
+        assert!(viewport.is_whole_board());
+        assert_eq!((viewport.x, viewport.y), (0, 0));
+    }
+}
@@ -0,0 +1,141 @@
+use crate::chess::controller::Controller;
+use crate::chess::{Move, State};
+use crate::system::PlayState::Playing;
+use crate::system::Turn::Computer;
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player};
+use itertools::Itertools;
+use rand::{thread_rng, Rng};
+
+pub(super) fn process(controller: &mut Controller, delta: f64) {
+    if controller.state.play_state.is_computer(SelectingPiece) {
+        controller.state.next_move_time -= delta;
+        if controller.state.next_move_time < 0. {
+            controller.state.last_human_cursor_pos = controller.state.piece_cursor.idx;
+            debug_log!("Starting computer move");
+            let all_moves: Vec<Move> = controller
+                .state
+                .all_possible_moves
+                .values()
+                .flatten()
+                .cloned()
+                .collect();
+            if all_moves.is_empty() {
+                let state = &controller.state;
+                let in_check = state
+                    .game_type
+                    .is_king_in_check(&state.board, Player::Computer);
+                debug_log!("Computer has no moves, in check: {}", in_check);
+                controller.state.play_state = if in_check {
+                    PlayState::HumanWin
+                } else {
+                    PlayState::Draw
+                };
+                return;
+            }
+
+            let mov = book_move(&controller.state, &all_moves)
+                .unwrap_or_else(|| fallback_move(&controller.state, &all_moves));
+
+            controller.state.piece_cursor.idx = mov.from;
+            let (idx, _) = controller
+                .state
+                .get_moves_for_selected_piece()
+                .iter()
+                .find_position(|&other| other == &mov)
+                .unwrap();
+            controller.state.move_cursor = idx;
+            debug_log!("Will play {}", mov);
+            controller.state.next_move_time = 0.5;
+            controller.state.play_state = Playing(Computer(SelectingMove))
+        }
+    }
+    if controller.state.play_state.is_computer(SelectingMove) {
+        controller.state.next_move_time -= delta;
+        if controller.state.next_move_time < 0. {
+            let mov = controller.state.get_selected_move();
+            debug_log!("Now playing {}", mov);
+            controller.process_move(&mov);
+            controller.state.piece_cursor.idx = controller.state.last_human_cursor_pos;
+        }
+    }
+}
+
+fn book_move(state: &State, all_moves: &[Move]) -> Option<Move> {
+    let book = state.opening_book.as_ref()?;
+    let mov = book.choose_move(&state.played_moves(), all_moves);
+    if let Some(mov) = &mov {
+        debug_log!("Using book move {}", mov);
+    }
+    mov
+}
+
+//Takes the most valuable piece available, otherwise moves randomly
+fn fallback_move(state: &State, all_moves: &[Move]) -> Move {
+    let map = all_moves.iter().into_group_map_by(|mov| {
+        state.board[mov.to]
+            .get_piece()
+            .map(|piece| state.game_type.get_piece_value(&piece))
+            .unwrap_or(0)
+    });
+    let highest_value = map.keys().max().unwrap();
+    let best_moves = &map[highest_value];
+    debug_log!(
+        "{} best moves available, value: {}",
+        best_moves.len(),
+        highest_value
+    );
+    best_moves[thread_rng().gen_range(0..best_moves.len())].clone()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::chess::rules::ChessPiece::{King, Queen};
+    use crate::chess::Square::{Computer as C, Empty as E, Human as H};
+    use crate::constants::games;
+    use crate::system::NEW_TURN_COMPUTER;
+    use serial_test::serial;
+
+    fn play_with_king_only(queen: usize, king: usize) -> PlayState {
+        let mut controller = Controller::new(games::CHESS_STANDARD);
+        let state = &mut controller.state;
+        state.board = vec![E; 64];
+        state.board[0] = C(King);
+        state.board[queen] = H(Queen);
+        state.board[king] = H(King);
+        state.all_possible_moves = state
+            .game_type
+            .calc_valid_moves(&state.board, Player::Computer);
+        state.play_state = NEW_TURN_COMPUTER;
+        process(&mut controller, 10.);
+        controller.state.play_state
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_no_moves() {
+        //Queen on b7 protected by the king on c6
+        assert_eq!(play_with_king_only(9, 18), PlayState::HumanWin);
+        //Queen on c7 covers every square around the king but not a8
+        assert_eq!(play_with_king_only(10, 63), PlayState::Draw);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_book_reply() {
+        let mut controller = Controller::new(games::CHESS_STANDARD);
+        controller.process_move(&Move::new(52, 36));
+        assert_eq!(
+            controller.state.opening.as_deref(),
+            Some("King's Pawn Opening")
+        );
+        process(&mut controller, 10.);
+        process(&mut controller, 10.);
+        let reply = controller.state.played_moves()[1].clone();
+        //The computer replies with one of the book's answers to e4
+        let book = controller.state.opening_book.as_ref().unwrap();
+        let first = [Move::new(52, 36)];
+        assert!(book.candidates(&first).iter().any(|(mov, _)| mov == &reply));
+    }
+}
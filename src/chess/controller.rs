@@ -1,9 +1,14 @@
-use crate::boards::cursor::Cursor;
+use crate::boards::chessboard::ChessBoard;
+use crate::boards::cursor::{movable, Cursor};
 use crate::boards::idx_coord::BoardCoord;
+use crate::boards::move_entry::{EntryAction, MoveEntry};
+use crate::boards::multi_char_board_converter::MultiCharBoardConverter;
 use crate::boards::set_board_size;
-use crate::chess::game_types::get_chess_game_type;
+use crate::chess::ai::process;
+use crate::chess::game_types::{get_chess_game_type, GameType};
+use crate::chess::openings::OpeningBook;
 use crate::chess::renderer::render;
-use crate::chess::{Move, State};
+use crate::chess::{Move, PieceStyle, State};
 use crate::constants::AI_MOVE_DELAY;
 use crate::system::find_nearest::find_nearest;
 use crate::system::ggez_ext::keycode_to_direction;
@@ -15,8 +20,10 @@ use ggez::event::KeyCode;
 use ggez::{Context, GameResult};
 use std::collections::HashMap;
 
+pub(super) const PIECE_STYLE_KEY: KeyCode = KeyCode::F2;
+
 pub(crate) struct Controller {
-    state: State,
+    pub(super) state: State,
 }
 
 impl Controller {
@@ -26,6 +33,15 @@ impl Controller {
 
         debug_log!("Game board set to {:?}", game_type.get_board_size());
 
+        let opening_book = if game_type == GameType::Standard {
+            OpeningBook::load()
+        } else {
+            None
+        };
+
+        let (rows, cols) = game_type.get_board_size();
+        let converter = MultiCharBoardConverter::new(rows, cols);
+
         let mut captured = HashMap::new();
         captured.insert(Player::Human, Vec::new());
         captured.insert(Player::Computer, Vec::new());
@@ -43,6 +59,11 @@ impl Controller {
                 last_human_cursor_pos: 0,
                 captured,
                 moves_left_this_turn: 0,
+                piece_style: PieceStyle::Glyphs,
+                opening_book,
+                opening: None,
+                coords: ChessBoard::new(Box::new(converter), rows, cols),
+                entry: MoveEntry::new(),
             },
         }
     }
@@ -65,16 +86,16 @@ impl Controller {
             .calc_valid_moves(&self.state.board, player);
     }
 
-    fn process_move(&mut self, mov: &Move) {
+    pub(super) fn process_move(&mut self, mov: &Move) {
         let player = self.state.board[mov.from]
             .get_player()
             .expect("Attempted to process move for non existent piece");
 
-        self.state
-            .game_type
-            .process_move(&mut self.state.board, mov);
+        let game_type = self.state.game_type.clone();
+        game_type.play_move(&mut self.state, mov);
+        self.update_opening();
 
-        self.state.moves_left_this_turn -= 1;
+        self.state.moves_left_this_turn = self.state.moves_left_this_turn.saturating_sub(1);
         if self.state.moves_left_this_turn == 0 {
             self.start_new_turn(player.opposite(), true)
         } else {
@@ -82,6 +103,32 @@ impl Controller {
         }
     }
 
+    fn update_opening(&mut self) {
+        if let Some(book) = &self.state.opening_book {
+            if let Some(name) = book.name_opening(&self.state.played_moves()) {
+                self.state.opening = Some(name.to_string());
+            }
+        }
+    }
+
+    //Return and Back while a move is being typed
+    fn handle_entry_key(&mut self, key: KeyCode) {
+        let options = self.state.entry_options();
+        if key == KeyCode::Back {
+            self.state.entry.pop(&options);
+            return;
+        }
+        match self.state.entry.submit(&options) {
+            Some(EntryAction::SelectPiece(idx)) => {
+                self.state.piece_cursor.idx = *idx;
+                self.state.move_cursor = 0;
+                self.state.play_state = PlayState::Playing(Turn::Human(SelectingMove));
+            }
+            Some(EntryAction::PlayMove(mov)) => self.process_move(mov),
+            None => {}
+        }
+    }
+
     fn select_piece(&mut self) {
         let highlighted_piece = self.state.board[self.state.piece_cursor.idx];
         if highlighted_piece.get_player() == Some(Human)
@@ -105,8 +152,17 @@ impl Controller {
 
 impl Scene for Controller {
     fn on_key_down(&mut self, key: KeyCode) {
+        if key == PIECE_STYLE_KEY {
+            self.state.piece_style = self.state.piece_style.next();
+            return;
+        }
+        if !self.state.entry.is_empty() && (key == KeyCode::Return || key == KeyCode::Back) {
+            self.handle_entry_key(key);
+            return;
+        }
         if self.state.play_state.is_human(SelectingPiece) {
-            if self.state.piece_cursor.handle_input(key) {
+            let movable = movable(&self.state.all_possible_moves);
+            if self.state.piece_cursor.handle_movable_input(key, &movable) {
                 self.state.move_cursor = 0;
             } else if let KeyCode::Return = key {
                 self.select_piece()
@@ -144,6 +200,10 @@ impl Scene for Controller {
     }
 
     fn on_key_up(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Escape && !self.state.entry.is_empty() {
+            self.state.entry.clear();
+            return true;
+        }
         if self.state.play_state.is_human(SelectingMove) {
             if let KeyCode::Escape = key {
                 self.state.play_state = NEW_TURN_HUMAN;
@@ -154,12 +214,22 @@ impl Scene for Controller {
         false
     }
 
-    fn update(&mut self, _: f64) -> GameResult<()> {
+    fn on_keyboard_entry(&mut self, input: char) {
+        if self.state.play_state.is_human(SelectingPiece)
+            || self.state.play_state.is_human(SelectingMove)
+        {
+            let options = self.state.entry_options();
+            self.state.entry.push(input, &options);
+        }
+    }
+
+    fn update(&mut self, delta: f64) -> GameResult<()> {
         self.check_board_size();
 
         if self.state.play_state == PlayState::Init {
             self.start_new_turn(Human, true);
         }
+        process(self, delta);
 
         Ok(())
     }
@@ -1,4 +1,4 @@
-use crate::chess::rules::ChessPiece;
+use crate::chess::rules::{calc_attacks, ChessPiece};
 use crate::chess::{Board, Move, MoveFlags, PastMove, Square, State};
 use crate::constants::games::*;
 use crate::system::Player;
@@ -101,15 +101,13 @@ impl GameType {
             .get_piece()
             .expect("Attempted to play move for non existent piece");
 
-        // if self == &GameType::HOSTAGE {
-        //     if let Some(piece) = state.board[mov.to].get_piece() {
-        //         state
-        //             .captured
-        //             .get(&player.opposite())
-        //             .expect("Missing capture for player")
-        //             .push(piece)
-        //     }
-        // }
+        if let Some(captured) = state.board[mov.to].get_piece() {
+            state
+                .captured
+                .get_mut(&player)
+                .expect("Missing capture list for player")
+                .push(captured);
+        }
 
         self.process_move(&mut state.board, mov);
 
@@ -162,9 +160,17 @@ impl GameType {
 
     pub(super) fn is_king_in_check(&self, board: &Board, player_to_check: Player) -> bool {
         if self == &GameType::Checkless {
-            false
-        } else {
-            false //TODO calc
+            return false;
+        }
+        let king = board.iter().position(|square| {
+            square.get_player() == Some(player_to_check)
+                && square.get_piece() == Some(ChessPiece::King)
+        });
+        match king {
+            Some(king) => (0..self.get_board_cell_count())
+                .filter(|idx| board[*idx].get_player() == Some(player_to_check.opposite()))
+                .any(|idx| calc_attacks(self, board, idx).contains(&king)),
+            None => false,
         }
     }
 
@@ -221,26 +227,15 @@ mod init {
     #[rustfmt::skip]
     lazy_static! {
         pub(super) static ref CHESS_STANDARD: Board = vec![
+            C(R), C(N), C(B), C(Q), C(K), C(B), C(N), C(R),
+            C(P), C(P), C(P), C(P), C(P), C(P), C(P), C(P),
             E, E, E, E, E, E, E, E,
             E, E, E, E, E, E, E, E,
             E, E, E, E, E, E, E, E,
-            E, C(Q), E, E, E, E, E, E,
-            E, E, E, E, E, E, E, E,
-            E, E, E, H(Q), E, E, E, E,
-            E, E, E, E, E, E, E, E,
             E, E, E, E, E, E, E, E,
+            H(P), H(P), H(P), H(P), H(P), H(P), H(P), H(P),
+            H(R), H(N), H(B), H(Q), H(K), H(B), H(N), H(R)
         ];
-        
-        // pub(super) static ref CHESS_STANDARD: Board = vec![
-        //     C(R), C(N), C(B), C(Q), C(K), C(B), C(N), C(R),
-        //     C(P), C(P), C(P), C(P), C(P), C(P), C(P), C(P),
-        //     E, E, E, E, E, E, E, E,
-        //     E, E, E, E, E, E, E, E,
-        //     E, E, E, E, E, E, E, E,
-        //     E, E, E, E, E, E, E, E,
-        //     H(P), H(P), H(P), H(P), H(P), H(P), H(P), H(P),
-        //     H(R), H(N), H(B), H(Q), H(K), H(B), H(N), H(R)
-        // ];
 
         pub(super) static ref CHESS_MODERN: Board = vec![
             C(R), C(N), C(B), C(Q), C(K), C(KB), C(B), C(N), C(R),
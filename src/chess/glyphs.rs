@@ -0,0 +1,159 @@
+use crate::chess::rules::ChessPiece;
+use crate::constants::colors::WHITE;
+use crate::constants::TOLERANCE;
+use crate::system::math::pt;
+use crate::system::mesh_helper::MeshHelper;
+use ggez::graphics::{DrawMode, Mesh, MeshBuilder};
+use ggez::{Context, GameResult};
+use std::rc::Rc;
+
+const OUTLINE_THICKNESS: f32 = 0.025;
+
+//Glyphs are described in a unit square (0,0 top left to 1,1 bottom right)
+//and scaled to the cell size when the mesh is built
+enum Shape {
+    Polygon(&'static [(f32, f32)]),
+    Circle(f32, f32, f32),
+}
+
+#[rustfmt::skip]
+const BASE: Shape = Shape::Polygon(&[(0.2, 0.9), (0.8, 0.9), (0.75, 0.8), (0.25, 0.8)]);
+
+#[rustfmt::skip]
+const PAWN: [Shape; 3] = [
+    BASE,
+    Shape::Polygon(&[(0.35, 0.8), (0.65, 0.8), (0.56, 0.42), (0.44, 0.42)]),
+    Shape::Circle(0.5, 0.32, 0.13),
+];
+
+#[rustfmt::skip]
+const ROOK: [Shape; 3] = [
+    BASE,
+    Shape::Polygon(&[(0.3, 0.8), (0.7, 0.8), (0.66, 0.4), (0.34, 0.4)]),
+    Shape::Polygon(&[
+        (0.25, 0.42), (0.75, 0.42), (0.75, 0.18), (0.65, 0.18), (0.65, 0.27), (0.56, 0.27),
+        (0.56, 0.18), (0.44, 0.18), (0.44, 0.27), (0.35, 0.27), (0.35, 0.18), (0.25, 0.18),
+    ]),
+];
+
+#[rustfmt::skip]
+const KNIGHT: [Shape; 2] = [
+    BASE,
+    Shape::Polygon(&[
+        (0.3, 0.8), (0.72, 0.8), (0.7, 0.55), (0.66, 0.35), (0.58, 0.2), (0.5, 0.14),
+        (0.46, 0.22), (0.3, 0.36), (0.24, 0.48), (0.32, 0.54), (0.46, 0.45), (0.4, 0.62),
+    ]),
+];
+
+#[rustfmt::skip]
+const BISHOP: [Shape; 4] = [
+    BASE,
+    Shape::Polygon(&[(0.42, 0.8), (0.58, 0.8), (0.55, 0.54), (0.45, 0.54)]),
+    Shape::Polygon(&[
+        (0.5, 0.17), (0.6, 0.26), (0.65, 0.38), (0.6, 0.55), (0.4, 0.55), (0.35, 0.38),
+        (0.4, 0.26),
+    ]),
+    Shape::Circle(0.5, 0.13, 0.05),
+];
+
+#[rustfmt::skip]
+const QUEEN: [Shape; 7] = [
+    BASE,
+    Shape::Polygon(&[
+        (0.28, 0.8), (0.72, 0.8), (0.8, 0.3), (0.68, 0.52), (0.65, 0.25), (0.57, 0.5),
+        (0.5, 0.2), (0.43, 0.5), (0.35, 0.25), (0.32, 0.52), (0.2, 0.3),
+    ]),
+    Shape::Circle(0.2, 0.27, 0.045),
+    Shape::Circle(0.35, 0.22, 0.045),
+    Shape::Circle(0.5, 0.17, 0.045),
+    Shape::Circle(0.65, 0.22, 0.045),
+    Shape::Circle(0.8, 0.27, 0.045),
+];
+
+#[rustfmt::skip]
+const KING: [Shape; 5] = [
+    BASE,
+    Shape::Polygon(&[(0.3, 0.8), (0.7, 0.8), (0.66, 0.45), (0.34, 0.45)]),
+    Shape::Polygon(&[(0.28, 0.46), (0.72, 0.46), (0.62, 0.34), (0.38, 0.34)]),
+    Shape::Polygon(&[(0.46, 0.35), (0.54, 0.35), (0.54, 0.08), (0.46, 0.08)]),
+    Shape::Polygon(&[(0.38, 0.15), (0.62, 0.15), (0.62, 0.22), (0.38, 0.22)]),
+];
+
+//(shapes, scale, x offset, y offset)
+type GlyphPart = (&'static [Shape], f32, f32, f32);
+
+fn glyph_parts(piece: &ChessPiece) -> Vec<GlyphPart> {
+    match piece {
+        ChessPiece::Pawn => vec![(&PAWN, 1., 0., 0.)],
+        ChessPiece::Rook => vec![(&ROOK, 1., 0., 0.)],
+        ChessPiece::Knight => vec![(&KNIGHT, 1., 0., 0.)],
+        ChessPiece::Bishop => vec![(&BISHOP, 1., 0., 0.)],
+        ChessPiece::Queen => vec![(&QUEEN, 1., 0., 0.)],
+        ChessPiece::King => vec![(&KING, 1., 0., 0.)],
+        //Compound pieces are drawn as a knight with a smaller copy of the other piece beside it
+        ChessPiece::KnightBishop => vec![(&KNIGHT, 0.8, 0., 0.18), (&BISHOP, 0.55, 0.45, 0.)],
+        ChessPiece::KnightRook => vec![(&KNIGHT, 0.8, 0., 0.18), (&ROOK, 0.55, 0.45, 0.)],
+    }
+}
+
+pub(super) fn make_piece_mesh(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    size: f32,
+    piece: &ChessPiece,
+) -> GameResult<Rc<Mesh>> {
+    mesh_helper.make_mesh(
+        ctx,
+        format!("chess_glyph_{:?}_{}", piece, size),
+        &(|builder| build_glyph(builder, size, piece, DrawMode::fill())),
+    )
+}
+
+pub(super) fn make_piece_outline_mesh(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    size: f32,
+    piece: &ChessPiece,
+) -> GameResult<Rc<Mesh>> {
+    mesh_helper.make_mesh(
+        ctx,
+        format!("chess_glyph_outline_{:?}_{}", piece, size),
+        &(|builder| {
+            build_glyph(
+                builder,
+                size,
+                piece,
+                DrawMode::stroke(size * OUTLINE_THICKNESS),
+            )
+        }),
+    )
+}
+
+fn build_glyph(
+    builder: &mut MeshBuilder,
+    size: f32,
+    piece: &ChessPiece,
+    mode: DrawMode,
+) -> GameResult<()> {
+    for (shapes, scale, x, y) in glyph_parts(piece) {
+        let transform = |(px, py): (f32, f32)| pt((px * scale + x) * size, (py * scale + y) * size);
+        for shape in shapes {
+            match shape {
+                Shape::Polygon(points) => {
+                    let points: Vec<_> = points.iter().map(|point| transform(*point)).collect();
+                    builder.polygon(mode, &points, WHITE)?;
+                }
+                Shape::Circle(cx, cy, radius) => {
+                    builder.circle(
+                        mode,
+                        transform((*cx, *cy)),
+                        radius * scale * size,
+                        TOLERANCE,
+                        WHITE,
+                    )?;
+                }
+            }
+        }
+    }
+    Ok(())
+}
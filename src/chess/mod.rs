@@ -2,14 +2,20 @@ use std::collections::HashMap;
 use std::fmt;
 use std::fmt::{Display, Formatter};
 
-use crate::boards::cursor::Cursor;
+use crate::boards::chessboard::ChessBoard;
+use crate::boards::cursor::{movable, Cursor};
+use crate::boards::move_entry::{EntryAction, EntryOptions, MoveEntry};
 use crate::chess::game_types::GameType;
+use crate::chess::openings::OpeningBook;
 use crate::chess::rules::ChessPiece;
 use crate::system::Player::{Computer, Human};
 use crate::system::{PlayState, Player};
 
+mod ai;
 pub mod controller;
 mod game_types;
+mod glyphs;
+mod openings;
 mod renderer;
 mod rules;
 
@@ -40,6 +46,21 @@ impl Square {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum PieceStyle {
+    Letters,
+    Glyphs,
+}
+
+impl PieceStyle {
+    fn next(&self) -> PieceStyle {
+        match self {
+            PieceStyle::Letters => PieceStyle::Glyphs,
+            PieceStyle::Glyphs => PieceStyle::Letters,
+        }
+    }
+}
+
 #[derive(Debug)]
 enum MoveFlags {
     EnPassant,
@@ -77,6 +98,11 @@ struct State {
     all_possible_moves: HashMap<usize, Vec<Move>>,
     captured: HashMap<Player, Vec<ChessPiece>>,
     moves_left_this_turn: usize,
+    piece_style: PieceStyle,
+    opening_book: Option<OpeningBook>,
+    opening: Option<String>,
+    coords: ChessBoard,
+    entry: MoveEntry,
 }
 
 #[derive(Debug, Clone, Eq, PartialEq)]
@@ -98,6 +124,27 @@ impl Move {
 }
 
 impl State {
+    //Squares and moves the player can type, moves can be written as e2e4 or e2-e4
+    fn entry_options(&self) -> EntryOptions<Move> {
+        let name = |idx: usize| self.coords.idx_to_coord(idx).unwrap_or_default();
+        let mut options = vec![];
+        for origin in movable(&self.all_possible_moves) {
+            options.push((name(origin), EntryAction::SelectPiece(origin)));
+            for mov in &self.all_possible_moves[&origin] {
+                let (from, to) = (name(mov.from), name(mov.to));
+                options.push((
+                    format!("{}{}", from, to),
+                    EntryAction::PlayMove(mov.clone()),
+                ));
+                options.push((
+                    format!("{}-{}", from, to),
+                    EntryAction::PlayMove(mov.clone()),
+                ));
+            }
+        }
+        options
+    }
+
     //Safe, will return empty vec for empty/other players squares
     fn get_moves_for_selected_piece(&self) -> Vec<Move> {
         self.all_possible_moves
@@ -118,4 +165,23 @@ impl State {
             })
             .clone()
     }
+
+    fn played_moves(&self) -> Vec<Move> {
+        self.move_history
+            .iter()
+            .map(|past| Move::new(past.start, past.end))
+            .collect()
+    }
+
+    fn captured_value(&self, player: Player) -> usize {
+        self.captured[&player]
+            .iter()
+            .map(|piece| self.game_type.get_piece_value(piece))
+            .sum()
+    }
+
+    //Positive if the human has captured more material than the computer
+    fn material_balance(&self) -> isize {
+        self.captured_value(Human) as isize - self.captured_value(Computer) as isize
+    }
 }
@@ -0,0 +1,202 @@
+use crate::boards::single_char_board_converter::SingleCharBoardConverter;
+use crate::boards::CoordIdxConverter;
+use crate::chess::Move;
+use crate::system::resources::read_resource;
+use rand::distributions::WeightedIndex;
+use rand::prelude::Distribution;
+use rand::thread_rng;
+
+const BOOK_FILE: &str = "chess_openings.txt";
+const BOOK_SIZE: usize = 8;
+
+#[derive(Debug)]
+struct OpeningLine {
+    name: String,
+    weight: usize,
+    moves: Vec<Move>,
+}
+
+//Text based book, see resources/chess_openings.txt for the format
+//Only used for standard chess as the moves are for an 8x8 board
+#[derive(Debug)]
+pub(super) struct OpeningBook {
+    lines: Vec<OpeningLine>,
+}
+
+impl OpeningBook {
+    pub(super) fn load() -> Option<OpeningBook> {
+        let text = read_resource(BOOK_FILE)?;
+        match OpeningBook::parse(&text) {
+            Ok(book) => {
+                debug_log!("Loaded {} opening lines", book.lines.len());
+                Some(book)
+            }
+            Err(err) => {
+                debug_log!("Unable to parse opening book: {}", err);
+                None
+            }
+        }
+    }
+
+    fn parse(text: &str) -> Result<OpeningBook, String> {
+        let converter = SingleCharBoardConverter::new(BOOK_SIZE, BOOK_SIZE);
+        let mut lines = vec![];
+        for (line_num, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let parts: Vec<&str> = line.split('|').collect();
+            if parts.len() != 3 {
+                return Err(format!("Line {} must be weight|name|moves", line_num + 1));
+            }
+            let weight = parts[0]
+                .trim()
+                .parse::<usize>()
+                .map_err(|_| format!("Line {} has invalid weight '{}'", line_num + 1, parts[0]))?;
+            let moves = parts[2]
+                .split_whitespace()
+                .map(|text| {
+                    parse_move(&converter, text)
+                        .ok_or_else(|| format!("Line {} has invalid move '{}'", line_num + 1, text))
+                })
+                .collect::<Result<Vec<Move>, String>>()?;
+            if moves.is_empty() {
+                return Err(format!("Line {} has no moves", line_num + 1));
+            }
+            lines.push(OpeningLine {
+                name: parts[1].trim().to_string(),
+                weight,
+                moves,
+            });
+        }
+        Ok(OpeningBook { lines })
+    }
+
+    //Returns each move that continues a line from history with its combined weight
+    pub(super) fn candidates(&self, history: &[Move]) -> Vec<(Move, usize)> {
+        let mut results: Vec<(Move, usize)> = vec![];
+        for line in &self.lines {
+            if line.moves.len() > history.len() && line.moves.starts_with(history) {
+                let mov = &line.moves[history.len()];
+                match results.iter_mut().find(|(existing, _)| existing == mov) {
+                    Some((_, weight)) => *weight += line.weight,
+                    None => results.push((mov.clone(), line.weight)),
+                }
+            }
+        }
+        results
+    }
+
+    //Picks a book move, weighted random, limited to moves that are currently legal
+    pub(super) fn choose_move(&self, history: &[Move], legal_moves: &[Move]) -> Option<Move> {
+        let candidates: Vec<(Move, usize)> = self
+            .candidates(history)
+            .into_iter()
+            .filter(|(mov, weight)| *weight > 0 && legal_moves.contains(mov))
+            .collect();
+        if candidates.is_empty() {
+            return None;
+        }
+        let dist = WeightedIndex::new(candidates.iter().map(|(_, weight)| *weight)).ok()?;
+        Some(candidates[dist.sample(&mut thread_rng())].0.clone())
+    }
+
+    //The name of the longest line that has been played in full
+    pub(super) fn name_opening(&self, history: &[Move]) -> Option<&str> {
+        self.lines
+            .iter()
+            .filter(|line| history.starts_with(&line.moves))
+            .max_by_key(|line| line.moves.len())
+            .map(|line| line.name.as_str())
+    }
+}
+
+fn parse_move(converter: &SingleCharBoardConverter, text: &str) -> Option<Move> {
+    if text.len() != 4 || !text.is_ascii() {
+        return None;
+    }
+    let square = |coord: &str| {
+        let (alpha, num) = coord.split_at(1);
+        if converter.is_valid_coord(alpha, num) {
+            Some(converter.coord_to_idx(alpha, num))
+        } else {
+            None
+        }
+    };
+    Some(Move::new(square(&text[0..2])?, square(&text[2..4])?))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const BOOK: &str = "# comment\n\
+        4|Open Game|e2e4 e7e5\n\
+        \n\
+        2|Sicilian Defence|e2e4 c7c5\n\
+        1|Italian Game|e2e4 e7e5 g1f3 b8c6 f1c4\n";
+
+    #[test]
+    fn test_parse_move() {
+        let converter = SingleCharBoardConverter::new(8, 8);
+        assert_eq!(parse_move(&converter, "e2e4"), Some(Move::new(52, 36)));
+        assert_eq!(parse_move(&converter, "a8h1"), Some(Move::new(0, 63)));
+        assert_eq!(parse_move(&converter, "e2e9"), None);
+        assert_eq!(parse_move(&converter, "e2"), None);
+        assert_eq!(parse_move(&converter, "i2e4"), None);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(OpeningBook::parse("Open Game|e2e4").is_err());
+        assert!(OpeningBook::parse("x|Open Game|e2e4").is_err());
+        assert!(OpeningBook::parse("1|Open Game|e2e4 e7").is_err());
+        assert!(OpeningBook::parse("1|Open Game|").is_err());
+    }
+
+    #[test]
+    fn test_candidates() {
+        let book = OpeningBook::parse(BOOK).unwrap();
+        let e4 = Move::new(52, 36);
+
+        assert_eq!(book.candidates(&[]), vec![(e4.clone(), 7)]);
+        assert_eq!(
+            book.candidates(std::slice::from_ref(&e4)),
+            vec![(Move::new(12, 28), 5), (Move::new(10, 26), 2)]
+        );
+        assert!(book.candidates(&[e4, Move::new(10, 26)]).is_empty());
+    }
+
+    #[test]
+    fn test_choose_move_only_legal() {
+        let book = OpeningBook::parse(BOOK).unwrap();
+        let history = vec![Move::new(52, 36)];
+        let legal = vec![Move::new(10, 26), Move::new(8, 16)];
+
+        for _ in 0..10 {
+            assert_eq!(book.choose_move(&history, &legal), Some(Move::new(10, 26)));
+        }
+        assert_eq!(book.choose_move(&history, &[Move::new(8, 16)]), None);
+    }
+
+    #[test]
+    fn test_name_opening() {
+        let book = OpeningBook::parse(BOOK).unwrap();
+        let mut history = vec![Move::new(52, 36)];
+        assert_eq!(book.name_opening(&history), None);
+        history.push(Move::new(12, 28));
+        assert_eq!(book.name_opening(&history), Some("Open Game"));
+        history.push(Move::new(62, 45));
+        assert_eq!(book.name_opening(&history), Some("Open Game"));
+        history.push(Move::new(1, 18));
+        history.push(Move::new(61, 34));
+        assert_eq!(book.name_opening(&history), Some("Italian Game"));
+    }
+
+    #[test]
+    fn test_resource_book_parses() {
+        let text = read_resource(BOOK_FILE).expect("Missing opening book");
+        assert!(!OpeningBook::parse(&text).unwrap().lines.is_empty());
+    }
+}
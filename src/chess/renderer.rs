@@ -1,22 +1,27 @@
+use crate::boards::cursor::movable;
 use crate::boards::idx_coord::BoardCoord;
 use crate::boards::{board_cols, board_rows};
 use crate::constants::colors::{
-    alpha, APRICOT, BLACK, COPPER, CREAM, DARK_GREEN, LIGHT_BLUE, PIECE_COMPUTER, PIECE_HUMAN,
-    TRANSPARENT, WHITE,
+    alpha, APRICOT, BLACK, COPPER, CREAM, DARK_GREEN, LIGHT_BLUE, LIGHT_GRAY, PIECE_COMPUTER,
+    PIECE_HUMAN, TRANSPARENT, WHITE,
 };
 
-use crate::chess::State;
+use crate::chess::glyphs::{make_piece_mesh, make_piece_outline_mesh};
+use crate::chess::rules::ChessPiece;
+use crate::chess::{PieceStyle, State};
+use crate::system::captured_panel::{self, CAPTURED_PIECE_SIZE};
 use crate::system::letter_mesh::make_letter_mesh;
-use crate::system::math::{pt, pt_usize, Point, Offset, OffsetTuple};
+use crate::system::math::{pt, Point, Offset, OffsetTuple};
 use crate::system::mesh_helper::MeshHelper;
 use crate::system::Player;
 use crate::system::TurnState::{SelectingMove, SelectingPiece};
-use ggez::graphics::{Color, DrawMode, Mesh};
+use ggez::graphics::{Color, DrawMode};
 use ggez::{Context, GameResult};
-use std::rc::Rc;
 
 pub const HUMAN_PIECE: Color = WHITE;
 pub const COMPUTER_PIECE: Color = BLACK;
+//Limited so the move list doesn't run into the captured pieces panel
+const HISTORY_LINES: usize = 60;
 
 pub(super) fn render(
     ctx: &mut Context,
@@ -70,21 +75,11 @@ pub(super) fn render(
     for x in 0..board_cols() {
         for y in 0..board_rows() {
             let square = state.board[x + y * board_cols()];
-            if let Some(piece) = square.get_piece() {
-                let mesh = make_letter_mesh(
-                    ctx,
-                    mesh_helper,
-                    cell_size,
-                    state.game_type.get_piece_letter(&piece),
-                )?;
-                let pt = pt_usize(x, y)
+            if let (Some(piece), Some(player)) = (square.get_piece(), square.get_player()) {
+                let xy = Point::from(BoardCoord(x, y))
                     .multiply(cell_size, cell_size)
                     .offset_point(board_start);
-                let colour = match square.get_player().unwrap() {
-                    Player::Human => HUMAN_PIECE,
-                    Player::Computer => COMPUTER_PIECE,
-                };
-                mesh_helper.draw_coloured_mesh(ctx, mesh.as_ref(), pt, colour);
+                draw_chess_piece(ctx, mesh_helper, state, &piece, player, xy, cell_size)?;
             }
         }
     }
@@ -97,6 +92,13 @@ pub(super) fn render(
     // });
 
     if state.play_state.is_human(SelectingPiece) {
+        state.piece_cursor.render_movable(
+            ctx,
+            mesh_helper,
+            board_start,
+            cell_size,
+            &movable(&state.all_possible_moves),
+        )?;
         state
             .piece_cursor
             .render(ctx, mesh_helper, board_start, cell_size)?;
@@ -142,28 +144,109 @@ pub(super) fn render(
         .collect::<Vec<String>>()
         .join("\n");
 
-    let offset = if state.move_history.len() > 80 {
-        (state.move_history.len() - 80) as f32 * 12.
+    let offset = if state.move_history.len() > HISTORY_LINES {
+        (state.move_history.len() - HISTORY_LINES) as f32 * 12.
     } else {
         0.
     };
 
     mesh_helper.draw_white_text(ctx, &moves, pt(8., 8. - offset), 12., false);
 
+    draw_captured_panel(ctx, mesh_helper, state)?;
+
+    if state.play_state.is_human(SelectingPiece) || state.play_state.is_human(SelectingMove) {
+        state
+            .entry
+            .render(ctx, mesh_helper, pt(8., mesh_helper.height - 72.));
+    }
+
+    mesh_helper.draw_text(
+        ctx,
+        "F2 - Change piece style",
+        pt(8., mesh_helper.height - 24.),
+        LIGHT_GRAY,
+        14.,
+        false,
+    );
+
     Ok(())
 }
 
-fn draw_piece(
+fn draw_chess_piece(
     ctx: &mut Context,
     mesh_helper: &mut MeshHelper,
-    idx: usize,
-    cell_size: f32,
-    color: Color,
-    mesh: Rc<Mesh>,
-    board_start: Point,
-) {
-    let xy = Point::from(BoardCoord::from(idx))
-        .multiply(cell_size, cell_size)
-        .offset_point(board_start);
-    mesh_helper.draw_coloured_mesh(ctx, mesh.as_ref(), xy, color);
+    state: &State,
+    piece: &ChessPiece,
+    player: Player,
+    xy: Point,
+    size: f32,
+) -> GameResult<()> {
+    let (colour, outline_colour) = match player {
+        Player::Human => (HUMAN_PIECE, COMPUTER_PIECE),
+        Player::Computer => (COMPUTER_PIECE, LIGHT_GRAY),
+    };
+    match state.piece_style {
+        PieceStyle::Letters => {
+            let mesh = make_letter_mesh(
+                ctx,
+                mesh_helper,
+                size,
+                state.game_type.get_piece_letter(piece),
+            )?;
+            mesh_helper.draw_coloured_mesh(ctx, mesh.as_ref(), xy, colour);
+        }
+        PieceStyle::Glyphs => {
+            let mesh = make_piece_mesh(ctx, mesh_helper, size, piece)?;
+            let outline = make_piece_outline_mesh(ctx, mesh_helper, size, piece)?;
+            mesh_helper.draw_coloured_mesh(ctx, mesh.as_ref(), xy, colour);
+            mesh_helper.draw_coloured_mesh(ctx, outline.as_ref(), xy, outline_colour);
+        }
+    }
+    Ok(())
+}
+
+fn draw_captured_panel(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let rows = [
+        (String::from("You"), state.captured[&Player::Human].len()),
+        (
+            String::from("Computer"),
+            state.captured[&Player::Computer].len(),
+        ),
+    ];
+    let next_line = captured_panel::draw_captured_panel(
+        ctx,
+        mesh_helper,
+        rows,
+        Some(COPPER),
+        state.material_balance(),
+        |ctx, mesh_helper, player, i, xy| {
+            //Captured pieces are drawn in the colour of the side they were taken from
+            draw_chess_piece(
+                ctx,
+                mesh_helper,
+                state,
+                &state.captured[&player][i],
+                player.opposite(),
+                xy,
+                CAPTURED_PIECE_SIZE,
+            )
+        },
+    )?;
+
+    if let Some(opening) = &state.opening {
+        mesh_helper.draw_text(
+            ctx,
+            &format!("Opening: {}", opening),
+            next_line,
+            LIGHT_GRAY,
+            14.,
+            false,
+        );
+    }
+
+    Ok(())
 }
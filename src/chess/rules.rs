@@ -50,7 +50,7 @@ impl ChessPiece {
 
 fn is_capturable(from: Square, target: Square) -> bool {
     if let Some(origin_player) = from.get_player() {
-        target.get_player() == None || target.get_player() == Some(origin_player.opposite())
+        target.get_player().is_none() || target.get_player() == Some(origin_player.opposite())
     } else {
         panic!("Checked capturable from empty square");
     }
@@ -70,6 +70,26 @@ fn does_move_result_in_self_check(game_type: &GameType, board: &Board, mov: &Mov
     game_type.is_king_in_check(&board, player)
 }
 
+//Squares the piece at origin could capture on, kings are handled here as their moves check for self check
+pub(super) fn calc_attacks(game_type: &GameType, board: &Board, origin: usize) -> Vec<usize> {
+    let piece = board[origin]
+        .get_piece()
+        .expect("No piece for calc attacks");
+    if piece != ChessPiece::King {
+        return piece
+            .calc_moves(game_type, board, origin)
+            .iter()
+            .map(|mov| mov.to)
+            .collect();
+    }
+    let (x, y): (isize, isize) = BoardCoord::from(origin).into();
+    (x - 1..=x + 1)
+        .flat_map(|x| (y - 1..=y + 1).map(move |y| (x, y)))
+        .filter(|target| target != &(x, y) && target.is_in_board())
+        .map(|target| BoardCoord::from(target).idx())
+        .collect()
+}
+
 fn check_line(board: &Board, origin: usize, x_diff: isize, y_diff: isize) -> Vec<Move> {
     let mut result = vec![];
     let mut target: (isize, isize) = BoardCoord::from(origin).into();
@@ -5,13 +5,22 @@ pub const ANIMATION_DURATION: f64 = 0.5;
 pub mod games {
     //These are the code names used in the code for swapping scenes, etc
     pub const TICTACTOE: &str = "tictactoe";
-    pub const MANCALA: &str = "mancala";
+    pub const MANCALA_KALAH: &str = "mancala_kalah";
+    pub const MANCALA_OWARE: &str = "mancala_oware";
+    pub const MANCALA_CONGKAK: &str = "mancala_congkak";
     pub const DRAUGHTS_ENGLISH: &str = "draughts_english";
     pub const DRAUGHTS_INTERNATIONAL: &str = "draughts_international";
     pub const DRAUGHTS_BRAZILIAN: &str = "draughts_brazilian";
     pub const DRAUGHTS_CANADIAN: &str = "draughts_canadian";
+    pub const DRAUGHTS_RUSSIAN: &str = "draughts_russian";
+    pub const DRAUGHTS_ITALIAN: &str = "draughts_italian";
+    pub const DRAUGHTS_SPANISH: &str = "draughts_spanish";
+    pub const DRAUGHTS_TURKISH: &str = "draughts_turkish";
+    pub const DRAUGHTS_FRISIAN: &str = "draughts_frisian";
     pub const TABLUT: &str = "tablut";
-    pub const GO: &str = "go";
+    pub const GO_SMALL: &str = "go_small";
+    pub const GO_MEDIUM: &str = "go_medium";
+    pub const GO_STANDARD: &str = "go_standard";
     pub const UR: &str = "ur";
     pub const SENET: &str = "senet";
     pub const BLACKHOLE: &str = "blackhole";
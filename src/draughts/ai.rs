@@ -1,10 +1,185 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::{board_cols, board_rows};
 use crate::draughts::controller::Controller;
+use crate::draughts::moves::Move;
+use crate::draughts::rules::{all_moves, apply_move, RuleSet};
+use crate::draughts::{Board, Square};
+use crate::system::transposition_table::{Bound, TranspositionTable};
+use crate::system::zobrist::Zobrist;
 use crate::system::PlayState::Playing;
 use crate::system::Player;
 use crate::system::Turn::Computer;
 use crate::system::TurnState::{SelectingMove, SelectingPiece};
 use itertools::Itertools;
-use rand::{thread_rng, Rng};
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+
+const WIN: i32 = 1_000_000;
+pub(super) const MAN_VALUE: i32 = 100;
+pub(super) const KING_VALUE: i32 = 300;
+//Men still on their own back row stop the opponent crowning
+const BACK_RANK_VALUE: i32 = 12;
+const CENTRE_VALUE: i32 = 6;
+//Per row a man has advanced
+const TEMPO_VALUE: i32 = 2;
+
+pub(super) type Table = TranspositionTable<(i32, Option<Move>)>;
+
+fn piece_score(idx: usize, square: Square) -> i32 {
+    let BoardCoord(x, y) = BoardCoord::from(idx);
+    let (cols, rows) = (board_cols(), board_rows());
+    let advanced = match Player::from(square) {
+        Player::Human => rows - 1 - y,
+        Player::Computer => y,
+    };
+    let mut score = if square.is_king() {
+        KING_VALUE
+    } else if advanced == 0 {
+        MAN_VALUE + BACK_RANK_VALUE
+    } else {
+        MAN_VALUE + advanced as i32 * TEMPO_VALUE
+    };
+    if (cols / 4..cols - cols / 4).contains(&x) && (rows / 4..rows - rows / 4).contains(&y) {
+        score += CENTRE_VALUE;
+    }
+    score
+}
+
+//Score from the point of view of player
+fn evaluate(board: &Board, player: Player) -> i32 {
+    board
+        .iter()
+        .enumerate()
+        .filter(|(_, square)| **square != Square::Empty)
+        .map(|(idx, square)| {
+            let score = piece_score(idx, *square);
+            if Player::from(*square) == player {
+                score
+            } else {
+                -score
+            }
+        })
+        .sum()
+}
+
+fn order_moves(moves: &mut [Move], best: &Option<Move>) {
+    //Shuffled first so equal moves aren't always played in the same order
+    moves.shuffle(&mut thread_rng());
+    moves.sort_by_key(|mov| {
+        if Some(mov) == best.as_ref() {
+            return i32::MIN;
+        }
+        -(mov.captures().len() as i32)
+    });
+}
+
+struct Search<'a> {
+    rules: &'a dyn RuleSet,
+    zobrist: &'a Zobrist,
+    table: &'a mut Table,
+}
+
+impl Search<'_> {
+    //Captures are compulsory so positions where the player to move can capture are searched
+    //past depth 0 until they're quiet
+    fn negamax(
+        &mut self,
+        board: &Board,
+        player: Player,
+        depth: usize,
+        mut alpha: i32,
+        beta: i32,
+        ply: i32,
+    ) -> (i32, Option<Move>) {
+        let mut moves = all_moves(self.rules, board, player);
+        if moves.is_empty() {
+            return (-WIN + ply, None);
+        }
+        let quiet = moves.iter().all(|mov| matches!(mov, Move::Step { .. }));
+        if depth == 0 && quiet {
+            return (evaluate(board, player), None);
+        }
+
+        let hash = self.zobrist.hash(board, player);
+        let mut best_move = None;
+        if let Some(entry) = self.table.get(hash) {
+            best_move = entry.value.1.clone();
+            if entry.depth >= depth {
+                let (score, mov) = entry.value.clone();
+                match entry.bound {
+                    Bound::Exact => return (score, mov),
+                    Bound::Lower if score >= beta => return (score, mov),
+                    Bound::Upper if score <= alpha => return (score, mov),
+                    _ => {}
+                }
+            }
+        }
+        order_moves(&mut moves, &best_move);
+
+        let original_alpha = alpha;
+        let mut best_score = i32::MIN;
+        for mov in moves {
+            let mut next = board.clone();
+            apply_move(self.rules, &mut next, &mov);
+            let score = -self
+                .negamax(
+                    &next,
+                    player.opposite(),
+                    depth.saturating_sub(1),
+                    -beta,
+                    -alpha,
+                    ply + 1,
+                )
+                .0;
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mov);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table
+            .insert(hash, depth, bound, (best_score, best_move.clone()));
+        (best_score, best_move)
+    }
+}
+
+//Iterative deepening so the table has good moves to try first at each depth
+pub(super) fn choose_move(
+    rules: &dyn RuleSet,
+    zobrist: &Zobrist,
+    table: &mut Table,
+    board: &Board,
+    player: Player,
+    max_depth: usize,
+) -> Option<Move> {
+    table.new_search();
+    let mut search = Search {
+        rules,
+        zobrist,
+        table,
+    };
+    let mut best = None;
+    for depth in 1..=max_depth {
+        let (score, mov) = search.negamax(board, player, depth, -WIN - 1, WIN + 1, 0);
+        debug_log!("Depth {}: {:?} scores {}", depth, mov, score);
+        best = mov.or(best);
+        if score.abs() >= WIN - depth as i32 {
+            break;
+        }
+    }
+    best
+}
 
 pub(super) fn process(controller: &mut Controller, delta: f64) {
     if controller.state.play_state.is_computer(SelectingPiece) {
@@ -12,27 +187,29 @@ pub(super) fn process(controller: &mut Controller, delta: f64) {
         if controller.state.next_move_time < 0. {
             controller.state.last_human_cursor_pos = controller.state.piece_cursor.idx;
             debug_log!("Starting computer move");
-            let map = controller
-                .state
-                .all_possible_moves
-                .values()
-                .flatten()
-                .into_group_map_by(|mov| mov.value());
-            let highest_value = map.keys().sorted().last().unwrap();
-            debug_log!("Highest value: {}", highest_value);
-            let best_moves = map.get(highest_value).unwrap();
-
-            debug_log!("{} best moves available", best_moves.len());
-
-            let move_idx = thread_rng().gen_range(0.. best_moves.len());
-            let mov = best_moves[move_idx];
+            let scripted = controller.scripted_move();
+            let rules = controller.rules.as_ref();
+            let state = &mut controller.state;
+            let mov = scripted
+                .or_else(|| {
+                    choose_move(
+                        rules,
+                        &state.zobrist,
+                        &mut state.ai_table,
+                        &state.board,
+                        Player::Computer,
+                        state.strength.depth(),
+                    )
+                })
+                .or_else(|| state.all_possible_moves.values().flatten().next().cloned())
+                .expect("Computer has no moves");
             controller.state.piece_cursor.idx = mov.origin();
             let (idx, _) = controller
                 .state
                 .get_moves_for_selected_piece()
                 .iter()
-                .find_position(|&mov| mov == best_moves[move_idx])
-                .unwrap();
+                .find_position(|&other| *other == mov)
+                .unwrap_or((0, &mov));
 
             debug_log!("Using piece {}", mov.origin());
             debug_log!("Using move {}", idx);
@@ -45,10 +222,63 @@ pub(super) fn process(controller: &mut Controller, delta: f64) {
     if controller.state.play_state.is_computer(SelectingMove) {
         controller.state.next_move_time -= delta;
         if controller.state.next_move_time < 0. {
-            debug_log!("Now playing {}", controller.state.get_selected_move());
-            controller.process_move(controller.state.get_selected_move());
+            let mov = controller.state.get_selected_move();
+            debug_log!("Now playing {}", mov);
             controller.state.piece_cursor.idx = controller.state.last_human_cursor_pos;
-            controller.start_new_turn(Player::Human);
+            controller.play_move(mov, Player::Computer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::rules::GameVariant;
+    use crate::draughts::Square::*;
+    use serial_test::serial;
+
+    fn board_with(pieces: &[(usize, Square)]) -> Board {
+        let mut board = vec![Empty; 64];
+        for (idx, square) in pieces {
+            board[*idx] = *square;
+        }
+        board
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_evaluate() {
+        set_board_size((8, 8));
+        let board = board_with(&[(62, HumanMan), (1, ComputerKing)]);
+        assert!(evaluate(&board, Player::Computer) > 0);
+        assert_eq!(
+            evaluate(&board, Player::Human),
+            -evaluate(&board, Player::Computer)
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_avoids_shot() {
+        set_board_size((8, 8));
+        //Stepping from 37 to 46 lets the man on 55 jump it, at depth 1 the
+        //reply is only seen by carrying on through the capture
+        let board = board_with(&[(37, ComputerMan), (55, HumanMan), (1, ComputerMan)]);
+        let rules = GameVariant::English.get_rules();
+        let zobrist = Zobrist::new::<Square>(64);
+        let mut table = Table::new(1024);
+        for _ in 0..5 {
+            let mov = choose_move(
+                rules.as_ref(),
+                &zobrist,
+                &mut table,
+                &board,
+                Player::Computer,
+                1,
+            )
+            .unwrap();
+            assert_ne!(mov.dest(), 46);
         }
     }
 }
@@ -0,0 +1,121 @@
+use crate::constants::ANIMATION_DURATION;
+use crate::draughts::moves::Move;
+use crate::draughts::{Board, Square};
+
+//Multi-jumps are played back one hop at a time, each hop takes ANIMATION_DURATION
+//and the piece jumped fades out over the following ANIMATION_DURATION
+#[derive(Debug)]
+pub(super) struct Animation {
+    //Board before the move with the moving piece taken off
+    pub(super) board: Board,
+    pub(super) piece: Square,
+    //Squares the piece visits, starting with its origin
+    pub(super) path: Vec<usize>,
+    //Square jumped on each hop
+    pub(super) captured: Vec<usize>,
+    elapsed: f64,
+}
+
+impl Animation {
+    //Only multi-jumps are animated, anything else is shown straight away
+    pub(super) fn new(board: &Board, mov: &Move) -> Option<Self> {
+        let captures = match mov {
+            Move::MultiJump { captures, .. } => captures,
+            _ => return None,
+        };
+        let mut board = board.clone();
+        let piece = board[mov.origin()];
+        board[mov.origin()] = Square::Empty;
+        let mut path = vec![mov.origin()];
+        path.extend(captures.iter().map(|capture| capture.dest));
+        Some(Animation {
+            board,
+            piece,
+            path,
+            captured: captures.iter().map(|capture| capture.capturing).collect(),
+            elapsed: 0.,
+        })
+    }
+
+    pub(super) fn update(&mut self, delta: f64) {
+        self.elapsed += delta;
+    }
+
+    //Includes the time for the last captured piece to fade
+    pub(super) fn is_finished(&self) -> bool {
+        self.elapsed >= (self.captured.len() + 1) as f64 * ANIMATION_DURATION
+    }
+
+    //Squares the moving piece is between and how far along it is (0 to 1)
+    pub(super) fn position(&self) -> (usize, usize, f32) {
+        let hop = (self.elapsed / ANIMATION_DURATION) as usize;
+        if hop >= self.captured.len() {
+            let last = self.path.len() - 1;
+            return (self.path[last - 1], self.path[last], 1.);
+        }
+        let progress = (self.elapsed % ANIMATION_DURATION) / ANIMATION_DURATION;
+        (self.path[hop], self.path[hop + 1], progress as f32)
+    }
+
+    //Opacity of the piece jumped on the hop, 1 until it's been jumped
+    pub(super) fn captured_alpha(&self, hop: usize) -> f32 {
+        let since = self.elapsed - (hop + 1) as f64 * ANIMATION_DURATION;
+        (1. - since / ANIMATION_DURATION).clamp(0., 1.) as f32
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::draughts::moves::Capture;
+    use crate::draughts::Square::*;
+
+    #[test]
+    fn test_animation() {
+        let mut board = vec![Empty; 64];
+        board[54] = HumanMan;
+        board[45] = ComputerMan;
+        board[27] = ComputerMan;
+        let mov = Move::MultiJump {
+            origin: 54,
+            captures: vec![
+                Capture {
+                    dest: 36,
+                    capturing: 45,
+                },
+                Capture {
+                    dest: 18,
+                    capturing: 27,
+                },
+            ],
+            value: 0,
+        };
+        assert!(Animation::new(
+            &board,
+            &Move::Step {
+                origin: 54,
+                dest: 47,
+                value: 0
+            }
+        )
+        .is_none());
+
+        let mut animation = Animation::new(&board, &mov).unwrap();
+        assert_eq!(animation.board[54], Empty);
+        assert_eq!(animation.piece, HumanMan);
+        assert_eq!(animation.path, vec![54, 36, 18]);
+        assert_eq!(animation.position(), (54, 36, 0.));
+
+        animation.update(ANIMATION_DURATION * 1.5);
+        assert_eq!(animation.position(), (36, 18, 0.5));
+        assert_eq!(animation.captured_alpha(0), 0.5);
+        assert_eq!(animation.captured_alpha(1), 1.);
+
+        animation.update(ANIMATION_DURATION);
+        assert_eq!(animation.position(), (36, 18, 1.));
+        assert_eq!(animation.captured_alpha(0), 0.);
+        assert!(!animation.is_finished());
+        animation.update(ANIMATION_DURATION * 0.5);
+        assert!(animation.is_finished());
+    }
+}
@@ -1,60 +1,148 @@
 use crate::boards::chessboard::ChessBoard;
-use crate::boards::cursor::Cursor;
+use crate::boards::cursor::{movable, Cursor};
 use crate::boards::idx_coord::BoardCoord;
-use crate::boards::single_char_board_converter::SingleCharBoardConverter;
+use crate::boards::move_entry::{EntryAction, MoveEntry};
+use crate::boards::multi_char_board_converter::MultiCharBoardConverter;
 use crate::boards::{board_cols, board_rows, set_board_size};
-use crate::constants::{games, AI_MOVE_DELAY};
+use crate::constants::AI_MOVE_DELAY;
 use crate::debug_log;
-use crate::draughts::ai::process;
+use crate::draughts::ai::{process, Table};
+use crate::draughts::animation::Animation;
+use crate::draughts::draw::{active_counter, CounterStarts, REPETITIONS};
 use crate::draughts::moves::Move;
+use crate::draughts::pdn::{matches_squares, move_text, parse_pdn, square_name, to_pdn};
+use crate::draughts::problems::{parse_problems, ProblemSet};
 use crate::draughts::renderer::render;
-use crate::draughts::rules::{GameVariant, RuleSet};
-use crate::draughts::Square::{ComputerKing, ComputerMan, Empty, HumanKing, HumanMan};
-use crate::draughts::{PastMove, Square, State};
+use crate::draughts::rules::{all_moves, apply_move, GameVariant, RuleSet};
+use crate::draughts::{PastMove, Phase, Square, State, Strength, SETUP_POSITION, SETUP_STRENGTH};
 use crate::system::find_nearest::find_nearest;
 use crate::system::ggez_ext::keycode_to_direction;
 use crate::system::mesh_helper::MeshHelper;
+use crate::system::zobrist::{PositionHistory, Zobrist};
 use crate::system::PlayState::{ComputerWin, HumanWin, Playing};
 use crate::system::Turn::Human;
-use crate::system::TurnState::{SelectingMove, SelectingPiece};
-use crate::system::{PlayState, Player, Scene, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
+use crate::system::TurnState::{Animating, SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player, Scene, Turn, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
 use ggez::event::KeyCode;
 use ggez::{Context, GameResult};
 use itertools::Itertools;
 use std::collections::HashMap;
+use std::fs;
+
+pub(super) const SAVE_KEY: KeyCode = KeyCode::S;
+pub(super) const NEXT_PROBLEM_KEY: KeyCode = KeyCode::N;
+const AI_TABLE_SIZE: usize = 1 << 16;
 
 pub struct Controller {
     pub(super) state: State,
-    rules: Box<dyn RuleSet>,
+    pub(super) rules: Box<dyn RuleSet>,
+    pub(super) variant: GameVariant,
 }
 
 impl Controller {
     pub fn new(game: &str) -> Controller {
-        let variant = match game {
-            games::DRAUGHTS_ENGLISH => GameVariant::English,
-            games::DRAUGHTS_INTERNATIONAL => GameVariant::International,
-            games::DRAUGHTS_CANADIAN => GameVariant::Canadian,
-            games::DRAUGHTS_BRAZILIAN => GameVariant::Brazilian,
-            _ => panic!("Invalid game for draughts controller: {}", game),
-        };
+        let variant = GameVariant::from_game(game)
+            .unwrap_or_else(|| panic!("Invalid game for draughts controller: {}", game));
         set_board_size(variant.get_board_size());
-        let converter = SingleCharBoardConverter::new(board_rows(), board_cols());
+        let converter = MultiCharBoardConverter::new(board_rows(), board_cols());
         let calc = ChessBoard::new(Box::new(converter), board_rows(), board_cols());
         Controller {
             state: State {
                 board: variant.get_init_board(),
                 board_calc: calc,
-                play_state: PlayState::Init,
+                play_state: PlayState::ModeSelection,
                 piece_cursor: Cursor::new(),
                 all_possible_moves: HashMap::new(),
                 move_cursor: 0,
                 move_history: vec![],
+                moves_played: vec![],
+                start_position: None,
                 next_move_time: 0.,
                 last_human_cursor_pos: 0,
+                message: None,
+                strength: Strength::Medium,
+                zobrist: Zobrist::new::<Square>(board_cols() * board_rows()),
+                ai_table: Table::new(AI_TABLE_SIZE),
+                phase: Phase::Setup,
+                setup_row: SETUP_STRENGTH,
+                custom_position: false,
+                edit_to_move: Player::Human,
+                problems: None,
+                animation: None,
+                positions: PositionHistory::default(),
+                draw_counter: None,
+                counter_starts: CounterStarts::default(),
+                entry: MoveEntry::new(),
             },
             rules: variant.get_rules(),
+            variant,
         }
     }
+
+    //Replays a PDN record and continues play from the end of it, the human plays white
+    pub fn from_record(game: &str, record: &str) -> Result<Self, String> {
+        let mut controller = Controller::new(game);
+        controller.state.phase = Phase::Playing;
+        let loaded = parse_pdn(record, controller.variant)?;
+        if loaded.variant != controller.variant {
+            return Err(format!(
+                "Record is for {} but {} was chosen",
+                loaded.variant.get_game(),
+                game
+            ));
+        }
+        let mut player = Player::Human;
+        if let Some(setup) = loaded.setup {
+            controller.state.board = setup.board.clone();
+            player = setup.to_move;
+            controller.state.start_position = Some(setup);
+        }
+        for (i, squares) in loaded.moves.iter().enumerate() {
+            controller.update_valid_moves(player);
+            let mov = controller
+                .state
+                .all_possible_moves
+                .values()
+                .flatten()
+                .find(|mov| matches_squares(mov, squares))
+                .cloned()
+                .ok_or_else(|| {
+                    format!(
+                        "Illegal move {} at ply {}",
+                        squares_text(controller.variant, squares),
+                        i + 1
+                    )
+                })?;
+            debug_log!("Replaying {}", move_text(controller.variant, &mov));
+            controller.process_move(mov);
+            player = player.opposite();
+        }
+        debug_log!("Loaded {} moves", loaded.moves.len());
+        controller.start_new_turn(player);
+        if let Some(result) = loaded.result {
+            controller.state.message = Some(format!("Record ended {}", result));
+        }
+        Ok(controller)
+    }
+
+    //Loads composed problems from a PDN file, the human has to find each solution
+    pub fn from_problems(game: &str, text: &str) -> Result<Self, String> {
+        let mut controller = Controller::new(game);
+        let problems = parse_problems(text, controller.variant)?;
+        debug_log!("Loaded {} problems", problems.len());
+        controller.state.problems = Some(ProblemSet::new(problems));
+        controller.state.phase = Phase::Playing;
+        controller.start_problem(0);
+        Ok(controller)
+    }
+}
+
+fn squares_text(variant: GameVariant, squares: &[usize]) -> String {
+    squares
+        .iter()
+        .map(|idx| square_name(variant, *idx))
+        .collect::<Vec<String>>()
+        .join("-")
 }
 
 impl Controller {
@@ -68,8 +156,138 @@ impl Controller {
     }
 
     fn select_move(&mut self) {
-        self.process_move(self.state.get_selected_move());
-        self.start_new_turn(Player::Computer);
+        self.play_human_move(self.state.get_selected_move());
+    }
+
+    //Return and Back while a move is being typed
+    fn handle_entry_key(&mut self, key: KeyCode) {
+        let options = self.state.entry_options(self.variant);
+        if key == KeyCode::Back {
+            self.state.entry.pop(&options);
+            return;
+        }
+        match self.state.entry.submit(&options) {
+            Some(EntryAction::SelectPiece(idx)) => {
+                self.state.piece_cursor.idx = *idx;
+                self.state.move_cursor = 0;
+                self.state.play_state = Playing(Human(SelectingMove));
+            }
+            Some(EntryAction::PlayMove(mov)) => self.play_human_move(mov.clone()),
+            None => {}
+        }
+    }
+
+    fn play_human_move(&mut self, mov: Move) {
+        if let Some(problems) = &self.state.problems {
+            if !problems
+                .expected()
+                .map(|squares| matches_squares(&mov, squares))
+                .unwrap_or(false)
+            {
+                self.state.message = Some(String::from("That's not the solution, try again"));
+                self.state.play_state = NEW_TURN_HUMAN;
+                return;
+            }
+        }
+        self.play_move(mov, Player::Human);
+    }
+
+    //Plays a move during the game, multi-jumps are animated before the other player's turn
+    pub(super) fn play_move(&mut self, mov: Move, player: Player) {
+        let animation = Animation::new(&self.state.board, &mov);
+        self.process_move(mov);
+        if animation.is_some() {
+            self.state.animation = animation;
+            self.state.play_state = Playing(Turn::new(player, Animating));
+        } else {
+            self.end_turn(player);
+        }
+    }
+
+    fn end_turn(&mut self, player: Player) {
+        if !self.check_problem_solved() {
+            self.start_new_turn(player.opposite());
+        }
+    }
+
+    fn update_animation(&mut self, delta: f64) {
+        let animation = match &mut self.state.animation {
+            Some(animation) => animation,
+            None => return,
+        };
+        animation.update(delta);
+        if animation.is_finished() {
+            self.state.animation = None;
+            let player = if self.state.play_state.is_human(Animating) {
+                Player::Human
+            } else {
+                Player::Computer
+            };
+            self.end_turn(player);
+        }
+    }
+
+    fn start_problem(&mut self, idx: usize) {
+        let problems = match &mut self.state.problems {
+            Some(problems) => problems,
+            None => return,
+        };
+        problems.current = idx;
+        problems.progress = 0;
+        let setup = problems.problem().setup.clone();
+        self.state.message = Some(format!(
+            "Problem {} of {}, find the best move",
+            idx + 1,
+            problems.problems.len()
+        ));
+        self.state.board = setup.board.clone();
+        self.state.start_position = Some(setup);
+        self.state.moves_played.clear();
+        self.state.move_history.clear();
+        self.state.positions.clear();
+        self.state.counter_starts.clear();
+        self.state.animation = None;
+        self.start_new_turn(Player::Human);
+    }
+
+    fn next_problem(&mut self) {
+        if let Some(problems) = &self.state.problems {
+            if !problems.is_last() {
+                self.start_problem(problems.current + 1);
+            }
+        }
+    }
+
+    //The move the computer has to play in the current problem's solution
+    pub(super) fn scripted_move(&self) -> Option<Move> {
+        let squares = self.state.problems.as_ref()?.expected()?;
+        self.state
+            .all_possible_moves
+            .values()
+            .flatten()
+            .find(|mov| matches_squares(mov, squares))
+            .cloned()
+    }
+
+    //Returns true if the whole solution has been played, ending the game after the last problem
+    pub(super) fn check_problem_solved(&mut self) -> bool {
+        let problems = match &self.state.problems {
+            Some(problems) if problems.is_solved() => problems,
+            _ => return false,
+        };
+        if problems.is_last() {
+            self.state.message = Some(String::from("Solved, that was the last problem"));
+            self.state.play_state = HumanWin;
+        } else {
+            self.state.message = Some(format!(
+                "Solved, press {:?} for the next problem",
+                NEXT_PROBLEM_KEY
+            ));
+            //Nothing more to play until the next problem is chosen
+            self.state.all_possible_moves.clear();
+            self.state.play_state = NEW_TURN_HUMAN;
+        }
+        true
     }
 
     pub(super) fn start_new_turn(&mut self, player: Player) {
@@ -95,69 +313,32 @@ impl Controller {
             }
             return;
         }
-        if let Some(new_state) = self
-            .rules
-            .check_game_over(&self.state.board, &self.state.move_history)
-        {
+        if let Some(new_state) = self.rules.check_game_over(&self.state.board) {
             debug_log!("Game over: {:?}", new_state);
             self.state.play_state = new_state;
+            return;
+        }
+        self.state.draw_counter = active_counter(
+            &self.variant.draw_counters(),
+            &self.state.board,
+            &self.state.move_history,
+            &self.state.counter_starts,
+        );
+        if self.state.positions.is_repeated(REPETITIONS) {
+            debug_log!("Position repeated: draw!");
+            self.state.message = Some(String::from("Draw by repetition"));
+            self.state.play_state = PlayState::Draw;
+        } else if let Some(status) = self.state.draw_counter.filter(|status| status.is_draw()) {
+            debug_log!("{}: draw!", status);
+            self.state.message = Some(format!("Draw, {}", status));
+            self.state.play_state = PlayState::Draw;
         }
     }
 
     fn update_valid_moves(&mut self, player: Player) {
         debug_log_start!("Calculating all possible moves for {:?}", player);
-        let squares = match player {
-            Player::Human => vec![HumanMan, HumanKing],
-            Player::Computer => vec![ComputerKing, ComputerMan],
-        };
-        let mut all_moves = self
-            .state
-            .board
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, square)| {
-                if squares.contains(square) {
-                    Some(self.rules.calc_valid_moves(&self.state.board, idx))
-                } else {
-                    None
-                }
-            })
-            .flatten()
-            .collect::<Vec<Move>>();
-
-        let has_multijump = all_moves.iter().any(|mov| mov.is_multi_jump());
-        let has_jump = all_moves.iter().any(|mov| mov.is_jump());
-
-        debug_log!(
-            "Found {} moves, any multijumps: {}, any jumps: {}",
-            all_moves.len(),
-            has_multijump,
-            has_jump
-        );
-
-        if has_multijump {
-            all_moves = all_moves
-                .iter()
-                .filter(|mov| mov.is_multi_jump())
-                .cloned()
-                .collect();
-            let longest = all_moves
-                .iter()
-                .map(|mov| mov.len())
-                .max()
-                .expect("Failed to find longest multijump");
-            all_moves = all_moves
-                .iter()
-                .filter(|mov| mov.len() == longest)
-                .cloned()
-                .collect();
-        } else if has_jump {
-            all_moves = all_moves
-                .iter()
-                .filter(|mov| mov.is_jump())
-                .cloned()
-                .collect();
-        }
+        let all_moves = all_moves(self.rules.as_ref(), &self.state.board, player);
+        debug_log!("Found {} moves", all_moves.len());
 
         self.state.all_possible_moves = all_moves
             .iter()
@@ -170,117 +351,116 @@ impl Controller {
         );
     }
 
-    pub(super) fn process_move(&mut self, mov: Move) {
-        let board = &mut self.state.board;
-        match mov {
-            Move::Step {
-                origin,
-                dest,
-                value: _,
-            } => {
-                let (is_promotion, end_piece) =
-                    if let Some(king) = self.rules.is_promotion(board, origin, dest) {
-                        (true, king)
-                    } else {
-                        (false, board[origin])
-                    };
-                self.state.move_history.push(PastMove::new(
-                    board[origin].into(),
-                    origin,
-                    vec![dest],
-                    0,
-                    0,
-                    board[origin],
-                    is_promotion,
-                ));
-                board[dest] = end_piece;
-                board[origin] = Empty;
-            }
-            Move::Jump {
-                origin,
-                capture,
-                value: _,
-            } => {
-                let (is_promotion, end_piece) =
-                    if let Some(king) = self.rules.is_promotion(board, origin, capture.dest) {
-                        (true, king)
-                    } else {
-                        (false, board[origin])
-                    };
-                let (king, man) = if board[capture.capturing].is_king() {
-                    (1, 0)
+    fn handle_setup_input(&mut self, key: KeyCode) {
+        let count = Strength::ALL.len();
+        let current = Strength::ALL
+            .iter()
+            .position(|strength| *strength == self.state.strength)
+            .unwrap_or(0);
+        match key {
+            KeyCode::Up | KeyCode::Down => {
+                self.state.setup_row = if self.state.setup_row == SETUP_STRENGTH {
+                    SETUP_POSITION
                 } else {
-                    (0, 1)
-                };
-                self.state.move_history.push(PastMove::new(
-                    board[origin].into(),
-                    origin,
-                    vec![capture.dest],
-                    king,
-                    man,
-                    board[origin],
-                    is_promotion,
-                ));
-                board[capture.dest] = end_piece;
-                board[capture.capturing] = Empty;
-                board[origin] = Empty;
-            }
-            Move::MultiJump {
-                origin,
-                captures,
-                value: _,
-            } => {
-                let mut king = 0;
-                let mut man = 0;
-                for capture in &captures {
-                    if board[capture.capturing].is_king() {
-                        king += 1;
-                    } else {
-                        man += 1;
-                    }
+                    SETUP_STRENGTH
                 }
-                let last = captures.iter().last().unwrap();
-                let (is_promotion, end_piece) =
-                    if let Some(king) = self.rules.is_promotion(board, origin, last.dest) {
-                        (true, king)
-                    } else {
-                        (false, board[origin])
-                    };
-                self.state.move_history.push(PastMove::new(
-                    board[origin].into(),
-                    origin,
-                    captures.iter().map(|cap| cap.dest).collect(),
-                    king,
-                    man,
-                    board[origin],
-                    is_promotion,
-                ));
-                board[last.dest] = end_piece;
-                board[last.capturing] = Empty;
-                for cap in captures {
-                    board[cap.capturing] = Empty;
-                }
-                board[origin] = Empty;
             }
-        }
-        #[allow(clippy::needless_range_loop)] //looks awful in comparison
-        for idx in 0..board_cols() {
-            if board[idx] == HumanMan {
-                board[idx] = HumanKing
+            KeyCode::Left | KeyCode::Right if self.state.setup_row == SETUP_POSITION => {
+                self.state.custom_position = !self.state.custom_position
             }
-        }
-        for idx in (board.len() - board_cols())..(board.len()) {
-            if board[idx] == ComputerMan {
-                board[idx] = ComputerKing
+            KeyCode::Left => self.state.strength = Strength::ALL[(current + count - 1) % count],
+            KeyCode::Right => self.state.strength = Strength::ALL[(current + 1) % count],
+            KeyCode::Return if self.state.custom_position => self.state.phase = Phase::Editing,
+            KeyCode::Return => {
+                self.state.phase = Phase::Playing;
+                self.state.play_state = PlayState::Init;
             }
+            _ => {}
         }
     }
+
+    //Writes the game so far as PDN to the working directory
+    fn save_record(&mut self) {
+        let path = format!("{}.pdn", self.variant.get_game());
+        let result = match self.state.play_state {
+            HumanWin => Some("1-0"),
+            ComputerWin => Some("0-1"),
+            PlayState::Draw => Some("1/2-1/2"),
+            _ => None,
+        };
+        let pdn = to_pdn(
+            self.variant,
+            self.state.start_position.as_ref(),
+            &self.state.moves_played,
+            result,
+        );
+        self.state.message = Some(match fs::write(&path, pdn) {
+            Ok(_) => format!("Saved {}", path),
+            Err(err) => format!("Could not save: {}", err),
+        });
+    }
+
+    pub(super) fn process_move(&mut self, mov: Move) {
+        if let Some(problems) = &mut self.state.problems {
+            problems.progress += 1;
+        }
+        self.state.moves_played.push(mov.clone());
+        let board = &mut self.state.board;
+        let piece = board[mov.origin()];
+        let captures = mov.captures();
+        let kings = captures
+            .iter()
+            .filter(|capture| board[capture.capturing].is_king())
+            .count();
+        let hops = match &mov {
+            Move::Step { dest, .. } => vec![*dest],
+            _ => captures.iter().map(|capture| capture.dest).collect(),
+        };
+        let player = Player::from(piece);
+        let counters = self.variant.draw_counters();
+        if self.state.positions.is_empty() {
+            self.state
+                .positions
+                .push(self.state.zobrist.hash(board, player));
+            self.state.counter_starts.update(&counters, board, 0);
+        }
+        let promotion = apply_move(self.rules.as_ref(), board, &mov);
+        self.state
+            .positions
+            .push(self.state.zobrist.hash(board, player.opposite()));
+        self.state.move_history.push(PastMove::new(
+            player,
+            mov.origin(),
+            hops,
+            kings,
+            captures.len() - kings,
+            piece,
+            promotion,
+        ));
+        self.state.counter_starts.update(
+            &counters,
+            &self.state.board,
+            self.state.move_history.len(),
+        );
+    }
 }
 
 impl Scene for Controller {
     fn on_key_down(&mut self, key: KeyCode) {
-        if self.state.play_state.is_human(SelectingPiece) {
-            if self.state.piece_cursor.handle_input(key) {
+        match self.state.phase {
+            Phase::Setup => return self.handle_setup_input(key),
+            Phase::Editing => return self.handle_editor_input(key),
+            Phase::Playing => {}
+        }
+        if !self.state.entry.is_empty() && (key == KeyCode::Return || key == KeyCode::Back) {
+            self.handle_entry_key(key);
+        } else if key == SAVE_KEY && self.state.play_state != PlayState::Init {
+            self.save_record();
+        } else if key == NEXT_PROBLEM_KEY && self.state.problems.is_some() {
+            self.next_problem();
+        } else if self.state.play_state.is_human(SelectingPiece) {
+            let movable = movable(&self.state.all_possible_moves);
+            if self.state.piece_cursor.handle_movable_input(key, &movable) {
                 self.state.move_cursor = 0;
             } else if let KeyCode::Return = key {
                 self.select_piece()
@@ -318,6 +498,14 @@ impl Scene for Controller {
     }
 
     fn on_key_up(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Escape && !self.state.entry.is_empty() {
+            self.state.entry.clear();
+            return true;
+        }
+        if self.state.phase == Phase::Editing && key == KeyCode::Escape {
+            self.state.phase = Phase::Setup;
+            return true;
+        }
         if self.state.play_state.is_human(SelectingMove) {
             if let KeyCode::Escape = key {
                 self.state.play_state = NEW_TURN_HUMAN;
@@ -328,10 +516,23 @@ impl Scene for Controller {
         false
     }
 
+    fn on_keyboard_entry(&mut self, input: char) {
+        if self.state.phase == Phase::Playing
+            && (self.state.play_state.is_human(SelectingPiece)
+                || self.state.play_state.is_human(SelectingMove))
+        {
+            let options = self.state.entry_options(self.variant);
+            self.state.entry.push(input, &options);
+        }
+    }
+
     fn update(&mut self, delta: f64) -> GameResult<()> {
         if self.state.play_state == PlayState::Init {
             self.start_new_turn(Player::Human)
         }
+        if self.state.play_state.is_either(Animating) {
+            self.update_animation(delta);
+        }
         process(self, delta);
         Ok(())
     }
@@ -0,0 +1,304 @@
+use crate::draughts::{Board, PastMove, Square};
+use crate::system::Player;
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+//The same position with the same player to move
+pub(super) const REPETITIONS: usize = 3;
+
+//Endgame counters, the game is drawn once one reaches its limit
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum DrawCounter {
+    //Moves each without a capture or a man moving
+    NoProgress(usize),
+    //Moves each once one side only has a single king and the other has at most pieces,
+    //including a king
+    LoneKing { pieces: usize, moves: usize },
+}
+
+//Returns the number of (pieces, kings) the player has
+fn count_pieces(board: &Board, player: Player) -> (usize, usize) {
+    board
+        .iter()
+        .filter(|square| **square != Square::Empty && Player::from(**square) == player)
+        .fold((0, 0), |(pieces, kings), square| {
+            (pieces + 1, kings + square.is_king() as usize)
+        })
+}
+
+impl DrawCounter {
+    fn limit(&self) -> usize {
+        match self {
+            DrawCounter::NoProgress(moves) => *moves,
+            DrawCounter::LoneKing { moves, .. } => *moves,
+        }
+    }
+
+    fn applies(&self, board: &Board) -> bool {
+        match self {
+            DrawCounter::NoProgress(_) => true,
+            DrawCounter::LoneKing { pieces, .. } => {
+                [Player::Human, Player::Computer].iter().any(|player| {
+                    let (other_pieces, other_kings) = count_pieces(board, player.opposite());
+                    count_pieces(board, *player) == (1, 1)
+                        && other_pieces <= *pieces
+                        && other_kings > 0
+                })
+            }
+        }
+    }
+
+    //Plies played since the counter started, start is the ply it began to apply at
+    fn plies(&self, move_history: &[PastMove], start: Option<usize>) -> usize {
+        let quiet = |mov: &&PastMove| mov.king_capture_count + mov.man_capture_count == 0;
+        match self {
+            DrawCounter::NoProgress(_) => move_history
+                .iter()
+                .rev()
+                .take_while(|mov| quiet(mov) && mov.piece.is_king())
+                .count(),
+            DrawCounter::LoneKing { .. } => {
+                start.map_or(0, |start| move_history.len().saturating_sub(start))
+            }
+        }
+    }
+}
+
+//The ply each counter started to apply at, kept as the game is played as an endgame can be
+//reached without a capture, e.g. by the stronger side crowning its first king
+#[derive(Debug, Clone, Default)]
+pub(super) struct CounterStarts {
+    starts: Vec<Option<usize>>,
+}
+
+impl CounterStarts {
+    //Call with the board before the first move and after every move
+    pub(super) fn update(&mut self, counters: &[DrawCounter], board: &Board, ply: usize) {
+        self.starts.resize(counters.len(), None);
+        for (counter, start) in counters.iter().zip(self.starts.iter_mut()) {
+            if counter.applies(board) {
+                start.get_or_insert(ply);
+            } else {
+                *start = None;
+            }
+        }
+    }
+
+    pub(super) fn clear(&mut self) {
+        self.starts.clear();
+    }
+
+    fn get(&self, idx: usize) -> Option<usize> {
+        self.starts.get(idx).copied().flatten()
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) struct CounterStatus {
+    pub(super) counter: DrawCounter,
+    plies: usize,
+}
+
+impl CounterStatus {
+    fn remaining(&self) -> usize {
+        (self.counter.limit() * 2).saturating_sub(self.plies)
+    }
+
+    pub(super) fn is_draw(&self) -> bool {
+        self.remaining() == 0
+    }
+}
+
+impl Display for CounterStatus {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let name = match self.counter {
+            DrawCounter::NoProgress(moves) => format!("{}-move rule", moves),
+            DrawCounter::LoneKing { pieces, .. } => format!("{} against a lone king", pieces),
+        };
+        write!(
+            f,
+            "{}: {} of {} moves",
+            name,
+            self.plies / 2,
+            self.counter.limit()
+        )
+    }
+}
+
+//The running counter closest to drawing the game
+pub(super) fn active_counter(
+    counters: &[DrawCounter],
+    board: &Board,
+    move_history: &[PastMove],
+    starts: &CounterStarts,
+) -> Option<CounterStatus> {
+    counters
+        .iter()
+        .enumerate()
+        .filter(|(_, counter)| counter.applies(board))
+        .map(|(idx, counter)| CounterStatus {
+            counter: *counter,
+            plies: counter.plies(move_history, starts.get(idx)),
+        })
+        .filter(|status| status.plies > 0)
+        .min_by_key(|status| status.remaining())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::constants::games;
+    use crate::draughts::controller::Controller;
+    use crate::draughts::Square::*;
+    use crate::system::PlayState;
+    use serial_test::serial;
+
+    fn king_move(player: Player) -> PastMove {
+        let piece = match player {
+            Player::Human => HumanKing,
+            Player::Computer => ComputerKing,
+        };
+        PastMove::new(player, 1, vec![10], 0, 0, piece, false)
+    }
+
+    fn board_with(pieces: &[(usize, Square)]) -> Board {
+        let mut board = vec![Empty; 100];
+        for (idx, square) in pieces {
+            board[*idx] = *square;
+        }
+        board
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_no_progress() {
+        set_board_size((10, 10));
+        let counters = [DrawCounter::NoProgress(25)];
+        let starts = CounterStarts::default();
+        let board = board_with(&[(1, HumanKing), (3, HumanMan), (98, ComputerKing)]);
+        let mut history = vec![PastMove::new(
+            Player::Human,
+            61,
+            vec![52],
+            0,
+            0,
+            HumanMan,
+            false,
+        )];
+        assert_eq!(active_counter(&counters, &board, &history, &starts), None);
+
+        history.extend((0..49).map(|i| king_move([Player::Human, Player::Computer][i % 2])));
+        let status = active_counter(&counters, &board, &history, &starts).unwrap();
+        assert_eq!(status.to_string(), "25-move rule: 24 of 25 moves");
+        assert!(!status.is_draw());
+        history.push(king_move(Player::Computer));
+        assert!(active_counter(&counters, &board, &history, &starts)
+            .unwrap()
+            .is_draw());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_lone_king() {
+        set_board_size((10, 10));
+        let counters = [
+            DrawCounter::NoProgress(25),
+            DrawCounter::LoneKing {
+                pieces: 3,
+                moves: 16,
+            },
+            DrawCounter::LoneKing {
+                pieces: 2,
+                moves: 5,
+            },
+        ];
+        let history: Vec<PastMove> = (0..10).map(|_| king_move(Player::Human)).collect();
+        let three = board_with(&[
+            (1, HumanKing),
+            (3, HumanMan),
+            (5, HumanMan),
+            (98, ComputerKing),
+        ]);
+        let mut starts = CounterStarts::default();
+        starts.update(&counters, &three, 0);
+        let status = active_counter(&counters, &three, &history, &starts).unwrap();
+        assert_eq!(
+            status.counter,
+            DrawCounter::LoneKing {
+                pieces: 3,
+                moves: 16
+            }
+        );
+
+        let two = board_with(&[(1, HumanKing), (98, ComputerKing)]);
+        starts.update(&counters, &two, 0);
+        assert!(active_counter(&counters, &two, &history, &starts)
+            .unwrap()
+            .is_draw());
+
+        //Without a king the stronger side can still be trying to crown
+        let men = board_with(&[(61, HumanMan), (63, HumanMan), (98, ComputerKing)]);
+        assert_eq!(
+            active_counter(&counters, &men, &history, &CounterStarts::default())
+                .unwrap()
+                .counter,
+            DrawCounter::NoProgress(25)
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_lone_king_after_promotion() {
+        set_board_size((10, 10));
+        let counters = [DrawCounter::LoneKing {
+            pieces: 2,
+            moves: 5,
+        }];
+        let mut starts = CounterStarts::default();
+        let mut history = vec![];
+        //Moving men towards the far row doesn't count
+        let men = board_with(&[(21, HumanMan), (61, HumanMan), (98, ComputerKing)]);
+        starts.update(&counters, &men, 0);
+        for i in 0..6 {
+            history.push(match i % 2 {
+                0 => PastMove::new(Player::Human, 31, vec![21], 0, 0, HumanMan, false),
+                _ => king_move(Player::Computer),
+            });
+            starts.update(&counters, &men, history.len());
+        }
+        assert_eq!(active_counter(&counters, &men, &history, &starts), None);
+
+        //Crowning starts the count without any capture
+        history.push(PastMove::new(
+            Player::Human,
+            12,
+            vec![1],
+            0,
+            0,
+            HumanMan,
+            true,
+        ));
+        let crowned = board_with(&[(1, HumanKing), (61, HumanMan), (98, ComputerKing)]);
+        starts.update(&counters, &crowned, history.len());
+        for i in 0..4 {
+            history.push(king_move([Player::Computer, Player::Human][i % 2]));
+            starts.update(&counters, &crowned, history.len());
+        }
+        let status = active_counter(&counters, &crowned, &history, &starts).unwrap();
+        assert_eq!(status.to_string(), "2 against a lone king: 2 of 5 moves");
+        assert!(!status.is_draw());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_repetition() {
+        let record = "[FEN \"W:WK32:BK1\"]\n1. 32-27 1-6 2. 27-32 6-1 3. 32-27 1-6";
+        let controller = Controller::from_record(games::DRAUGHTS_ENGLISH, record).unwrap();
+        assert!(controller.state.play_state.is_playing());
+
+        let record = format!("{} 4. 27-32 6-1", record);
+        let controller = Controller::from_record(games::DRAUGHTS_ENGLISH, &record).unwrap();
+        assert_eq!(controller.state.play_state, PlayState::Draw);
+    }
+}
@@ -0,0 +1,649 @@
+use crate::boards::set_board_size;
+use crate::draughts::ai::{choose_move, Table};
+use crate::draughts::moves::Move;
+use crate::draughts::pdn::parse_square;
+use crate::draughts::rules::{all_moves, apply_move, GameVariant, RuleSet};
+use crate::draughts::Square::*;
+use crate::draughts::{board_index_to_pdn_num, Board, Square, Strength};
+use crate::system::zobrist::Zobrist;
+use crate::system::Player;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{TcpListener, TcpStream};
+use std::time::Instant;
+
+//Draughts eXchange Protocol, lets the international draughts AI play other programs over TCP
+//Messages are a type letter followed by fixed width fields and end with a null byte
+//White (W) starts at the bottom of the board (squares 31-50) and moves first, black is Z (zwart)
+//White is the human side of the board as far as the rules are concerned
+
+const VARIANT: GameVariant = GameVariant::International;
+const VERSION: &str = "01";
+const NAME_WIDTH: usize = 32;
+const SQUARES: usize = 50;
+const TABLE_SIZE: usize = 1 << 18;
+//Offered when starting games
+const DEFAULT_MINUTES: usize = 10;
+const DEFAULT_MOVES: usize = 75;
+
+//GAMEACC codes
+const ACCEPT: char = '0';
+//BACKACC codes
+const BACK_NOT_SUPPORTED: char = '1';
+//GAMEEND reasons, from the point of view of the sender
+const END_UNKNOWN: char = '0';
+const END_LOSS: char = '1';
+const END_WIN: char = '3';
+//GAMEEND stop codes
+const STOP_NEXT_GAME: char = '0';
+const STOP_NO_MORE: char = '1';
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) enum Message {
+    GameReq {
+        name: String,
+        follower: Player,
+        minutes: usize,
+        moves: usize,
+        //Side to move and board when not starting from the normal position
+        setup: Option<(Player, Board)>,
+    },
+    GameAcc {
+        name: String,
+        code: char,
+    },
+    //Squares are numbered 1-50, captured squares can be in any order
+    Move {
+        seconds: usize,
+        from: usize,
+        to: usize,
+        captured: Vec<usize>,
+    },
+    GameEnd {
+        reason: char,
+        stop: char,
+    },
+    Chat(String),
+    BackReq {
+        move_number: usize,
+        to_move: Player,
+    },
+    BackAcc {
+        code: char,
+    },
+}
+
+fn colour_letter(player: Player) -> char {
+    match player {
+        Player::Human => 'W',
+        Player::Computer => 'Z',
+    }
+}
+
+fn parse_colour(letter: char) -> Result<Player, String> {
+    match letter {
+        'W' => Ok(Player::Human),
+        'Z' => Ok(Player::Computer),
+        _ => Err(format!("Invalid colour: {}", letter)),
+    }
+}
+
+fn board_text(board: &Board) -> String {
+    (1..=SQUARES)
+        .map(
+            |num| match parse_square(VARIANT, &num.to_string()).map(|idx| board[idx]) {
+                Ok(HumanMan) => 'w',
+                Ok(HumanKing) => 'W',
+                Ok(ComputerMan) => 'z',
+                Ok(ComputerKing) => 'Z',
+                _ => 'e',
+            },
+        )
+        .collect()
+}
+
+fn parse_board(text: &str) -> Result<Board, String> {
+    let mut board = vec![Empty; SQUARES * 2];
+    for (i, chr) in text.chars().enumerate() {
+        board[parse_square(VARIANT, &(i + 1).to_string())?] = match chr {
+            'w' => HumanMan,
+            'W' => HumanKing,
+            'z' => ComputerMan,
+            'Z' => ComputerKing,
+            'e' => Empty,
+            _ => return Err(format!("Invalid square in position: {}", chr)),
+        };
+    }
+    Ok(board)
+}
+
+fn name_field(name: &str) -> String {
+    let name: String = name.chars().take(NAME_WIDTH).collect();
+    format!("{:<width$}", name, width = NAME_WIDTH)
+}
+
+impl Message {
+    pub(super) fn encode(&self) -> String {
+        match self {
+            Message::GameReq {
+                name,
+                follower,
+                minutes,
+                moves,
+                setup,
+            } => {
+                let setup = match setup {
+                    None => String::from("A"),
+                    Some((to_move, board)) => {
+                        format!("B{}{}", colour_letter(*to_move), board_text(board))
+                    }
+                };
+                format!(
+                    "R{}{}{}{:03}{:03}{}",
+                    VERSION,
+                    name_field(name),
+                    colour_letter(*follower),
+                    minutes,
+                    moves,
+                    setup
+                )
+            }
+            Message::GameAcc { name, code } => format!("A{}{}", name_field(name), code),
+            Message::Move {
+                seconds,
+                from,
+                to,
+                captured,
+            } => {
+                let squares: String = captured.iter().map(|num| format!("{:02}", num)).collect();
+                format!(
+                    "M{:04}{:02}{:02}{:02}{}",
+                    (*seconds).min(9999),
+                    from,
+                    to,
+                    captured.len(),
+                    squares
+                )
+            }
+            Message::GameEnd { reason, stop } => format!("E{}{}", reason, stop),
+            Message::Chat(text) => format!("C{}", text),
+            Message::BackReq {
+                move_number,
+                to_move,
+            } => format!("B{:03}{}", move_number, colour_letter(*to_move)),
+            Message::BackAcc { code } => format!("K{}", code),
+        }
+    }
+
+    pub(super) fn parse(text: &str) -> Result<Message, String> {
+        let field = |start: usize, len: usize| {
+            text.get(start..start + len)
+                .ok_or_else(|| format!("Message too short: {}", text))
+        };
+        let number = |start: usize, len: usize| -> Result<usize, String> {
+            field(start, len)?
+                .trim()
+                .parse()
+                .map_err(|_| format!("Invalid number in message: {}", text))
+        };
+        let letter = |start: usize| field(start, 1).map(|chr| chr.chars().next().unwrap_or(' '));
+        match text.chars().next() {
+            Some('R') => {
+                if field(1, 2)? != VERSION {
+                    return Err(format!("Unsupported DXP version: {}", field(1, 2)?));
+                }
+                let setup = match letter(42)? {
+                    'A' => None,
+                    'B' => Some((
+                        parse_colour(letter(43)?)?,
+                        parse_board(field(44, SQUARES)?)?,
+                    )),
+                    other => return Err(format!("Invalid starting position: {}", other)),
+                };
+                Ok(Message::GameReq {
+                    name: field(3, NAME_WIDTH)?.trim().to_string(),
+                    follower: parse_colour(letter(35)?)?,
+                    minutes: number(36, 3)?,
+                    moves: number(39, 3)?,
+                    setup,
+                })
+            }
+            Some('A') => Ok(Message::GameAcc {
+                name: field(1, NAME_WIDTH)?.trim().to_string(),
+                code: letter(1 + NAME_WIDTH)?,
+            }),
+            Some('M') => {
+                let count = number(9, 2)?;
+                Ok(Message::Move {
+                    seconds: number(1, 4)?,
+                    from: number(5, 2)?,
+                    to: number(7, 2)?,
+                    captured: (0..count)
+                        .map(|i| number(11 + i * 2, 2))
+                        .collect::<Result<Vec<usize>, String>>()?,
+                })
+            }
+            Some('E') => Ok(Message::GameEnd {
+                reason: letter(1)?,
+                stop: letter(2)?,
+            }),
+            Some('C') => Ok(Message::Chat(text[1..].to_string())),
+            Some('B') => Ok(Message::BackReq {
+                move_number: number(1, 3)?,
+                to_move: parse_colour(letter(4)?)?,
+            }),
+            Some('K') => Ok(Message::BackAcc { code: letter(1)? }),
+            _ => Err(format!("Unknown message: {}", text)),
+        }
+    }
+}
+
+pub(super) fn move_message(mov: &Move, seconds: usize) -> Message {
+    Message::Move {
+        seconds,
+        from: board_index_to_pdn_num(mov.origin()),
+        to: board_index_to_pdn_num(mov.dest()),
+        captured: mov
+            .captures()
+            .iter()
+            .map(|capture| board_index_to_pdn_num(capture.capturing))
+            .collect(),
+    }
+}
+
+//Whether mov is the DXP move, captured squares can be given in any order
+pub(super) fn matches_message(mov: &Move, from: usize, to: usize, captured: &[usize]) -> bool {
+    let mut expected: Vec<usize> = captured.to_vec();
+    let mut actual: Vec<usize> = mov
+        .captures()
+        .iter()
+        .map(|capture| board_index_to_pdn_num(capture.capturing))
+        .collect();
+    expected.sort_unstable();
+    actual.sort_unstable();
+    board_index_to_pdn_num(mov.origin()) == from
+        && board_index_to_pdn_num(mov.dest()) == to
+        && expected == actual
+}
+
+struct Game {
+    rules: Box<dyn RuleSet>,
+    zobrist: Zobrist,
+    table: Table,
+    board: Board,
+    to_move: Player,
+    //Side played by this program
+    engine: Player,
+    depth: usize,
+}
+
+impl Game {
+    fn new(engine: Player, setup: Option<(Player, Board)>, depth: usize) -> Self {
+        let (to_move, board) = setup.unwrap_or_else(|| (Player::Human, VARIANT.get_init_board()));
+        Game {
+            rules: VARIANT.get_rules(),
+            zobrist: Zobrist::new::<Square>(board.len()),
+            table: Table::new(TABLE_SIZE),
+            board,
+            to_move,
+            engine,
+            depth,
+        }
+    }
+
+    fn moves(&self) -> Vec<Move> {
+        all_moves(self.rules.as_ref(), &self.board, self.to_move)
+    }
+
+    fn play(&mut self, mov: &Move) {
+        apply_move(self.rules.as_ref(), &mut self.board, mov);
+        self.to_move = self.to_move.opposite();
+    }
+
+    fn find_move(&self, from: usize, to: usize, captured: &[usize]) -> Result<Move, String> {
+        self.moves()
+            .into_iter()
+            .find(|mov| matches_message(mov, from, to, captured))
+            .ok_or_else(|| {
+                format!(
+                    "Illegal move from {} to {} capturing {:?}",
+                    from, to, captured
+                )
+            })
+    }
+
+    fn choose(&mut self) -> Option<Move> {
+        choose_move(
+            self.rules.as_ref(),
+            &self.zobrist,
+            &mut self.table,
+            &self.board,
+            self.to_move,
+            self.depth,
+        )
+    }
+}
+
+struct Connection {
+    stream: TcpStream,
+    reader: BufReader<TcpStream>,
+}
+
+impl Connection {
+    fn new(stream: TcpStream) -> Result<Self, String> {
+        let reader = stream
+            .try_clone()
+            .map(BufReader::new)
+            .map_err(|err| err.to_string())?;
+        Ok(Connection { stream, reader })
+    }
+
+    fn send(&mut self, message: &Message) -> Result<(), String> {
+        let text = message.encode();
+        debug_log!("DXP sent {}", text);
+        self.stream
+            .write_all(format!("{}\0", text).as_bytes())
+            .map_err(|err| format!("Could not send: {}", err))
+    }
+
+    //None if the peer disconnected
+    fn receive(&mut self) -> Result<Option<Message>, String> {
+        let mut bytes = vec![];
+        let count = self
+            .reader
+            .read_until(0, &mut bytes)
+            .map_err(|err| format!("Could not receive: {}", err))?;
+        if count == 0 {
+            return Ok(None);
+        }
+        let text = String::from_utf8_lossy(&bytes);
+        let text = text.trim_end_matches('\0');
+        debug_log!("DXP received {}", text);
+        Message::parse(text).map(Some)
+    }
+}
+
+//Waits for the peer to acknowledge the end of the game, returns true if they want another
+fn finish_game(conn: &mut Connection) -> Result<bool, String> {
+    loop {
+        match conn.receive()? {
+            None => return Ok(false),
+            Some(Message::GameEnd { stop, .. }) => return Ok(stop == STOP_NEXT_GAME),
+            Some(Message::Chat(_)) | Some(Message::Move { .. }) => {}
+            Some(other) => return Err(format!("Unexpected message: {:?}", other)),
+        }
+    }
+}
+
+//Returns true if the peer wants another game
+fn play_game(conn: &mut Connection, game: &mut Game) -> Result<bool, String> {
+    loop {
+        if game.moves().is_empty() {
+            //A player who can't move has lost
+            let reason = if game.to_move == game.engine {
+                END_LOSS
+            } else {
+                END_WIN
+            };
+            conn.send(&Message::GameEnd {
+                reason,
+                stop: STOP_NEXT_GAME,
+            })?;
+            return finish_game(conn);
+        }
+        if game.to_move == game.engine {
+            let start = Instant::now();
+            let mov = game.choose().ok_or("No move found")?;
+            game.play(&mov);
+            conn.send(&move_message(&mov, start.elapsed().as_secs() as usize))?;
+            continue;
+        }
+        match conn.receive()? {
+            None => return Err(String::from("Peer disconnected")),
+            Some(Message::Move {
+                from, to, captured, ..
+            }) => match game.find_move(from, to, &captured) {
+                Ok(mov) => game.play(&mov),
+                Err(err) => {
+                    conn.send(&Message::GameEnd {
+                        reason: END_UNKNOWN,
+                        stop: STOP_NO_MORE,
+                    })?;
+                    return Err(err);
+                }
+            },
+            Some(Message::GameEnd { stop, .. }) => {
+                conn.send(&Message::GameEnd {
+                    reason: END_UNKNOWN,
+                    stop,
+                })?;
+                return Ok(stop == STOP_NEXT_GAME);
+            }
+            //Taking back moves isn't supported
+            Some(Message::BackReq { .. }) => conn.send(&Message::BackAcc {
+                code: BACK_NOT_SUPPORTED,
+            })?,
+            Some(Message::Chat(text)) => debug_log!("DXP chat: {}", text),
+            Some(other) => return Err(format!("Unexpected message: {:?}", other)),
+        }
+    }
+}
+
+//Plays every game the peer requests on the connection
+fn serve(stream: TcpStream, depth: usize) -> Result<(), String> {
+    let mut conn = Connection::new(stream)?;
+    loop {
+        match conn.receive()? {
+            None => return Ok(()),
+            Some(Message::GameReq {
+                name,
+                follower,
+                setup,
+                ..
+            }) => {
+                debug_log!("DXP game requested by {}", name);
+                conn.send(&Message::GameAcc {
+                    name: String::from(env!("CARGO_PKG_NAME")),
+                    code: ACCEPT,
+                })?;
+                let mut game = Game::new(follower, setup, depth);
+                if !play_game(&mut conn, &mut game)? {
+                    return Ok(());
+                }
+            }
+            Some(Message::Chat(_)) => {}
+            Some(other) => return Err(format!("Unexpected message: {:?}", other)),
+        }
+    }
+}
+
+//Waits for other programs to connect and request games
+pub fn run_server(addr: &str) -> Result<(), String> {
+    set_board_size(VARIANT.get_board_size());
+    let listener =
+        TcpListener::bind(addr).map_err(|err| format!("Could not listen on {}: {}", addr, err))?;
+    println!("Waiting for DXP connections on {}", addr);
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                if let Err(err) = serve(stream, Strength::Hard.depth()) {
+                    eprintln!("{}", err);
+                }
+            }
+            Err(err) => eprintln!("Connection failed: {}", err),
+        }
+    }
+    Ok(())
+}
+
+//Connects to another program and plays one game as white
+pub fn run_client(addr: &str) -> Result<(), String> {
+    set_board_size(VARIANT.get_board_size());
+    let stream = TcpStream::connect(addr)
+        .map_err(|err| format!("Could not connect to {}: {}", addr, err))?;
+    let mut conn = Connection::new(stream)?;
+    conn.send(&Message::GameReq {
+        name: String::from(env!("CARGO_PKG_NAME")),
+        follower: Player::Computer,
+        minutes: DEFAULT_MINUTES,
+        moves: DEFAULT_MOVES,
+        setup: None,
+    })?;
+    match conn.receive()? {
+        Some(Message::GameAcc { code: ACCEPT, name }) => {
+            println!("Playing {} as white", name)
+        }
+        Some(Message::GameAcc { code, .. }) => return Err(format!("Game refused ({})", code)),
+        other => return Err(format!("Unexpected reply: {:?}", other)),
+    }
+    let mut game = Game::new(Player::Human, None, Strength::Hard.depth());
+    play_game(&mut conn, &mut game)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::draughts::moves::Capture;
+    use serial_test::serial;
+    use std::thread;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_messages() {
+        set_board_size(VARIANT.get_board_size());
+        let req = Message::GameReq {
+            name: String::from("Peer"),
+            follower: Player::Computer,
+            minutes: 10,
+            moves: 75,
+            setup: None,
+        };
+        let text = req.encode();
+        assert_eq!(text.len(), 43);
+        assert!(text.starts_with("R01Peer "));
+        assert!(text.ends_with("Z010075A"));
+        assert_eq!(Message::parse(&text), Ok(req));
+
+        let board = VARIANT.get_init_board();
+        let setup = Message::GameReq {
+            name: String::from("Peer"),
+            follower: Player::Human,
+            minutes: 1,
+            moves: 50,
+            setup: Some((Player::Computer, board)),
+        };
+        let text = setup.encode();
+        assert!(text.ends_with(&format!(
+            "BZ{}{}{}",
+            "z".repeat(20),
+            "e".repeat(10),
+            "w".repeat(20)
+        )));
+        assert_eq!(Message::parse(&text), Ok(setup));
+
+        let mov = Message::Move {
+            seconds: 3,
+            from: 32,
+            to: 12,
+            captured: vec![27, 17],
+        };
+        assert_eq!(mov.encode(), "M00033212022717");
+        assert_eq!(Message::parse("M00033212022717"), Ok(mov));
+        assert_eq!(
+            Message::parse("E31"),
+            Ok(Message::GameEnd {
+                reason: END_WIN,
+                stop: STOP_NO_MORE
+            })
+        );
+        assert_eq!(
+            Message::parse("B012W"),
+            Ok(Message::BackReq {
+                move_number: 12,
+                to_move: Player::Human
+            })
+        );
+        assert!(Message::parse("R02").is_err());
+        assert!(Message::parse("M12").is_err());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_move_mapping() {
+        set_board_size(VARIANT.get_board_size());
+        let idx = |num: usize| parse_square(VARIANT, &num.to_string()).unwrap();
+        let mov = Move::MultiJump {
+            origin: idx(32),
+            captures: vec![
+                Capture {
+                    dest: idx(21),
+                    capturing: idx(27),
+                },
+                Capture {
+                    dest: idx(12),
+                    capturing: idx(17),
+                },
+            ],
+            value: 0,
+        };
+        assert_eq!(
+            move_message(&mov, 1),
+            Message::Move {
+                seconds: 1,
+                from: 32,
+                to: 12,
+                captured: vec![27, 17]
+            }
+        );
+        assert!(matches_message(&mov, 32, 12, &[17, 27]));
+        assert!(!matches_message(&mov, 32, 12, &[17]));
+        assert!(!matches_message(&mov, 32, 21, &[27, 17]));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_game_with_local_peer() {
+        set_board_size(VARIANT.get_board_size());
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        let server = thread::spawn(move || serve(listener.accept().unwrap().0, 2));
+
+        //Stand in peer playing white
+        let mut peer = Connection::new(TcpStream::connect(addr).unwrap()).unwrap();
+        peer.send(&Message::GameReq {
+            name: String::from("Peer"),
+            follower: Player::Computer,
+            minutes: 1,
+            moves: 10,
+            setup: None,
+        })
+        .unwrap();
+        assert!(matches!(
+            peer.receive(),
+            Ok(Some(Message::GameAcc { code: ACCEPT, .. }))
+        ));
+        let mut mirror = Game::new(Player::Human, None, 2);
+        let opening = mirror.find_move(32, 28, &[]).unwrap();
+        mirror.play(&opening);
+        peer.send(&move_message(&opening, 0)).unwrap();
+        match peer.receive().unwrap() {
+            Some(Message::Move {
+                from, to, captured, ..
+            }) => assert!(mirror.find_move(from, to, &captured).is_ok()),
+            other => panic!("Expected a move, got {:?}", other),
+        }
+        peer.send(&Message::GameEnd {
+            reason: END_UNKNOWN,
+            stop: STOP_NO_MORE,
+        })
+        .unwrap();
+        assert_eq!(
+            peer.receive(),
+            Ok(Some(Message::GameEnd {
+                reason: END_UNKNOWN,
+                stop: STOP_NO_MORE
+            }))
+        );
+        assert_eq!(server.join().unwrap(), Ok(()));
+    }
+}
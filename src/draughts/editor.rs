@@ -0,0 +1,143 @@
+use crate::boards::board_cols;
+use crate::boards::idx_coord::BoardCoord;
+use crate::draughts::controller::Controller;
+use crate::draughts::pdn::Setup;
+use crate::draughts::Square::*;
+use crate::draughts::{Board, Phase, Square};
+use crate::system::Player;
+use ggez::event::KeyCode;
+
+//Position editor, reached from the setup screen
+pub(super) const CLEAR_KEY: KeyCode = KeyCode::C;
+pub(super) const RESET_KEY: KeyCode = KeyCode::R;
+pub(super) const SIDE_KEY: KeyCode = KeyCode::T;
+pub(super) const START_KEY: KeyCode = KeyCode::Space;
+
+//Order pieces are placed in when Return is pressed repeatedly
+fn next_piece(square: Square) -> Square {
+    match square {
+        Empty => HumanMan,
+        HumanMan => HumanKing,
+        HumanKing => ComputerMan,
+        ComputerMan => ComputerKing,
+        ComputerKing => Empty,
+    }
+}
+
+//Returns why the position can't be played from, if it can't
+pub(super) fn check_position(board: &Board) -> Result<(), String> {
+    let has = |player: Player| {
+        board
+            .iter()
+            .any(|square| *square != Empty && Player::from(*square) == player)
+    };
+    if !has(Player::Human) || !has(Player::Computer) {
+        return Err(String::from("Both sides need pieces"));
+    }
+    let cols = board_cols();
+    if board[..cols].contains(&HumanMan) || board[board.len() - cols..].contains(&ComputerMan) {
+        return Err(String::from("Men can't start on the far row"));
+    }
+    Ok(())
+}
+
+impl Controller {
+    fn is_playable_square(&self, idx: usize) -> bool {
+        let BoardCoord(x, y) = BoardCoord::from(idx);
+        self.variant.uses_all_squares() || (x + y) % 2 == 1
+    }
+
+    pub(super) fn handle_editor_input(&mut self, key: KeyCode) {
+        if self.state.piece_cursor.handle_input(key) {
+            return;
+        }
+        let idx = self.state.piece_cursor.idx;
+        match key {
+            KeyCode::Return if self.is_playable_square(idx) => {
+                self.state.board[idx] = next_piece(self.state.board[idx])
+            }
+            KeyCode::Back | KeyCode::Delete => self.state.board[idx] = Empty,
+            CLEAR_KEY => self
+                .state
+                .board
+                .iter_mut()
+                .for_each(|square| *square = Empty),
+            RESET_KEY => self.state.board = self.variant.get_init_board(),
+            SIDE_KEY => self.state.edit_to_move = self.state.edit_to_move.opposite(),
+            START_KEY => self.start_from_editor(),
+            _ => {}
+        }
+    }
+
+    fn start_from_editor(&mut self) {
+        match check_position(&self.state.board) {
+            Ok(_) => {
+                let to_move = self.state.edit_to_move;
+                self.state.start_position = Some(Setup {
+                    board: self.state.board.clone(),
+                    to_move,
+                });
+                self.state.message = None;
+                self.state.phase = Phase::Playing;
+                self.start_new_turn(to_move);
+            }
+            Err(err) => self.state.message = Some(err),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::constants::games;
+    use crate::system::NEW_TURN_COMPUTER;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_check_position() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        board[37] = HumanMan;
+        assert!(check_position(&board).is_err());
+        board[26] = ComputerKing;
+        assert!(check_position(&board).is_ok());
+        board[1] = HumanMan;
+        assert!(check_position(&board).is_err());
+        board[1] = HumanKing;
+        assert!(check_position(&board).is_ok());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_editing() {
+        let mut controller = Controller::new(games::DRAUGHTS_ENGLISH);
+        controller.state.phase = Phase::Editing;
+        controller.handle_editor_input(CLEAR_KEY);
+        //Cursor starts on a light square
+        controller.handle_editor_input(KeyCode::Return);
+        assert_eq!(controller.state.board[0], Empty);
+        controller.handle_editor_input(KeyCode::Right);
+        controller.handle_editor_input(KeyCode::Return);
+        controller.handle_editor_input(KeyCode::Return);
+        assert_eq!(controller.state.board[1], HumanKing);
+        controller.handle_editor_input(START_KEY);
+        assert!(controller.state.message.is_some());
+        assert_eq!(controller.state.phase, Phase::Editing);
+
+        controller.state.board[53] = ComputerMan;
+        controller.handle_editor_input(SIDE_KEY);
+        controller.handle_editor_input(START_KEY);
+        assert_eq!(controller.state.phase, Phase::Playing);
+        assert_eq!(controller.state.play_state, NEW_TURN_COMPUTER);
+        assert_eq!(
+            controller
+                .state
+                .start_position
+                .as_ref()
+                .map(|setup| setup.to_move),
+            Some(Player::Computer)
+        );
+    }
+}
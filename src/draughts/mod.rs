@@ -3,14 +3,29 @@ use std::fmt;
 use std::fmt::{Display, Formatter};
 
 use crate::boards::chessboard::ChessBoard;
-use crate::boards::cursor::Cursor;
+use crate::boards::cursor::{movable, Cursor};
+use crate::boards::move_entry::{EntryAction, EntryOptions, MoveEntry};
+use crate::draughts::ai::{Table, KING_VALUE, MAN_VALUE};
+use crate::draughts::animation::Animation;
+use crate::draughts::draw::{CounterStarts, CounterStatus};
 use crate::draughts::moves::Move;
+use crate::draughts::pdn::{move_text, square_name, Setup};
+use crate::draughts::problems::ProblemSet;
+use crate::draughts::rules::GameVariant;
+use crate::system::zobrist::{PositionHistory, Zobrist, ZobristSquare};
 use crate::system::Player::{Computer, Human};
 use crate::system::{PlayState, Player};
 
 mod ai;
+mod animation;
 pub mod controller;
+mod draw;
+pub mod dxp;
+mod editor;
 mod moves;
+mod pdn;
+mod problems;
+mod render_mode_selection;
 mod renderer;
 mod rules;
 
@@ -29,6 +44,20 @@ impl Square {
     }
 }
 
+impl ZobristSquare for Square {
+    const KINDS: usize = 4;
+
+    fn zobrist_kind(&self) -> Option<usize> {
+        match self {
+            Square::HumanMan => Some(0),
+            Square::HumanKing => Some(1),
+            Square::ComputerMan => Some(2),
+            Square::ComputerKing => Some(3),
+            Square::Empty => None,
+        }
+    }
+}
+
 #[derive(Debug)]
 struct PastMove {
     player: Player,
@@ -89,6 +118,48 @@ impl Display for PastMove {
 
 type Board = Vec<Square>;
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Phase {
+    //Choosing the computer's strength and starting position
+    Setup,
+    //Placing pieces for a custom starting position
+    Editing,
+    Playing,
+}
+
+//Rows on the setup screen
+const SETUP_STRENGTH: usize = 0;
+const SETUP_POSITION: usize = 1;
+
+//How far ahead the computer looks
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Strength {
+    Easy,
+    Medium,
+    Hard,
+}
+
+impl Strength {
+    const ALL: [Strength; 3] = [Strength::Easy, Strength::Medium, Strength::Hard];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Strength::Easy => "Easy",
+            Strength::Medium => "Medium",
+            Strength::Hard => "Hard",
+        }
+    }
+
+    //Plies searched before only captures are followed
+    fn depth(&self) -> usize {
+        match self {
+            Strength::Easy => 2,
+            Strength::Medium => 4,
+            Strength::Hard => 7,
+        }
+    }
+}
+
 impl From<Square> for Player {
     fn from(square: Square) -> Self {
         match square {
@@ -116,11 +187,91 @@ struct State {
     all_possible_moves: HashMap<usize, Vec<Move>>,
     move_cursor: usize,
     move_history: Vec<PastMove>,
+    //Moves in the order played, for saving as PDN
+    moves_played: Vec<Move>,
+    //Set when the game didn't start from the normal position
+    start_position: Option<Setup>,
     next_move_time: f64,
     last_human_cursor_pos: usize,
+    message: Option<String>,
+    strength: Strength,
+    zobrist: Zobrist,
+    ai_table: Table,
+    phase: Phase,
+    setup_row: usize,
+    //Whether the editor is opened after the setup screen
+    custom_position: bool,
+    //Side to move first from the edited position
+    edit_to_move: Player,
+    //Set when solving problems instead of playing a game
+    problems: Option<ProblemSet>,
+    //Multi-jump being shown hop by hop, the board already has the move applied
+    animation: Option<Animation>,
+    //Positions reached with the player to move, for spotting repetitions
+    positions: PositionHistory,
+    //Endgame counter closest to drawing the game
+    draw_counter: Option<CounterStatus>,
+    //When each draw counter started, as the endgames can be reached without a capture
+    counter_starts: CounterStarts,
+    entry: MoveEntry,
 }
 
 impl State {
+    //Squares and moves the player can type, as in PDN (e.g. 32-28 or 28x19x10, captures can be
+    //shortened to the start and end if that's unambiguous) or algebraically (e.g. c3xe5xg7)
+    fn entry_options(&self, variant: GameVariant) -> EntryOptions<Move> {
+        let algebraic = |squares: &[usize], separator: &str| {
+            squares
+                .iter()
+                .map(|idx| self.board_calc.idx_to_coord(*idx).unwrap_or_default())
+                .collect::<Vec<String>>()
+                .join(separator)
+        };
+        let moves: Vec<&Move> = self.all_possible_moves.values().flatten().collect();
+        let mut options = vec![];
+        for origin in movable(&self.all_possible_moves) {
+            options.push((
+                square_name(variant, origin),
+                EntryAction::SelectPiece(origin),
+            ));
+            if !variant.uses_all_squares() {
+                options.push((algebraic(&[origin], ""), EntryAction::SelectPiece(origin)));
+            }
+        }
+        for mov in &moves {
+            let action = EntryAction::PlayMove((*mov).clone());
+            let mut path = vec![mov.origin()];
+            let separator = match mov {
+                Move::Step { dest, .. } => {
+                    path.push(*dest);
+                    "-"
+                }
+                _ => {
+                    path.extend(mov.captures().iter().map(|capture| capture.dest));
+                    "x"
+                }
+            };
+            options.push((move_text(variant, mov), action.clone()));
+            let same_ends = moves
+                .iter()
+                .filter(|other| other.origin() == mov.origin() && other.dest() == mov.dest())
+                .count();
+            if path.len() > 2 && same_ends == 1 {
+                let ends = [mov.origin(), mov.dest()];
+                let short = ends
+                    .iter()
+                    .map(|idx| square_name(variant, *idx))
+                    .collect::<Vec<String>>()
+                    .join(separator);
+                options.push((short, action.clone()));
+            }
+            if !variant.uses_all_squares() {
+                options.push((algebraic(&path, separator), action));
+            }
+        }
+        options
+    }
+
     //Safe, will return empty vec for empty/other players squares
     fn get_moves_for_selected_piece(&self) -> Vec<Move> {
         self.all_possible_moves
@@ -141,11 +292,34 @@ impl State {
             })
             .clone()
     }
+
+    //Returns the number of (men, kings) captured by player
+    fn captured_counts(&self, player: Player) -> (usize, usize) {
+        self.move_history
+            .iter()
+            .filter(|mov| mov.player == player)
+            .fold((0, 0), |(men, kings), mov| {
+                (men + mov.man_capture_count, kings + mov.king_capture_count)
+            })
+    }
+
+    //Positive if the human has captured more material than the computer, in men with kings weighted as the AI does
+    fn material_balance(&self) -> isize {
+        let value = |player| {
+            let (men, kings) = self.captured_counts(player);
+            (men as i32 * MAN_VALUE + kings as i32 * KING_VALUE) / MAN_VALUE
+        };
+        (value(Human) - value(Computer)) as isize
+    }
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::constants::games;
+    use crate::draughts::controller::Controller;
+    use crate::system::zobrist::{PositionHistory, Zobrist};
+    use serial_test::serial;
 
     #[test]
     fn test_board_index_to_pdn_num() {
@@ -157,4 +331,58 @@ mod test {
         assert_eq!(board_index_to_pdn_num(35), 18);
         assert_eq!(board_index_to_pdn_num(53), 27);
     }
+
+    #[test]
+    fn test_zobrist_repetition() {
+        let zobrist = Zobrist::new::<Square>(4);
+        let mut history = PositionHistory::default();
+        let start = [
+            Square::HumanMan,
+            Square::Empty,
+            Square::Empty,
+            Square::ComputerKing,
+        ];
+        let moved = [
+            Square::Empty,
+            Square::HumanMan,
+            Square::Empty,
+            Square::ComputerKing,
+        ];
+        let promoted = [
+            Square::Empty,
+            Square::HumanKing,
+            Square::Empty,
+            Square::ComputerKing,
+        ];
+
+        assert_ne!(
+            zobrist.hash(&moved, Player::Computer),
+            zobrist.hash(&promoted, Player::Computer)
+        );
+
+        for _ in 0..2 {
+            history.push(zobrist.hash(&start, Player::Human));
+            history.push(zobrist.hash(&moved, Player::Computer));
+        }
+        history.push(zobrist.hash(&start, Player::Human));
+        assert!(history.is_repeated(3));
+    }
+    #[test]
+    #[serial(board_size)]
+    fn test_entry_options() {
+        let record = "[FEN \"W:W30,21:B26,18,1\"]";
+        let controller = Controller::from_record(games::DRAUGHTS_ENGLISH, record).unwrap();
+        let options = controller.state.entry_options(GameVariant::English);
+        let texts: Vec<&str> = options.iter().map(|(text, _)| text.as_str()).collect();
+        //The capture is compulsory so it's the only move
+        assert_eq!(texts, vec!["30", "c1", "30x23x14", "30x14", "c1xe3xc5"]);
+        assert_eq!(options[1].1, EntryAction::SelectPiece(58));
+
+        let mut entry = MoveEntry::new();
+        "c1xe".chars().for_each(|chr| {
+            entry.push(chr, &options);
+        });
+        assert_eq!(entry.completion, Some(String::from("3xc5")));
+        assert_eq!(entry.submit(&options), Some(&options[2].1));
+    }
 }
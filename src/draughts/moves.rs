@@ -49,26 +49,6 @@ impl Move {
         }
     }
 
-    pub(super) fn value(&self) -> usize {
-        *match self {
-            Move::Step {
-                origin: _,
-                dest: _,
-                value,
-            } => value,
-            Move::Jump {
-                origin: _,
-                capture: _,
-                value,
-            } => value,
-            Move::MultiJump {
-                origin: _,
-                captures: _,
-                value,
-            } => value,
-        }
-    }
-
     pub(super) fn dest(&self) -> usize {
         match self {
             Move::Step {
@@ -113,6 +93,15 @@ impl Move {
         }
     }
 
+    //Captures in the order they're made, empty for steps
+    pub(super) fn captures(&self) -> Vec<Capture> {
+        match self {
+            Move::Step { .. } => vec![],
+            Move::Jump { capture, .. } => vec![capture.clone()],
+            Move::MultiJump { captures, .. } => captures.clone(),
+        }
+    }
+
     pub(super) fn len(&self) -> usize {
         match self {
             Move::Step { .. } => 1,
@@ -0,0 +1,468 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::{board_cols, board_rows};
+use crate::draughts::moves::Move;
+use crate::draughts::rules::GameVariant;
+use crate::draughts::Square::*;
+use crate::draughts::{board_index_to_pdn_num, Board, Square};
+use crate::system::Player;
+
+//Portable Draughts Notation
+//The human always moves first and is recorded as white, the computer as black
+
+const RESULTS: [&str; 7] = ["1-0", "0-1", "1/2-1/2", "2-0", "0-2", "1-1", "*"];
+
+//Position the game started from if it wasn't the normal start
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) struct Setup {
+    pub(super) board: Board,
+    pub(super) to_move: Player,
+}
+
+#[derive(Debug)]
+pub(super) struct Record {
+    pub(super) variant: GameVariant,
+    pub(super) setup: Option<Setup>,
+    //Squares each move visits, starting with the origin
+    pub(super) moves: Vec<Vec<usize>>,
+    pub(super) result: Option<String>,
+}
+
+pub(super) fn square_name(variant: GameVariant, idx: usize) -> String {
+    if variant.uses_all_squares() {
+        let BoardCoord(x, y) = BoardCoord::from(idx);
+        format!("{}{}", (b'a' + x as u8) as char, board_rows() - y)
+    } else {
+        board_index_to_pdn_num(idx).to_string()
+    }
+}
+
+pub(super) fn parse_square(variant: GameVariant, text: &str) -> Result<usize, String> {
+    let invalid = || format!("Invalid square: {}", text);
+    let idx = if variant.uses_all_squares() {
+        let mut chars = text.chars();
+        let file = chars
+            .next()
+            .filter(char::is_ascii_lowercase)
+            .ok_or_else(invalid)?;
+        let rank: usize = chars.as_str().parse().map_err(|_| invalid())?;
+        let x = (file as u8 - b'a') as usize;
+        if x >= board_cols() || rank == 0 || rank > board_rows() {
+            return Err(invalid());
+        }
+        x + (board_rows() - rank) * board_cols()
+    } else {
+        let num: usize = text.parse().map_err(|_| invalid())?;
+        //Half the squares are dark
+        if num == 0 || num > board_cols() * board_rows() / 2 {
+            return Err(invalid());
+        }
+        //Numbers count the dark squares from the top left
+        let idx = (num - 1) * 2;
+        let BoardCoord(x, y) = BoardCoord::from(idx);
+        if (x + y) % 2 == 1 {
+            idx
+        } else {
+            idx + 1
+        }
+    };
+    if idx < board_cols() * board_rows() {
+        Ok(idx)
+    } else {
+        Err(invalid())
+    }
+}
+
+pub(super) fn move_text(variant: GameVariant, mov: &Move) -> String {
+    let origin = square_name(variant, mov.origin());
+    match mov {
+        Move::Step { dest, .. } => format!("{}-{}", origin, square_name(variant, *dest)),
+        Move::Jump { .. } | Move::MultiJump { .. } => {
+            let hops: Vec<String> = mov
+                .captures()
+                .iter()
+                .map(|capture| square_name(variant, capture.dest))
+                .collect();
+            format!("{}x{}", origin, hops.join("x"))
+        }
+    }
+}
+
+fn side_letter(player: Player) -> char {
+    match player {
+        Player::Human => 'W',
+        Player::Computer => 'B',
+    }
+}
+
+//e.g. W:W31,32,K22:B1,2 for white to move with men on 31 and 32, a king on 22 and black men on 1 and 2
+pub(super) fn to_fen(variant: GameVariant, setup: &Setup) -> String {
+    let pieces = |man: Square, king: Square| -> String {
+        setup
+            .board
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, square)| {
+                if *square == man {
+                    Some(square_name(variant, idx))
+                } else if *square == king {
+                    Some(format!("K{}", square_name(variant, idx)))
+                } else {
+                    None
+                }
+            })
+            .collect::<Vec<String>>()
+            .join(",")
+    };
+    format!(
+        "{}:W{}:B{}",
+        side_letter(setup.to_move),
+        pieces(HumanMan, HumanKing),
+        pieces(ComputerMan, ComputerKing)
+    )
+}
+
+pub(super) fn parse_fen(variant: GameVariant, text: &str) -> Result<Setup, String> {
+    let mut sections = text.trim().split(':');
+    let to_move = match sections.next().map(str::trim) {
+        Some("W") => Player::Human,
+        Some("B") => Player::Computer,
+        other => return Err(format!("Invalid side to move in FEN: {:?}", other)),
+    };
+    let mut board = vec![Empty; board_cols() * board_rows()];
+    for section in sections {
+        let section = section.trim();
+        let (man, king) = match section.chars().next() {
+            Some('W') => (HumanMan, HumanKing),
+            Some('B') => (ComputerMan, ComputerKing),
+            _ => return Err(format!("Invalid FEN section: {}", section)),
+        };
+        for item in section[1..].split(',').map(str::trim) {
+            if item.is_empty() {
+                continue;
+            }
+            let (piece, squares) = match item.strip_prefix('K') {
+                Some(squares) => (king, squares),
+                None => (man, item),
+            };
+            //Ranges such as 1-12 can be used for square numbers
+            let range: Vec<&str> = squares.split('-').collect();
+            let squares = match range.as_slice() {
+                [first, last] if !variant.uses_all_squares() => {
+                    let first: usize = first
+                        .parse()
+                        .map_err(|_| format!("Invalid range: {}", item))?;
+                    let last: usize = last
+                        .parse()
+                        .map_err(|_| format!("Invalid range: {}", item))?;
+                    (first..=last).map(|num| num.to_string()).collect()
+                }
+                _ => vec![squares.to_string()],
+            };
+            for square in squares {
+                board[parse_square(variant, &square)?] = piece;
+            }
+        }
+    }
+    Ok(Setup { board, to_move })
+}
+
+pub(super) fn to_pdn(
+    variant: GameVariant,
+    setup: Option<&Setup>,
+    moves: &[Move],
+    result: Option<&str>,
+) -> String {
+    let result = result.unwrap_or("*");
+    let mut lines = vec![
+        String::from("[Event \"Casual game\"]"),
+        String::from("[White \"Human\"]"),
+        String::from("[Black \"Computer\"]"),
+        format!("[GameType \"{}\"]", variant.get_game_type()),
+    ];
+    if let Some(setup) = setup {
+        lines.push(format!("[FEN \"{}\"]", to_fen(variant, setup)));
+    }
+    lines.push(format!("[Result \"{}\"]", result));
+    lines.push(String::new());
+
+    //Plies are counted from white's first move so black starting skips one
+    let offset = match setup.map(|setup| setup.to_move) {
+        Some(Player::Computer) => 1,
+        _ => 0,
+    };
+    let mut tokens = vec![];
+    for (i, mov) in moves.iter().enumerate() {
+        let ply = i + offset;
+        if ply % 2 == 0 {
+            tokens.push(format!("{}.", ply / 2 + 1));
+        } else if i == 0 {
+            tokens.push(String::from("1..."));
+        }
+        tokens.push(move_text(variant, mov));
+    }
+    tokens.push(result.to_string());
+    //Keep lines a readable length
+    let mut line = String::new();
+    for token in tokens {
+        if !line.is_empty() && line.len() + token.len() >= 80 {
+            lines.push(line);
+            line = String::new();
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&token);
+    }
+    lines.push(line);
+    lines.push(String::new());
+    lines.join("\n")
+}
+
+//Splits a file of several games where each starts with its tags
+pub(super) fn split_games(text: &str) -> Vec<String> {
+    let mut games = vec![];
+    let mut current = String::new();
+    let mut in_moves = false;
+    for line in text.lines() {
+        let is_tag = line.trim_start().starts_with('[');
+        if is_tag && in_moves {
+            games.push(current);
+            current = String::new();
+            in_moves = false;
+        }
+        if !is_tag && !line.trim().is_empty() {
+            in_moves = true;
+        }
+        current.push_str(line);
+        current.push('\n');
+    }
+    if !current.trim().is_empty() {
+        games.push(current);
+    }
+    games
+}
+
+enum Token {
+    Tag(String, String),
+    Text(String),
+}
+
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = vec![];
+    let mut chars = text.chars().peekable();
+    while let Some(chr) = chars.next() {
+        match chr {
+            '[' => {
+                let tag: String = chars.by_ref().take_while(|chr| *chr != ']').collect();
+                let (name, value) = tag
+                    .trim()
+                    .split_once(char::is_whitespace)
+                    .ok_or_else(|| format!("Invalid tag: [{}]", tag))?;
+                tokens.push(Token::Tag(
+                    name.to_string(),
+                    value.trim().trim_matches('"').to_string(),
+                ));
+            }
+            '{' => {
+                chars.by_ref().find(|chr| *chr == '}');
+            }
+            //Variations are skipped
+            '(' => {
+                let mut depth = 1;
+                for chr in chars.by_ref() {
+                    match chr {
+                        '(' => depth += 1,
+                        ')' => depth -= 1,
+                        _ => {}
+                    }
+                    if depth == 0 {
+                        break;
+                    }
+                }
+            }
+            chr if chr.is_whitespace() => {}
+            chr => {
+                let mut text = chr.to_string();
+                while let Some(next) = chars.peek() {
+                    if next.is_whitespace() || "[{(".contains(*next) {
+                        break;
+                    }
+                    text.push(*next);
+                    chars.next();
+                }
+                tokens.push(Token::Text(text));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+//Records without a GameType tag are assumed to be for the default variant
+pub(super) fn parse_pdn(text: &str, default: GameVariant) -> Result<Record, String> {
+    let tokens = tokenize(text)?;
+    let mut variant = default;
+    let mut fen = None;
+    let mut result = None;
+    let mut moves = vec![];
+    let mut move_texts = vec![];
+    for token in tokens {
+        match token {
+            Token::Tag(name, value) => match name.as_str() {
+                "GameType" => {
+                    let game_type = value
+                        .split(',')
+                        .next()
+                        .and_then(|num| num.trim().parse().ok())
+                        .ok_or_else(|| format!("Invalid GameType: {}", value))?;
+                    variant = GameVariant::from_game_type(game_type)
+                        .ok_or_else(|| format!("Unsupported GameType: {}", game_type))?;
+                }
+                "FEN" => fen = Some(value),
+                "Result" if value != "*" => result = Some(value),
+                _ => {}
+            },
+            Token::Text(text) if RESULTS.contains(&text.as_str()) => {
+                if text != "*" {
+                    result = Some(text);
+                }
+            }
+            Token::Text(text) => {
+                //Drop move numbers (1. or 1...) and annotations such as ! or ?
+                let text = text.rsplit('.').next().unwrap_or("");
+                let text = text.trim_end_matches(['!', '?']);
+                if !text.is_empty() {
+                    move_texts.push(text.to_string());
+                }
+            }
+        }
+    }
+    for text in move_texts {
+        let squares = text
+            .split(['-', 'x'])
+            .map(|square| parse_square(variant, square))
+            .collect::<Result<Vec<usize>, String>>()
+            .map_err(|err| format!("Invalid move {}: {}", text, err))?;
+        if squares.len() < 2 {
+            return Err(format!("Invalid move: {}", text));
+        }
+        moves.push(squares);
+    }
+    let setup = match fen {
+        Some(fen) => Some(parse_fen(variant, &fen)?),
+        None => None,
+    };
+    Ok(Record {
+        variant,
+        setup,
+        moves,
+        result,
+    })
+}
+
+//Whether the move visits the squares, captures can leave out the squares in between
+pub(super) fn matches_squares(mov: &Move, squares: &[usize]) -> bool {
+    let mut visited = vec![mov.origin()];
+    match mov {
+        Move::Step { dest, .. } => visited.push(*dest),
+        _ => visited.extend(mov.captures().iter().map(|capture| capture.dest)),
+    }
+    if squares.len() == 2 {
+        visited.first() == squares.first() && visited.last() == squares.last()
+    } else {
+        visited == squares
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::moves::Capture;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_squares() {
+        set_board_size((8, 8));
+        assert_eq!(parse_square(GameVariant::English, "1"), Ok(1));
+        assert_eq!(parse_square(GameVariant::English, "5"), Ok(8));
+        assert_eq!(parse_square(GameVariant::English, "32"), Ok(62));
+        assert!(parse_square(GameVariant::English, "33").is_err());
+        assert!(parse_square(GameVariant::English, "18446744073709551615").is_err());
+        for idx in [1, 8, 35, 62].iter() {
+            let name = square_name(GameVariant::Russian, *idx);
+            assert_eq!(parse_square(GameVariant::Russian, &name), Ok(*idx));
+        }
+        assert_eq!(square_name(GameVariant::Turkish, 56), "a1");
+        assert_eq!(parse_square(GameVariant::Turkish, "h8"), Ok(7));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_fen() {
+        set_board_size((10, 10));
+        let setup = parse_fen(GameVariant::International, "B:W31-33,K46:BK5,18").unwrap();
+        assert_eq!(setup.to_move, Player::Computer);
+        assert_eq!(setup.board.iter().filter(|sq| **sq == HumanMan).count(), 3);
+        assert_eq!(
+            setup.board[parse_square(GameVariant::International, "46").unwrap()],
+            HumanKing
+        );
+        assert_eq!(
+            setup.board[parse_square(GameVariant::International, "5").unwrap()],
+            ComputerKing
+        );
+        assert_eq!(
+            to_fen(GameVariant::International, &setup),
+            "B:W31,32,33,K46:BK5,18"
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_round_trip() {
+        set_board_size((8, 8));
+        let moves = vec![
+            Move::Step {
+                origin: 42,
+                dest: 35,
+                value: 1,
+            },
+            Move::MultiJump {
+                origin: 17,
+                captures: vec![
+                    Capture {
+                        dest: 35,
+                        capturing: 26,
+                    },
+                    Capture {
+                        dest: 53,
+                        capturing: 44,
+                    },
+                ],
+                value: 20,
+            },
+        ];
+        let text = to_pdn(GameVariant::English, None, &moves, Some("0-1"));
+        assert!(text.contains("[GameType \"21\"]"));
+        assert!(text.contains("1. 22-18 9x18x27 0-1"));
+        let record = parse_pdn(&text, GameVariant::International).unwrap();
+        assert_eq!(record.variant, GameVariant::English);
+        assert_eq!(record.result.as_deref(), Some("0-1"));
+        assert_eq!(record.moves, vec![vec![42, 35], vec![17, 35, 53]]);
+        assert!(matches_squares(&moves[1], &[17, 53]));
+        assert!(matches_squares(&moves[1], &record.moves[1]));
+        assert!(!matches_squares(&moves[0], &[42, 33]));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_parse_comments() {
+        set_board_size((8, 8));
+        let text = "[Event \"Test\"]\n1. 11-15 {good} 23-19 (1... 9-13) 2. 8-11! *";
+        let record = parse_pdn(text, GameVariant::English).unwrap();
+        assert_eq!(record.moves.len(), 3);
+        assert_eq!(record.result, None);
+        assert!(parse_pdn("[GameType \"99\"]", GameVariant::English).is_err());
+    }
+}
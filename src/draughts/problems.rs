@@ -0,0 +1,158 @@
+use crate::draughts::pdn::{parse_pdn, split_games, Setup};
+use crate::draughts::rules::GameVariant;
+use crate::draughts::Square;
+use crate::draughts::Square::*;
+use crate::system::Player;
+
+//Composed problems are read from PDN files, each game needs a FEN tag for the position
+//and has the solution (including the replies) as its moves
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) struct Problem {
+    pub(super) setup: Setup,
+    //Squares each move of the solution visits, the human plays every other move
+    pub(super) solution: Vec<Vec<usize>>,
+}
+
+#[derive(Debug)]
+pub(super) struct ProblemSet {
+    pub(super) problems: Vec<Problem>,
+    pub(super) current: usize,
+    //Moves of the current solution played so far
+    pub(super) progress: usize,
+}
+
+impl ProblemSet {
+    pub(super) fn new(problems: Vec<Problem>) -> Self {
+        ProblemSet {
+            problems,
+            current: 0,
+            progress: 0,
+        }
+    }
+
+    pub(super) fn problem(&self) -> &Problem {
+        &self.problems[self.current]
+    }
+
+    pub(super) fn expected(&self) -> Option<&Vec<usize>> {
+        self.problem().solution.get(self.progress)
+    }
+
+    pub(super) fn is_solved(&self) -> bool {
+        self.progress >= self.problem().solution.len()
+    }
+
+    pub(super) fn is_last(&self) -> bool {
+        self.current + 1 >= self.problems.len()
+    }
+}
+
+fn swap_sides(square: Square) -> Square {
+    match square {
+        HumanMan => ComputerMan,
+        HumanKing => ComputerKing,
+        ComputerMan => HumanMan,
+        ComputerKing => HumanKing,
+        Empty => Empty,
+    }
+}
+
+//The human always solves from the bottom of the board so problems for the other
+//side are turned around
+fn orient(setup: Setup, solution: Vec<Vec<usize>>) -> Problem {
+    if setup.to_move == Player::Human {
+        return Problem { setup, solution };
+    }
+    let last = setup.board.len() - 1;
+    let board = setup
+        .board
+        .iter()
+        .rev()
+        .map(|square| swap_sides(*square))
+        .collect();
+    let solution = solution
+        .into_iter()
+        .map(|squares| squares.into_iter().map(|idx| last - idx).collect())
+        .collect();
+    Problem {
+        setup: Setup {
+            board,
+            to_move: Player::Human,
+        },
+        solution,
+    }
+}
+
+pub(super) fn parse_problems(text: &str, variant: GameVariant) -> Result<Vec<Problem>, String> {
+    let mut problems = vec![];
+    for (i, game) in split_games(text).iter().enumerate() {
+        let err = |msg: String| format!("Problem {}: {}", i + 1, msg);
+        let record = parse_pdn(game, variant).map_err(err)?;
+        if record.variant != variant {
+            return Err(err(format!("is for {}", record.variant.get_game())));
+        }
+        let setup = record
+            .setup
+            .ok_or_else(|| err(String::from("no FEN position")))?;
+        if record.moves.is_empty() {
+            return Err(err(String::from("no solution")));
+        }
+        problems.push(orient(setup, record.moves));
+    }
+    if problems.is_empty() {
+        return Err(String::from("No problems found"));
+    }
+    Ok(problems)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::pdn::parse_square;
+    use serial_test::serial;
+
+    const PROBLEMS: &str = r#"[Event "Double jump"]
+[FEN "W:W27:B23,14"]
+1. 27x18x9
+
+[Event "Black to play"]
+[FEN "B:W14:BK1"]
+1... 1-5 2. 14-10 *
+"#;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_parse_problems() {
+        set_board_size((8, 8));
+        let variant = GameVariant::English;
+        let idx = |num: usize| parse_square(variant, &num.to_string()).unwrap();
+        let problems = parse_problems(PROBLEMS, variant).unwrap();
+        assert_eq!(problems.len(), 2);
+        assert_eq!(problems[0].setup.board[idx(27)], HumanMan);
+        assert_eq!(problems[0].solution, vec![vec![idx(27), idx(18), idx(9)]]);
+
+        //Turned around so the human plays the black pieces from the bottom
+        let flipped = &problems[1];
+        assert_eq!(flipped.setup.to_move, Player::Human);
+        assert_eq!(flipped.setup.board[63 - idx(1)], HumanKing);
+        assert_eq!(flipped.setup.board[63 - idx(14)], ComputerMan);
+        assert_eq!(flipped.solution[0], vec![63 - idx(1), 63 - idx(5)]);
+
+        let mut set = ProblemSet::new(problems);
+        assert_eq!(set.expected(), Some(&vec![idx(27), idx(18), idx(9)]));
+        set.progress = 1;
+        assert!(set.is_solved());
+        assert!(!set.is_last());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_invalid_problems() {
+        set_board_size((8, 8));
+        assert!(parse_problems("1. 11-15 *", GameVariant::English).is_err());
+        assert!(parse_problems("[FEN \"W:W27:B18\"]", GameVariant::English).is_err());
+        assert!(parse_problems("", GameVariant::English).is_err());
+    }
+}
@@ -0,0 +1,69 @@
+use crate::constants::colors::{LIGHT_BLUE, LIGHT_GRAY, WHITE};
+use crate::constants::Direction;
+use crate::draughts::{State, Strength, SETUP_POSITION};
+use crate::system::math::{pt, Offset};
+use crate::system::mesh_helper::MeshHelper;
+use ggez::{Context, GameResult};
+
+pub(super) fn render_mode_selection(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let cursor = mesh_helper.make_triangle(ctx, 12., 12., Direction::Right)?;
+    let start = pt(mesh_helper.calc_width(0.2), mesh_helper.calc_height(0.3));
+    let row_height = mesh_helper.calc_height(0.12);
+    let option_width = mesh_helper.calc_width(0.15);
+
+    let rows: [(&str, Vec<(&str, bool)>); 2] = [
+        (
+            "Computer",
+            Strength::ALL
+                .iter()
+                .map(|strength| (strength.name(), *strength == state.strength))
+                .collect(),
+        ),
+        (
+            "Position",
+            vec![
+                ("Standard", !state.custom_position),
+                ("Custom", state.custom_position),
+            ],
+        ),
+    ];
+
+    for (row, (title, options)) in rows.iter().enumerate() {
+        let row_start = start.offset(0., row_height * row as f32);
+        mesh_helper.draw_white_text(ctx, title, row_start, 20., false);
+        if row == state.setup_row {
+            mesh_helper.draw_mesh(ctx, cursor.as_ref(), row_start.offset(-20., 6.));
+        }
+        for (i, (name, selected)) in options.iter().enumerate() {
+            mesh_helper.draw_text(
+                ctx,
+                name,
+                row_start.offset(option_width * (i + 1) as f32, 0.),
+                if *selected { LIGHT_BLUE } else { LIGHT_GRAY },
+                20.,
+                false,
+            );
+        }
+    }
+
+    mesh_helper.draw_text(
+        ctx,
+        &if state.setup_row == SETUP_POSITION && state.custom_position {
+            String::from("Place the pieces before starting")
+        } else {
+            format!("Looks {} moves ahead", state.strength.depth())
+        },
+        mesh_helper
+            .center()
+            .offset(0., mesh_helper.calc_height(0.12)),
+        WHITE,
+        16.,
+        true,
+    );
+
+    Ok(())
+}
@@ -1,13 +1,20 @@
+use crate::boards::cursor::movable;
 use crate::boards::idx_coord::BoardCoord;
 use crate::boards::{board_cols, board_rows};
 use crate::constants::colors::{
-    alpha, CREAM, DARK_GREEN, LIGHT_BLUE, PIECE_COMPUTER, PIECE_HUMAN, TRANSPARENT,
+    alpha, CREAM, DARK_GREEN, LIGHT_BLUE, LIGHT_GRAY, PIECE_COMPUTER, PIECE_HUMAN, TRANSPARENT,
+    WHITE,
 };
+use crate::draughts::controller::{NEXT_PROBLEM_KEY, SAVE_KEY};
+use crate::draughts::editor::{CLEAR_KEY, RESET_KEY, SIDE_KEY, START_KEY};
 use crate::draughts::moves::Move::*;
-use crate::draughts::{board_index_to_pdn_num, Square, State};
+use crate::draughts::render_mode_selection::render_mode_selection;
+use crate::draughts::{board_index_to_pdn_num, Phase, Square, State};
+use crate::system::captured_panel::{self, CAPTURED_PIECE_SIZE};
 use crate::system::letter_mesh::make_letter_mesh;
-use crate::system::math::{pt, Point, Offset, OffsetTuple};
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
 use crate::system::mesh_helper::MeshHelper;
+use crate::system::Player;
 use crate::system::TurnState::{SelectingMove, SelectingPiece};
 use ggez::graphics::{Color, DrawMode, Mesh};
 use ggez::{Context, GameResult};
@@ -26,11 +33,17 @@ pub const COMPUTER_MOVE_PIECE: Color = Color {
     a: 1.,
 };
 
+//Limited so the move list doesn't run into the captured pieces panel
+const HISTORY_LINES: usize = 60;
+
 pub(super) fn render(
     ctx: &mut Context,
     mesh_helper: &mut MeshHelper,
     state: &State,
 ) -> GameResult<()> {
+    if state.phase == Phase::Setup {
+        return render_mode_selection(ctx, mesh_helper, state);
+    }
     let board_size = mesh_helper.calc_height(0.9);
     let cell_size = board_size / (state.board_calc.rows as f32);
     let grid = mesh_helper.make_grid(
@@ -55,6 +68,18 @@ pub(super) fn render(
     let piece = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.4, DrawMode::fill())?;
     let king = make_letter_mesh(ctx, mesh_helper, cell_size * 0.8, 'k')?;
 
+    //Shown above the board as the captured panel can fill the left side
+    if let Some(status) = &state.draw_counter {
+        mesh_helper.draw_text(
+            ctx,
+            &status.to_string(),
+            board_start.offsety(-28.),
+            LIGHT_GRAY,
+            16.,
+            false,
+        );
+    }
+
     mesh_helper.draw_mesh(ctx, grid.as_ref(), board_start);
     mesh_helper.draw_mesh(ctx, grid_box.as_ref(), board_start);
 
@@ -90,39 +115,67 @@ pub(super) fn render(
         }
     }
 
-    state.board.iter().enumerate().for_each(|(i, item)| {
+    let board = state
+        .animation
+        .as_ref()
+        .map(|animation| &animation.board)
+        .unwrap_or(&state.board);
+    for (i, square) in board.iter().enumerate() {
         let xy = Point::from(BoardCoord::from(i))
             .multiply(cell_size, cell_size)
             .offset_point(board_start);
+        let opacity = state
+            .animation
+            .as_ref()
+            .and_then(|animation| {
+                let hop = animation.captured.iter().position(|idx| *idx == i)?;
+                Some(animation.captured_alpha(hop))
+            })
+            .unwrap_or(1.);
+        draw_square(
+            ctx,
+            mesh_helper,
+            &piece,
+            &king,
+            *square,
+            xy,
+            cell_size,
+            opacity,
+        );
+    }
 
-        match item {
-            Square::ComputerMan => {
-                mesh_helper.draw_coloured_mesh(ctx, piece.as_ref(), xy, PIECE_COMPUTER)
-            }
-            Square::ComputerKing => {
-                mesh_helper.draw_coloured_mesh(ctx, piece.as_ref(), xy, PIECE_COMPUTER);
-                mesh_helper.draw_mesh(
-                    ctx,
-                    king.as_ref(),
-                    xy.offset(cell_size * 0.1, cell_size * 0.1),
-                );
-            }
-            Square::HumanMan => {
-                mesh_helper.draw_coloured_mesh(ctx, piece.as_ref(), xy, PIECE_HUMAN)
-            }
-            Square::HumanKing => {
-                mesh_helper.draw_coloured_mesh(ctx, piece.as_ref(), xy, PIECE_HUMAN);
-                mesh_helper.draw_mesh(
-                    ctx,
-                    king.as_ref(),
-                    xy.offset(cell_size * 0.1, cell_size * 0.1),
-                );
-            }
-            Square::Empty => {}
-        }
-    });
+    if let Some(animation) = &state.animation {
+        let (from, to, progress) = animation.position();
+        let from = Point::from(BoardCoord::from(from));
+        let to = Point::from(BoardCoord::from(to));
+        let xy = pt(
+            from.x + (to.x - from.x) * progress,
+            from.y + (to.y - from.y) * progress,
+        )
+        .multiply(cell_size, cell_size)
+        .offset_point(board_start);
+        draw_square(
+            ctx,
+            mesh_helper,
+            &piece,
+            &king,
+            animation.piece,
+            xy,
+            cell_size,
+            1.,
+        );
+    }
 
-    if state.play_state.is_human(SelectingPiece) {
+    if state.play_state.is_human(SelectingPiece) || state.phase == Phase::Editing {
+        if state.phase == Phase::Playing {
+            state.piece_cursor.render_movable(
+                ctx,
+                mesh_helper,
+                board_start,
+                cell_size,
+                &movable(&state.all_possible_moves),
+            )?;
+        }
         state
             .piece_cursor
             .render(ctx, mesh_helper, board_start, cell_size)?;
@@ -237,14 +290,111 @@ pub(super) fn render(
         .collect::<Vec<String>>()
         .join("\n");
 
-    let offset = if state.move_history.len() > 80 {
-        (state.move_history.len() - 80) as f32 * 12.
+    let offset = if state.move_history.len() > HISTORY_LINES {
+        (state.move_history.len() - HISTORY_LINES) as f32 * 12.
     } else {
         0.
     };
 
     mesh_helper.draw_white_text(ctx, &moves, pt(8., 8. - offset), 12., false);
 
+    draw_captured_panel(ctx, mesh_helper, state)?;
+
+    if state.phase == Phase::Playing
+        && (state.play_state.is_human(SelectingPiece) || state.play_state.is_human(SelectingMove))
+    {
+        state
+            .entry
+            .render(ctx, mesh_helper, pt(8., mesh_helper.height - 96.));
+    }
+
+    if let Some(message) = &state.message {
+        mesh_helper.draw_text(
+            ctx,
+            message,
+            pt(8., mesh_helper.height - 48.),
+            CREAM,
+            16.,
+            false,
+        );
+    }
+    let hint = if state.phase == Phase::Editing {
+        let side = match state.edit_to_move {
+            Player::Human => "You",
+            Player::Computer => "Computer",
+        };
+        format!(
+            "Return - Place piece, Back - Remove, {:?} - Clear, {:?} - Reset, {:?} - To move ({}), {:?} - Start",
+            CLEAR_KEY, RESET_KEY, SIDE_KEY, side, START_KEY
+        )
+    } else if state.problems.is_some() {
+        format!(
+            "{:?} - Next problem, {:?} - Save PDN",
+            NEXT_PROBLEM_KEY, SAVE_KEY
+        )
+    } else {
+        format!("{:?} - Save PDN", SAVE_KEY)
+    };
+    mesh_helper.draw_text(
+        ctx,
+        &hint,
+        pt(8., mesh_helper.height - 24.),
+        LIGHT_GRAY,
+        14.,
+        false,
+    );
+
+    Ok(())
+}
+
+fn draw_captured_panel(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let piece = mesh_helper.make_circle(
+        ctx,
+        CAPTURED_PIECE_SIZE,
+        CAPTURED_PIECE_SIZE * 0.4,
+        DrawMode::fill(),
+    )?;
+    let king = make_letter_mesh(ctx, mesh_helper, CAPTURED_PIECE_SIZE * 0.8, 'k')?;
+
+    let captured = |player, title| {
+        let (men, kings) = state.captured_counts(player);
+        (
+            format!("{} - {} men, {} kings", title, men, kings),
+            men + kings,
+        )
+    };
+    let rows = [
+        captured(Player::Human, "You"),
+        captured(Player::Computer, "Computer"),
+    ];
+    captured_panel::draw_captured_panel(
+        ctx,
+        mesh_helper,
+        rows,
+        None,
+        state.material_balance(),
+        |ctx, mesh_helper, player, i, xy| {
+            //Captured pieces are drawn in the colour of the side they were taken from
+            let colour = match player {
+                Player::Human => PIECE_COMPUTER,
+                Player::Computer => PIECE_HUMAN,
+            };
+            mesh_helper.draw_coloured_mesh(ctx, piece.as_ref(), xy, colour);
+            if i < state.captured_counts(player).1 {
+                mesh_helper.draw_mesh(
+                    ctx,
+                    king.as_ref(),
+                    xy.offset(CAPTURED_PIECE_SIZE * 0.1, CAPTURED_PIECE_SIZE * 0.1),
+                );
+            }
+            Ok(())
+        },
+    )?;
+
     Ok(())
 }
 
@@ -262,3 +412,30 @@ fn draw_piece(
         .offset_point(board_start);
     mesh_helper.draw_coloured_mesh(ctx, mesh.as_ref(), xy, color);
 }
+
+#[allow(clippy::too_many_arguments)]
+fn draw_square(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    piece: &Rc<Mesh>,
+    king: &Rc<Mesh>,
+    square: Square,
+    xy: Point,
+    cell_size: f32,
+    opacity: f32,
+) {
+    let color = match square {
+        Square::ComputerMan | Square::ComputerKing => PIECE_COMPUTER,
+        Square::HumanMan | Square::HumanKing => PIECE_HUMAN,
+        Square::Empty => return,
+    };
+    mesh_helper.draw_coloured_mesh(ctx, piece.as_ref(), xy, alpha(color, opacity));
+    if square.is_king() {
+        mesh_helper.draw_coloured_mesh(
+            ctx,
+            king.as_ref(),
+            xy.offset(cell_size * 0.1, cell_size * 0.1),
+            alpha(WHITE, opacity),
+        );
+    }
+}
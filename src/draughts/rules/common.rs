@@ -1,11 +1,36 @@
+use crate::boards::{board_cols, board_rows};
 use crate::draughts::moves::{Capture, Move};
-use crate::draughts::rules::VALUE_CAPTURE;
+use crate::draughts::rules::{CAPTURABLE, VALUE_CAPTURE, VALUE_STEP};
 use crate::draughts::Square::*;
-use crate::draughts::{Board, PastMove, Square};
+use crate::draughts::{Board, Square};
+use crate::system::PlayState;
+use crate::system::PlayState::{ComputerWin, HumanWin};
 use crate::system::Player;
+use crate::system::Player::{Computer, Human};
 use crate::tree::Leaf;
 
+//Every move for the piece, only the longest captures are kept
 pub(super) fn calc_valid_moves<F>(board: &Board, origin: usize, moves_for_square: F) -> Vec<Move>
+where
+    F: Fn(&Board, usize, bool) -> Vec<Move>,
+{
+    let moves = calc_all_moves(board, origin, false, moves_for_square);
+    let longest = moves.iter().map(|mov| mov.len()).max().unwrap_or(0);
+    moves
+        .into_iter()
+        .filter(|mov| mov.len() == longest)
+        .collect()
+}
+
+//Every move for the piece including every complete capture sequence, if any captures are possible then
+//only captures are returned
+//If promote_mid_capture is set then a man passing over the far row continues capturing as a king
+pub(super) fn calc_all_moves<F>(
+    board: &Board,
+    origin: usize,
+    promote_mid_capture: bool,
+    moves_for_square: F,
+) -> Vec<Move>
 where
     F: Fn(&Board, usize, bool) -> Vec<Move>,
 {
@@ -15,7 +40,7 @@ where
         return vec![];
     }
 
-    let mut first_leaf = create_move_tree(board, origin, &moves_for_square);
+    let mut first_leaf = create_move_tree(board, origin, promote_mid_capture, &moves_for_square);
     let mut move_lists = vec![];
     first_leaf.print(0, "");
     loop {
@@ -28,66 +53,51 @@ where
             break;
         }
     }
-    if move_lists.is_empty() {
-        debug_log_end!("No moves found");
-        return vec![];
-    }
-    let longest = move_lists.iter().map(|list| list.len()).max().unwrap();
-    debug_log!(
-        "Found {} potential moves, longest: {}",
-        move_lists.len(),
-        longest
-    );
-    move_lists = move_lists
+    debug_log_end!("Found {} potential moves", move_lists.len());
+    move_lists
         .into_iter()
-        .filter(|list| list.len() == longest)
-        .collect();
-
-    if longest > 1 {
-        debug_log_end!("Found {} multijumps", move_lists.len());
-        move_lists
-            .into_iter()
-            .map(|list| {
-                let mut captures: Vec<Capture> = list
-                    .iter()
-                    .map(|mov| {
-                        if let Move::Jump {
-                            origin: _,
-                            capture,
-                            value: _,
-                        } = mov
-                        {
-                            capture
-                        } else {
-                            panic!("Invalid move");
-                        }
-                    })
-                    .cloned()
-                    .collect();
-                captures.reverse();
-                let value = captures.len() * VALUE_CAPTURE;
-                Move::MultiJump {
-                    origin,
-                    captures,
-                    value,
-                }
-            })
-            .collect()
-    } else {
-        debug_log_end!("Found {} steps/jumps", move_lists.len());
-        move_lists
-            .into_iter()
-            .map(|mut list| list.remove(0))
-            .collect()
-    }
+        .map(|mut list| {
+            if list.len() == 1 {
+                return list.remove(0);
+            }
+            let mut captures: Vec<Capture> = list
+                .iter()
+                .map(|mov| {
+                    if let Move::Jump {
+                        origin: _,
+                        capture,
+                        value: _,
+                    } = mov
+                    {
+                        capture
+                    } else {
+                        panic!("Invalid move");
+                    }
+                })
+                .cloned()
+                .collect();
+            captures.reverse();
+            let value = captures.len() * VALUE_CAPTURE;
+            Move::MultiJump {
+                origin,
+                captures,
+                value,
+            }
+        })
+        .collect()
 }
 
-pub(super) fn create_move_tree<F>(board: &Board, origin: usize, moves_for_square: &F) -> Leaf<Move>
+pub(super) fn create_move_tree<F>(
+    board: &Board,
+    origin: usize,
+    promote_mid_capture: bool,
+    moves_for_square: &F,
+) -> Leaf<Move>
 where
     F: Fn(&Board, usize, bool) -> Vec<Move>,
 {
     debug_log!("Creating move tree from {}", origin);
-    let mut first_leaf = make_leaf(board, origin, false, moves_for_square);
+    let mut first_leaf = make_leaf(board, origin, false, promote_mid_capture, moves_for_square);
     let any_captures = first_leaf.contents.iter().any(|(mov, _)| mov.is_jump());
     if any_captures {
         debug_log!("At least one capture found");
@@ -108,6 +118,7 @@ pub(super) fn make_leaf<F>(
     board: &Board,
     origin: usize,
     capture_only: bool,
+    promote_mid_capture: bool,
     moves_for_square: &F,
 ) -> Leaf<Move>
 where
@@ -132,10 +143,19 @@ where
                 } => {
                     let mut new_board = board.clone();
                     new_board[capture.capturing] = Square::Empty;
-                    let piece = new_board[origin];
+                    let mut piece = new_board[origin];
+                    if promote_mid_capture {
+                        piece = promoted(piece, capture.dest, board.len());
+                    }
                     new_board[origin] = Square::Empty;
                     new_board[capture.dest] = piece;
-                    Some(make_leaf(&new_board, capture.dest, true, moves_for_square))
+                    Some(make_leaf(
+                        &new_board,
+                        capture.dest,
+                        true,
+                        promote_mid_capture,
+                        moves_for_square,
+                    ))
                 }
                 Move::MultiJump { .. } => panic!("Invalid move found at {}", origin),
             };
@@ -150,14 +170,73 @@ where
     Leaf::new(moves)
 }
 
-pub(super) fn check_moves<F>(move_history: &[PastMove], count: usize, predicate: F) -> bool
+//The piece after landing on dest, men on the far row become kings
+pub(super) fn promoted(piece: Square, dest: usize, board_len: usize) -> Square {
+    if piece == HumanMan && dest < board_cols() {
+        HumanKing
+    } else if piece == ComputerMan && dest >= board_len - board_cols() {
+        ComputerKing
+    } else {
+        piece
+    }
+}
+
+//If the player has any captures then only the ones capturing the most pieces can be played
+pub(super) fn longest_captures(moves: Vec<Move>) -> Vec<Move> {
+    let longest_capture = moves
+        .iter()
+        .filter(|mov| !mov.is_step())
+        .map(|mov| mov.len())
+        .max();
+    match longest_capture {
+        Some(longest) => moves
+            .into_iter()
+            .filter(|mov| !mov.is_step() && mov.len() == longest)
+            .collect(),
+        None => moves,
+    }
+}
+
+//Only captures can be played if there are any
+pub(super) fn any_captures(moves: Vec<Move>) -> Vec<Move> {
+    if moves.iter().any(|mov| !mov.is_step()) {
+        moves.into_iter().filter(|mov| !mov.is_step()).collect()
+    } else {
+        moves
+    }
+}
+
+//Number of kings taken by the move
+pub(super) fn kings_captured(board: &Board, mov: &Move) -> usize {
+    mov.captures()
+        .iter()
+        .filter(|capture| board[capture.capturing].is_king())
+        .count()
+}
+
+//Keeps the moves with the highest score
+pub(super) fn keep_best<F>(moves: Vec<Move>, score: F) -> Vec<Move>
 where
-    F: Fn(&PastMove) -> bool,
+    F: Fn(&Move) -> isize,
 {
-    if move_history.len() >= count {
-        move_history.iter().rev().take(count).all(predicate)
+    match moves.iter().map(&score).max() {
+        Some(best) => moves.into_iter().filter(|mov| score(mov) == best).collect(),
+        None => moves,
+    }
+}
+
+//Pieces run out, draws are checked separately
+pub(super) fn check_game_over(board: &Board) -> Option<PlayState> {
+    let human_count = get_piece_count(board, Human);
+    let computer_count = get_piece_count(board, Computer);
+    if human_count == 0 {
+        debug_log!("Human has no remaining pieces: computer wins!");
+        Some(ComputerWin)
+    } else if computer_count == 0 {
+        debug_log!("Computer has no remaining pieces: human wins!");
+        Some(HumanWin)
     } else {
-        false
+        None
     }
 }
 
@@ -184,3 +263,119 @@ pub(super) fn get_piece_count(board: &Board, player: Player) -> usize {
 fn get_count(board: &Board, piece: Square) -> usize {
     board.iter().filter(|&square| square == &piece).count()
 }
+
+pub(super) const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+pub(super) const ORTHOGONALS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+
+pub(super) fn offset_idx(idx: usize, (dx, dy): (isize, isize), dist: isize) -> Option<usize> {
+    let x = (idx % board_cols()) as isize + dx * dist;
+    let y = (idx / board_cols()) as isize + dy * dist;
+    if x < 0 || y < 0 || x >= board_cols() as isize || y >= board_rows() as isize {
+        None
+    } else {
+        Some(y as usize * board_cols() + x as usize)
+    }
+}
+
+//Directions the piece can go without moving backwards, kings can go any way
+pub(super) fn forward_dirs(piece: Square, dirs: &[(isize, isize)]) -> Vec<(isize, isize)> {
+    dirs.iter()
+        .filter(|(_, dy)| match piece {
+            HumanMan => *dy <= 0,
+            ComputerMan => *dy >= 0,
+            _ => true,
+        })
+        .cloned()
+        .collect()
+}
+
+//Steps onto empty squares, flying pieces can keep going until blocked
+pub(super) fn line_steps(
+    board: &Board,
+    origin: usize,
+    dirs: &[(isize, isize)],
+    flying: bool,
+) -> Vec<Move> {
+    let mut moves = vec![];
+    for dir in dirs {
+        let mut dist = 1;
+        while let Some(dest) = offset_idx(origin, *dir, dist) {
+            if board[dest] != Empty {
+                break;
+            }
+            moves.push(Move::Step {
+                origin,
+                dest,
+                value: VALUE_STEP,
+            });
+            if !flying {
+                break;
+            }
+            dist += 1;
+        }
+    }
+    moves
+}
+
+//Jumps over a capturable piece 'stride' squares away onto the empty square 'stride' squares past it,
+//flying pieces can first cross any number of empty squares and land on any empty square beyond
+pub(super) fn line_jumps(
+    board: &Board,
+    origin: usize,
+    dirs: &[(isize, isize)],
+    flying: bool,
+    stride: isize,
+    can_capture: fn(Square, Square) -> bool,
+) -> Vec<Move> {
+    let piece = board[origin];
+    dirs.iter()
+        .flat_map(|dir| {
+            let mut jumps = vec![];
+            let mut dist = stride;
+            while let Some(current) = offset_idx(origin, *dir, dist) {
+                if board[current] == Empty && flying {
+                    dist += stride;
+                    continue;
+                }
+                if board[current] != Empty && can_capture(piece, board[current]) {
+                    let mut landing_dist = dist + stride;
+                    while let Some(landing) = offset_idx(origin, *dir, landing_dist) {
+                        if board[landing] != Empty {
+                            break;
+                        }
+                        jumps.push(Move::Jump {
+                            origin,
+                            capture: Capture {
+                                dest: landing,
+                                capturing: current,
+                            },
+                            value: VALUE_CAPTURE,
+                        });
+                        if !flying {
+                            break;
+                        }
+                        landing_dist += stride;
+                    }
+                }
+                break;
+            }
+            jumps
+        })
+        .collect()
+}
+
+pub(super) fn can_capture(piece: Square, target: Square) -> bool {
+    CAPTURABLE
+        .get(&piece)
+        .is_some_and(|targets| targets.contains(&target))
+}
+
+//The king the man on origin becomes by landing on dest, if any
+pub(super) fn promotion(board: &Board, origin: usize, dest: usize) -> Option<Square> {
+    let piece = promoted(board[origin], dest, board.len());
+    if piece != board[origin] {
+        Some(piece)
+    } else {
+        None
+    }
+}
@@ -1,15 +1,12 @@
 use crate::boards::board_cols;
 use crate::draughts::moves::{Capture, Move};
-use crate::draughts::rules::common::check_moves;
 use crate::draughts::rules::{common, MoveDir, RuleSet, VALUE_STEP};
 use crate::draughts::rules::{CAPTURABLE, VALUE_CAPTURE};
 use crate::draughts::Square::*;
-use crate::draughts::{Board, PastMove, Square};
+use crate::draughts::{Board, Square};
 use crate::system::math::next_step;
 use crate::system::neighbours::get_neighbours;
 use crate::system::PlayState;
-use crate::system::PlayState::{ComputerWin, Draw, HumanWin};
-use crate::system::Player::*;
 
 //International, Canadian
 pub(super) struct FlyingKingsBothDirectionJumping;
@@ -129,35 +126,8 @@ impl RuleSet for FlyingKingsBothDirectionJumping {
         })
     }
 
-    fn check_game_over(&self, board: &Board, move_history: &[PastMove]) -> Option<PlayState> {
-        let human_king_count = common::get_king_count(board, Human);
-        let human_man_count = common::get_man_count(board, Human);
-        let computer_king_count = common::get_king_count(board, Computer);
-        let computer_man_count = common::get_man_count(board, Computer);
-        if human_king_count == 0 && human_man_count == 0 {
-            debug_log!("Human has no remaining pieces: computer wins!");
-            return Some(ComputerWin);
-        } else if computer_king_count == 0 && computer_man_count == 0 {
-            debug_log!("Computer has no remaining pieces: human wins!");
-            return Some(HumanWin);
-        } else if computer_king_count == 1
-            && human_king_count == 1
-            && human_man_count == 0
-            && computer_man_count == 0
-        {
-            debug_log!("Only one king per player remains: draw!");
-            return Some(Draw);
-        } else if move_history.len() > 40 {
-            let is_stalement = check_moves(move_history, 25, |mov| {
-                mov.king_capture_count == 0 && mov.man_capture_count == 0 && (mov.piece.is_king())
-            });
-            if is_stalement {
-                debug_log!("At least 25 moves with no captures and only kings remain: draw!");
-                return Some(Draw);
-            }
-        }
-
-        None
+    fn check_game_over(&self, board: &Board) -> Option<PlayState> {
+        common::check_game_over(board)
     }
 
     fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square> {
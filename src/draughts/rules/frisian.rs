@@ -0,0 +1,122 @@
+use crate::draughts::moves::Move;
+use crate::draughts::rules::common::{
+    can_capture, forward_dirs, keep_best, kings_captured, line_jumps, line_steps, DIAGONALS,
+    ORTHOGONALS,
+};
+use crate::draughts::rules::{common, RuleSet};
+use crate::draughts::{Board, Square};
+use crate::system::PlayState;
+
+//Values used to pick the capture, a king is worth more than a man but less than two
+const MAN_VALUE: isize = 2;
+const KING_VALUE: isize = 3;
+
+//Frisian
+//Pieces also capture orthogonally, over the piece two squares away as only the dark squares are used.
+//Kings fly. The player must take the highest value of pieces, if that's equal then a king must capture
+pub(super) struct Frisian;
+
+impl Frisian {
+    pub(super) fn new() -> Self {
+        Frisian {}
+    }
+
+    fn get_moves_for_square(&self, board: &Board, origin: usize, capture_only: bool) -> Vec<Move> {
+        let flying = board[origin].is_king();
+        let mut moves = if capture_only {
+            vec![]
+        } else {
+            line_steps(
+                board,
+                origin,
+                &forward_dirs(board[origin], &DIAGONALS),
+                flying,
+            )
+        };
+        moves.append(&mut line_jumps(
+            board,
+            origin,
+            &DIAGONALS,
+            flying,
+            1,
+            can_capture,
+        ));
+        moves.append(&mut line_jumps(
+            board,
+            origin,
+            &ORTHOGONALS,
+            flying,
+            2,
+            can_capture,
+        ));
+        moves
+    }
+}
+
+fn capture_value(board: &Board, mov: &Move) -> isize {
+    let kings = kings_captured(board, mov) as isize;
+    let men = mov.captures().len() as isize - kings;
+    kings * KING_VALUE + men * MAN_VALUE
+}
+
+impl RuleSet for Frisian {
+    fn calc_valid_moves(&self, board: &Board, origin: usize) -> Vec<Move> {
+        common::calc_all_moves(board, origin, false, |board, origin, capture_only| {
+            self.get_moves_for_square(board, origin, capture_only)
+        })
+    }
+
+    fn check_game_over(&self, board: &Board) -> Option<PlayState> {
+        common::check_game_over(board)
+    }
+
+    fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square> {
+        common::promotion(board, origin, dest)
+    }
+
+    fn filter_moves(&self, board: &Board, moves: Vec<Move>) -> Vec<Move> {
+        let moves = common::any_captures(moves);
+        if moves.iter().all(|mov| mov.is_step()) {
+            return moves;
+        }
+        let moves = keep_best(moves, |mov| capture_value(board, mov));
+        keep_best(moves, |mov| board[mov.origin()].is_king() as isize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::Square::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_orthogonal_capture() {
+        set_board_size((10, 10));
+        let mut board = vec![Empty; 100];
+        //Man on 54 captures the man two squares above on 34 and lands on 14
+        board[54] = HumanMan;
+        board[34] = ComputerMan;
+        let rules = Frisian::new();
+        let moves = rules.filter_moves(&board, rules.calc_valid_moves(&board, 54));
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].dest(), 14);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_value_majority() {
+        set_board_size((10, 10));
+        let mut board = vec![Empty; 100];
+        //Taking a king is worth more than a man
+        board[54] = HumanMan;
+        board[43] = ComputerMan;
+        board[45] = ComputerKing;
+        let rules = Frisian::new();
+        let moves = rules.filter_moves(&board, rules.calc_valid_moves(&board, 54));
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].dest(), 36);
+    }
+}
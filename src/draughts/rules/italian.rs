@@ -0,0 +1,115 @@
+use crate::draughts::moves::Move;
+use crate::draughts::rules::common::{
+    can_capture, forward_dirs, keep_best, kings_captured, line_jumps, line_steps, DIAGONALS,
+};
+use crate::draughts::rules::{common, RuleSet};
+use crate::draughts::{Board, Square};
+use crate::system::PlayState;
+
+//Italian
+//Men only move and capture forwards and can't capture kings, kings move one space.
+//When choosing a capture the player must take the most pieces, then capture with a king, then take
+//the most kings, then take a king as early as possible
+pub(super) struct Italian;
+
+impl Italian {
+    pub(super) fn new() -> Self {
+        Italian {}
+    }
+
+    fn get_moves_for_square(&self, board: &Board, origin: usize, capture_only: bool) -> Vec<Move> {
+        let dirs = forward_dirs(board[origin], &DIAGONALS);
+        let mut moves = if capture_only {
+            vec![]
+        } else {
+            line_steps(board, origin, &dirs, false)
+        };
+        moves.append(&mut line_jumps(
+            board,
+            origin,
+            &dirs,
+            false,
+            1,
+            |piece, target| (piece.is_king() || !target.is_king()) && can_capture(piece, target),
+        ));
+        moves
+    }
+}
+
+//Index of the first king captured, moves that take no kings go last
+fn first_king_capture(board: &Board, mov: &Move) -> isize {
+    mov.captures()
+        .iter()
+        .position(|capture| board[capture.capturing].is_king())
+        .map_or(isize::MIN, |idx| -(idx as isize))
+}
+
+impl RuleSet for Italian {
+    fn calc_valid_moves(&self, board: &Board, origin: usize) -> Vec<Move> {
+        common::calc_all_moves(board, origin, false, |board, origin, capture_only| {
+            self.get_moves_for_square(board, origin, capture_only)
+        })
+    }
+
+    fn check_game_over(&self, board: &Board) -> Option<PlayState> {
+        common::check_game_over(board)
+    }
+
+    fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square> {
+        common::promotion(board, origin, dest)
+    }
+
+    fn filter_moves(&self, board: &Board, moves: Vec<Move>) -> Vec<Move> {
+        let moves = common::longest_captures(moves);
+        if moves.iter().all(|mov| mov.is_step()) {
+            return moves;
+        }
+        let moves = keep_best(moves, |mov| board[mov.origin()].is_king() as isize);
+        let moves = keep_best(moves, |mov| kings_captured(board, mov) as isize);
+        keep_best(moves, |mov| first_king_capture(board, mov))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::Square::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_men_cannot_capture_kings() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        board[44] = HumanMan;
+        board[35] = ComputerKing;
+        let rules = Italian::new();
+        let moves = rules.calc_valid_moves(&board, 44);
+        assert!(moves.iter().all(|mov| mov.is_step()));
+        board[44] = HumanKing;
+        let moves = rules.calc_valid_moves(&board, 44);
+        assert_eq!(moves.len(), 1);
+        assert!(moves[0].is_jump());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_capture_priority() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        //A man and a king can both take the same man, the king must capture
+        board[44] = HumanMan;
+        board[46] = HumanKing;
+        board[37] = ComputerMan;
+        let rules = Italian::new();
+        let moves: Vec<Move> = [44, 46]
+            .iter()
+            .flat_map(|idx| rules.calc_valid_moves(&board, *idx))
+            .collect();
+        assert_eq!(moves.len(), 2);
+        let moves = rules.filter_moves(&board, moves);
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].origin(), 46);
+    }
+}
@@ -1,11 +1,19 @@
+use crate::boards::board_cols;
+use crate::constants::games;
+use crate::draughts::draw::DrawCounter;
 use crate::draughts::moves::Move;
 use crate::draughts::rules::flying_kings_both_direction_jumping::FlyingKingsBothDirectionJumping;
+use crate::draughts::rules::frisian::Frisian;
 use crate::draughts::rules::init::*;
+use crate::draughts::rules::italian::Italian;
 use crate::draughts::rules::no_flying_kings_single_direction_men::NoFlyingKingsSingleDirectionMen;
+use crate::draughts::rules::russian::Russian;
+use crate::draughts::rules::spanish::Spanish;
+use crate::draughts::rules::turkish::Turkish;
+use crate::draughts::Board;
 use crate::draughts::Square;
 use crate::draughts::Square::*;
-use crate::draughts::{Board, PastMove};
-use crate::system::PlayState;
+use crate::system::{PlayState, Player};
 use std::collections::HashMap;
 
 const VALUE_CAPTURE: usize = 10;
@@ -26,7 +34,12 @@ lazy_static! {
 
 mod common;
 mod flying_kings_both_direction_jumping;
+mod frisian;
+mod italian;
 mod no_flying_kings_single_direction_men;
+mod russian;
+mod spanish;
+mod turkish;
 
 enum MoveDir {
     Up,
@@ -36,8 +49,62 @@ enum MoveDir {
 
 pub(super) trait RuleSet {
     fn calc_valid_moves(&self, board: &Board, origin: usize) -> Vec<Move>;
-    fn check_game_over(&self, board: &Board, move_history: &[PastMove]) -> Option<PlayState>;
+    fn check_game_over(&self, board: &Board) -> Option<PlayState>;
     fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square>;
+
+    //Narrows every move the player has down to the ones they can choose from
+    fn filter_moves(&self, _board: &Board, moves: Vec<Move>) -> Vec<Move> {
+        common::longest_captures(moves)
+    }
+
+    //Whether a man passing over the far row while capturing carries on as a king
+    fn promotes_mid_capture(&self) -> bool {
+        false
+    }
+}
+
+//Every move the player can choose from
+pub(super) fn all_moves(rules: &dyn RuleSet, board: &Board, player: Player) -> Vec<Move> {
+    let moves = board
+        .iter()
+        .enumerate()
+        .filter(|(_, square)| **square != Empty && Player::from(**square) == player)
+        .flat_map(|(idx, _)| rules.calc_valid_moves(board, idx))
+        .collect();
+    rules.filter_moves(board, moves)
+}
+
+//Plays the move on the board, returns true if the piece was promoted by the rules
+pub(super) fn apply_move(rules: &dyn RuleSet, board: &mut Board, mov: &Move) -> bool {
+    let origin = mov.origin();
+    let dest = mov.dest();
+    let piece = board[origin];
+    let captures = mov.captures();
+    let promotion = if rules.promotes_mid_capture() && !captures.is_empty() {
+        captures
+            .iter()
+            .find_map(|capture| rules.is_promotion(board, origin, capture.dest))
+    } else {
+        rules.is_promotion(board, origin, dest)
+    };
+    for capture in &captures {
+        board[capture.capturing] = Empty;
+    }
+    //Origin is cleared first as a king's capture can end where it started
+    board[origin] = Empty;
+    board[dest] = promotion.unwrap_or(piece);
+    #[allow(clippy::needless_range_loop)] //looks awful in comparison
+    for idx in 0..board_cols() {
+        if board[idx] == HumanMan {
+            board[idx] = HumanKing
+        }
+    }
+    for idx in (board.len() - board_cols())..(board.len()) {
+        if board[idx] == ComputerMan {
+            board[idx] = ComputerKing
+        }
+    }
+    promotion.is_some()
 }
 
 mod init {
@@ -76,6 +143,21 @@ mod init {
         ]
     }
 
+    //Turkish pieces use every square, leaving the back rows empty
+    #[rustfmt::skip]
+    pub(super) fn get_turkish_init_board() -> Board {
+        vec![
+            E, E, E, E, E, E, E, E,
+            C, C, C, C, C, C, C, C,
+            C, C, C, C, C, C, C, C,
+            E, E, E, E, E, E, E, E,
+            E, E, E, E, E, E, E, E,
+            P, P, P, P, P, P, P, P,
+            P, P, P, P, P, P, P, P,
+            E, E, E, E, E, E, E, E,
+        ]
+    }
+
     #[rustfmt::skip]
     pub(super) fn get_12x12_init_board() -> Board {
         vec![
@@ -95,37 +177,158 @@ mod init {
     }
 }
 
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
 pub(super) enum GameVariant {
     English,
     International,
     Canadian,
     Brazilian,
+    Russian,
+    Italian,
+    Spanish,
+    Turkish,
+    Frisian,
 }
 
 impl GameVariant {
+    const ALL: [GameVariant; 9] = [
+        GameVariant::English,
+        GameVariant::International,
+        GameVariant::Canadian,
+        GameVariant::Brazilian,
+        GameVariant::Russian,
+        GameVariant::Italian,
+        GameVariant::Spanish,
+        GameVariant::Turkish,
+        GameVariant::Frisian,
+    ];
+
+    pub(super) fn from_game(game: &str) -> Option<GameVariant> {
+        GameVariant::ALL
+            .iter()
+            .find(|variant| variant.get_game() == game)
+            .copied()
+    }
+
+    pub(super) fn get_game(&self) -> &'static str {
+        match self {
+            GameVariant::English => games::DRAUGHTS_ENGLISH,
+            GameVariant::International => games::DRAUGHTS_INTERNATIONAL,
+            GameVariant::Canadian => games::DRAUGHTS_CANADIAN,
+            GameVariant::Brazilian => games::DRAUGHTS_BRAZILIAN,
+            GameVariant::Russian => games::DRAUGHTS_RUSSIAN,
+            GameVariant::Italian => games::DRAUGHTS_ITALIAN,
+            GameVariant::Spanish => games::DRAUGHTS_SPANISH,
+            GameVariant::Turkish => games::DRAUGHTS_TURKISH,
+            GameVariant::Frisian => games::DRAUGHTS_FRISIAN,
+        }
+    }
+
+    //PDN GameType tag
+    pub(super) fn get_game_type(&self) -> usize {
+        match self {
+            GameVariant::International => 20,
+            GameVariant::English => 21,
+            GameVariant::Italian => 22,
+            GameVariant::Spanish => 24,
+            GameVariant::Russian => 25,
+            GameVariant::Brazilian => 26,
+            GameVariant::Canadian => 27,
+            GameVariant::Turkish => 30,
+            GameVariant::Frisian => 40,
+        }
+    }
+
+    pub(super) fn from_game_type(game_type: usize) -> Option<GameVariant> {
+        GameVariant::ALL
+            .iter()
+            .find(|variant| variant.get_game_type() == game_type)
+            .copied()
+    }
+
+    //Turkish uses every square so has no square numbers
+    pub(super) fn uses_all_squares(&self) -> bool {
+        *self == GameVariant::Turkish
+    }
+
     pub(super) fn get_board_size(&self) -> (usize, usize) {
         match self {
             GameVariant::English => (8, 8),
             GameVariant::International => (10, 10),
             GameVariant::Canadian => (12, 12),
             GameVariant::Brazilian => (8, 8),
+            GameVariant::Russian => (8, 8),
+            GameVariant::Italian => (8, 8),
+            GameVariant::Spanish => (8, 8),
+            GameVariant::Turkish => (8, 8),
+            GameVariant::Frisian => (10, 10),
         }
     }
 
     pub(super) fn get_init_board(&self) -> Board {
         match self {
-            GameVariant::English | GameVariant::Brazilian => get_8x8_init_board(),
-            GameVariant::International => get_10x10_init_board(),
+            GameVariant::English
+            | GameVariant::Brazilian
+            | GameVariant::Russian
+            | GameVariant::Italian
+            | GameVariant::Spanish => get_8x8_init_board(),
+            GameVariant::Turkish => get_turkish_init_board(),
+            GameVariant::International | GameVariant::Frisian => get_10x10_init_board(),
             GameVariant::Canadian => get_12x12_init_board(),
         }
     }
 
+    //The FMJD counts from 3 and 2 pieces against a lone king, the others draw once
+    //only kings have been moving for a while
+    pub(super) fn draw_counters(&self) -> Vec<DrawCounter> {
+        let fmjd = |no_progress| {
+            vec![
+                DrawCounter::NoProgress(no_progress),
+                DrawCounter::LoneKing {
+                    pieces: 3,
+                    moves: 16,
+                },
+                DrawCounter::LoneKing {
+                    pieces: 2,
+                    moves: 5,
+                },
+            ]
+        };
+        match self {
+            GameVariant::International | GameVariant::Canadian => fmjd(25),
+            GameVariant::Brazilian => fmjd(20),
+            GameVariant::English => vec![DrawCounter::NoProgress(40)],
+            GameVariant::Russian => vec![
+                DrawCounter::NoProgress(15),
+                DrawCounter::LoneKing {
+                    pieces: 3,
+                    moves: 15,
+                },
+            ],
+            GameVariant::Frisian => vec![
+                DrawCounter::NoProgress(25),
+                DrawCounter::LoneKing {
+                    pieces: 2,
+                    moves: 7,
+                },
+            ],
+            GameVariant::Italian | GameVariant::Spanish | GameVariant::Turkish => {
+                vec![DrawCounter::NoProgress(25)]
+            }
+        }
+    }
+
     pub(super) fn get_rules(&self) -> Box<dyn RuleSet> {
         match self {
             GameVariant::English => Box::new(NoFlyingKingsSingleDirectionMen::new()),
             GameVariant::International | GameVariant::Canadian | GameVariant::Brazilian => {
                 Box::new(FlyingKingsBothDirectionJumping::new())
             }
+            GameVariant::Russian => Box::new(Russian::new()),
+            GameVariant::Italian => Box::new(Italian::new()),
+            GameVariant::Spanish => Box::new(Spanish::new()),
+            GameVariant::Turkish => Box::new(Turkish::new()),
+            GameVariant::Frisian => Box::new(Frisian::new()),
         }
     }
 }
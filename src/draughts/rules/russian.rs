@@ -0,0 +1,126 @@
+use crate::draughts::moves::Move;
+use crate::draughts::rules::common::{
+    can_capture, forward_dirs, line_jumps, line_steps, DIAGONALS,
+};
+use crate::draughts::rules::{common, RuleSet};
+use crate::draughts::{Board, Square};
+use crate::system::PlayState;
+
+//Russian
+//Men capture backwards, kings fly and a man reaching the far row mid capture carries on as a king.
+//Capturing is compulsory but any capture can be chosen
+pub(super) struct Russian;
+
+impl Russian {
+    pub(super) fn new() -> Self {
+        Russian {}
+    }
+
+    fn get_moves_for_square(&self, board: &Board, origin: usize, capture_only: bool) -> Vec<Move> {
+        let flying = board[origin].is_king();
+        let mut moves = if capture_only {
+            vec![]
+        } else {
+            line_steps(
+                board,
+                origin,
+                &forward_dirs(board[origin], &DIAGONALS),
+                flying,
+            )
+        };
+        moves.append(&mut line_jumps(
+            board,
+            origin,
+            &DIAGONALS,
+            flying,
+            1,
+            can_capture,
+        ));
+        moves
+    }
+}
+
+impl RuleSet for Russian {
+    fn calc_valid_moves(&self, board: &Board, origin: usize) -> Vec<Move> {
+        common::calc_all_moves(board, origin, true, |board, origin, capture_only| {
+            self.get_moves_for_square(board, origin, capture_only)
+        })
+    }
+
+    fn check_game_over(&self, board: &Board) -> Option<PlayState> {
+        common::check_game_over(board)
+    }
+
+    fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square> {
+        common::promotion(board, origin, dest)
+    }
+
+    fn filter_moves(&self, _board: &Board, moves: Vec<Move>) -> Vec<Move> {
+        common::any_captures(moves)
+    }
+
+    fn promotes_mid_capture(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::Square::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_mid_capture_promotion() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        //Man on 21 jumps 12 to land on 3, then as a king flies over 10 to take 17 and land on 24
+        board[21] = HumanMan;
+        board[12] = ComputerMan;
+        board[17] = ComputerMan;
+        let rules = Russian::new();
+        let moves = rules.filter_moves(&board, rules.calc_valid_moves(&board, 21));
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].len(), 2);
+        assert_eq!(moves[0].dest(), 24);
+        assert_eq!(rules.is_promotion(&board, 21, 3), Some(HumanKing));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_free_choice_of_capture() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        //Either take one piece to the left or two to the right
+        board[44] = HumanMan;
+        board[35] = ComputerMan;
+        board[37] = ComputerMan;
+        board[21] = ComputerMan;
+        let rules = Russian::new();
+        let moves = rules.filter_moves(&board, rules.calc_valid_moves(&board, 44));
+        assert_eq!(moves.len(), 2);
+        assert!(moves.iter().any(|mov| mov.len() == 1));
+        assert!(moves.iter().any(|mov| mov.len() == 2));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_king_lands_beyond_capture() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        //King on 56 takes 42 and can stop on either empty square before its own man on 21
+        board[56] = HumanKing;
+        board[42] = ComputerMan;
+        board[21] = HumanMan;
+        let rules = Russian::new();
+        let mut dests: Vec<usize> = rules
+            .filter_moves(&board, rules.calc_valid_moves(&board, 56))
+            .iter()
+            .map(|mov| mov.dest())
+            .collect();
+        dests.sort_unstable();
+        assert_eq!(dests, vec![28, 35]);
+    }
+}
@@ -0,0 +1,88 @@
+use crate::draughts::moves::Move;
+use crate::draughts::rules::common::{
+    can_capture, forward_dirs, keep_best, kings_captured, line_jumps, line_steps, DIAGONALS,
+};
+use crate::draughts::rules::{common, RuleSet};
+use crate::draughts::{Board, Square};
+use crate::system::PlayState;
+
+//Spanish
+//Men only move and capture forwards, kings fly. The player must take the most pieces and then the
+//most kings
+pub(super) struct Spanish;
+
+impl Spanish {
+    pub(super) fn new() -> Self {
+        Spanish {}
+    }
+
+    fn get_moves_for_square(&self, board: &Board, origin: usize, capture_only: bool) -> Vec<Move> {
+        let dirs = forward_dirs(board[origin], &DIAGONALS);
+        let flying = board[origin].is_king();
+        let mut moves = if capture_only {
+            vec![]
+        } else {
+            line_steps(board, origin, &dirs, flying)
+        };
+        moves.append(&mut line_jumps(
+            board,
+            origin,
+            &dirs,
+            flying,
+            1,
+            can_capture,
+        ));
+        moves
+    }
+}
+
+impl RuleSet for Spanish {
+    fn calc_valid_moves(&self, board: &Board, origin: usize) -> Vec<Move> {
+        common::calc_all_moves(board, origin, false, |board, origin, capture_only| {
+            self.get_moves_for_square(board, origin, capture_only)
+        })
+    }
+
+    fn check_game_over(&self, board: &Board) -> Option<PlayState> {
+        common::check_game_over(board)
+    }
+
+    fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square> {
+        common::promotion(board, origin, dest)
+    }
+
+    fn filter_moves(&self, board: &Board, moves: Vec<Move>) -> Vec<Move> {
+        let moves = common::longest_captures(moves);
+        keep_best(moves, |mov| kings_captured(board, mov) as isize)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::Square::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_most_kings() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        //Either capture can take one piece, the king must be taken
+        board[44] = HumanMan;
+        board[35] = ComputerMan;
+        board[37] = ComputerKing;
+        let rules = Spanish::new();
+        let moves = rules.filter_moves(&board, rules.calc_valid_moves(&board, 44));
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].dest(), 30);
+        //Men don't capture backwards
+        board[44] = Empty;
+        board[28] = HumanMan;
+        assert!(rules
+            .calc_valid_moves(&board, 28)
+            .iter()
+            .all(|mov| mov.is_step()));
+    }
+}
@@ -0,0 +1,88 @@
+use crate::draughts::moves::Move;
+use crate::draughts::rules::common::{
+    can_capture, forward_dirs, line_jumps, line_steps, ORTHOGONALS,
+};
+use crate::draughts::rules::{common, RuleSet};
+use crate::draughts::{Board, Square};
+use crate::system::PlayState;
+
+//Turkish
+//Pieces use every square and move orthogonally, men move and capture forwards or sideways and kings fly.
+//The player must take the most pieces
+pub(super) struct Turkish;
+
+impl Turkish {
+    pub(super) fn new() -> Self {
+        Turkish {}
+    }
+
+    fn get_moves_for_square(&self, board: &Board, origin: usize, capture_only: bool) -> Vec<Move> {
+        let dirs = forward_dirs(board[origin], &ORTHOGONALS);
+        let flying = board[origin].is_king();
+        let mut moves = if capture_only {
+            vec![]
+        } else {
+            line_steps(board, origin, &dirs, flying)
+        };
+        moves.append(&mut line_jumps(
+            board,
+            origin,
+            &dirs,
+            flying,
+            1,
+            can_capture,
+        ));
+        moves
+    }
+}
+
+impl RuleSet for Turkish {
+    fn calc_valid_moves(&self, board: &Board, origin: usize) -> Vec<Move> {
+        common::calc_all_moves(board, origin, false, |board, origin, capture_only| {
+            self.get_moves_for_square(board, origin, capture_only)
+        })
+    }
+
+    fn check_game_over(&self, board: &Board) -> Option<PlayState> {
+        common::check_game_over(board)
+    }
+
+    fn is_promotion(&self, board: &Board, origin: usize, dest: usize) -> Option<Square> {
+        common::promotion(board, origin, dest)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::draughts::Square::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_orthogonal_moves() {
+        set_board_size((8, 8));
+        let mut board = vec![Empty; 64];
+        board[36] = HumanMan;
+        let rules = Turkish::new();
+        let mut dests: Vec<usize> = rules
+            .calc_valid_moves(&board, 36)
+            .iter()
+            .map(|mov| mov.dest())
+            .collect();
+        dests.sort_unstable();
+        assert_eq!(dests, vec![28, 35, 37]);
+        //Sideways capture then forward capture
+        board[37] = ComputerMan;
+        board[30] = ComputerMan;
+        let moves = rules.filter_moves(&board, rules.calc_valid_moves(&board, 36));
+        assert_eq!(moves.len(), 1);
+        assert_eq!(moves[0].dest(), 22);
+        //Kings fly
+        board[36] = HumanKing;
+        board[37] = Empty;
+        board[30] = Empty;
+        assert_eq!(rules.calc_valid_moves(&board, 36).len(), 14);
+    }
+}
@@ -0,0 +1,218 @@
+use crate::go::rules::{place, suggest_dead};
+use crate::go::{Game, Move, Square, Stone};
+use crate::system::mcts::{MctsGame, MctsSettings, Outcome, Search, FRAME_BUDGET};
+use crate::system::neighbours::get_neighbours;
+use crate::system::Player;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{thread_rng, Rng};
+use std::time::Duration;
+
+const PLAYOUTS: usize = 20_000;
+const THINKING_TIME: Duration = Duration::from_millis(2000);
+const RAVE_EQUIVALENCE: f64 = 500.;
+//Captures start as if they had won this many playouts
+const CAPTURE_PRIOR: f64 = 10.;
+
+//An empty point surrounded by the player's own stones
+fn is_own_eye(board: &[Square], idx: usize, stone: Stone) -> bool {
+    get_neighbours(idx, true, false)
+        .iter()
+        .all(|next| board[*next] == stone.into())
+}
+
+//The search only needs to tell the colours apart
+fn side(stone: Stone) -> Player {
+    match stone {
+        Stone::Black => Player::Human,
+        Stone::White => Player::Computer,
+    }
+}
+
+impl MctsGame for Game {
+    type Move = Move;
+
+    fn to_move(&self) -> Player {
+        side(self.position.to_move)
+    }
+
+    //Filling your own eyes is never worth searching
+    fn legal_moves(&self) -> Vec<Move> {
+        let board = &self.position.board;
+        let stone = self.position.to_move;
+        let mut moves: Vec<Move> = (0..board.len())
+            .filter(|idx| board[*idx] == Square::Empty && !is_own_eye(board, *idx, stone))
+            .map(Move::Place)
+            .filter(|mov| self.is_legal(mov))
+            .collect();
+        moves.push(Move::Pass);
+        moves
+    }
+
+    fn apply(&mut self, mov: &Move, _: &mut StdRng) {
+        self.play(*mov).expect("Search played an illegal move");
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if !self.is_over() {
+            return None;
+        }
+        if let Some((stone, Move::Resign)) = self.moves.last() {
+            return Some(Outcome::Win(side(stone.opposite())));
+        }
+        Some(self.estimate())
+    }
+
+    //Random placements that aren't in the player's own eyes, passing once there are none left
+    fn playout_move(&self, rng: &mut StdRng) -> Option<Move> {
+        let board = &self.position.board;
+        let stone = self.position.to_move;
+        let mut empty: Vec<usize> = (0..board.len())
+            .filter(|idx| board[*idx] == Square::Empty)
+            .collect();
+        empty.shuffle(rng);
+        let mov = empty
+            .into_iter()
+            .map(Move::Place)
+            .find(|mov| match mov {
+                Move::Place(idx) => !is_own_eye(board, *idx, stone) && self.is_legal(mov),
+                _ => false,
+            })
+            .unwrap_or(Move::Pass);
+        Some(mov)
+    }
+
+    fn prior(&self, mov: &Move) -> (f64, f64) {
+        let stone = self.position.to_move.idx();
+        let captures = match mov {
+            Move::Place(idx) => place(&self.position, *idx)
+                .map(|next| next.prisoners[stone] > self.position.prisoners[stone])
+                .unwrap_or(false),
+            Move::Pass | Move::Resign => false,
+        };
+        if captures {
+            (CAPTURE_PRIOR, CAPTURE_PRIOR)
+        } else {
+            (0., 0.)
+        }
+    }
+
+    fn estimate(&self) -> Outcome {
+        match self.score(&suggest_dead(&self.position)).winner() {
+            Some(stone) => Outcome::Win(side(stone)),
+            None => Outcome::Draw,
+        }
+    }
+}
+
+//Passes straight away if the opponent passed and the game is already won, otherwise searches
+//for a frame at a time, returning None until the search has finished
+pub(super) fn think(game: &Game, search: &mut Option<Search<Game>>) -> Option<Move> {
+    let current = match search {
+        Some(current) => current,
+        None => {
+            let stone = game.position.to_move;
+            if game.position.passes > 0 && game.estimate() == Outcome::Win(side(stone)) {
+                return Some(Move::Pass);
+            }
+            let size = game.settings.size;
+            let settings = MctsSettings::new(PLAYOUTS, thread_rng().gen())
+                .with_time(THINKING_TIME)
+                .with_rave(RAVE_EQUIVALENCE)
+                .with_max_playout_moves(size * size * 3);
+            search.insert(Search::new(game.clone(), settings))
+        }
+    };
+    if !current.run(FRAME_BUDGET) {
+        return None;
+    }
+    let mov = current.best_move().unwrap_or(Move::Pass);
+    *search = None;
+    Some(mov)
+}
+
+//Searches until a move has been chosen, for when there are no frames to draw
+pub(super) fn choose_move(game: &Game) -> Move {
+    let mut search = None;
+    loop {
+        if let Some(mov) = think(game, &mut search) {
+            return mov;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::go::{parse_point_name, Scoring, Settings};
+    use rand::SeedableRng;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_takes_capture() {
+        let mut game = Game::new(Settings::new(9, 0, Scoring::Area));
+        for (stone, name) in [
+            (Stone::White, "E5"),
+            (Stone::Black, "E6"),
+            (Stone::Black, "D5"),
+            (Stone::Black, "F5"),
+        ]
+        .iter()
+        {
+            game.add_setup(*stone, parse_point_name(name).unwrap());
+        }
+        assert_eq!(
+            choose_move(&game),
+            Move::Place(parse_point_name("E4").unwrap())
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_keeps_eyes() {
+        let mut game = Game::new(Settings::new(9, 0, Scoring::Area));
+        //Black fills the board except for two eyes, filling either would be suicide
+        //for white and self destructive for black
+        let eyes = [
+            parse_point_name("A1").unwrap(),
+            parse_point_name("J9").unwrap(),
+        ];
+        for idx in 0..81 {
+            if !eyes.contains(&idx) {
+                game.add_setup(Stone::Black, idx);
+            }
+        }
+        assert_eq!(game.legal_moves(), vec![Move::Pass]);
+        assert_eq!(choose_move(&game), Move::Pass);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_playouts_finish() {
+        let mut game = Game::new(Settings::new(9, 0, Scoring::Area));
+        let mut rng = StdRng::seed_from_u64(3);
+        let mut moves = 0;
+        while game.outcome().is_none() {
+            let mov = game.playout_move(&mut rng).unwrap();
+            game.apply(&mov, &mut rng);
+            moves += 1;
+            assert!(moves < 81 * 3);
+        }
+        //Only eyes are left empty
+        let board = &game.position.board;
+        assert!((0..81).all(|idx| board[idx] != Square::Empty
+            || is_own_eye(board, idx, Stone::Black)
+            || is_own_eye(board, idx, Stone::White)));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_passes_when_won() {
+        let mut game = Game::new(Settings::new(9, 0, Scoring::Area));
+        game.play(Move::Place(parse_point_name("E5").unwrap()))
+            .unwrap();
+        game.play(Move::Pass).unwrap();
+        assert_eq!(choose_move(&game), Move::Pass);
+    }
+}
@@ -0,0 +1,315 @@
+use crate::boards::cursor::Cursor;
+use crate::constants::games;
+use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
+use crate::go::ai::think;
+use crate::go::renderer::render;
+use crate::go::rules::{group, suggest_dead};
+use crate::go::sgf::{parse_sgf, to_sgf};
+use crate::go::{
+    point_name, Game, Move, Phase, Scoring, Settings, SetupRow, State, Stone, MAX_HANDICAP,
+};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::PlayState::{ComputerWin, Draw, HumanWin};
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player, Scene, Turn, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
+use ggez::event::KeyCode;
+use ggez::{Context, GameResult};
+use std::fs;
+
+pub(super) const PASS_KEY: KeyCode = KeyCode::P;
+pub(super) const RESIGN_KEY: KeyCode = KeyCode::R;
+pub(super) const SAVE_KEY: KeyCode = KeyCode::S;
+pub(super) const DONE_KEY: KeyCode = KeyCode::D;
+
+pub(crate) fn get_board_size(game: &str) -> usize {
+    match game {
+        games::GO_SMALL => 9,
+        games::GO_MEDIUM => 13,
+        games::GO_STANDARD => 19,
+        _ => panic!("Invalid game: {}", game),
+    }
+}
+
+pub(crate) struct Controller {
+    state: State,
+}
+
+impl Controller {
+    pub fn new(game: &str) -> Self {
+        let size = get_board_size(game);
+        let settings = Settings::new(size, 0, Scoring::Area);
+        Controller::with_game(Game::new(settings), PlayState::ModeSelection, Phase::Setup)
+    }
+
+    //Continues the main line of an SGF record, the human plays black
+    pub fn from_record(game: &str, record: &str) -> Result<Self, String> {
+        let size = get_board_size(game);
+        let loaded = parse_sgf(record)?;
+        if loaded.settings.size != size {
+            return Err(format!(
+                "Record is for {0}x{0} but {1} is {2}x{2}",
+                loaded.settings.size, game, size
+            ));
+        }
+        debug_log!("Loaded {} moves", loaded.moves.len());
+        Ok(Controller::with_game(
+            loaded,
+            PlayState::Init,
+            Phase::Playing,
+        ))
+    }
+
+    fn with_game(game: Game, play_state: PlayState, phase: Phase) -> Self {
+        Controller {
+            state: State {
+                play_state,
+                phase,
+                setup_row: SetupRow::Colour,
+                game,
+                human: Stone::Black,
+                cursor: Cursor::new(),
+                dead: vec![],
+                score: None,
+                next_move_time: AI_MOVE_DELAY,
+                computer_move: None,
+                search: None,
+                message: None,
+            },
+        }
+    }
+}
+
+impl Controller {
+    fn start_game(&mut self) {
+        let settings = &self.state.game.settings;
+        self.state.game = Game::new(Settings::new(
+            settings.size,
+            settings.handicap,
+            settings.scoring,
+        ));
+        self.state.phase = Phase::Playing;
+        self.state.play_state = PlayState::Init;
+    }
+
+    fn start_new_turn(&mut self) {
+        self.state.next_move_time = AI_MOVE_DELAY;
+        if let Some((stone, Move::Resign)) = self.state.game.moves.last() {
+            debug_log!("{} resigned", stone);
+            self.state.phase = Phase::Finished;
+            self.state.play_state = self.win_for(Some(stone.opposite()));
+            return;
+        }
+        if self.state.game.is_over() {
+            debug_log!("Both players passed, marking dead stones");
+            self.state.phase = Phase::MarkingDead;
+            self.state.dead = suggest_dead(&self.state.game.position);
+            self.state.play_state = NEW_TURN_HUMAN;
+            return;
+        }
+        self.state.play_state = match self.state.player(self.state.game.position.to_move) {
+            Player::Human => NEW_TURN_HUMAN,
+            Player::Computer => NEW_TURN_COMPUTER,
+        };
+    }
+
+    fn win_for(&self, winner: Option<Stone>) -> PlayState {
+        match winner.map(|stone| self.state.player(stone)) {
+            Some(Player::Human) => HumanWin,
+            Some(Player::Computer) => ComputerWin,
+            None => Draw,
+        }
+    }
+
+    fn process_move(&mut self, mov: Move) {
+        let stone = self.state.game.position.to_move;
+        if let Err(err) = self.state.game.play(mov) {
+            self.state.message = Some(err);
+            return;
+        }
+        self.state.message = match mov {
+            Move::Pass => Some(format!("{} passed", stone)),
+            _ => None,
+        };
+        self.start_new_turn();
+    }
+
+    fn place_stone(&mut self) {
+        let mov = Move::Place(self.state.cursor.idx);
+        if self.state.game.is_legal(&mov) {
+            self.process_move(mov);
+        } else {
+            self.state.message = Some(format!(
+                "Can't play at {}",
+                point_name(self.state.cursor.idx)
+            ));
+        }
+    }
+
+    //Marks or unmarks the whole group under the cursor
+    fn toggle_dead(&mut self) {
+        let board = &self.state.game.position.board;
+        let idx = self.state.cursor.idx;
+        if board[idx].stone().is_none() {
+            return;
+        }
+        let (stones, _) = group(board, idx);
+        if self.state.dead.contains(&idx) {
+            self.state.dead.retain(|stone| !stones.contains(stone));
+        } else {
+            self.state.dead.extend(stones);
+        }
+    }
+
+    fn finish_scoring(&mut self) {
+        let score = self.state.game.score(&self.state.dead);
+        debug_log!("Final score: {}", score);
+        self.state.score = Some(score);
+        self.state.phase = Phase::Finished;
+        self.state.play_state = self.win_for(score.winner());
+    }
+
+    //SGF result, e.g. B+R for a resignation or W+2.5
+    fn result(&self) -> Option<String> {
+        if let Some((stone, Move::Resign)) = self.state.game.moves.last() {
+            let winner = match stone.opposite() {
+                Stone::Black => "B",
+                Stone::White => "W",
+            };
+            return Some(format!("{}+R", winner));
+        }
+        self.state.score.map(|score| score.to_string())
+    }
+
+    //Writes the game so far as SGF to the working directory
+    fn save_record(&mut self) {
+        let size = self.state.game.settings.size;
+        let path = format!("go_{}x{}.sgf", size, size);
+        let sgf = to_sgf(&self.state.game, self.result().as_deref());
+        self.state.message = Some(match fs::write(&path, sgf) {
+            Ok(_) => format!("Saved {}", path),
+            Err(err) => format!("Could not save: {}", err),
+        });
+    }
+
+    fn change_setup(&mut self, forward: bool) {
+        let settings = &mut self.state.game.settings;
+        match self.state.setup_row {
+            SetupRow::Colour => self.state.human = self.state.human.opposite(),
+            SetupRow::Handicap => {
+                //A handicap of one is just black moving first
+                settings.handicap = match (settings.handicap, forward) {
+                    (0, true) => 2,
+                    (2, false) => 0,
+                    (0, false) => MAX_HANDICAP,
+                    (handicap, true) if handicap == MAX_HANDICAP => 0,
+                    (handicap, true) => handicap + 1,
+                    (handicap, false) => handicap - 1,
+                };
+            }
+            SetupRow::Scoring => {
+                settings.scoring = match settings.scoring {
+                    Scoring::Area => Scoring::Territory,
+                    Scoring::Territory => Scoring::Area,
+                }
+            }
+        }
+        *settings = Settings::new(settings.size, settings.handicap, settings.scoring);
+    }
+
+    fn handle_setup_input(&mut self, key: KeyCode) {
+        self.state.setup_row = match (self.state.setup_row, key) {
+            (SetupRow::Colour, KeyCode::Down) | (SetupRow::Scoring, KeyCode::Up) => {
+                SetupRow::Handicap
+            }
+            (SetupRow::Handicap, KeyCode::Down) | (SetupRow::Colour, KeyCode::Up) => {
+                SetupRow::Scoring
+            }
+            (SetupRow::Scoring, KeyCode::Down) | (SetupRow::Handicap, KeyCode::Up) => {
+                SetupRow::Colour
+            }
+            (row, _) => row,
+        };
+        match key {
+            KeyCode::Left => self.change_setup(false),
+            KeyCode::Right => self.change_setup(true),
+            KeyCode::Return => self.start_game(),
+            _ => {}
+        }
+    }
+
+    fn process_computer_turn(&mut self, delta: f64) {
+        if self.state.play_state.is_computer(SelectingPiece) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                //The search carries on each frame until it has chosen a move
+                if let Some(mov) = think(&self.state.game, &mut self.state.search) {
+                    debug_log!("Computer will play {:?}", mov);
+                    self.state.computer_move = Some(mov);
+                    self.state.next_move_time = ANIMATION_DURATION;
+                    self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove));
+                }
+            }
+        } else if self.state.play_state.is_computer(SelectingMove) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                let mov = self
+                    .state
+                    .computer_move
+                    .take()
+                    .expect("No computer move chosen");
+                self.process_move(mov);
+            }
+        }
+    }
+}
+
+impl Scene for Controller {
+    fn on_key_down(&mut self, key: KeyCode) {
+        match self.state.phase {
+            Phase::Setup => self.handle_setup_input(key),
+            Phase::Playing => {
+                if !self.state.play_state.is_human(SelectingPiece) {
+                    return;
+                }
+                if self.state.cursor.handle_input(key) {
+                    return;
+                }
+                match key {
+                    KeyCode::Return => self.place_stone(),
+                    PASS_KEY => self.process_move(Move::Pass),
+                    RESIGN_KEY => self.process_move(Move::Resign),
+                    SAVE_KEY => self.save_record(),
+                    _ => {}
+                }
+            }
+            Phase::MarkingDead => {
+                if self.state.cursor.handle_input(key) {
+                    return;
+                }
+                match key {
+                    KeyCode::Return => self.toggle_dead(),
+                    DONE_KEY => self.finish_scoring(),
+                    SAVE_KEY => self.save_record(),
+                    _ => {}
+                }
+            }
+            Phase::Finished => {}
+        }
+    }
+
+    fn update(&mut self, delta: f64) -> GameResult<()> {
+        if self.state.play_state == PlayState::Init {
+            self.start_new_turn();
+        }
+        self.process_computer_turn(delta);
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut Context, mesh_helper: &mut MeshHelper) -> GameResult<()> {
+        render(ctx, mesh_helper, &self.state)
+    }
+
+    fn play_state(&self) -> PlayState {
+        self.state.play_state
+    }
+}
@@ -0,0 +1,333 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::go::ai::choose_move;
+use crate::go::{
+    handicap_points, parse_point_name, point_name, star_points, Game, Move, Scoring, Settings,
+    Square, Stone, MAX_HANDICAP, SIZES,
+};
+use std::io;
+use std::io::BufRead;
+
+//Go Text Protocol (version 2), lets GUIs and match scripts use the rules and AI over stdin/stdout
+//Games are scored by area as is usual for computer go, with every stone on the board counted as
+//alive, so controllers should have games played out until dead stones are captured
+
+const DEFAULT_SIZE: usize = 19;
+const COMMANDS: [&str; 15] = [
+    "protocol_version",
+    "name",
+    "version",
+    "known_command",
+    "list_commands",
+    "quit",
+    "boardsize",
+    "clear_board",
+    "komi",
+    "fixed_handicap",
+    "play",
+    "genmove",
+    "undo",
+    "final_score",
+    "showboard",
+];
+
+fn parse_colour(text: &str) -> Result<Stone, String> {
+    match text.to_ascii_lowercase().as_str() {
+        "b" | "black" => Ok(Stone::Black),
+        "w" | "white" => Ok(Stone::White),
+        _ => Err(String::from("invalid color")),
+    }
+}
+
+fn parse_vertex(text: &str) -> Result<Move, String> {
+    if text.eq_ignore_ascii_case("pass") {
+        return Ok(Move::Pass);
+    }
+    parse_point_name(text)
+        .map(Move::Place)
+        .ok_or_else(|| String::from("invalid coordinate"))
+}
+
+fn format_vertex(mov: &Move) -> String {
+    match mov {
+        Move::Place(idx) => point_name(*idx),
+        Move::Pass => String::from("pass"),
+        Move::Resign => String::from("resign"),
+    }
+}
+
+struct Engine {
+    game: Game,
+}
+
+impl Engine {
+    fn new() -> Self {
+        Engine {
+            game: Game::new(Settings::new(DEFAULT_SIZE, 0, Scoring::Area)),
+        }
+    }
+
+    //Returns the full response and false when the engine should quit
+    fn handle(&mut self, line: &str) -> Option<(String, bool)> {
+        let line: String = line
+            .split('#')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .filter(|chr| !chr.is_control() || *chr == '\t')
+            .collect();
+        let mut tokens: Vec<&str> = line.split_whitespace().collect();
+        let id = match tokens.first() {
+            Some(first) if first.parse::<u32>().is_ok() => tokens.remove(0),
+            _ => "",
+        };
+        let (command, args) = tokens.split_first()?;
+        let result = self.execute(command, args);
+        let (prefix, body) = match result {
+            Ok(body) => ('=', body),
+            Err(err) => ('?', err),
+        };
+        let response = if body.is_empty() {
+            format!("{}{}\n", prefix, id)
+        } else {
+            format!("{}{} {}\n", prefix, id, body)
+        };
+        Some((response, *command != "quit"))
+    }
+
+    fn execute(&mut self, command: &str, args: &[&str]) -> Result<String, String> {
+        match command {
+            "protocol_version" => Ok(String::from("2")),
+            "name" => Ok(String::from(env!("CARGO_PKG_NAME"))),
+            "version" => Ok(String::from(env!("CARGO_PKG_VERSION"))),
+            "known_command" => Ok(args
+                .first()
+                .map(|name| COMMANDS.contains(name))
+                .unwrap_or(false)
+                .to_string()),
+            "list_commands" => Ok(COMMANDS.join("\n")),
+            "quit" => Ok(String::new()),
+            "boardsize" => {
+                let size = args
+                    .first()
+                    .and_then(|size| size.parse::<usize>().ok())
+                    .ok_or("boardsize not an integer")?;
+                if !SIZES.contains(&size) {
+                    return Err(String::from("unacceptable size"));
+                }
+                self.reset(size, 0);
+                Ok(String::new())
+            }
+            "clear_board" => {
+                self.reset(self.game.settings.size, 0);
+                Ok(String::new())
+            }
+            "komi" => {
+                self.game.settings.komi = args
+                    .first()
+                    .and_then(|komi| komi.parse().ok())
+                    .ok_or("komi not a float")?;
+                Ok(String::new())
+            }
+            "fixed_handicap" => self.fixed_handicap(args),
+            "play" => match args {
+                [colour, vertex] => self.play(parse_colour(colour)?, parse_vertex(vertex)?),
+                _ => Err(String::from("invalid color or coordinate")),
+            },
+            "genmove" => {
+                let colour = parse_colour(args.first().ok_or("invalid color")?)?;
+                let mov = self.genmove(colour)?;
+                Ok(format_vertex(&mov))
+            }
+            "undo" => self.undo(),
+            "final_score" => Ok(self.game.score(&[]).to_string()),
+            "showboard" => Ok(format!("\n{}", self.show_board())),
+            _ => Err(String::from("unknown command")),
+        }
+    }
+
+    //Starts a new game keeping the komi
+    fn reset(&mut self, size: usize, handicap: usize) {
+        let komi = self.game.settings.komi;
+        let mut settings = Settings::new(size, handicap, Scoring::Area);
+        settings.komi = komi;
+        self.game = Game::new(settings);
+    }
+
+    fn fixed_handicap(&mut self, args: &[&str]) -> Result<String, String> {
+        let count = args
+            .first()
+            .and_then(|count| count.parse::<usize>().ok())
+            .ok_or("handicap not an integer")?;
+        if !(2..=MAX_HANDICAP).contains(&count) {
+            return Err(String::from("invalid handicap"));
+        }
+        if !self.game.moves.is_empty()
+            || self
+                .game
+                .position
+                .board
+                .iter()
+                .any(|square| *square != Square::Empty)
+        {
+            return Err(String::from("board not empty"));
+        }
+        let size = self.game.settings.size;
+        self.reset(size, count);
+        let points: Vec<String> = handicap_points(size, count)
+            .into_iter()
+            .map(point_name)
+            .collect();
+        Ok(points.join(" "))
+    }
+
+    //Any colour may move, the controller decides when the game ends so passes don't end it here
+    fn play(&mut self, stone: Stone, mov: Move) -> Result<String, String> {
+        if self.game.position.passes >= 2 {
+            self.game.position.passes = 0;
+        }
+        self.game.position.to_move = stone;
+        self.game
+            .play(mov)
+            .map(|_| String::new())
+            .map_err(|_| String::from("illegal move"))
+    }
+
+    fn genmove(&mut self, stone: Stone) -> Result<Move, String> {
+        self.game.position.to_move = stone;
+        let mov = choose_move(&self.game);
+        self.play(stone, mov)?;
+        Ok(mov)
+    }
+
+    //Replays every move but the last
+    fn undo(&mut self) -> Result<String, String> {
+        let mut moves = self.game.moves.clone();
+        moves.pop().ok_or("cannot undo")?;
+        let mut game = Game::new(self.game.settings.clone());
+        for (stone, idx) in &self.game.setup {
+            game.add_setup(*stone, *idx);
+        }
+        self.game = game;
+        for (stone, mov) in moves {
+            self.play(stone, mov)?;
+        }
+        Ok(String::new())
+    }
+
+    //Columns across the top and bottom, rows numbered from the bottom, X is black and O white
+    fn show_board(&self) -> String {
+        let size = self.game.settings.size;
+        let columns: Vec<String> = (0..size).map(|x| point_name(x)[..1].to_string()).collect();
+        let columns = columns.join(" ");
+        let stars = star_points(size);
+        let mut lines = vec![format!("   {}", columns)];
+        for y in 0..size {
+            let row: Vec<&str> = (0..size)
+                .map(|x| {
+                    let idx = BoardCoord(x, y).idx();
+                    match self.game.position.board[idx] {
+                        Square::Black => "X",
+                        Square::White => "O",
+                        Square::Empty if stars.contains(&idx) => "+",
+                        Square::Empty => ".",
+                    }
+                })
+                .collect();
+            lines.push(format!("{:>2} {} {}", size - y, row.join(" "), size - y));
+        }
+        lines.push(format!("   {}", columns));
+        let prisoners = self.game.position.prisoners;
+        lines.push(format!(
+            "Captures: black {} white {}, komi {}",
+            prisoners[Stone::Black.idx()],
+            prisoners[Stone::White.idx()],
+            self.game.settings.komi
+        ));
+        lines.join("\n")
+    }
+}
+
+pub fn run() {
+    let mut engine = Engine::new();
+    for line in io::stdin().lock().lines() {
+        match line {
+            Ok(line) => {
+                if let Some((response, running)) = engine.handle(&line) {
+                    println!("{}", response);
+                    if !running {
+                        return;
+                    }
+                }
+            }
+            Err(_) => break,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serial_test::serial;
+
+    fn respond(engine: &mut Engine, line: &str) -> String {
+        engine.handle(line).unwrap().0
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_protocol() {
+        let mut engine = Engine::new();
+        assert_eq!(respond(&mut engine, "protocol_version"), "= 2\n");
+        assert_eq!(respond(&mut engine, "7 known_command komi"), "=7 true\n");
+        assert_eq!(respond(&mut engine, "known_command foo"), "= false\n");
+        assert_eq!(respond(&mut engine, "3 foo"), "?3 unknown command\n");
+        assert_eq!(
+            respond(&mut engine, "boardsize 10"),
+            "? unacceptable size\n"
+        );
+        assert!(engine.handle("  # just a comment").is_none());
+        assert_eq!(engine.handle("quit"), Some((String::from("=\n"), false)));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_play_and_score() {
+        let mut engine = Engine::new();
+        assert_eq!(respond(&mut engine, "boardsize 9"), "=\n");
+        assert_eq!(respond(&mut engine, "komi 5.5"), "=\n");
+        assert_eq!(respond(&mut engine, "play black E5"), "=\n");
+        assert_eq!(respond(&mut engine, "play w e5"), "? illegal move\n");
+        assert_eq!(respond(&mut engine, "play w x5"), "? invalid coordinate\n");
+        //A lone stone owns the whole board
+        assert_eq!(respond(&mut engine, "final_score"), "= B+75.5\n");
+        //Stones short of liberties are still counted
+        assert_eq!(respond(&mut engine, "play black A1"), "=\n");
+        assert_eq!(respond(&mut engine, "final_score"), "= B+75.5\n");
+        assert_eq!(respond(&mut engine, "undo"), "=\n");
+        let response = respond(&mut engine, "genmove white");
+        let vertex = response.trim_start_matches("= ").trim();
+        assert!(parse_point_name(vertex).is_some());
+        assert_eq!(engine.game.moves.len(), 2);
+        assert_eq!(respond(&mut engine, "undo"), "=\n");
+        assert_eq!(engine.game.moves.len(), 1);
+        assert_eq!(engine.game.settings.komi, 5.5);
+        let board = respond(&mut engine, "showboard");
+        assert!(board.contains(" 5 . . . . X . . . . 5"));
+        assert!(board.contains(" 3 . . + . . . + . . 3"));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_handicap_and_passes() {
+        let mut engine = Engine::new();
+        assert_eq!(respond(&mut engine, "fixed_handicap 2"), "= Q16 D4\n");
+        assert_eq!(
+            respond(&mut engine, "fixed_handicap 3"),
+            "? board not empty\n"
+        );
+        assert_eq!(respond(&mut engine, "clear_board"), "=\n");
+        assert_eq!(respond(&mut engine, "play b pass"), "=\n");
+        assert_eq!(respond(&mut engine, "play w PASS"), "=\n");
+        assert_eq!(respond(&mut engine, "play b D4"), "=\n");
+    }
+}
@@ -0,0 +1,428 @@
+use crate::boards::cursor::Cursor;
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::{board_cols, set_board_size};
+use crate::go::rules::{place, score_position};
+use crate::system::mcts::Search;
+use crate::system::zobrist::{PositionHistory, Zobrist, ZobristSquare};
+use crate::system::{PlayState, Player};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+mod ai;
+pub mod controller;
+pub mod gtp;
+mod render_mode_selection;
+mod renderer;
+mod rules;
+mod sgf;
+
+pub(crate) const SIZES: [usize; 3] = [9, 13, 19];
+pub(crate) const MAX_HANDICAP: usize = 9;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+enum Stone {
+    Black,
+    White,
+}
+
+impl Stone {
+    fn opposite(&self) -> Stone {
+        match self {
+            Stone::Black => Stone::White,
+            Stone::White => Stone::Black,
+        }
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Stone::Black => 0,
+            Stone::White => 1,
+        }
+    }
+}
+
+impl Display for Stone {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Stone::Black => write!(f, "Black"),
+            Stone::White => write!(f, "White"),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Square {
+    Empty,
+    Black,
+    White,
+}
+
+impl Square {
+    fn stone(&self) -> Option<Stone> {
+        match self {
+            Square::Empty => None,
+            Square::Black => Some(Stone::Black),
+            Square::White => Some(Stone::White),
+        }
+    }
+}
+
+impl From<Stone> for Square {
+    fn from(stone: Stone) -> Self {
+        match stone {
+            Stone::Black => Square::Black,
+            Stone::White => Square::White,
+        }
+    }
+}
+
+impl ZobristSquare for Square {
+    const KINDS: usize = 2;
+
+    fn zobrist_kind(&self) -> Option<usize> {
+        self.stone().map(|stone| stone.idx())
+    }
+}
+
+type Board = Vec<Square>;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Move {
+    Place(usize),
+    Pass,
+    Resign,
+}
+
+//Area (Chinese) counts stones and territory, territory (Japanese) counts territory and prisoners
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Scoring {
+    Area,
+    Territory,
+}
+
+impl Scoring {
+    fn default_komi(&self) -> f32 {
+        match self {
+            Scoring::Area => 7.5,
+            Scoring::Territory => 6.5,
+        }
+    }
+}
+
+//Komi is compensation given to white for moving second
+#[derive(Debug, Clone, PartialEq)]
+struct Settings {
+    size: usize,
+    komi: f32,
+    handicap: usize,
+    scoring: Scoring,
+}
+
+impl Settings {
+    fn new(size: usize, handicap: usize, scoring: Scoring) -> Self {
+        let komi = if handicap > 1 {
+            0.5
+        } else {
+            scoring.default_komi()
+        };
+        Settings {
+            size,
+            komi,
+            handicap,
+            scoring,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Position {
+    board: Board,
+    to_move: Stone,
+    //Stones captured by black and by white
+    prisoners: [usize; 2],
+    //Consecutive passes, two ends the game
+    passes: usize,
+}
+
+impl Position {
+    fn new(board: Board, to_move: Stone) -> Self {
+        Position {
+            board,
+            to_move,
+            prisoners: [0, 0],
+            passes: 0,
+        }
+    }
+
+    //Only the stones are hashed as superko is positional
+    fn hash(&self, zobrist: &Zobrist) -> u64 {
+        zobrist.hash(&self.board, Player::Human)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq)]
+struct Score {
+    black: f32,
+    white: f32,
+}
+
+impl Score {
+    fn winner(&self) -> Option<Stone> {
+        if self.black > self.white {
+            Some(Stone::Black)
+        } else if self.white > self.black {
+            Some(Stone::White)
+        } else {
+            None
+        }
+    }
+}
+
+//As written in SGF, e.g. B+3.5 or 0 for a draw
+impl Display for Score {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self.winner() {
+            Some(Stone::Black) => write!(f, "B+{}", self.black - self.white),
+            Some(Stone::White) => write!(f, "W+{}", self.white - self.black),
+            None => write!(f, "0"),
+        }
+    }
+}
+
+//Star points used for handicap stones, in the usual order they're placed
+fn handicap_points(size: usize, count: usize) -> Vec<usize> {
+    let edge = if size < 13 { 2 } else { 3 };
+    let far = size - 1 - edge;
+    let mid = size / 2;
+    let top_right = (far, edge);
+    let bottom_left = (edge, far);
+    let bottom_right = (far, far);
+    let top_left = (edge, edge);
+    let centre = (mid, mid);
+    let left = (edge, mid);
+    let right = (far, mid);
+    let top = (mid, edge);
+    let bottom = (mid, far);
+    let corners = vec![top_right, bottom_left, bottom_right, top_left];
+    let points = match count {
+        0 | 1 => vec![],
+        2..=4 => corners[..count].to_vec(),
+        5 => [corners, vec![centre]].concat(),
+        6 => [corners, vec![left, right]].concat(),
+        7 => [corners, vec![left, right, centre]].concat(),
+        8 => [corners, vec![left, right, top, bottom]].concat(),
+        _ => [corners, vec![left, right, top, bottom, centre]].concat(),
+    };
+    points.into_iter().map(|(x, y)| x + y * size).collect()
+}
+
+//The marked points on the board, small boards only have the corners and centre
+fn star_points(size: usize) -> Vec<usize> {
+    if size < 13 {
+        handicap_points(size, 5)
+    } else {
+        handicap_points(size, MAX_HANDICAP)
+    }
+}
+
+//A game in progress, shared by the GUI, SGF and the AI
+#[derive(Debug, Clone)]
+struct Game {
+    settings: Settings,
+    position: Position,
+    zobrist: Zobrist,
+    //Every board so far, for superko
+    history: PositionHistory,
+    moves: Vec<(Stone, Move)>,
+    //Handicap stones placed from the settings
+    handicap: Vec<usize>,
+    //Stones placed before the first move, other than handicap stones
+    setup: Vec<(Stone, usize)>,
+}
+
+impl Game {
+    //Also sets the board size
+    fn new(settings: Settings) -> Self {
+        set_board_size((settings.size, settings.size));
+        let mut board = vec![Square::Empty; settings.size * settings.size];
+        let handicap = handicap_points(settings.size, settings.handicap);
+        for idx in &handicap {
+            board[*idx] = Square::Black;
+        }
+        let to_move = if handicap.is_empty() {
+            Stone::Black
+        } else {
+            Stone::White
+        };
+        let zobrist = Zobrist::new::<Square>(board.len());
+        let position = Position::new(board, to_move);
+        let mut history = PositionHistory::default();
+        history.push(position.hash(&zobrist));
+        Game {
+            settings,
+            position,
+            zobrist,
+            history,
+            moves: vec![],
+            handicap,
+            setup: vec![],
+        }
+    }
+
+    //Adds stones before any moves are played, e.g. from an SGF record
+    fn add_setup(&mut self, stone: Stone, idx: usize) {
+        self.position.board[idx] = stone.into();
+        self.setup.push((stone, idx));
+        self.history.clear();
+        self.history.push(self.position.hash(&self.zobrist));
+    }
+
+    fn is_over(&self) -> bool {
+        self.position.passes >= 2 || matches!(self.moves.last(), Some((_, Move::Resign)))
+    }
+
+    //Placing on an empty point that isn't suicide and doesn't repeat an earlier board
+    fn is_legal(&self, mov: &Move) -> bool {
+        match mov {
+            Move::Place(idx) => match place(&self.position, *idx) {
+                Some(next) => self.history.count(next.hash(&self.zobrist)) == 0,
+                None => false,
+            },
+            Move::Pass | Move::Resign => true,
+        }
+    }
+
+    fn play(&mut self, mov: Move) -> Result<(), String> {
+        if self.is_over() {
+            return Err(String::from("The game is over"));
+        }
+        let stone = self.position.to_move;
+        match mov {
+            Move::Place(idx) => {
+                let next = place(&self.position, idx)
+                    .map(|next| (next.hash(&self.zobrist), next))
+                    .filter(|(hash, _)| self.history.count(*hash) == 0);
+                let (hash, next) =
+                    next.ok_or_else(|| format!("Illegal move at {}", point_name(idx)))?;
+                self.position = next;
+                self.history.push(hash);
+            }
+            Move::Pass => {
+                self.position.passes += 1;
+                self.position.to_move = stone.opposite();
+            }
+            Move::Resign => {}
+        }
+        self.moves.push((stone, mov));
+        Ok(())
+    }
+
+    fn score(&self, dead: &[usize]) -> Score {
+        score_position(&self.position, dead, &self.settings)
+    }
+}
+
+//Columns skip I as is traditional, rows count up from the bottom, e.g. D4
+fn point_name(idx: usize) -> String {
+    let BoardCoord(x, y) = BoardCoord::from(idx);
+    let column = b"ABCDEFGHJKLMNOPQRSTUVWXYZ"[x] as char;
+    format!("{}{}", column, board_cols() - y)
+}
+
+//Reverse of point_name, case insensitive
+fn parse_point_name(name: &str) -> Option<usize> {
+    let mut chars = name.chars();
+    let column = chars.next()?.to_ascii_uppercase();
+    let x = b"ABCDEFGHJKLMNOPQRSTUVWXYZ"
+        .iter()
+        .position(|chr| *chr as char == column)?;
+    let row: usize = chars.as_str().parse().ok()?;
+    let size = board_cols();
+    if x >= size || row == 0 || row > size {
+        return None;
+    }
+    Some(BoardCoord(x, size - row).idx())
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Phase {
+    //Choosing colour, handicap and scoring
+    Setup,
+    Playing,
+    //After both players pass the human marks dead stones before scoring
+    MarkingDead,
+    Finished,
+}
+
+//Rows on the setup screen
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum SetupRow {
+    Colour,
+    Handicap,
+    Scoring,
+}
+
+#[derive(Debug)]
+struct State {
+    play_state: PlayState,
+    phase: Phase,
+    setup_row: SetupRow,
+    game: Game,
+    human: Stone,
+    cursor: Cursor,
+    dead: Vec<usize>,
+    score: Option<Score>,
+    next_move_time: f64,
+    computer_move: Option<Move>,
+    //Computer's move search, run a slice at a time
+    search: Option<Search<Game>>,
+    //Shown beside the board, e.g. after saving the game or when the computer passes
+    message: Option<String>,
+}
+
+impl State {
+    fn player(&self, stone: Stone) -> Player {
+        if stone == self.human {
+            Player::Human
+        } else {
+            Player::Computer
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_point_names() {
+        set_board_size((19, 19));
+        assert_eq!(point_name(0), "A19");
+        assert_eq!(point_name(360), "T1");
+        assert_eq!(parse_point_name("d4"), Some(BoardCoord(3, 15).idx()));
+        assert_eq!(parse_point_name("J10"), Some(BoardCoord(8, 9).idx()));
+        assert_eq!(parse_point_name("I10"), None);
+        assert_eq!(parse_point_name("A20"), None);
+        for idx in 0..361 {
+            assert_eq!(parse_point_name(&point_name(idx)), Some(idx));
+        }
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_handicap() {
+        let game = Game::new(Settings::new(19, 4, Scoring::Territory));
+        assert_eq!(game.settings.komi, 0.5);
+        assert_eq!(game.position.to_move, Stone::White);
+        let stones: Vec<usize> = (0..361)
+            .filter(|idx| game.position.board[*idx] == Square::Black)
+            .collect();
+        assert_eq!(stones.len(), 4);
+        assert!(stones.contains(&parse_point_name("D4").unwrap()));
+        assert!(stones.contains(&parse_point_name("Q16").unwrap()));
+        assert_eq!(handicap_points(9, 5).len(), 5);
+        assert_eq!(handicap_points(13, 9).len(), 9);
+    }
+}
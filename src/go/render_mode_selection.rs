@@ -0,0 +1,77 @@
+use crate::constants::colors::{LIGHT_BLUE, LIGHT_GRAY, WHITE};
+use crate::constants::Direction;
+use crate::go::{Scoring, SetupRow, State};
+use crate::system::math::{pt, Offset};
+use crate::system::mesh_helper::MeshHelper;
+use ggez::{Context, GameResult};
+
+pub(super) fn render_mode_selection(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let settings = &state.game.settings;
+    let cursor = mesh_helper.make_triangle(ctx, 12., 12., Direction::Right)?;
+    let row_height = mesh_helper.calc_height(0.08);
+    let label_x = mesh_helper.calc_width(0.3);
+    let value_x = mesh_helper.calc_width(0.55);
+    let top = mesh_helper.calc_height(0.3);
+
+    mesh_helper.draw_white_text(
+        ctx,
+        &format!("Go {0}x{0}", settings.size),
+        mesh_helper
+            .center()
+            .offset(0., -mesh_helper.calc_height(0.35)),
+        36.,
+        true,
+    );
+
+    let handicap = if settings.handicap == 0 {
+        String::from("None")
+    } else {
+        format!("{} stones", settings.handicap)
+    };
+    let scoring = match settings.scoring {
+        Scoring::Area => "Area (Chinese)",
+        Scoring::Territory => "Territory (Japanese)",
+    };
+    let rows = [
+        (SetupRow::Colour, "Play as", state.human.to_string()),
+        (SetupRow::Handicap, "Handicap", handicap),
+        (SetupRow::Scoring, "Scoring", scoring.to_string()),
+    ];
+    for (i, (row, label, value)) in rows.iter().enumerate() {
+        let y = top + row_height * i as f32;
+        let colour = if *row == state.setup_row {
+            mesh_helper.draw_mesh(ctx, cursor.as_ref(), pt(label_x - 24., y + 6.));
+            LIGHT_BLUE
+        } else {
+            WHITE
+        };
+        mesh_helper.draw_text(ctx, label, pt(label_x, y), colour, 24., false);
+        mesh_helper.draw_text(ctx, value, pt(value_x, y), colour, 24., false);
+    }
+
+    mesh_helper.draw_white_text(
+        ctx,
+        &format!("Komi: {}", settings.komi),
+        mesh_helper
+            .center()
+            .offset(0., mesh_helper.calc_height(0.05)),
+        20.,
+        true,
+    );
+    mesh_helper.draw_text(
+        ctx,
+        "Up/Down - Choose option, Left/Right - Change, Return - Start",
+        mesh_helper
+            .center()
+            .offset(0., mesh_helper.calc_height(0.15)),
+        LIGHT_GRAY,
+        16.,
+        true,
+    );
+
+    Ok(())
+}
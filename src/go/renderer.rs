@@ -0,0 +1,227 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::constants::colors::{alpha, APRICOT, BLACK, CREAM, DARK_BROWN, LIGHT_GRAY, RED, WHITE};
+use crate::go::controller::{DONE_KEY, PASS_KEY, RESIGN_KEY, SAVE_KEY};
+use crate::go::render_mode_selection::render_mode_selection;
+use crate::go::rules::territory;
+use crate::go::{star_points, Move, Phase, Scoring, Square, State, Stone};
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::PlayState::ModeSelection;
+use crate::system::TurnState::SelectingPiece;
+use ggez::graphics::{Color, DrawMode};
+use ggez::{Context, GameResult};
+
+pub(super) fn render(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    if state.play_state == ModeSelection {
+        render_mode_selection(ctx, mesh_helper, state)
+    } else {
+        render_game(ctx, mesh_helper, state)
+    }
+}
+
+fn stone_colour(stone: Stone) -> Color {
+    match stone {
+        Stone::Black => BLACK,
+        Stone::White => WHITE,
+    }
+}
+
+fn cell_point(idx: usize, board_start: Point, cell_size: f32) -> Point {
+    Point::from(BoardCoord::from(idx))
+        .multiply(cell_size, cell_size)
+        .offset_point(board_start)
+}
+
+fn render_game(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State) -> GameResult<()> {
+    let size = state.game.settings.size;
+    let board_width = mesh_helper.calc_height(0.85);
+    let cell_size = board_width / size as f32;
+    let board_start = pt(mesh_helper.calc_height(0.05), mesh_helper.calc_height(0.05));
+    //Lines run through the middle of each cell
+    let line_start = board_start.offset(cell_size * 0.5, cell_size * 0.5);
+    let line_width = cell_size * (size - 1) as f32;
+
+    let background = mesh_helper.make_rect(ctx, board_width, board_width, DrawMode::fill())?;
+    let grid = mesh_helper.make_grid(
+        ctx,
+        line_width,
+        line_width,
+        size - 1,
+        size - 1,
+        1.,
+        DARK_BROWN,
+        None,
+    )?;
+    let border = mesh_helper.make_rect(ctx, line_width, line_width, DrawMode::stroke(2.))?;
+    let star = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.08, DrawMode::fill())?;
+    let stone = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.46, DrawMode::fill())?;
+    let outline =
+        mesh_helper.make_circle(ctx, cell_size, cell_size * 0.46, DrawMode::stroke(1.))?;
+    let marker = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.2, DrawMode::stroke(2.))?;
+    let owner = mesh_helper.make_rect(ctx, cell_size * 0.3, cell_size * 0.3, DrawMode::fill())?;
+
+    mesh_helper.draw_coloured_mesh(ctx, background.as_ref(), board_start, APRICOT);
+    mesh_helper.draw_mesh(ctx, grid.as_ref(), line_start);
+    mesh_helper.draw_coloured_mesh(ctx, border.as_ref(), line_start, DARK_BROWN);
+    for idx in star_points(size) {
+        mesh_helper.draw_coloured_mesh(
+            ctx,
+            star.as_ref(),
+            cell_point(idx, board_start, cell_size),
+            DARK_BROWN,
+        );
+    }
+
+    let scoring = matches!(state.phase, Phase::MarkingDead | Phase::Finished);
+    for (idx, square) in state.game.position.board.iter().enumerate() {
+        if let Some(colour) = square.stone() {
+            let xy = cell_point(idx, board_start, cell_size);
+            let dead = scoring && state.dead.contains(&idx);
+            let fill = if dead {
+                alpha(stone_colour(colour), 0.4)
+            } else {
+                stone_colour(colour)
+            };
+            mesh_helper.draw_coloured_mesh(ctx, stone.as_ref(), xy, fill);
+            mesh_helper.draw_coloured_mesh(ctx, outline.as_ref(), xy, BLACK);
+        }
+    }
+
+    if scoring {
+        let mut board = state.game.position.board.clone();
+        for idx in &state.dead {
+            board[*idx] = Square::Empty;
+        }
+        for (idx, owner_stone) in territory(&board).iter().enumerate() {
+            if let Some(owner_stone) = owner_stone {
+                mesh_helper.draw_coloured_mesh(
+                    ctx,
+                    owner.as_ref(),
+                    cell_point(idx, board_start, cell_size)
+                        .offset(cell_size * 0.35, cell_size * 0.35),
+                    stone_colour(*owner_stone),
+                );
+            }
+        }
+    }
+
+    let last_move = state
+        .game
+        .moves
+        .iter()
+        .rev()
+        .find_map(|(_, mov)| match mov {
+            Move::Place(idx) => Some(*idx),
+            _ => None,
+        });
+    if let Some(idx) = last_move {
+        mesh_helper.draw_coloured_mesh(
+            ctx,
+            marker.as_ref(),
+            cell_point(idx, board_start, cell_size),
+            RED,
+        );
+    }
+    if let Some(Move::Place(idx)) = state.computer_move {
+        mesh_helper.draw_coloured_mesh(
+            ctx,
+            stone.as_ref(),
+            cell_point(idx, board_start, cell_size),
+            alpha(stone_colour(state.game.position.to_move), 0.5),
+        );
+    }
+
+    if state.play_state.is_human(SelectingPiece) {
+        state
+            .cursor
+            .render(ctx, mesh_helper, board_start, cell_size)?;
+    }
+
+    render_panel(
+        ctx,
+        mesh_helper,
+        state,
+        pt(board_start.x + board_width + 30., board_start.y),
+    );
+
+    Ok(())
+}
+
+fn render_panel(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State, start: Point) {
+    let game = &state.game;
+    let settings = &game.settings;
+    let mut lines = vec![
+        format!("You: {}", state.human),
+        format!(
+            "Scoring: {}",
+            match settings.scoring {
+                Scoring::Area => "Area",
+                Scoring::Territory => "Territory",
+            }
+        ),
+        format!("Komi: {}", settings.komi),
+        format!(
+            "Black captures: {}",
+            game.position.prisoners[Stone::Black.idx()]
+        ),
+        format!(
+            "White captures: {}",
+            game.position.prisoners[Stone::White.idx()]
+        ),
+        String::new(),
+    ];
+    match state.phase {
+        Phase::Setup | Phase::Playing => {
+            lines.push(format!("{} to play", game.position.to_move));
+        }
+        Phase::MarkingDead => {
+            let score = game.score(&state.dead);
+            lines.push(String::from("Mark dead stones"));
+            lines.push(format!("Black: {}", score.black));
+            lines.push(format!("White: {}", score.white));
+        }
+        Phase::Finished => {
+            if let Some(score) = state.score {
+                lines.push(format!("Black: {}", score.black));
+                lines.push(format!("White: {}", score.white));
+                lines.push(format!("Result: {}", score));
+            }
+        }
+    }
+    for (i, line) in lines.iter().enumerate() {
+        mesh_helper.draw_white_text(ctx, line, start.offset(0., i as f32 * 28.), 20., false);
+    }
+    if let Some(message) = &state.message {
+        mesh_helper.draw_text(
+            ctx,
+            message,
+            start.offset(0., (lines.len() + 1) as f32 * 28.),
+            CREAM,
+            18.,
+            false,
+        );
+    }
+
+    let hint = match state.phase {
+        Phase::MarkingDead => format!(
+            "Return - Mark/unmark group, {:?} - Done, {:?} - Save SGF",
+            DONE_KEY, SAVE_KEY
+        ),
+        _ => format!(
+            "Return - Place stone, {:?} - Pass, {:?} - Resign, {:?} - Save SGF",
+            PASS_KEY, RESIGN_KEY, SAVE_KEY
+        ),
+    };
+    mesh_helper.draw_text(
+        ctx,
+        &hint,
+        pt(8., mesh_helper.height - 24.),
+        LIGHT_GRAY,
+        14.,
+        false,
+    );
+}
@@ -0,0 +1,251 @@
+use crate::go::{Position, Score, Scoring, Settings, Square, Stone};
+use crate::system::neighbours::get_neighbours;
+
+fn adjacent(idx: usize) -> Vec<usize> {
+    get_neighbours(idx, true, false)
+}
+
+//The connected stones including origin and how many liberties they have
+pub(super) fn group(board: &[Square], origin: usize) -> (Vec<usize>, usize) {
+    let colour = board[origin];
+    let mut stones = vec![origin];
+    let mut liberties = vec![];
+    let mut i = 0;
+    while i < stones.len() {
+        for next in adjacent(stones[i]) {
+            if board[next] == colour {
+                if !stones.contains(&next) {
+                    stones.push(next);
+                }
+            } else if board[next] == Square::Empty && !liberties.contains(&next) {
+                liberties.push(next);
+            }
+        }
+        i += 1;
+    }
+    (stones, liberties.len())
+}
+
+//The position after the player to move places a stone on idx, capturing any groups left
+//without liberties, None if the point is taken or it would be suicide
+pub(super) fn place(position: &Position, idx: usize) -> Option<Position> {
+    if position.board[idx] != Square::Empty {
+        return None;
+    }
+    let stone = position.to_move;
+    let mut next = position.clone();
+    next.board[idx] = stone.into();
+    let mut captured = 0;
+    for neighbour in adjacent(idx) {
+        if next.board[neighbour] == stone.opposite().into() {
+            let (stones, liberties) = group(&next.board, neighbour);
+            if liberties == 0 {
+                captured += stones.len();
+                for stone in stones {
+                    next.board[stone] = Square::Empty;
+                }
+            }
+        }
+    }
+    if captured == 0 && group(&next.board, idx).1 == 0 {
+        return None;
+    }
+    next.prisoners[stone.idx()] += captured;
+    next.to_move = stone.opposite();
+    next.passes = 0;
+    Some(next)
+}
+
+//Owner of each empty point, a region of empty points belongs to a colour if it only borders that colour
+pub(super) fn territory(board: &[Square]) -> Vec<Option<Stone>> {
+    let mut owners = vec![None; board.len()];
+    let mut visited = vec![false; board.len()];
+    for start in 0..board.len() {
+        if board[start] != Square::Empty || visited[start] {
+            continue;
+        }
+        let mut region = vec![start];
+        visited[start] = true;
+        let mut borders = (false, false);
+        let mut i = 0;
+        while i < region.len() {
+            for next in adjacent(region[i]) {
+                match board[next] {
+                    Square::Empty => {
+                        if !visited[next] {
+                            visited[next] = true;
+                            region.push(next);
+                        }
+                    }
+                    Square::Black => borders.0 = true,
+                    Square::White => borders.1 = true,
+                }
+            }
+            i += 1;
+        }
+        let owner = match borders {
+            (true, false) => Some(Stone::Black),
+            (false, true) => Some(Stone::White),
+            _ => None,
+        };
+        for idx in region {
+            owners[idx] = owner;
+        }
+    }
+    owners
+}
+
+//Dead stones are removed and count as prisoners for territory scoring
+pub(super) fn score_position(position: &Position, dead: &[usize], settings: &Settings) -> Score {
+    let mut board = position.board.clone();
+    let mut prisoners = position.prisoners;
+    for idx in dead {
+        if let Some(stone) = board[*idx].stone() {
+            prisoners[stone.opposite().idx()] += 1;
+            board[*idx] = Square::Empty;
+        }
+    }
+    let owners = territory(&board);
+    let count_territory =
+        |stone: Stone| owners.iter().filter(|owner| **owner == Some(stone)).count();
+    let count_stones = |stone: Stone| {
+        board
+            .iter()
+            .filter(|square| square.stone() == Some(stone))
+            .count()
+    };
+    let points = |stone: Stone| {
+        let extra = match settings.scoring {
+            Scoring::Area => count_stones(stone),
+            Scoring::Territory => prisoners[stone.idx()],
+        };
+        (count_territory(stone) + extra) as f32
+    };
+    Score {
+        black: points(Stone::Black),
+        white: points(Stone::White) + settings.komi,
+    }
+}
+
+//A starting guess at dead stones for the human to correct, groups without an eye
+//that are short of liberties
+//Groups in seki or with only large eyes can be wrongly marked, so this isn't used for GTP scoring
+pub(super) fn suggest_dead(position: &Position) -> Vec<usize> {
+    let board = &position.board;
+    let mut dead = vec![];
+    let mut checked = vec![false; board.len()];
+    for idx in 0..board.len() {
+        if board[idx] == Square::Empty || checked[idx] {
+            continue;
+        }
+        let (stones, liberties) = group(board, idx);
+        for stone in &stones {
+            checked[*stone] = true;
+        }
+        let has_eye = stones
+            .iter()
+            .flat_map(|stone| adjacent(*stone))
+            .any(|point| {
+                board[point] == Square::Empty
+                    && adjacent(point)
+                        .iter()
+                        .all(|next| board[*next] == board[idx])
+            });
+        if !has_eye && liberties < 4 {
+            dead.extend(stones);
+        }
+    }
+    dead
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    const E: Square = Square::Empty;
+    const B: Square = Square::Black;
+    const W: Square = Square::White;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_capture_and_suicide() {
+        set_board_size((5, 5));
+        #[rustfmt::skip]
+        let position = Position::new(vec![
+            W, B, E, E, E,
+            E, E, W, E, E,
+            E, W, E, W, E,
+            E, E, W, E, E,
+            E, E, E, E, E,
+        ], Stone::Black);
+        //Surrounded by white, then an occupied point
+        assert!(place(&position, 12).is_none());
+        assert!(place(&position, 1).is_none());
+        let next = place(&position, 5).unwrap();
+        assert_eq!(next.board[0], E);
+        assert_eq!(next.prisoners, [1, 0]);
+        assert_eq!(next.to_move, Stone::White);
+
+        //Capturing makes a move that would otherwise be suicide legal
+        #[rustfmt::skip]
+        let position = Position::new(vec![
+            E, W, B, E, E,
+            W, B, E, E, E,
+            B, E, E, E, E,
+            E, E, E, E, E,
+            E, E, E, E, E,
+        ], Stone::Black);
+        let next = place(&position, 0).unwrap();
+        assert_eq!(next.board[1], E);
+        assert_eq!(next.board[5], E);
+        assert_eq!(next.prisoners, [2, 0]);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_group_liberties() {
+        set_board_size((5, 5));
+        #[rustfmt::skip]
+        let board = vec![
+            B, B, W, E, E,
+            E, B, W, E, E,
+            E, E, E, E, E,
+            E, E, E, E, E,
+            E, E, E, E, E,
+        ];
+        let (stones, liberties) = group(&board, 0);
+        assert_eq!(stones.len(), 3);
+        assert_eq!(liberties, 2);
+        assert_eq!(group(&board, 2), (vec![2, 7], 3));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_scoring() {
+        set_board_size((5, 5));
+        #[rustfmt::skip]
+        let mut position = Position::new(vec![
+            E, B, W, E, E,
+            E, B, W, E, E,
+            E, B, W, W, W,
+            B, B, W, E, E,
+            E, B, W, E, B,
+        ], Stone::Black);
+        position.prisoners = [1, 2];
+        let area = Settings::new(5, 0, Scoring::Area);
+        let territory = Settings::new(5, 0, Scoring::Territory);
+        //Once the lone black stone is dead black has 6 stones and 4 points, white 7 stones and 8 points
+        let score = score_position(&position, &[24], &area);
+        assert_eq!(score.black, 6. + 4.);
+        assert_eq!(score.white, 7. + 8. + 7.5);
+        let score = score_position(&position, &[24], &territory);
+        assert_eq!(score.black, 4. + 1.);
+        assert_eq!(score.white, 8. + 3. + 6.5);
+        assert_eq!(score.to_string(), "W+12.5");
+        //Without marking it dead the bottom right region is neutral
+        let score = score_position(&position, &[], &territory);
+        assert_eq!(score.white, 4. + 2. + 6.5);
+    }
+}
@@ -0,0 +1,339 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::go::{Game, Move, Scoring, Settings, Stone, SIZES};
+
+//Smart Game Format, only the main line of a record is read, variations are skipped
+
+type Node = Vec<(String, Vec<String>)>;
+
+fn point(idx: usize) -> String {
+    let BoardCoord(x, y) = BoardCoord::from(idx);
+    format!("{}{}", (b'a' + x as u8) as char, (b'a' + y as u8) as char)
+}
+
+fn parse_point(value: &str, size: usize) -> Result<Option<usize>, String> {
+    let bytes = value.as_bytes();
+    //Empty or tt (on boards up to 19x19) is a pass
+    if value.is_empty() || (value == "tt" && size <= 19) {
+        return Ok(None);
+    }
+    if bytes.len() != 2 || !bytes.iter().all(u8::is_ascii_lowercase) {
+        return Err(format!("Invalid point: {}", value));
+    }
+    let x = (bytes[0] - b'a') as usize;
+    let y = (bytes[1] - b'a') as usize;
+    if x >= size || y >= size {
+        return Err(format!("Point outside board: {}", value));
+    }
+    Ok(Some(x + y * size))
+}
+
+fn stone_letter(stone: Stone) -> &'static str {
+    match stone {
+        Stone::Black => "B",
+        Stone::White => "W",
+    }
+}
+
+//result is written as RE, e.g. B+R or W+3.5
+pub(super) fn to_sgf(game: &Game, result: Option<&str>) -> String {
+    let settings = &game.settings;
+    let mut root = format!(
+        "(;GM[1]FF[4]CA[UTF-8]SZ[{}]KM[{}]RU[{}]",
+        settings.size,
+        settings.komi,
+        match settings.scoring {
+            Scoring::Area => "Chinese",
+            Scoring::Territory => "Japanese",
+        }
+    );
+    if settings.handicap > 1 {
+        root.push_str(&format!("HA[{}]", settings.handicap));
+    }
+    if let Some(result) = result {
+        root.push_str(&format!("RE[{}]", result));
+    }
+    for stone in [Stone::Black, Stone::White].iter() {
+        //Handicap stones placed by Game::new aren't in setup, those read from a record are
+        let handicap = match stone {
+            Stone::Black => game.handicap.as_slice(),
+            Stone::White => &[],
+        };
+        let points: Vec<usize> = handicap
+            .iter()
+            .copied()
+            .chain(
+                game.setup
+                    .iter()
+                    .filter(|(other, _)| other == stone)
+                    .map(|(_, idx)| *idx),
+            )
+            .collect();
+        if !points.is_empty() {
+            root.push_str(&format!("A{}", stone_letter(*stone)));
+            for idx in points {
+                root.push_str(&format!("[{}]", point(idx)));
+            }
+        }
+    }
+    let mut lines = vec![root];
+    for (stone, mov) in &game.moves {
+        let value = match mov {
+            Move::Place(idx) => point(*idx),
+            Move::Pass => String::new(),
+            Move::Resign => continue,
+        };
+        lines.push(format!(";{}[{}]", stone_letter(*stone), value));
+    }
+    lines.push(String::from(")\n"));
+    lines.join("\n")
+}
+
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn skip_whitespace(&mut self) {
+        while self
+            .chars
+            .peek()
+            .map(|chr| chr.is_whitespace())
+            .unwrap_or(false)
+        {
+            self.chars.next();
+        }
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), String> {
+        self.skip_whitespace();
+        match self.chars.next() {
+            Some(chr) if chr == expected => Ok(()),
+            Some(chr) => Err(format!("Expected '{}' but found '{}'", expected, chr)),
+            None => Err(format!("Expected '{}' but the record ended", expected)),
+        }
+    }
+
+    fn value(&mut self) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('\\') => value.extend(self.chars.next()),
+                Some(']') => return Ok(value),
+                Some(chr) => value.push(chr),
+                None => return Err(String::from("Unterminated property value")),
+            }
+        }
+    }
+
+    fn node(&mut self) -> Result<Node, String> {
+        self.expect(';')?;
+        let mut node = vec![];
+        loop {
+            self.skip_whitespace();
+            let mut name = String::new();
+            while let Some(chr) = self.chars.peek().filter(|chr| chr.is_ascii_uppercase()) {
+                name.push(*chr);
+                self.chars.next();
+            }
+            if name.is_empty() {
+                return Ok(node);
+            }
+            let mut values = vec![];
+            self.skip_whitespace();
+            while self.chars.peek() == Some(&'[') {
+                self.chars.next();
+                values.push(self.value()?);
+                self.skip_whitespace();
+            }
+            if values.is_empty() {
+                return Err(format!("Property {} has no value", name));
+            }
+            node.push((name, values));
+        }
+    }
+
+    //The nodes of a game tree following the first variation at each branch
+    fn main_line(&mut self) -> Result<Vec<Node>, String> {
+        self.expect('(')?;
+        let mut nodes = vec![];
+        let mut took_variation = false;
+        loop {
+            self.skip_whitespace();
+            match self.chars.peek() {
+                Some(';') if !took_variation => nodes.push(self.node()?),
+                Some('(') => {
+                    let variation = self.main_line()?;
+                    if !took_variation {
+                        nodes.extend(variation);
+                        took_variation = true;
+                    }
+                }
+                Some(')') => {
+                    self.chars.next();
+                    return Ok(nodes);
+                }
+                Some(chr) => return Err(format!("Unexpected '{}'", chr)),
+                None => return Err(String::from("Record ended inside a game tree")),
+            }
+        }
+    }
+}
+
+fn property<'a>(node: &'a Node, name: &str) -> Option<&'a Vec<String>> {
+    node.iter()
+        .find(|(other, _)| other == name)
+        .map(|(_, values)| values)
+}
+
+//Builds a game from the first game tree in the record, also sets the board size
+pub(super) fn parse_sgf(text: &str) -> Result<Game, String> {
+    let start = text.find('(').ok_or("No game tree found")?;
+    let nodes = Parser {
+        chars: text[start..].chars().peekable(),
+    }
+    .main_line()?;
+    let root = nodes.first().ok_or("Record has no nodes")?;
+    if let Some(game) = property(root, "GM") {
+        if game[0].trim() != "1" {
+            return Err(format!("Not a Go record (GM[{}])", game[0]));
+        }
+    }
+    let size = match property(root, "SZ") {
+        Some(size) => size[0]
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid board size: {}", size[0]))?,
+        None => 19,
+    };
+    if !SIZES.contains(&size) {
+        return Err(format!("Unsupported board size: {}", size));
+    }
+    let scoring = match property(root, "RU").map(|rules| rules[0].to_ascii_lowercase()) {
+        Some(rules) if rules.contains("chinese") || rules.contains("aga") || rules == "nz" => {
+            Scoring::Area
+        }
+        _ => Scoring::Territory,
+    };
+    let mut settings = Settings::new(size, 0, scoring);
+    if let Some(komi) = property(root, "KM") {
+        settings.komi = komi[0]
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid komi: {}", komi[0]))?;
+    }
+    if let Some(handicap) = property(root, "HA") {
+        settings.handicap = handicap[0]
+            .trim()
+            .parse()
+            .map_err(|_| format!("Invalid handicap: {}", handicap[0]))?;
+    }
+    //Handicap stones are read from AB rather than placed automatically
+    let handicap = settings.handicap;
+    settings.handicap = 0;
+    let mut game = Game::new(settings);
+    game.settings.handicap = handicap;
+    for (name, stone) in [("AB", Stone::Black), ("AW", Stone::White)].iter() {
+        for value in property(root, name).into_iter().flatten() {
+            let idx = parse_point(value, size)?.ok_or("Setup stones can't pass")?;
+            game.add_setup(*stone, idx);
+        }
+    }
+    if handicap > 1 || property(root, "PL").map(|player| player[0] == "W") == Some(true) {
+        game.position.to_move = Stone::White;
+    }
+    for node in &nodes {
+        for (name, stone) in [("B", Stone::Black), ("W", Stone::White)].iter() {
+            if let Some(values) = property(node, name) {
+                let mov = match parse_point(&values[0], size)? {
+                    Some(idx) => Move::Place(idx),
+                    None => Move::Pass,
+                };
+                //Records don't always alternate, e.g. after handicap stones
+                game.position.to_move = *stone;
+                game.play(mov)
+                    .map_err(|err| format!("Move {}: {}", game.moves.len() + 1, err))?;
+            }
+        }
+    }
+    Ok(game)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::go::{parse_point_name, Square};
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_round_trip() {
+        let mut game = Game::new(Settings::new(9, 0, Scoring::Area));
+        for name in ["E5", "C3", "G7"].iter() {
+            game.play(Move::Place(parse_point_name(name).unwrap()))
+                .unwrap();
+        }
+        game.play(Move::Pass).unwrap();
+        let sgf = to_sgf(&game, None);
+        assert!(sgf.contains("SZ[9]KM[7.5]RU[Chinese]"));
+        assert!(sgf.contains(";B[ee]"));
+        assert!(sgf.contains(";W[cg]"));
+        let loaded = parse_sgf(&sgf).unwrap();
+        assert_eq!(loaded.settings, game.settings);
+        assert_eq!(loaded.moves, game.moves);
+        assert_eq!(loaded.position, game.position);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_handicap_and_variations() {
+        let sgf = "(;GM[1]FF[4]SZ[19]KM[0.5]HA[2]AB[pd][dp]RU[Japanese]
+            ;W[qp](;B[dd]C[main line];W[pq])(;B[cc]))";
+        let game = parse_sgf(sgf).unwrap();
+        assert_eq!(game.settings.handicap, 2);
+        assert_eq!(game.settings.scoring, Scoring::Territory);
+        assert_eq!(game.moves.len(), 3);
+        assert_eq!(game.moves[0].0, Stone::White);
+        assert_eq!(
+            game.position.board[parse_point_name("D16").unwrap()],
+            Square::Black
+        );
+        assert_eq!(
+            game.position.board[parse_point_name("C17").unwrap()],
+            Square::Empty
+        );
+        let saved = to_sgf(&game, Some("W+R"));
+        assert!(saved.contains("HA[2]RE[W+R]AB[pd][dp]"));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_free_handicap_round_trip() {
+        let sgf = "(;GM[1]FF[4]SZ[9]KM[0.5]HA[2]AB[aa][bb];W[ee])";
+        let game = parse_sgf(sgf).unwrap();
+        let saved = to_sgf(&game, None);
+        assert!(saved.contains("AB[aa][bb]"));
+        assert!(!saved.contains("[gc]") && !saved.contains("[cg]"));
+        let loaded = parse_sgf(&saved).unwrap();
+        assert_eq!(loaded.position, game.position);
+
+        //A handicap without stones stays without stones
+        let game = parse_sgf("(;GM[1]FF[4]SZ[9]HA[2];W[ee])").unwrap();
+        assert!(!to_sgf(&game, None).contains("AB"));
+
+        let mut game = Game::new(Settings::new(9, 2, Scoring::Area));
+        game.play(Move::Place(parse_point_name("E5").unwrap()))
+            .unwrap();
+        let loaded = parse_sgf(&to_sgf(&game, None)).unwrap();
+        assert_eq!(loaded.position, game.position);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_invalid_records() {
+        assert!(parse_sgf("").is_err());
+        assert!(parse_sgf("(;GM[1]SZ[7])").is_err());
+        assert!(parse_sgf("(;GM[1]SZ[9];B[zz])").is_err());
+        assert!(parse_sgf("(;GM[1]SZ[9];B[ee];W[ee])").is_err());
+        assert!(parse_sgf("(;GM[1]SZ[9];B[ee]").is_err());
+    }
+}
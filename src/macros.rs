@@ -25,12 +25,12 @@ pub fn padding() -> String {
 macro_rules! debug_log {
     ($msg:expr) => {
         if $crate::LOGGING_ENABLED {
-            println!("{}{}", $crate::macros::padding(), $msg);
+            eprintln!("{}{}", $crate::macros::padding(), $msg);
         }
     };
     ($($x:tt)*)=> {
         if $crate::LOGGING_ENABLED {
-            println!("{}{}", $crate::macros::padding(), format!($($x)*));
+            eprintln!("{}{}", $crate::macros::padding(), format!($($x)*));
         }
     };
 }
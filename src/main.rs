@@ -6,14 +6,13 @@ extern crate lazy_static;
 #[macro_use]
 extern crate variantly;
 
-use crate::args::{ARG_GAME, ARG_RULES, ARG_TEST, args_matches};
+use crate::args::{ARG_DXP_CONNECT, ARG_DXP_SERVER, ARG_GAME, ARG_GTP, ARG_LOAD, ARG_PROBLEMS, ARG_RULES, ARG_TEST, ARG_USI, args_matches};
 use crate::constants::games::TEST_MENU;
 use crate::menu::print_rules;
 use crate::system::game_system::GameSystem;
+use crate::system::resources::resources_dir;
 use ggez::conf::{WindowMode, WindowSetup};
 use ggez::{event, graphics, ContextBuilder};
-use std::env;
-use std::path::PathBuf;
 use ggez::event::KeyCode::P;
 
 const SCREEN_WIDTH: f32 = 1280.;
@@ -31,26 +30,49 @@ const FPS_ENABLED: bool = false;
 #[macro_use]
 mod macros;
 mod args;
+mod blackhole;
 mod boards;
 mod chess;
 mod constants;
 mod draughts;
 mod ext;
+mod go;
 mod graphics_testing;
 mod mancala;
 mod menu;
 mod orderchaos;
+mod rithmomachy;
 mod senet;
+mod shogi;
 mod system;
 mod tablut;
 mod tictactoe;
 mod tree;
+mod ur;
 
 fn main() {
     debug_log!("Games starting...");
     let matches = args_matches();
 
-    if let Some(true) =  matches.get_one(ARG_RULES) {
+    if let Some(true) = matches.get_one(ARG_USI) {
+        debug_log!("USI mode");
+        shogi::usi::run();
+    } else if let Some(true) = matches.get_one(ARG_GTP) {
+        debug_log!("GTP mode");
+        go::gtp::run();
+    } else if let Some(addr) = matches.get_one::<String>(ARG_DXP_SERVER) {
+        debug_log!("DXP server mode");
+        if let Err(err) = draughts::dxp::run_server(addr) {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    } else if let Some(addr) = matches.get_one::<String>(ARG_DXP_CONNECT) {
+        debug_log!("DXP client mode");
+        if let Err(err) = draughts::dxp::run_client(addr) {
+            eprintln!("{}", err);
+            std::process::exit(1);
+        }
+    } else if let Some(true) =  matches.get_one(ARG_RULES) {
         debug_log!("Rules only");
         let game: &String = matches.get_one(ARG_GAME).unwrap();
         print_rules(&game);
@@ -66,10 +88,24 @@ fn main() {
         if let Some(true) = matches.get_one(ARG_TEST) {
             system.start_game(TEST_MENU);
         } else {
-            if let Some(game) = matches.get_one::<&String>(ARG_GAME) {
+            if let Some(game) = matches.get_one::<String>(ARG_GAME) {
                 debug_log!("Game specified from args: {}", game);
                 graphics::set_window_title(&ctx, game);
-                system.start_game(game);
+                let loaded = match (
+                    matches.get_one::<String>(ARG_LOAD),
+                    matches.get_one::<String>(ARG_PROBLEMS),
+                ) {
+                    (Some(path), _) => system.load_game(game, path),
+                    (_, Some(path)) => system.load_problems(game, path),
+                    (None, None) => {
+                        system.start_game(game);
+                        Ok(())
+                    }
+                };
+                if let Err(err) = loaded {
+                    eprintln!("{}", err);
+                    std::process::exit(1);
+                }
             }
         }
 
@@ -78,7 +114,7 @@ fn main() {
 }
 
 fn setup_ggez() -> ContextBuilder {
-    let mut cb = ContextBuilder::new("games", "Ray Britton")
+    let cb = ContextBuilder::new("games", "Ray Britton")
         .window_mode(WindowMode {
             width: SCREEN_WIDTH,
             height: SCREEN_HEIGHT,
@@ -90,14 +126,5 @@ fn setup_ggez() -> ContextBuilder {
             ..WindowSetup::default()
         });
 
-    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
-        let mut path = PathBuf::from(manifest_dir);
-        path.push("resources");
-        //        println!("Adding path {:?} from manifest", path);
-        cb = cb.add_resource_path(path);
-    } else {
-        cb = cb.add_resource_path(PathBuf::from("./resources"));
-    }
-
-    cb
+    cb.add_resource_path(resources_dir())
 }
@@ -4,53 +4,39 @@ use rand::{thread_rng, Rng};
 
 pub(super) fn process(state: &mut State) {
     debug_log!("Homes: {:?}", state.board.computer.homes);
+    //play each move out to see what it does
+    let outcomes: Vec<(usize, usize, bool)> = state
+        .variant
+        .legal_moves(&state.board, Player::Computer)
+        .into_iter()
+        .map(|idx| {
+            let mut board = state.board.clone();
+            let (captured, another_turn) = board.sow(
+                state.variant,
+                &board.home_idx_to_square(Player::Computer, idx),
+            );
+            (idx, captured, another_turn)
+        })
+        .collect();
     //if any home slot will allow another turn play it
-    for i in (0..=5).rev() {
-        if state.board.computer.homes[i] == 6 - i {
-            debug_log!("Another turn chance found, picking up from {}", i);
-            state.computer_cursor = state
-                .board
-                .square_to_idx(&state.board.home_idx_to_square(Player::Computer, i));
-            return;
-        }
-    }
-    //if any captures are possible then play
-    let mut capturables = vec![];
-    for i in (0..=5).rev() {
-        if state.board.computer.homes[i] == 0 && state.board.human.homes[5 - i] > 0 {
-            capturables.push(i);
-        }
-    }
-    debug_log!("Capturables: {:?}", capturables);
-    for capturable in capturables {
-        for i in (0..=5).rev() {
-            let dest = if capturable > i {
-                capturable - i
-            } else {
-                capturable + 8
-            };
-            if state.board.computer.homes[i] == dest {
-                debug_log!("Capture chance found, picking up from {}", i);
-                state.computer_cursor = state
-                    .board
-                    .square_to_idx(&state.board.home_idx_to_square(Player::Computer, i));
-                return;
-            }
-        }
-    }
-    //otherwise pick a random move
-    let valid_squares: Vec<usize> = state
-        .board
-        .computer
-        .homes
+    let idx = if let Some((idx, _, _)) = outcomes.iter().rev().find(|(_, _, again)| *again) {
+        debug_log!("Another turn chance found, picking up from {}", idx);
+        *idx
+    } else if let Some((idx, captured, _)) = outcomes
         .iter()
-        .enumerate()
-        .filter_map(|(idx, num)| if num > &0 { Some(idx) } else { None })
-        .collect();
-    let computer_square = state.board.home_idx_to_square(
-        Player::Computer,
-        valid_squares[thread_rng().gen_range(0.. valid_squares.len())],
-    );
-    state.computer_cursor = state.board.square_to_idx(&computer_square);
-    debug_log!("Going with random move from {}", state.computer_cursor);
+        .filter(|(_, captured, _)| *captured > 0)
+        .max_by_key(|(_, captured, _)| *captured)
+    {
+        //if any captures are possible then play the biggest
+        debug_log!("Capture of {} found, picking up from {}", captured, idx);
+        *idx
+    } else {
+        //otherwise pick a random move
+        let (idx, _, _) = outcomes[thread_rng().gen_range(0..outcomes.len())];
+        debug_log!("Going with random move from {}", idx);
+        idx
+    };
+    state.computer_cursor = state
+        .board
+        .square_to_idx(&state.board.home_idx_to_square(Player::Computer, idx));
 }
@@ -1,16 +1,17 @@
-use crate::mancala::{Hole, Square, HOME_COUNT};
+use crate::mancala::rules::{SowingEnd, Variant};
+use crate::mancala::{Hole, Square};
 use crate::system::Player;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Hash, Eq, PartialEq)]
 pub(super) struct Board {
     pub(super) human: SubBoard,
     pub(super) computer: SubBoard,
 }
 
-#[derive(Default, Debug)]
+#[derive(Default, Debug, Clone, Hash, Eq, PartialEq)]
 pub(super) struct SubBoard {
     pub(super) end: usize,
-    pub(super) homes: [usize; 6],
+    pub(super) homes: Vec<usize>,
 }
 
 #[derive(Debug, PartialEq, Eq)]
@@ -21,35 +22,53 @@ pub(super) struct DropMove {
 }
 
 impl DropMove {
-    fn new(current_square: Square, remaining: usize) -> Self {
+    fn new(current_square: Square, remaining: usize, pits: usize, variant: Variant) -> Self {
         let mut drop_move = DropMove {
             origin: current_square.clone(),
             current_square,
             remaining,
         };
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(pits, variant);
         drop_move
     }
 }
 
 impl DropMove {
-    pub(super) fn move_to_next_square(&mut self) {
-        self.current_square = match self.current_square.hole {
-            Hole::End => Square::new(self.current_square.player.opposite(), Hole::Home(0)),
-            Hole::Home(idx) => {
-                if idx < HOME_COUNT - 1 {
-                    Square::new(self.current_square.player, Hole::Home(idx + 1))
-                } else if self.current_square.player == self.origin.player {
-                    Square::new(self.current_square.player, Hole::End)
-                } else {
-                    Square::new(self.current_square.player.opposite(), Hole::Home(0))
+    //Moves counterclockwise, skipping any squares the variant doesn't sow into
+    pub(super) fn move_to_next_square(&mut self, pits: usize, variant: Variant) {
+        loop {
+            let player = self.current_square.player;
+            self.current_square = match self.current_square.hole {
+                Hole::End => Square::new(player.opposite(), Hole::Home(0)),
+                Hole::Home(idx) if idx < pits - 1 => Square::new(player, Hole::Home(idx + 1)),
+                Hole::Home(_) => Square::new(player, Hole::End),
+            };
+            let skip = match self.current_square.hole {
+                Hole::End => {
+                    !variant.sows_into_store() || self.current_square.player != self.origin.player
                 }
+                Hole::Home(_) => variant.skips_origin() && self.current_square == self.origin,
+            };
+            if !skip {
+                return;
             }
-        };
+        }
+    }
+
+    //Drops one seed, if it was the last then the variant's rules for where it landed are applied
+    pub(super) fn step(&mut self, board: &mut Board, variant: Variant) -> Option<SowingEnd> {
+        self.remaining -= 1;
+        board.add_count(&self.current_square, 1);
+        if self.remaining > 0 {
+            self.move_to_next_square(board.pits(), variant);
+            None
+        } else {
+            Some(variant.finish_sowing(board, self))
+        }
     }
 }
 
-//square order:
+//square order (for 6 pits):
 //  |12|11|10| 9| 8| 7|
 //13|-----------------| 6
 //  | 0| 1| 2| 3| 4| 5|
@@ -60,15 +79,35 @@ impl DropMove {
 //| 0| 1| 2| 3| 4| 5|
 
 impl Board {
-    pub(super) fn new() -> Self {
+    pub(super) fn new(pits: usize) -> Self {
+        let side = SubBoard {
+            end: 0,
+            homes: vec![0; pits],
+        };
         Board {
-            human: SubBoard::default(),
-            computer: SubBoard::default(),
+            human: side.clone(),
+            computer: side,
         }
     }
 }
 
 impl Board {
+    pub(super) fn pits(&self) -> usize {
+        self.human.homes.len()
+    }
+
+    pub(super) fn side(&self, player: Player) -> &SubBoard {
+        match player {
+            Player::Human => &self.human,
+            Player::Computer => &self.computer,
+        }
+    }
+
+    //Seeds in play, including those in the stores
+    pub(super) fn total(&self) -> usize {
+        self.human.home_total() + self.human.end + self.computer.home_total() + self.computer.end
+    }
+
     pub(super) fn add_count(&mut self, square: &Square, amount: usize) {
         match square.player {
             Player::Human => match square.hole {
@@ -121,36 +160,59 @@ impl Board {
         }
     }
 
-    pub(super) fn create_drop_move(&self, start: &Square) -> DropMove {
-        DropMove::new(start.clone(), self.get_count(start))
+    pub(super) fn create_drop_move(&self, start: &Square, variant: Variant) -> DropMove {
+        DropMove::new(start.clone(), self.get_count(start), self.pits(), variant)
+    }
+
+    //Plays the whole move at once, returns the number of seeds captured and whether the player
+    //gets another turn
+    pub(super) fn sow(&mut self, variant: Variant, start: &Square) -> (usize, bool) {
+        let mut drop_move = self.create_drop_move(start, variant);
+        self.set_count(start, 0);
+        let mut captured = 0;
+        let mut another_turn = false;
+        while drop_move.remaining > 0 {
+            match drop_move.step(self, variant) {
+                Some(SowingEnd::Captured(count)) => captured += count,
+                Some(SowingEnd::AnotherTurn) => another_turn = true,
+                _ => {}
+            }
+        }
+        (captured, another_turn)
     }
 
     pub(super) fn home_idx_to_square(&self, player: Player, idx: usize) -> Square {
         match player {
             Player::Human => self.idx_to_square(idx),
-            Player::Computer => self.idx_to_square(7 + idx),
+            Player::Computer => self.idx_to_square(self.pits() + 1 + idx),
         }
     }
 
     pub(super) fn idx_to_square(&self, idx: usize) -> Square {
-        match idx {
-            0..=5 => Square::new(Player::Human, Hole::Home(idx)),
-            6 => Square::new(Player::Human, Hole::End),
-            7..=12 => Square::new(Player::Computer, Hole::Home(idx - 7)),
-            13 => Square::new(Player::Computer, Hole::End),
-            _ => panic!("Invalid idx for square: {}", idx),
+        let pits = self.pits();
+        if idx < pits {
+            Square::new(Player::Human, Hole::Home(idx))
+        } else if idx == pits {
+            Square::new(Player::Human, Hole::End)
+        } else if idx < pits * 2 + 1 {
+            Square::new(Player::Computer, Hole::Home(idx - pits - 1))
+        } else if idx == pits * 2 + 1 {
+            Square::new(Player::Computer, Hole::End)
+        } else {
+            panic!("Invalid idx for square: {}", idx)
         }
     }
 
     pub(super) fn square_to_idx(&self, square: &Square) -> usize {
+        let pits = self.pits();
         match square.player {
             Player::Human => match square.hole {
                 Hole::Home(idx) => idx,
-                Hole::End => 6,
+                Hole::End => pits,
             },
             Player::Computer => match square.hole {
-                Hole::Home(idx) => 7 + idx,
-                Hole::End => 13,
+                Hole::Home(idx) => pits + 1 + idx,
+                Hole::End => pits * 2 + 1,
             },
         }
     }
@@ -178,7 +240,7 @@ mod test {
     fn test_subboard_fill_holes() {
         let mut subboard = SubBoard {
             end: 0,
-            homes: [0, 0, 0, 0, 0, 0],
+            homes: vec![0, 0, 0, 0, 0, 0],
         };
 
         subboard.fill_homes(2);
@@ -193,7 +255,7 @@ mod test {
 
     #[test]
     fn test_board_square_to_idx() {
-        let board = Board::new();
+        let board = Board::new(6);
 
         assert_eq!(
             board.square_to_idx(&Square::new(Player::Human, Hole::Home(0))),
@@ -255,7 +317,7 @@ mod test {
 
     #[test]
     fn test_board_idx_to_square() {
-        let board = Board::new();
+        let board = Board::new(6);
 
         assert_eq!(
             board.idx_to_square(0),
@@ -317,7 +379,7 @@ mod test {
 
     #[test]
     fn test_board_home_idx_to_square() {
-        let board = Board::new();
+        let board = Board::new(6);
 
         assert_eq!(
             board.home_idx_to_square(Player::Human, 0),
@@ -372,86 +434,91 @@ mod test {
 
     #[test]
     fn test_drop_move_to_next_square_for_human() {
-        let mut drop_move = DropMove::new(Square::new(Player::Human, Hole::Home(0)), 0);
+        let mut drop_move = DropMove::new(
+            Square::new(Player::Human, Hole::Home(0)),
+            0,
+            6,
+            Variant::Kalah,
+        );
 
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(1))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(2))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(3))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(4))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(5))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::End)
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(0))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(1))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(2))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(3))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(4))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(5))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(0))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(1))
@@ -460,86 +527,91 @@ mod test {
 
     #[test]
     fn test_drop_move_to_next_square_for_computer() {
-        let mut drop_move = DropMove::new(Square::new(Player::Computer, Hole::Home(0)), 0);
+        let mut drop_move = DropMove::new(
+            Square::new(Player::Computer, Hole::Home(0)),
+            0,
+            6,
+            Variant::Kalah,
+        );
 
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(1))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(2))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(3))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(4))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(5))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::End)
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(0))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(1))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(2))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(3))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(4))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Human, Hole::Home(5))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(0))
         );
 
-        drop_move.move_to_next_square();
+        drop_move.move_to_next_square(6, Variant::Kalah);
         assert_eq!(
             drop_move.current_square,
             Square::new(Player::Computer, Hole::Home(1))
@@ -548,7 +620,7 @@ mod test {
 
     #[test]
     fn test_board_create_drop_move() {
-        let mut board = Board::new();
+        let mut board = Board::new(6);
 
         let square1 = Square::new(Player::Human, Hole::Home(0));
         let square2 = Square::new(Player::Human, Hole::Home(5));
@@ -560,10 +632,10 @@ mod test {
         board.set_count(&square3, 5);
         board.set_count(&square4, 1);
 
-        let drop_move_1 = board.create_drop_move(&square1);
-        let drop_move_2 = board.create_drop_move(&square2);
-        let drop_move_3 = board.create_drop_move(&square3);
-        let drop_move_4 = board.create_drop_move(&square4);
+        let drop_move_1 = board.create_drop_move(&square1, Variant::Kalah);
+        let drop_move_2 = board.create_drop_move(&square2, Variant::Kalah);
+        let drop_move_3 = board.create_drop_move(&square3, Variant::Kalah);
+        let drop_move_4 = board.create_drop_move(&square4, Variant::Kalah);
 
         assert_eq!(
             drop_move_1,
@@ -601,9 +673,9 @@ mod test {
 
     #[test]
     fn test_board_collect_remaining() {
-        let mut board1 = Board::new();
-        let mut board2 = Board::new();
-        let mut board3 = Board::new();
+        let mut board1 = Board::new(6);
+        let mut board2 = Board::new(6);
+        let mut board3 = Board::new(6);
 
         board1.human.homes[3] = 2;
         board1.human.homes[4] = 1;
@@ -2,7 +2,9 @@ use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
 use crate::mancala::ai::process;
 use crate::mancala::board::Board;
 use crate::mancala::render::render;
-use crate::mancala::{Hole, Square, State, HOME_COUNT};
+use crate::mancala::render_mode_selection::render_mode_selection;
+use crate::mancala::rules::{SowingEnd, Variant};
+use crate::mancala::State;
 use crate::system::math::WrappedUsize;
 use crate::system::mesh_helper::MeshHelper;
 use crate::system::PlayState::{ComputerWin, Draw, HumanWin, Playing};
@@ -13,32 +15,74 @@ use ggez::input::keyboard::KeyCode;
 use ggez::{Context, GameResult};
 use rand::{thread_rng, Rng};
 use std::cmp::Ordering;
+use std::collections::HashSet;
+
+pub(super) const SETUP_PITS: usize = 0;
+pub(super) const SETUP_SEEDS: usize = 1;
 
 pub struct Controller {
     state: State,
 }
 
 impl Controller {
-    pub fn new() -> Self {
-        let mut board = Board::new();
-        board.computer.fill_homes(4);
-        board.human.fill_homes(4);
+    pub fn new(game: &str) -> Self {
+        let variant = Variant::from_game(game)
+            .unwrap_or_else(|| panic!("Invalid game for mancala controller: {}", game));
+        let (pits, seeds) = variant.default_size();
         Controller {
             state: State {
-                play_state: PlayState::Init,
-                cursor: WrappedUsize::new_zero_based(6),
+                variant,
+                pits,
+                seeds,
+                setup_row: SETUP_PITS,
+                play_state: PlayState::ModeSelection,
+                cursor: WrappedUsize::new_zero_based(pits),
                 computer_cursor: 0,
                 drop_move: None,
-                board,
+                board: Board::new(pits),
                 next_move_time: 0.0,
                 animation_time: 0.0,
                 message: None,
+                positions: HashSet::new(),
             },
         }
     }
 }
 
 impl Controller {
+    fn handle_setup_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up | KeyCode::Down => {
+                self.state.setup_row = if self.state.setup_row == SETUP_PITS {
+                    SETUP_SEEDS
+                } else {
+                    SETUP_PITS
+                }
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let (value, range) = if self.state.setup_row == SETUP_PITS {
+                    (&mut self.state.pits, self.state.variant.pit_range())
+                } else {
+                    (&mut self.state.seeds, self.state.variant.seed_range())
+                };
+                *value = if key == KeyCode::Left {
+                    (*value - 1).max(*range.start())
+                } else {
+                    (*value + 1).min(*range.end())
+                };
+            }
+            KeyCode::Return => {
+                let mut board = Board::new(self.state.pits);
+                board.human.fill_homes(self.state.seeds);
+                board.computer.fill_homes(self.state.seeds);
+                self.state.board = board;
+                self.state.cursor = WrappedUsize::new_zero_based(self.state.pits);
+                self.state.play_state = PlayState::Init;
+            }
+            _ => {}
+        }
+    }
+
     pub(super) fn start_new_turn(&mut self, player: Player) {
         let old_state = self.state.play_state;
         debug_log_start!("\n\n----\nStarting new turn for {:?}", player);
@@ -55,13 +99,17 @@ impl Controller {
         let again = if again { " again" } else { "" };
         self.state.message = Some((format!("{} turn{}", pronoun, again), false));
         self.state.next_move_time = AI_MOVE_DELAY;
-        self.check_for_game_over();
+        self.check_for_game_over(player);
         debug_log_end!();
     }
 
-    fn check_for_game_over(&mut self) {
-        if self.state.board.human.home_total() == 0 || self.state.board.computer.home_total() == 0 {
-            debug_log!("A player has no stones left");
+    fn check_for_game_over(&mut self, player: Player) {
+        let repeated = !self
+            .state
+            .positions
+            .insert((self.state.board.clone(), player));
+        if repeated || self.state.variant.is_game_over(&self.state.board, player) {
+            debug_log!("Game over, position repeated: {}", repeated);
             debug_log_start!("Pre collection:");
             debug_log!(
                 "Human: End {} Homes {}",
@@ -110,7 +158,11 @@ impl Controller {
             Player::Computer => self.state.computer_cursor,
         };
         let square = self.state.board.idx_to_square(cursor);
-        self.state.drop_move = Some(self.state.board.create_drop_move(&square));
+        self.state.drop_move = Some(
+            self.state
+                .board
+                .create_drop_move(&square, self.state.variant),
+        );
         debug_log!(
             "Starting from {:?} with {} stones",
             square,
@@ -124,14 +176,25 @@ impl Controller {
 
 impl Scene for Controller {
     fn on_key_down(&mut self, key: KeyCode) {
-        if self.state.play_state.is_human(SelectingPiece) {
+        if self.state.play_state == PlayState::ModeSelection {
+            self.handle_setup_input(key);
+        } else if self.state.play_state.is_human(SelectingPiece) {
             match key {
                 KeyCode::Left => self.state.cursor.dec(),
                 KeyCode::Right => self.state.cursor.inc(),
                 KeyCode::Return => {
-                    let square = self.state.board.idx_to_square(self.state.cursor.value);
-                    if self.state.board.get_count(&square) > 0 {
+                    let legal_moves = self
+                        .state
+                        .variant
+                        .legal_moves(&self.state.board, Player::Human);
+                    if legal_moves.contains(&self.state.cursor.value) {
                         self.process_turn(Player::Human);
+                    } else if !legal_moves.is_empty() && self.state.board.computer.home_total() == 0
+                    {
+                        self.state.message = Some((
+                            String::from("The computer has no stones, you must give it some"),
+                            false,
+                        ));
                     }
                 }
                 _ => {}
@@ -159,37 +222,28 @@ impl Scene for Controller {
         {
             self.state.animation_time -= delta;
             if self.state.animation_time <= 0.0 {
-                let mut drop_move = self.state.drop_move.as_mut().unwrap();
+                let drop_move = self.state.drop_move.as_mut().unwrap();
                 if drop_move.remaining > 0 {
-                    drop_move.remaining -= 1;
                     debug_log!(
                         "Deposit at {:?}, {} left",
                         drop_move.current_square,
-                        drop_move.remaining
+                        drop_move.remaining - 1
                     );
-                    self.state.board.add_count(&drop_move.current_square, 1);
                     self.state.animation_time = ANIMATION_DURATION;
-                    if drop_move.remaining > 0 {
-                        drop_move.move_to_next_square();
-                    } else if let Hole::Home(idx) = drop_move.current_square.hole {
-                        if drop_move.current_square.player == drop_move.origin.player
-                            && self.state.board.get_count(&drop_move.current_square) == 1
-                        {
-                            let opposite_square = Square::new(
-                                drop_move.current_square.player.opposite(),
-                                Hole::Home(HOME_COUNT - idx - 1),
-                            );
-                            if self.state.board.get_count(&opposite_square) > 0 {
-                                self.state.message = Some((String::from("Capture!"), true));
-                                self.state.animation_time = ANIMATION_DURATION * 2.5;
-                                let count = self.state.board.get_count(&opposite_square)
-                                    + self.state.board.get_count(&drop_move.current_square);
-                                let end = Square::new(drop_move.origin.player, Hole::End);
-                                self.state.board.set_count(&opposite_square, 0);
-                                self.state.board.set_count(&drop_move.current_square, 0);
-                                self.state.board.add_count(&end, count);
-                            }
+                    match drop_move.step(&mut self.state.board, self.state.variant) {
+                        Some(SowingEnd::Captured(count)) => {
+                            self.state.message = Some((format!("Captured {}!", count), true));
+                            self.state.animation_time = ANIMATION_DURATION * 2.5;
+                        }
+                        Some(SowingEnd::GrandSlam) => {
+                            self.state.message =
+                                Some((String::from("Grand slam, nothing captured"), true));
+                            self.state.animation_time = ANIMATION_DURATION * 2.5;
                         }
+                        Some(SowingEnd::Relay) => {
+                            self.state.message = Some((String::from("Sowing on"), true));
+                        }
+                        _ => {}
                     }
                 } else {
                     let player = if !drop_move.current_square.is_home()
@@ -208,7 +262,11 @@ impl Scene for Controller {
     }
 
     fn render(&mut self, ctx: &mut Context, mesh_helper: &mut MeshHelper) -> GameResult<()> {
-        render(ctx, mesh_helper, &self.state)
+        if self.state.play_state == PlayState::ModeSelection {
+            render_mode_selection(ctx, mesh_helper, &self.state)
+        } else {
+            render(ctx, mesh_helper, &self.state)
+        }
     }
 
     fn play_state(&self) -> PlayState {
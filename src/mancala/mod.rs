@@ -1,15 +1,17 @@
 use crate::mancala::board::Board;
 use crate::mancala::board::DropMove;
+use crate::mancala::rules::Variant;
 use crate::mancala::Hole::Home;
 use crate::system::math::WrappedUsize;
 use crate::system::{PlayState, Player};
+use std::collections::HashSet;
 
 mod ai;
 mod board;
 pub mod controller;
 mod render;
-
-const HOME_COUNT: usize = 6;
+mod render_mode_selection;
+mod rules;
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 enum Hole {
@@ -36,6 +38,10 @@ impl Square {
 }
 
 struct State {
+    variant: Variant,
+    pits: usize,
+    seeds: usize,
+    setup_row: usize,
     play_state: PlayState,
     cursor: WrappedUsize,
     computer_cursor: usize,
@@ -44,4 +50,6 @@ struct State {
     next_move_time: f64,
     animation_time: f64,
     message: Option<(String, bool)>,
+    //Every position so far with the player to move, a repeat means the game would never end
+    positions: HashSet<(Board, Player)>,
 }
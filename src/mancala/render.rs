@@ -8,17 +8,24 @@ use crate::system::TurnState::{Animating, SelectingPiece};
 use ggez::graphics::DrawMode;
 use ggez::{Context, GameResult};
 
-fn square_pos(start: Point, width: f32, height: f32, spacing: f32, square: &Square) -> Point {
+fn square_pos(
+    start: Point,
+    width: f32,
+    height: f32,
+    spacing: f32,
+    pits: usize,
+    square: &Square,
+) -> Point {
     match square.player {
         Player::Human => match square.hole {
             Hole::Home(idx) => start.offset(
                 (width + spacing) * (idx + 1) as f32,
                 height + spacing + spacing,
             ),
-            Hole::End => start.offset((width + spacing) * 7., 0.),
+            Hole::End => start.offset((width + spacing) * (pits + 1) as f32, 0.),
         },
         Player::Computer => match square.hole {
-            Hole::Home(idx) => start.offset((width + spacing) * (5 - idx + 1) as f32, 0.),
+            Hole::Home(idx) => start.offset((width + spacing) * (pits - idx) as f32, 0.),
             Hole::End => start,
         },
     }
@@ -31,7 +38,9 @@ pub(super) fn render(
 ) -> GameResult<()> {
     let padding = mesh_helper.calc_width(0.03);
     let board_start = pt(padding, padding * 3.);
-    let hole_width = mesh_helper.calc_width(0.1);
+    let pits = state.board.pits();
+    //Sized so there's room for the pits and both stores
+    let hole_width = mesh_helper.calc_width(0.8 / (pits + 2) as f32);
     let hole_height = mesh_helper.calc_height(0.2);
     let spacing = mesh_helper.calc_height(0.02);
     let note_pos = pt(mesh_helper.calc_width(0.03), mesh_helper.calc_height(0.6));
@@ -46,17 +55,20 @@ pub(super) fn render(
     let home = mesh_helper.make_rect(ctx, hole_width, hole_height, DrawMode::stroke(2.))?;
 
     let mut draw_hole = |square: &Square, count: usize| {
-        let pos = square_pos(board_start, hole_width, hole_height, spacing, square);
-        let (mesh, max) = if square.is_home() {
-            (home.as_ref(), 24)
+        let pos = square_pos(board_start, hole_width, hole_height, spacing, pits, square);
+        let (mesh, height) = if square.is_home() {
+            (home.as_ref(), hole_height)
         } else {
-            (score.as_ref(), 56)
+            (
+                score.as_ref(),
+                hole_height + hole_height + spacing + spacing,
+            )
         };
         mesh_helper.draw_mesh(ctx, mesh, pos);
-        draw_stones(ctx, mesh_helper, pos, count, max).unwrap();
+        draw_stones(ctx, mesh_helper, pos, hole_width, height, count).unwrap();
     };
 
-    for i in 0..6 {
+    for i in 0..pits {
         draw_hole(
             &state.board.home_idx_to_square(Player::Computer, i),
             state.board.computer.homes[i],
@@ -95,7 +107,7 @@ pub(super) fn render(
     };
 
     if let Some((square, color, drop_move)) = dropping {
-        let cursor_pos = square_pos(board_start, hole_width, hole_height, spacing, &square);
+        let cursor_pos = square_pos(board_start, hole_width, hole_height, spacing, pits, &square);
         mesh_helper.draw_coloured_mesh(ctx, home.as_ref(), cursor_pos, color);
         if let Some(drop_move) = drop_move {
             if drop_move.remaining > 0 {
@@ -107,6 +119,7 @@ pub(super) fn render(
                         hole_width,
                         hole_height,
                         spacing,
+                        pits,
                         &drop_move.current_square,
                     )
                     .offset(hole_width * 0.5, -20.),
@@ -137,16 +150,19 @@ fn draw_stones(
     ctx: &mut Context,
     mesh_helper: &mut MeshHelper,
     start: Point,
+    width: f32,
+    height: f32,
     count: usize,
-    max: usize,
 ) -> GameResult<()> {
     let stone_size = mesh_helper.calc_width(0.02);
     let padding = mesh_helper.calc_width(0.004);
     let stone = mesh_helper.make_circle(ctx, stone_size, stone_size * 0.5, DrawMode::fill())?;
     let start = start.offset(padding, padding);
+    let per_row = (((width - padding) / (stone_size + padding)) as usize).max(1);
+    let rows = ((height - padding) / (stone_size + padding)) as usize;
 
-    if count > max {
-        let y = (max as f32 / 7.) * stone_size;
+    if count > per_row * rows {
+        let y = (rows as f32 / 2.) * stone_size;
         let stone_x = 1. * stone_size;
         let text_x = 2.2 * stone_size;
         mesh_helper.draw_mesh(ctx, stone.as_ref(), start.offset(stone_x, y));
@@ -170,7 +186,7 @@ fn draw_stones(
                 ),
             );
             x += 1;
-            if x > 3 {
+            if x >= per_row {
                 y += 1;
                 x = 0;
             }
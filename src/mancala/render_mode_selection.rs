@@ -0,0 +1,66 @@
+use crate::constants::colors::{LIGHT_BLUE, LIGHT_GRAY, WHITE};
+use crate::constants::Direction;
+use crate::mancala::controller::{SETUP_PITS, SETUP_SEEDS};
+use crate::mancala::State;
+use crate::system::math::{pt, Offset};
+use crate::system::mesh_helper::MeshHelper;
+use ggez::{Context, GameResult};
+
+pub(super) fn render_mode_selection(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let cursor = mesh_helper.make_triangle(ctx, 12., 12., Direction::Right)?;
+    let start = pt(mesh_helper.calc_width(0.2), mesh_helper.calc_height(0.3));
+    let row_height = mesh_helper.calc_height(0.12);
+    let option_width = mesh_helper.calc_width(0.06);
+
+    let rows = [
+        (SETUP_PITS, "Pits", state.variant.pit_range(), state.pits),
+        (
+            SETUP_SEEDS,
+            "Stones",
+            state.variant.seed_range(),
+            state.seeds,
+        ),
+    ];
+
+    for (row, title, range, value) in rows.iter() {
+        let row_start = start.offset(0., row_height * *row as f32);
+        mesh_helper.draw_white_text(ctx, title, row_start, 20., false);
+        if *row == state.setup_row {
+            mesh_helper.draw_mesh(ctx, cursor.as_ref(), row_start.offset(-20., 6.));
+        }
+        for (i, option) in range.clone().enumerate() {
+            mesh_helper.draw_text(
+                ctx,
+                &option.to_string(),
+                row_start.offset(option_width * (i + 2) as f32, 0.),
+                if option == *value {
+                    LIGHT_BLUE
+                } else {
+                    LIGHT_GRAY
+                },
+                20.,
+                false,
+            );
+        }
+    }
+
+    mesh_helper.draw_text(
+        ctx,
+        &format!(
+            "{} pits a side with {} stones in each, press return to start",
+            state.pits, state.seeds
+        ),
+        mesh_helper
+            .center()
+            .offset(0., mesh_helper.calc_height(0.12)),
+        WHITE,
+        16.,
+        true,
+    );
+
+    Ok(())
+}
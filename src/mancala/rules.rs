@@ -0,0 +1,239 @@
+use crate::constants::games;
+use crate::mancala::board::{Board, DropMove};
+use crate::mancala::{Hole, Square};
+use crate::system::Player;
+use std::ops::RangeInclusive;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum Variant {
+    Kalah,
+    //Uses the Abapa rules
+    Oware,
+    Congkak,
+}
+
+//What happens once the last seed has been dropped
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) enum SowingEnd {
+    //The seeds in the last pit were picked up and are being sown
+    Relay,
+    Captured(usize),
+    //The capture would have taken all of the opponent's seeds so nothing was taken
+    GrandSlam,
+    AnotherTurn,
+    EndTurn,
+}
+
+impl Variant {
+    pub(super) fn from_game(game: &str) -> Option<Variant> {
+        match game {
+            games::MANCALA_KALAH => Some(Variant::Kalah),
+            games::MANCALA_OWARE => Some(Variant::Oware),
+            games::MANCALA_CONGKAK => Some(Variant::Congkak),
+            _ => None,
+        }
+    }
+
+    //Default (pits, seeds) for each side
+    pub(super) fn default_size(&self) -> (usize, usize) {
+        match self {
+            Variant::Kalah => (6, 4),
+            Variant::Oware => (6, 4),
+            Variant::Congkak => (7, 7),
+        }
+    }
+
+    pub(super) fn pit_range(&self) -> RangeInclusive<usize> {
+        3..=9
+    }
+
+    pub(super) fn seed_range(&self) -> RangeInclusive<usize> {
+        match self {
+            Variant::Congkak => 3..=9,
+            _ => 2..=8,
+        }
+    }
+
+    //Whether seeds are dropped in the player's own store while sowing, the opponent's is always skipped
+    pub(super) fn sows_into_store(&self) -> bool {
+        !matches!(self, Variant::Oware)
+    }
+
+    //When sowing 12 or more seeds in Oware the pit they were taken from is skipped
+    pub(super) fn skips_origin(&self) -> bool {
+        matches!(self, Variant::Oware)
+    }
+
+    //Home slots the player can pick up from
+    pub(super) fn legal_moves(&self, board: &Board, player: Player) -> Vec<usize> {
+        let moves = (0..board.pits()).filter(|idx| board.side(player).homes[*idx] > 0);
+        if *self != Variant::Oware || board.side(player.opposite()).home_total() > 0 {
+            return moves.collect();
+        }
+        //Feeding: if the opponent has no seeds they must be given some
+        moves
+            .filter(|idx| {
+                let mut board = board.clone();
+                board.sow(*self, &board.home_idx_to_square(player, *idx));
+                board.side(player.opposite()).home_total() > 0
+            })
+            .collect()
+    }
+
+    //Called after the last seed has been dropped, drop_move.remaining is set again for a relay
+    pub(super) fn finish_sowing(&self, board: &mut Board, drop_move: &mut DropMove) -> SowingEnd {
+        let player = drop_move.origin.player;
+        let last = drop_move.current_square.clone();
+        let idx = match last.hole {
+            Hole::End if last.player == player => return SowingEnd::AnotherTurn,
+            Hole::End => return SowingEnd::EndTurn,
+            Hole::Home(idx) => idx,
+        };
+        let count = board.get_count(&last);
+        match self {
+            Variant::Kalah | Variant::Congkak => {
+                if *self == Variant::Congkak && count > 1 {
+                    board.set_count(&last, 0);
+                    drop_move.remaining = count;
+                    drop_move.move_to_next_square(board.pits(), *self);
+                    return SowingEnd::Relay;
+                }
+                let opposite = Square::new(player.opposite(), Hole::Home(board.pits() - idx - 1));
+                if last.player != player || count != 1 || board.get_count(&opposite) == 0 {
+                    return SowingEnd::EndTurn;
+                }
+                let captured = board.get_count(&opposite) + count;
+                board.set_count(&opposite, 0);
+                board.set_count(&last, 0);
+                board.add_count(&Square::new(player, Hole::End), captured);
+                SowingEnd::Captured(captured)
+            }
+            Variant::Oware => {
+                if last.player == player {
+                    return SowingEnd::EndTurn;
+                }
+                //Captures pits with 2 or 3 seeds, going back from the last one while they continue
+                let pits: Vec<Square> = (0..=idx)
+                    .rev()
+                    .map(|idx| Square::new(last.player, Hole::Home(idx)))
+                    .take_while(|square| (2..=3).contains(&board.get_count(square)))
+                    .collect();
+                let captured: usize = pits.iter().map(|square| board.get_count(square)).sum();
+                if captured == 0 {
+                    return SowingEnd::EndTurn;
+                }
+                if captured == board.side(last.player).home_total() {
+                    return SowingEnd::GrandSlam;
+                }
+                pits.iter().for_each(|square| board.set_count(square, 0));
+                board.add_count(&Square::new(player, Hole::End), captured);
+                SowingEnd::Captured(captured)
+            }
+        }
+    }
+
+    //Checked before the player moves, once over the seeds left are collected by the side they're on
+    pub(super) fn is_game_over(&self, board: &Board, player: Player) -> bool {
+        match self {
+            //Also ends once a player has captured more than half the seeds
+            Variant::Oware => {
+                let total = board.total();
+                self.legal_moves(board, player).is_empty()
+                    || board.human.score() * 2 > total
+                    || board.computer.score() * 2 > total
+            }
+            Variant::Kalah | Variant::Congkak => {
+                board.human.home_total() == 0 || board.computer.home_total() == 0
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn make_board(human: &[usize], computer: &[usize]) -> Board {
+        let mut board = Board::new(human.len());
+        board.human.homes = human.to_vec();
+        board.computer.homes = computer.to_vec();
+        board
+    }
+
+    #[test]
+    fn test_kalah_capture() {
+        let mut board = make_board(&[1, 0, 0, 0], &[0, 0, 5, 0]);
+        let (captured, another_turn) =
+            board.sow(Variant::Kalah, &Square::new(Player::Human, Hole::Home(0)));
+        assert_eq!(captured, 6);
+        assert!(!another_turn);
+        assert_eq!(board.human.end, 6);
+        assert_eq!(board.computer.homes, vec![0, 0, 0, 0]);
+
+        let mut board = make_board(&[0, 0, 0, 1], &[1, 0, 0, 0]);
+        let (_, another_turn) =
+            board.sow(Variant::Kalah, &Square::new(Player::Human, Hole::Home(3)));
+        assert!(another_turn);
+    }
+
+    #[test]
+    fn test_oware_captures() {
+        //Lands in the computer's pit 2 which goes to 3, pit 1 goes to 2 as well but pit 0 only has 1
+        let mut board = make_board(&[0, 0, 0, 0, 2, 3], &[0, 1, 2, 5, 0, 0]);
+        let (captured, another_turn) =
+            board.sow(Variant::Oware, &Square::new(Player::Human, Hole::Home(5)));
+        assert_eq!(captured, 5);
+        assert!(!another_turn);
+        assert_eq!(board.human.end, 5);
+        assert_eq!(board.computer.homes, vec![1, 0, 0, 5, 0, 0]);
+    }
+
+    #[test]
+    fn test_oware_grand_slam() {
+        let mut board = make_board(&[0, 0, 0, 0, 0, 2], &[1, 2, 0, 0, 0, 0]);
+        let (captured, _) = board.sow(Variant::Oware, &Square::new(Player::Human, Hole::Home(5)));
+        assert_eq!(captured, 0);
+        assert_eq!(board.computer.homes, vec![2, 3, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn test_oware_skips_origin_and_stores() {
+        let mut board = make_board(&[12, 0, 0, 0, 0, 0], &[0, 0, 0, 0, 0, 0]);
+        board.sow(Variant::Oware, &Square::new(Player::Human, Hole::Home(0)));
+        assert_eq!(board.human.homes, vec![0, 2, 1, 1, 1, 1]);
+        assert_eq!(board.computer.homes, vec![1, 1, 1, 1, 1, 1]);
+        assert_eq!(board.human.end, 0);
+        assert_eq!(board.computer.end, 0);
+    }
+
+    #[test]
+    fn test_oware_feeding() {
+        let board = make_board(&[1, 0, 0, 0, 0, 1], &[0, 0, 0, 0, 0, 0]);
+        assert_eq!(Variant::Oware.legal_moves(&board, Player::Human), vec![5]);
+        assert_eq!(
+            Variant::Kalah.legal_moves(&board, Player::Human),
+            vec![0, 5]
+        );
+
+        let board = make_board(&[1, 0, 0, 0, 0, 0], &[0, 0, 0, 0, 0, 0]);
+        assert!(Variant::Oware.legal_moves(&board, Player::Human).is_empty());
+        assert!(Variant::Oware.is_game_over(&board, Player::Human));
+
+        let mut board = make_board(&[1, 1, 1, 1, 1, 1], &[1, 1, 1, 1, 1, 1]);
+        assert!(!Variant::Oware.is_game_over(&board, Player::Human));
+        board.computer.end = 25;
+        assert!(Variant::Oware.is_game_over(&board, Player::Human));
+    }
+
+    #[test]
+    fn test_congkak_relay() {
+        //Lands in pit 2 which had seeds so they're picked up and sown, ending in the store
+        let mut board = make_board(&[2, 0, 1, 0], &[0, 0, 0, 0]);
+        let (captured, another_turn) =
+            board.sow(Variant::Congkak, &Square::new(Player::Human, Hole::Home(0)));
+        assert_eq!(captured, 0);
+        assert!(another_turn);
+        assert_eq!(board.human.homes, vec![0, 1, 0, 1]);
+        assert_eq!(board.human.end, 1);
+    }
+}
@@ -24,14 +24,47 @@ without a line being made the game is a draw.",
         );
         let mancala = MenuItem::new(
             "Mancala",
-            games::MANCALA,
-            r"Mancala is an ancient game originating from Africa. This is the Kalah variant which was invented in the 1940s in America.
-            
-Each player has 6 home slots and 1 score/end slot (the larger slot on to the right of the player). The game starts with 6 stones
-in each home slot. Players take turns taking all the stones from one of their home slots and dropping them one by one
-counterclockwise around the board (skipping the opponents end/score slot). If the last stone dropped lands in a players
-score/end slot, they get another turn. If the last stone dropped lands in a players home slot and it was empty, then all stones
-from the opposite home and the last one dropped are captured and placed in the players end/score slot.
+            games::SUBMENU,
+            "Mancala games are ancient 'sowing' games from Africa and Asia played with stones and rows of pits.",
+        );
+        let mancala_kalah = MenuItem::new(
+            "Kalah",
+            games::MANCALA_KALAH,
+            r"Kalah was invented in the 1940s in America. The number of pits and stones can be chosen before playing, by default
+each player has 6 home slots with 4 stones in each and 1 score/end slot (the larger slot on to the right of the player).
+
+Players take turns taking all the stones from one of their home slots and dropping them one by one counterclockwise around
+the board (skipping the opponents end/score slot). If the last stone dropped lands in a players score/end slot, they get
+another turn. If the last stone dropped lands in a players home slot and it was empty, then all stones from the opposite home
+and the last one dropped are captured and placed in the players end/score slot.
+
+If a player has no stones left in their home slots then all stones are moved from the home slots to the end/score slot and
+the player with the highest number of stones wins.",
+        );
+        let mancala_oware = MenuItem::new(
+            "Oware",
+            games::MANCALA_OWARE,
+            r"Oware is played across West Africa, these are the Abapa rules used in tournaments. By default each player has 6 home
+slots with 4 stones in each.
+
+Players take turns taking all the stones from one of their home slots and dropping them one by one counterclockwise around
+the board, stones are never dropped in the score/end slots or, if there are 12 or more, the slot they were taken from.
+If the last stone dropped lands in an opponents home slot making it 2 or 3 then they're captured, as are those in the
+previous slots while they also have 2 or 3. If that would capture all of the opponents stones (a grand slam) then nothing is
+captured.
+
+If the opponent has no stones a move that gives them some must be played. If that isn't possible, or the game starts to
+repeat, each player takes the stones on their side. The first player to capture more than half the stones wins.",
+        );
+        let mancala_congkak = MenuItem::new(
+            "Congkak",
+            games::MANCALA_CONGKAK,
+            r"Congkak is played in Malaysia, Indonesia and the Philippines. By default each player has 7 home slots with 7 stones in each.
+
+Players take turns taking all the stones from one of their home slots and dropping them one by one counterclockwise around
+the board (skipping the opponents end/score slot). If the last stone dropped lands in a slot that already had stones then
+they are all picked up and sowing continues. If it lands in the players score/end slot they get another turn, if it lands in
+an empty home slot of theirs then it and all stones in the opposite home are captured, otherwise the turn is over.
 
 If a player has no stones left in their home slots then all stones are moved from the home slots to the end/score slot and
 the player with the highest number of stones wins.",
@@ -66,8 +99,8 @@ Pieces can move one space diagonally forward per turn, if the space is occupied
 piece can jump over and capture the opponent piece, pieces can keep jumping after landing. Players must play the most capturing
 move per turn. If a piece reachs the far row it is promoted to king, these can move and capture forwards and backwards one space.
 
-If a player runs out of pieces or if they have no available moves then they lose. If game only has kings left and no captures have
-been made for 25 turns then the game is a draw.",
+If a player runs out of pieces or if they have no available moves then they lose. If only kings have moved and no captures have
+been made for 40 moves each, or the same position occurs three times, then the game is a draw.",
         );
         let draughts_international = MenuItem::new(
             "International",
@@ -79,19 +112,86 @@ can jump forwards or backwards over and capture the opponent piece, pieces can k
 capturing move per turn. If a piece reachs the far row it is promoted to king, these can move and capture forwards and backwards any
 number of spaces.
 
-If a player runs out of pieces or if they have no available moves then they lose. If game only has kings left and no captures have been
-made for 25 turns, or if each player only have one king each then the game is a draw.",
+If a player runs out of pieces or if they have no available moves then they lose. The game is a draw if only kings have moved and
+no captures have been made for 25 moves each, if the same position occurs three times, or if one player has a single king against
+three pieces (including a king) for 16 moves each or against two pieces for 5 moves each.",
         );
         let draughts_brazilian = MenuItem::new(
             "Brazilian",
             games::DRAUGHTS_BRAZILIAN,
-            r"Brazilian Draughts is played on a 8x8 board, each player has 12 pieces (known as men) but otherwise is the same as International.",
+            r"Brazilian Draughts is played on a 8x8 board, each player has 12 pieces (known as men) and draws after 20 king moves each but otherwise is the same as International.",
         );
         let draughts_canadian = MenuItem::new(
             "Canadian",
             games::DRAUGHTS_CANADIAN,
             r"Canadian Draughts is played on a 12x12 board, each player has 30 pieces (known as men) but otherwise is the same as International.",
         );
+        let draughts_russian = MenuItem::new(
+            "Russian",
+            games::DRAUGHTS_RUSSIAN,
+            r"Russian Draughts (Shashki) is played on a 8x8 board, each player has 12 pieces (known as men).
+
+Men move one space diagonally forward and can capture forwards or backwards, kings can move and capture any number of spaces.
+Capturing is compulsory but the player can choose any capture, not just the longest. If a man reaches the far row in the middle
+of a capture it is promoted straight away and carries on capturing as a king.
+
+If a player runs out of pieces or if they have no available moves then they lose. The game is a draw if only kings have moved and no
+captures have been made for 15 moves each, if the same position occurs three times, or if one player has a single king against three
+pieces (including a king) for 15 moves each.",
+        );
+        let draughts_italian = MenuItem::new(
+            "Italian",
+            games::DRAUGHTS_ITALIAN,
+            r"Italian Draughts is played on a 8x8 board, each player has 12 pieces (known as men).
+
+Men move and capture one space diagonally forward only and can not capture kings, kings move and capture one space in any direction.
+Capturing is compulsory and when there is a choice the player must:
+  1. capture the most pieces,
+  2. then capture with a king rather than a man,
+  3. then capture the most kings,
+  4. then capture a king as early as possible.
+
+If a player runs out of pieces or if they have no available moves then they lose. If only kings have moved and no captures have
+been made for 25 moves each, or the same position occurs three times, then the game is a draw.",
+        );
+        let draughts_spanish = MenuItem::new(
+            "Spanish",
+            games::DRAUGHTS_SPANISH,
+            r"Spanish Draughts is played on a 8x8 board, each player has 12 pieces (known as men).
+
+Men move and capture one space diagonally forward only, kings can move and capture any number of spaces in any direction.
+Capturing is compulsory, the player must capture the most pieces and if that is equal then the most kings.
+
+If a player runs out of pieces or if they have no available moves then they lose. If only kings have moved and no captures have
+been made for 25 moves each, or the same position occurs three times, then the game is a draw.",
+        );
+        let draughts_turkish = MenuItem::new(
+            "Turkish",
+            games::DRAUGHTS_TURKISH,
+            r"Turkish Draughts (Dama) is played on every square of a 8x8 board, each player has 16 pieces (known as men) on their second and
+third rows.
+
+Men move one space forward or sideways and capture by jumping forward or sideways, they never move diagonally or backwards. Kings
+can move and capture any number of spaces forwards, backwards or sideways. Captured pieces are removed straight away.
+Capturing is compulsory and the player must capture the most pieces.
+
+If a player runs out of pieces or if they have no available moves then they lose. If only kings have moved and no captures have
+been made for 25 moves each, or the same position occurs three times, then the game is a draw.",
+        );
+        let draughts_frisian = MenuItem::new(
+            "Frisian",
+            games::DRAUGHTS_FRISIAN,
+            r"Frisian Draughts is played on a 10x10 board, each player has 20 pieces (known as men).
+
+Men move one space diagonally forward. Pieces capture diagonally and also orthogonally, jumping over a piece two spaces away
+straight up, down, left or right. Kings can move and capture any number of spaces.
+Capturing is compulsory and the player must capture the highest value of pieces, where a king is worth more than one man but
+less than two. If the values are equal then a king must capture.
+
+If a player runs out of pieces or if they have no available moves then they lose. The game is a draw if only kings have moved and no
+captures have been made for 25 moves each, if the same position occurs three times, or if one player has a single king against two
+pieces (including a king) for 7 moves each.",
+        );
         let tablut = MenuItem::new(
             "Tablut",
             games::TABLUT,
@@ -108,9 +208,57 @@ surrounded by 4 pieces (or 3 pieces and the edge of the board, a corner or the c
 The castle acts like an ally when capturing even when occupied.
 The king can't capture units.",
         );
-        let go = MenuItem::new("Go", games::GO, "Go game");
-        let ur = MenuItem::new("Ur", games::UR, "The Royal Game of Ur");
-        let blackhole = MenuItem::new("Blackhole", games::BLACKHOLE, "Blackhole");
+        let go = MenuItem::new(
+            "Go",
+            games::SUBMENU,
+            "Go is an ancient game from China, players surround territory and capture stones on a grid of lines.",
+        );
+        let go_small = MenuItem::new(
+            "9x9",
+            games::GO_SMALL,
+            "Go played on a 9x9 board, a quicker game often used for learning, but otherwise the same as 19x19.",
+        );
+        let go_medium = MenuItem::new(
+            "13x13",
+            games::GO_MEDIUM,
+            "Go played on a 13x13 board but otherwise the same as 19x19.",
+        );
+        let go_standard = MenuItem::new(
+            "19x19",
+            games::GO_STANDARD,
+            r"Go played on the standard 19x19 board.
+
+Players take turns placing a stone of their colour on an empty intersection, black moves first. Stones that are connected
+horizontally or vertically form a group and the empty points next to a group are its liberties. A group with no liberties is
+captured and removed. Placing a stone that leaves its own group without liberties is not allowed unless it captures first,
+and no move may recreate an earlier board position (superko).
+
+Instead of placing a stone a player may pass, after two passes in a row the game ends. Both players then agree on which stones
+are dead (would be captured if play continued), these are removed and counted as captures. With area scoring each player gets a
+point for every stone and every empty point surrounded only by their stones, with territory scoring a point for every surrounded
+empty point and every captured stone. White receives komi for moving second. The player with the most points wins.
+
+A handicap gives black 2 to 9 stones on the star points to start, white then moves first and komi is reduced to 0.5.
+Press S to save the game as SGF, which can be continued with --load.",
+        );
+        let ur = MenuItem::new("Ur", games::UR, r"The Royal Game of Ur is from ancient Mesopotamia, played on a board of 20 squares.
+
+Each player has 7 pieces which enter the board on their side, travel up the shared middle row and leave on their side again.
+At the beginning of each turn the player throws 4 dice, each with two of its four tips marked, and can move one piece that
+many squares. A throw of 0 means no move. A piece can not land on a piece of the same colour and must land exactly on the
+end of the track to leave the board.
+
+Landing on a rosette gives the player another turn. Landing on an enemy piece in the middle row captures it, sending it back
+to the start, except on the middle rosette where pieces are safe.
+
+If no moves are available then the player must skip their turn. The first player to move all their pieces off the board wins.");
+        let blackhole = MenuItem::new("Black Hole", games::BLACKHOLE, r"Black Hole is a modern placement game played on a triangle of 21 circles.
+
+Each player has tiles numbered 1 to 10 and players take turns placing their next tile, in order from 1 to 10, in any empty
+circle. After all 20 tiles are placed one circle is left empty, this is the black hole.
+
+Every tile touching the black hole is sucked in, each player adds up the numbers on their tiles that were sucked in
+and the player with the lowest total wins.");
         let orderchaos = MenuItem::new(
             "Order and Chaos",
             games::ORDERCHAOS,
@@ -121,21 +269,98 @@ of 5 pieces (the sequence can be red or white) and chaos aims to block this. A s
 
 If a sequence is created then the Order player wins. If the board fills up before a sequence is made then the Chaos player wins.",
         );
-        let rithmomachy = MenuItem::new("Rithmomachy", games::RITHMOMANCHY, "Tactical math");
+        let rithmomachy = MenuItem::new("Rithmomachy", games::RITHMOMANCHY, r"Rithmomachy is a medieval battle of numbers played on a board of 16 by 8 squares.
+
+One player has the even pieces and starts on the left, the other has the odd pieces and starts on the right. Each player
+has 8 circles, 8 triangles, 7 squares and a pyramid, each with a number. The even player moves first.
+Circles move one square diagonally, triangles move exactly two squares and squares exactly three squares up, down, left
+or right. Pieces can not jump over other pieces or move onto an occupied square. The pyramid (worth 91 for even and 190 for
+odd) can move as any of the other shapes.
+
+After a move any enemy pieces the player can capture are removed:
+Meeting - one of the player's pieces could move onto the enemy piece and has the same value.
+Assault - one of the player's pieces is in a straight line with the enemy piece and its value multiplied by the number of
+empty squares between them equals the enemy's value.
+Ambush - two of the player's pieces could move onto the enemy piece and their values add up to its value.
+Siege - the enemy piece is surrounded on all four sides by the player's pieces or the edge of the board.
+
+The victory condition is chosen before the game:
+Common victories
+  Bodies - capture 12 pieces.
+  Goods - capture pieces worth 400 or more.
+Proper victories, made in the enemy's half of the board with a straight line of pieces:
+  Great - 3 pieces whose values form an arithmetic (2, 4, 6), geometric (2, 4, 8) or harmonic (3, 4, 6) progression.
+  Greater - 4 pieces containing two different kinds of progression.
+
+A player who can not move loses.");
         let shogi = MenuItem::new(
             "Shogi",
             games::SUBMENU,
             "Known as Japanese Chess but it has several major differences.",
         );
-        let shogi_standard =
-            MenuItem::new("Standard", games::SHOGI_STANDARD, "Played on a 9x9 board");
+        let shogi_standard = MenuItem::new(
+            "Standard",
+            games::SHOGI_STANDARD,
+            r"Played on a 9x9 board, each player has a king, rook, bishop, two gold generals, two silver generals, two knights,
+two lances and nine pawns. Pieces are pointed towards the opponent.
+The king moves one space in any direction, the gold one space in any direction except diagonally backwards, the silver one space
+diagonally or straight forward, the pawn one space forward, the lance any distance forward, the knight two spaces forward and
+one to the side (jumping over pieces) and the rook and bishop any distance orthogonally or diagonally.
+
+The far three rows are the promotion zone, a piece moving into, out of or within it may promote: the silver, knight, lance and
+pawn then move like a gold, the rook can also move one space diagonally and the bishop one space orthogonally. Pawns and
+lances must promote on the far row and knights on the far two rows.
+
+Captured pieces join the capturer's hand and may be dropped unpromoted on any empty square instead of moving. A piece can't be
+dropped where it would have no moves, a pawn can't be dropped on a file that already has an unpromoted pawn of the same player
+or to give immediate checkmate.
+
+The game is won by checkmating the opponent's king, a player with no legal moves loses. If the same position occurs four times
+the player giving perpetual check loses, otherwise the game is a draw. Once both kings have entered the enemy camp the player to
+move may declare an impasse with D, unless in check, and the game is decided on points (rooks and bishops 5, other pieces 1, on
+the board and in hand), a player with fewer than 24 loses.
+
+Press S to save the game as KIF and CSA records, which can be continued with --load.",
+        );
         let shogi_mini = MenuItem::new(
             "Mini",
             games::SHOGI_MINI,
-            "5x5 variant of the standard game with reduced pieces but otherwise the same rules",
+            r"Minishogi is a 5x5 variant of the standard game with reduced pieces but otherwise the same rules.
+
+Each player has a king, rook, bishop, gold general, silver general and pawn. Pieces are pointed towards the opponent.
+The king moves one space in any direction, the gold one space in any direction except diagonally backwards, the silver one space
+diagonally or straight forward, the pawn one space forward and the rook and bishop any distance orthogonally or diagonally.
+
+The far row is the promotion zone, a piece moving into, out of or within it may promote: the silver and pawn then move like a gold,
+the rook can also move one space diagonally and the bishop one space orthogonally. A pawn must promote on the far row.
+
+Captured pieces join the capturer's hand and may be dropped unpromoted on any empty square instead of moving. A pawn can't be
+dropped on the far row, on a file that already has an unpromoted pawn of the same player, or to give immediate checkmate.
+
+The game is won by checkmating the opponent's king, a player with no legal moves loses. If the same position occurs four times
+the player giving perpetual check loses, otherwise the first player loses.",
         );
         let chess = MenuItem::new("Chess", games::SUBMENU, "The ancient and popular board game");
-        let shogi_medium = MenuItem::new("Medium", games::SHOGI_MEDIUM, "Chu shogi, played on a 12x12 board. It introduces some new piece types over the standard game");
+        let shogi_medium = MenuItem::new(
+            "Medium",
+            games::SHOGI_MEDIUM,
+            r"Chu shogi, played on a 12x12 board with 46 pieces each and no drops, captured pieces are out of the game.
+Alongside the standard pieces there are coppers, leopards, tigers, elephants, the kirin and phoenix, chariots, side and vertical
+movers, dragons, go-betweens, the free king (FK, moves like a chess queen) and the lion. Pieces are pointed towards the opponent.
+
+The lion (Ln) moves twice per turn as a king, so it can reach any square two away by jumping, capture a piece and carry on or
+return, or pass by moving out and back. The horned falcon and soaring eagle have the same power in their forward directions.
+When several routes reach the same square use the arrows to pick one.
+
+The far four rows are the promotion zone, a piece may promote when it enters the zone or captures in it, most pieces become a
+stronger piece and the kirin becomes a lion. The drunk elephant becomes a crown prince which counts as a second king.
+
+A lion can't capture a protected lion two squares away unless it takes something else (not a pawn or go-between) on the way,
+and after a lion is captured by another piece the reply can't capture a lion except with a lion.
+
+There is no check, the game is won by capturing the king and crown prince. Repeating a position four times loses.
+Press = and - to zoom the board.",
+        );
         let shogi_large = MenuItem::new("Large", games::SHOGI_LARGE, "Tai shogi, played on a 25x25 board with 177 pieces. It introduces several new piece types over the standard game");
         let shogi_huge = MenuItem::new("Huge", games::SHOGI_HUGE, "Taikyoku, played on a 36x36 board with 402 pieces. It introduces a lot of new piece types over the standard game");
         let chess_standard = MenuItem::new(
@@ -174,7 +399,10 @@ If a sequence is created then the Order player wins. If the board fills up befor
 
         vec![
             (tictactoe, None),
-            (mancala, None),
+            (
+                mancala,
+                Some(vec![mancala_kalah, mancala_oware, mancala_congkak]),
+            ),
             (senet, None),
             (
                 draughts,
@@ -183,24 +411,29 @@ If a sequence is created then the Order player wins. If the board fills up befor
                     draughts_international,
                     draughts_brazilian,
                     draughts_canadian,
+                    draughts_russian,
+                    draughts_italian,
+                    draughts_spanish,
+                    draughts_turkish,
+                    draughts_frisian,
                 ]),
             ),
             (tablut, None),
-            // (blackhole, None),
+            (blackhole, None),
             (orderchaos, None),
-            // (go, None),
-            // (ur, None),
-            // (rithmomachy, None),
-            // (
-            //     shogi,
-            //     Some(vec![
-            //         shogi_standard,
-            //         shogi_mini,
-            //         shogi_medium,
-            //         shogi_large,
-            //         shogi_huge,
-            //     ]),
-            // ),
+            (go, Some(vec![go_small, go_medium, go_standard])),
+            (ur, None),
+            (rithmomachy, None),
+            (
+                shogi,
+                Some(vec![
+                    shogi_standard,
+                    shogi_mini,
+                    shogi_medium,
+                    // shogi_large,
+                    // shogi_huge,
+                ]),
+            ),
             (
                 chess,
                 Some(vec![
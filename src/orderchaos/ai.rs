@@ -1,82 +1,117 @@
-use crate::orderchaos::Square::Empty;
-use crate::orderchaos::{Board, Mode, State};
-use crate::system::neighbours::get_neighbours;
+use crate::orderchaos::rules::{has_line, is_full};
+use crate::orderchaos::Square::{Empty, Red, White};
+use crate::orderchaos::{Board, Mode, Square, State};
+use crate::system::mcts::{MctsGame, MctsSettings, Outcome, Search, FRAME_BUDGET};
+use crate::system::Player;
+use rand::rngs::StdRng;
 use rand::{thread_rng, Rng};
+use std::time::Duration;
 
-fn get_random_empty_square(board: &Board) -> usize {
-    let empties: Vec<usize> = board
-        .iter()
-        .enumerate()
-        .filter_map(|(idx, square)| if square == &Empty { Some(idx) } else { None })
-        .collect();
-    empties[thread_rng().gen_range(0.. empties.len())]
+const PLAYOUTS: usize = 20_000;
+const THINKING_TIME: Duration = Duration::from_millis(1000);
+const RAVE_EQUIVALENCE: f64 = 300.;
+
+#[derive(Debug, Clone)]
+pub(super) struct Position {
+    board: Board,
+    to_move: Mode,
 }
 
-pub(super) fn process(state: &mut State) {
-    match state.player_mode {
-        Mode::Order => {
-            let mut neighbours = get_neighbours(state.cursor.idx, true, true);
-            neighbours = neighbours
-                .iter()
-                .filter(|&idx| state.board[*idx] == Empty)
-                .cloned()
-                .collect();
-            let (idx, square) = if neighbours.is_empty() {
-                (
-                    get_random_empty_square(&state.board),
-                    if thread_rng().gen::<f32>() > 0.9 {
-                        state.board[state.cursor.idx].opposite().unwrap()
-                    } else {
-                        state.board[state.cursor.idx]
-                    },
-                )
-            } else {
-                (
-                    neighbours[thread_rng().gen_range(0.. neighbours.len())],
-                    state.board[state.cursor.idx].opposite().unwrap(),
-                )
-            };
-            state.cursor.idx = idx;
-            state.move_cursor = square.into();
-        }
-        Mode::Chaos => {
-            let already_placed: Vec<usize> = state
-                .board
-                .iter()
-                .enumerate()
-                .filter_map(|(idx, square)| if square != &Empty { Some(idx) } else { None })
-                .collect();
-            let mut count = 0;
-            let mut placed = false;
-            loop {
-                let idx = already_placed[thread_rng().gen_range(0.. already_placed.len())];
-                let neighbours: Vec<usize> = get_neighbours(idx, true, true)
-                    .iter()
-                    .filter(|&square| state.board[*square] == Empty)
-                    .cloned()
-                    .collect();
-                if !neighbours.is_empty() {
-                    let square = state.board[idx];
-                    let idx = neighbours[thread_rng().gen_range(0.. neighbours.len())];
-                    state.cursor.idx = idx;
-                    state.move_cursor = square.into();
-                    placed = true;
-                    break;
-                }
-                count += 1;
-                if count > 10 {
-                    break;
-                }
-            }
-            if !placed {
-                debug_log!("Unable to place neighbour");
-                state.cursor.idx = get_random_empty_square(&state.board);
-                state.move_cursor = if thread_rng().gen::<bool>() {
-                    Mode::Order
-                } else {
-                    Mode::Chaos
-                };
-            }
+//The search only needs to tell the sides apart
+fn side(mode: Mode) -> Player {
+    match mode {
+        Mode::Order => Player::Human,
+        Mode::Chaos => Player::Computer,
+    }
+}
+
+impl MctsGame for Position {
+    type Move = (usize, Square);
+
+    fn to_move(&self) -> Player {
+        side(self.to_move)
+    }
+
+    //Either player can place either colour on any empty square
+    fn legal_moves(&self) -> Vec<(usize, Square)> {
+        self.board
+            .iter()
+            .enumerate()
+            .filter(|(_, square)| **square == Empty)
+            .flat_map(|(idx, _)| vec![(idx, Red), (idx, White)])
+            .collect()
+    }
+
+    fn apply(&mut self, mov: &(usize, Square), _: &mut StdRng) {
+        self.board[mov.0] = mov.1;
+        self.to_move = self.to_move.opposite();
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        if has_line(&self.board) {
+            Some(Outcome::Win(side(Mode::Order)))
+        } else if is_full(&self.board) {
+            Some(Outcome::Win(side(Mode::Chaos)))
+        } else {
+            None
         }
     }
 }
+
+fn start_search(board: &Board, mode: Mode, seed: u64) -> Search<Position> {
+    let position = Position {
+        board: *board,
+        to_move: mode,
+    };
+    let settings = MctsSettings::new(PLAYOUTS, seed)
+        .with_time(THINKING_TIME)
+        .with_rave(RAVE_EQUIVALENCE);
+    Search::new(position, settings)
+}
+
+//Searches for a frame, once a move has been chosen sets the cursor to the square and colour
+//Returns false until then
+pub(super) fn process(state: &mut State) -> bool {
+    let mode = state.player_mode.opposite();
+    let board = state.board;
+    let search = state
+        .search
+        .get_or_insert_with(|| start_search(&board, mode, thread_rng().gen()));
+    if !search.run(FRAME_BUDGET) {
+        return false;
+    }
+    if let Some((idx, square)) = search.best_move() {
+        state.cursor.idx = idx;
+        state.move_cursor = square.into();
+    }
+    state.search = None;
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    fn choose_move(board: &Board, mode: Mode, seed: u64) -> Option<(usize, Square)> {
+        let mut search = start_search(board, mode, seed);
+        assert!(search.run(Duration::from_secs(60)));
+        search.best_move()
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_completes_and_blocks_lines() {
+        set_board_size((6, 6));
+        let mut board = [Empty; 36];
+        board[18..22].fill(White);
+        board[0] = Red;
+        board[35] = Red;
+        //Order finishes the line
+        assert_eq!(choose_move(&board, Mode::Order, 1), Some((22, White)));
+        //Chaos blocks it, either with red or by making sure the line would be six long
+        let mov = choose_move(&board, Mode::Chaos, 1).unwrap();
+        assert!(mov == (22, Red) || mov == (23, White));
+    }
+}
@@ -1,9 +1,9 @@
 use crate::boards::cursor::Cursor;
-use crate::boards::idx_coord::BoardCoord;
 use crate::boards::set_board_size;
 use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
 use crate::orderchaos::ai::process;
 use crate::orderchaos::renderer::render;
+use crate::orderchaos::rules::{has_line, is_full};
 use crate::orderchaos::Square::Empty;
 use crate::orderchaos::{Mode, Square, State};
 use crate::system::mesh_helper::MeshHelper;
@@ -30,6 +30,7 @@ impl Controller {
                 last_human_cursor_pos: 0,
                 last_human_placed: Square::Empty,
                 move_cursor: Mode::Order,
+                search: None,
             },
         }
     }
@@ -62,48 +63,8 @@ impl Controller {
     }
 
     fn check_game_over(&mut self) {
-        let horz_line = self.has_valid_line(0, 1, 0, Some(5))
-            || self.has_valid_line(1, 1, 0, Some(0))
-            || self.has_valid_line(6, 1, 0, Some(11))
-            || self.has_valid_line(7, 1, 0, Some(6))
-            || self.has_valid_line(12, 1, 0, Some(17))
-            || self.has_valid_line(13, 1, 0, Some(12))
-            || self.has_valid_line(18, 1, 0, Some(23))
-            || self.has_valid_line(19, 1, 0, Some(18))
-            || self.has_valid_line(24, 1, 0, Some(29))
-            || self.has_valid_line(25, 1, 0, Some(24))
-            || self.has_valid_line(30, 1, 0, Some(35))
-            || self.has_valid_line(31, 1, 0, Some(30));
-
-        let vert_line = self.has_valid_line(0, 0, 1, Some(30))
-            || self.has_valid_line(1, 0, 1, Some(31))
-            || self.has_valid_line(2, 0, 1, Some(32))
-            || self.has_valid_line(3, 0, 1, Some(33))
-            || self.has_valid_line(4, 0, 1, Some(34))
-            || self.has_valid_line(5, 0, 1, Some(35))
-            || self.has_valid_line(6, 0, 1, Some(0))
-            || self.has_valid_line(7, 0, 1, Some(1))
-            || self.has_valid_line(8, 0, 1, Some(2))
-            || self.has_valid_line(9, 0, 1, Some(3))
-            || self.has_valid_line(10, 0, 1, Some(4))
-            || self.has_valid_line(11, 0, 1, Some(5));
-
-        let diag_line = self.has_valid_line(0, 1, 1, Some(35))
-            || self.has_valid_line(1, 1, 1, None)
-            || self.has_valid_line(6, 1, 1, None)
-            || self.has_valid_line(7, 1, 1, Some(0))
-            || self.has_valid_line(4, -1, 1, None)
-            || self.has_valid_line(5, -1, 1, Some(30))
-            || self.has_valid_line(10, -1, 1, Some(5))
-            || self.has_valid_line(11, -1, 1, None);
-
-        if horz_line || vert_line || diag_line {
-            debug_log!(
-                "Line found    horz: {}  vert: {}  diag: {}",
-                horz_line,
-                vert_line,
-                diag_line
-            );
+        if has_line(&self.state.board) {
+            debug_log!("Line found");
             match self.state.player_mode {
                 Mode::Order => {
                     debug_log!("Human wins");
@@ -114,61 +75,20 @@ impl Controller {
                     self.state.play_state = ComputerWin;
                 }
             }
-        } else {
-            let empty_count = self
-                .state
-                .board
-                .iter()
-                .filter(|&square| square == &Empty)
-                .count();
-            if empty_count == 0 {
-                debug_log!("No squares left");
-                match self.state.player_mode {
-                    Mode::Order => {
-                        debug_log!("Computer wins");
-                        self.state.play_state = ComputerWin;
-                    }
-                    Mode::Chaos => {
-                        debug_log!("Human wins");
-                        self.state.play_state = HumanWin;
-                    }
+        } else if is_full(&self.state.board) {
+            debug_log!("No squares left");
+            match self.state.player_mode {
+                Mode::Order => {
+                    debug_log!("Computer wins");
+                    self.state.play_state = ComputerWin;
+                }
+                Mode::Chaos => {
+                    debug_log!("Human wins");
+                    self.state.play_state = HumanWin;
                 }
             }
         }
     }
-
-    fn has_valid_line(
-        &mut self,
-        start: usize,
-        x_diff: isize,
-        y_diff: isize,
-        diff: Option<usize>,
-    ) -> bool {
-        let start_square = self.state.board[start];
-        if start_square == Empty {
-            return false;
-        }
-        let (mut x, mut y): (isize, isize) = BoardCoord::from(start).into();
-        for _ in 1..5 {
-            x += x_diff;
-            y += y_diff;
-            if self.state.board[BoardCoord::from((x, y)).idx()] != start_square {
-                return false;
-            }
-        }
-        if let Some(diff) = diff {
-            if start_square == self.state.board[diff] {
-                return false;
-            }
-        }
-        debug_log!(
-            "Line found starting at {} incrementing by {},{}",
-            start,
-            x_diff,
-            y_diff
-        );
-        true
-    }
 }
 
 impl Scene for Controller {
@@ -223,11 +143,13 @@ impl Scene for Controller {
         if self.state.play_state.is_computer(SelectingPiece) {
             self.state.next_move_time -= delta;
             if self.state.next_move_time < 0. {
-                self.state.next_move_time = ANIMATION_DURATION;
                 self.state.last_human_cursor_pos = self.state.cursor.idx;
                 self.state.last_human_placed = self.state.board[self.state.cursor.idx];
-                process(&mut self.state);
-                self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove))
+                //The search carries on each frame until it has chosen a move
+                if process(&mut self.state) {
+                    self.state.next_move_time = ANIMATION_DURATION;
+                    self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove))
+                }
             }
         } else if self.state.play_state.is_computer(SelectingMove) {
             self.state.next_move_time -= delta;
@@ -1,10 +1,13 @@
 use crate::boards::cursor::Cursor;
+use crate::orderchaos::ai::Position;
+use crate::system::mcts::Search;
 use crate::system::PlayState;
 
 mod ai;
 pub mod controller;
 mod render_mode_selection;
 mod renderer;
+mod rules;
 
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Square {
@@ -13,22 +16,21 @@ enum Square {
     Empty,
 }
 
-impl Square {
-    fn opposite(&self) -> Option<Square> {
-        match self {
-            Square::Red => Some(Square::White),
-            Square::White => Some(Square::Red),
-            Square::Empty => None,
-        }
-    }
-}
-
 #[derive(Debug, Copy, Clone, Eq, PartialEq)]
 enum Mode {
     Order,
     Chaos,
 }
 
+impl Mode {
+    fn opposite(&self) -> Mode {
+        match self {
+            Mode::Order => Mode::Chaos,
+            Mode::Chaos => Mode::Order,
+        }
+    }
+}
+
 impl From<Mode> for Square {
     fn from(mode: Mode) -> Self {
         match mode {
@@ -59,4 +61,6 @@ struct State {
     move_cursor: Mode,
     player_mode: Mode,
     next_move_time: f64,
+    //Computer's move search, run a slice at a time
+    search: Option<Search<Position>>,
 }
@@ -0,0 +1,109 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::orderchaos::Board;
+use crate::orderchaos::Square::Empty;
+
+//Every place a line of five can start, with the direction and the square that would make it a
+//line of six (which doesn't count)
+const LINES: [(usize, isize, isize, Option<usize>); 32] = [
+    (0, 1, 0, Some(5)),
+    (1, 1, 0, Some(0)),
+    (6, 1, 0, Some(11)),
+    (7, 1, 0, Some(6)),
+    (12, 1, 0, Some(17)),
+    (13, 1, 0, Some(12)),
+    (18, 1, 0, Some(23)),
+    (19, 1, 0, Some(18)),
+    (24, 1, 0, Some(29)),
+    (25, 1, 0, Some(24)),
+    (30, 1, 0, Some(35)),
+    (31, 1, 0, Some(30)),
+    (0, 0, 1, Some(30)),
+    (1, 0, 1, Some(31)),
+    (2, 0, 1, Some(32)),
+    (3, 0, 1, Some(33)),
+    (4, 0, 1, Some(34)),
+    (5, 0, 1, Some(35)),
+    (6, 0, 1, Some(0)),
+    (7, 0, 1, Some(1)),
+    (8, 0, 1, Some(2)),
+    (9, 0, 1, Some(3)),
+    (10, 0, 1, Some(4)),
+    (11, 0, 1, Some(5)),
+    (0, 1, 1, Some(35)),
+    (1, 1, 1, None),
+    (6, 1, 1, None),
+    (7, 1, 1, Some(0)),
+    (4, -1, 1, None),
+    (5, -1, 1, Some(30)),
+    (10, -1, 1, Some(5)),
+    (11, -1, 1, None),
+];
+
+//True if there's a line of exactly five of either colour, which is a win for Order
+pub(super) fn has_line(board: &Board) -> bool {
+    LINES
+        .iter()
+        .any(|(start, x_diff, y_diff, diff)| has_valid_line(board, *start, *x_diff, *y_diff, *diff))
+}
+
+pub(super) fn is_full(board: &Board) -> bool {
+    !board.contains(&Empty)
+}
+
+fn has_valid_line(
+    board: &Board,
+    start: usize,
+    x_diff: isize,
+    y_diff: isize,
+    diff: Option<usize>,
+) -> bool {
+    let start_square = board[start];
+    if start_square == Empty {
+        return false;
+    }
+    let (mut x, mut y): (isize, isize) = BoardCoord::from(start).into();
+    for _ in 1..5 {
+        x += x_diff;
+        y += y_diff;
+        if board[BoardCoord::from((x, y)).idx()] != start_square {
+            return false;
+        }
+    }
+    if let Some(diff) = diff {
+        if start_square == board[diff] {
+            return false;
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::orderchaos::Square::{Red, White};
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_lines() {
+        set_board_size((6, 6));
+        let mut board = [Empty; 36];
+        for idx in [1, 8, 15, 22] {
+            board[idx] = Red;
+        }
+        assert!(!has_line(&board));
+        board[29] = Red;
+        assert!(has_line(&board));
+        board[29] = White;
+        assert!(!has_line(&board));
+
+        //Six in a row doesn't count
+        let mut board = [Empty; 36];
+        board[12..17].fill(White);
+        assert!(has_line(&board));
+        board[17] = White;
+        assert!(!has_line(&board));
+        assert!(!is_full(&board));
+    }
+}
@@ -0,0 +1,116 @@
+use crate::rithmomachy::rules::{apply_move, calc_valid_moves, winner};
+use crate::rithmomachy::{Move, Position, Side, Victory, COLS};
+
+const SEARCH_DEPTH: usize = 2;
+const WIN: i32 = 100000;
+//Each captured piece is worth this much on top of its value
+const PIECE_BONUS: i32 = 20;
+
+//Pieces across the middle are needed for proper victories
+fn advance_bonus(victory: Victory) -> i32 {
+    match victory {
+        Victory::Bodies | Victory::Goods => 2,
+        Victory::Great | Victory::Greater => 15,
+    }
+}
+
+fn score_for(position: &Position, side: Side, victory: Victory) -> i32 {
+    let captured = position.captured[side.idx()];
+    let half = side.enemy_half();
+    let advanced = position
+        .board
+        .iter()
+        .enumerate()
+        .filter(|(idx, square)| {
+            matches!(square, Some(piece) if piece.side == side) && half.contains(&(idx % COLS))
+        })
+        .count() as i32;
+    captured.value as i32 + captured.count as i32 * PIECE_BONUS + advanced * advance_bonus(victory)
+}
+
+fn evaluate(position: &Position, side: Side, victory: Victory) -> i32 {
+    score_for(position, side, victory) - score_for(position, side.opposite(), victory)
+}
+
+fn negamax(
+    position: &Position,
+    to_move: Side,
+    victory: Victory,
+    depth: usize,
+    mut alpha: i32,
+    beta: i32,
+) -> i32 {
+    if depth == 0 {
+        return evaluate(position, to_move, victory);
+    }
+    let moves = calc_valid_moves(&position.board, to_move);
+    //Unable to move loses
+    let mut best = -WIN;
+    for mov in moves {
+        let mut next = position.clone();
+        apply_move(&mut next, &mov);
+        let value = match winner(&next, to_move, victory) {
+            Some(side) if side == to_move => WIN + depth as i32,
+            Some(_) => -WIN - depth as i32,
+            None => -negamax(&next, to_move.opposite(), victory, depth - 1, -beta, -alpha),
+        };
+        best = best.max(value);
+        alpha = alpha.max(value);
+        if alpha >= beta {
+            break;
+        }
+    }
+    best
+}
+
+pub(super) fn choose_move(position: &Position, side: Side, victory: Victory) -> Option<Move> {
+    let mut best = None;
+    let mut alpha = -WIN * 2;
+    for mov in calc_valid_moves(&position.board, side) {
+        let mut next = position.clone();
+        apply_move(&mut next, &mov);
+        let value = match winner(&next, side, victory) {
+            Some(winner) if winner == side => WIN + SEARCH_DEPTH as i32,
+            Some(_) => -WIN - SEARCH_DEPTH as i32,
+            None => -negamax(
+                &next,
+                side.opposite(),
+                victory,
+                SEARCH_DEPTH - 1,
+                -WIN * 2,
+                -alpha,
+            ),
+        };
+        if best.is_none() || value > alpha {
+            alpha = value;
+            best = Some(mov);
+        }
+    }
+    debug_log!("Chose {:?} ({})", best, alpha);
+    best
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rithmomachy::{Captured, Piece, Shape, SQUARES};
+
+    fn piece(side: Side, shape: Shape, value: u32) -> Option<Piece> {
+        Some(Piece { side, shape, value })
+    }
+
+    #[test]
+    fn test_takes_capture() {
+        let mut position = Position {
+            board: [None; SQUARES],
+            captured: [Captured::default(); 2],
+        };
+        position.board[2 * COLS + 8] = piece(Side::Odd, Shape::Triangle, 12);
+        //The circle can step diagonally next to the triangle and meet it
+        position.board[4 * COLS + 6] = piece(Side::Even, Shape::Circle, 12);
+        let mov = choose_move(&position, Side::Even, Victory::Bodies).unwrap();
+        let mut next = position.clone();
+        apply_move(&mut next, &mov);
+        assert_eq!(next.captured[Side::Even.idx()].count, 1);
+    }
+}
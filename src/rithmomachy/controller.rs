@@ -0,0 +1,211 @@
+use crate::boards::cursor::Cursor;
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::set_board_size;
+use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
+use crate::rithmomachy::ai::choose_move;
+use crate::rithmomachy::renderer::render;
+use crate::rithmomachy::rules::{apply_move, calc_valid_moves, init_board, winner};
+use crate::rithmomachy::{Captured, Position, Side, State, Victory, COLS, ROWS};
+use crate::system::find_nearest::find_nearest;
+use crate::system::ggez_ext::keycode_to_direction;
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::PlayState::{ComputerWin, HumanWin};
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player, Scene, Turn, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
+use ggez::event::KeyCode;
+use ggez::{Context, GameResult};
+
+//Rows of the mode selection screen
+const SETUP_SIDE: usize = 0;
+const SETUP_VICTORY: usize = 1;
+
+pub struct Controller {
+    state: State,
+}
+
+impl Controller {
+    pub fn new() -> Self {
+        set_board_size((ROWS, COLS));
+        Controller {
+            state: State {
+                position: Position {
+                    board: init_board(),
+                    captured: [Captured::default(); 2],
+                },
+                cursor: Cursor::new(),
+                play_state: PlayState::ModeSelection,
+                last_human_cursor_pos: 0,
+                valid_moves: vec![],
+                move_cursor: 0,
+                human: Side::Even,
+                victory: Victory::Bodies,
+                setup_row: SETUP_SIDE,
+                last_captures: vec![],
+                next_move_time: AI_MOVE_DELAY,
+            },
+        }
+    }
+}
+
+impl Controller {
+    fn start_new_turn(&mut self, player: Player) {
+        self.state.play_state = match player {
+            Player::Human => NEW_TURN_HUMAN,
+            Player::Computer => NEW_TURN_COMPUTER,
+        };
+        self.state.valid_moves = calc_valid_moves(
+            &self.state.position.board,
+            self.state.get_side_for_player(player),
+        );
+        self.state.next_move_time = AI_MOVE_DELAY;
+        self.state.move_cursor = 0;
+        if self.state.valid_moves.is_empty() {
+            debug_log!("{:?} can't move", player);
+            self.state.play_state = match player {
+                Player::Human => ComputerWin,
+                Player::Computer => HumanWin,
+            };
+        }
+    }
+
+    //Returns true if the game is over
+    fn process_move(&mut self, player: Player) -> bool {
+        let mov = self.state.get_selected_move();
+        let side = self.state.get_side_for_player(player);
+        self.state.last_captures = apply_move(&mut self.state.position, &mov);
+        debug_log!(
+            "{:?} moved {:?} capturing {:?}",
+            side,
+            mov,
+            self.state.last_captures
+        );
+        if let Some(winner) = winner(&self.state.position, side, self.state.victory) {
+            self.state.play_state = if winner == self.state.human {
+                HumanWin
+            } else {
+                ComputerWin
+            };
+            return true;
+        }
+        false
+    }
+
+    fn handle_setup_input(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Up | KeyCode::Down => {
+                self.state.setup_row = if self.state.setup_row == SETUP_SIDE {
+                    SETUP_VICTORY
+                } else {
+                    SETUP_SIDE
+                }
+            }
+            KeyCode::Left | KeyCode::Right if self.state.setup_row == SETUP_SIDE => {
+                self.state.human = self.state.human.opposite()
+            }
+            KeyCode::Left | KeyCode::Right => {
+                let count = Victory::ALL.len();
+                let current = Victory::ALL
+                    .iter()
+                    .position(|victory| *victory == self.state.victory)
+                    .unwrap_or(0);
+                let next = if key == KeyCode::Left {
+                    (current + count - 1) % count
+                } else {
+                    (current + 1) % count
+                };
+                self.state.victory = Victory::ALL[next];
+            }
+            KeyCode::Return => self.state.play_state = PlayState::Init,
+            _ => {}
+        }
+    }
+}
+
+impl Scene for Controller {
+    fn on_key_down(&mut self, key: KeyCode) {
+        if self.state.play_state == PlayState::ModeSelection {
+            self.handle_setup_input(key);
+        } else if self.state.play_state.is_human(SelectingPiece) {
+            if !self.state.cursor.handle_input(key)
+                && key == KeyCode::Return
+                && !self.state.get_moves_for_selected_piece().is_empty()
+            {
+                self.state.play_state = PlayState::Playing(Turn::Human(SelectingMove));
+            }
+        } else if self.state.play_state.is_human(SelectingMove) {
+            match key {
+                KeyCode::Left | KeyCode::Up | KeyCode::Right | KeyCode::Down => {
+                    let dir = keycode_to_direction(key).unwrap();
+                    let nearest = find_nearest(
+                        &self.state.get_moves_for_selected_piece(),
+                        self.state.move_cursor,
+                        dir,
+                        &|mov| BoardCoord::from(mov.dest),
+                    );
+                    if let Some(nearest) = nearest {
+                        self.state.move_cursor = nearest;
+                    }
+                }
+                KeyCode::Return => {
+                    let game_over = self.process_move(Player::Human);
+                    if !game_over {
+                        self.start_new_turn(Player::Computer);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    fn on_key_up(&mut self, key: KeyCode) -> bool {
+        if self.state.play_state.is_human(SelectingMove) && key == KeyCode::Escape {
+            self.state.play_state = NEW_TURN_HUMAN;
+            return true;
+        }
+        false
+    }
+
+    fn update(&mut self, delta: f64) -> GameResult<()> {
+        if self.state.play_state == PlayState::Init {
+            match self.state.human {
+                Side::Even => self.start_new_turn(Player::Human),
+                Side::Odd => self.start_new_turn(Player::Computer),
+            }
+        } else if self.state.play_state == NEW_TURN_COMPUTER {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                self.state.last_human_cursor_pos = self.state.cursor.idx;
+                let side = self.state.get_side_for_player(Player::Computer);
+                if let Some(mov) = choose_move(&self.state.position, side, self.state.victory) {
+                    self.state.cursor.idx = mov.origin;
+                    self.state.move_cursor = self
+                        .state
+                        .get_moves_for_selected_piece()
+                        .iter()
+                        .position(|other| *other == mov)
+                        .unwrap_or(0);
+                }
+                self.state.next_move_time = ANIMATION_DURATION;
+                self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove));
+            }
+        } else if self.state.play_state.is_computer(SelectingMove) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                let game_over = self.process_move(Player::Computer);
+                self.state.cursor.idx = self.state.last_human_cursor_pos;
+                if !game_over {
+                    self.start_new_turn(Player::Human);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut Context, mesh_helper: &mut MeshHelper) -> GameResult<()> {
+        render(ctx, mesh_helper, &self.state)
+    }
+
+    fn play_state(&self) -> PlayState {
+        self.state.play_state
+    }
+}
@@ -0,0 +1,167 @@
+use crate::boards::cursor::Cursor;
+use crate::system::{PlayState, Player};
+
+mod ai;
+pub mod controller;
+mod render_mode_selection;
+mod renderer;
+mod rules;
+
+const COLS: usize = 16;
+const ROWS: usize = 8;
+const SQUARES: usize = COLS * ROWS;
+//Common victory targets
+const BODIES_TARGET: usize = 12;
+const GOODS_TARGET: u32 = 400;
+
+//Even moves first and starts on the left, Odd starts on the right
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Side {
+    Even,
+    Odd,
+}
+
+impl Side {
+    fn opposite(&self) -> Side {
+        match self {
+            Side::Even => Side::Odd,
+            Side::Odd => Side::Even,
+        }
+    }
+
+    fn idx(&self) -> usize {
+        match self {
+            Side::Even => 0,
+            Side::Odd => 1,
+        }
+    }
+
+    //Columns of the enemy's half of the board
+    fn enemy_half(&self) -> std::ops::Range<usize> {
+        match self {
+            Side::Even => COLS / 2..COLS,
+            Side::Odd => 0..COLS / 2,
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Shape {
+    Circle,
+    Triangle,
+    Square,
+    //Moves as any of the other shapes, its value is the total of its layers
+    Pyramid,
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Piece {
+    side: Side,
+    shape: Shape,
+    value: u32,
+}
+
+type Board = [Option<Piece>; SQUARES];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Victory {
+    //Common victories
+    Bodies,
+    Goods,
+    //Proper victories
+    Great,
+    Greater,
+}
+
+impl Victory {
+    const ALL: [Victory; 4] = [
+        Victory::Bodies,
+        Victory::Goods,
+        Victory::Great,
+        Victory::Greater,
+    ];
+
+    fn name(&self) -> &'static str {
+        match self {
+            Victory::Bodies => "Bodies",
+            Victory::Goods => "Goods",
+            Victory::Great => "Great",
+            Victory::Greater => "Greater",
+        }
+    }
+
+    fn desc(&self) -> String {
+        match self {
+            Victory::Bodies => format!("Capture {} pieces", BODIES_TARGET),
+            Victory::Goods => format!("Capture pieces worth {} or more", GOODS_TARGET),
+            Victory::Great => String::from("Line up 3 pieces in a progression in the enemy's half"),
+            Victory::Greater => {
+                String::from("Line up 4 pieces with two progressions in the enemy's half")
+            }
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Move {
+    origin: usize,
+    dest: usize,
+}
+
+//Pieces taken by a side
+#[derive(Debug, Copy, Clone, Default, Eq, PartialEq)]
+struct Captured {
+    count: usize,
+    value: u32,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Position {
+    board: Board,
+    captured: [Captured; 2],
+}
+
+struct State {
+    position: Position,
+    cursor: Cursor,
+    play_state: PlayState,
+    last_human_cursor_pos: usize,
+    valid_moves: Vec<Move>,
+    move_cursor: usize,
+    human: Side,
+    victory: Victory,
+    //Row of the mode selection screen being changed
+    setup_row: usize,
+    //Squares emptied by captures on the last move
+    last_captures: Vec<usize>,
+    next_move_time: f64,
+}
+
+impl State {
+    fn get_moves_for_selected_piece(&self) -> Vec<Move> {
+        self.valid_moves
+            .iter()
+            .filter(|mov| mov.origin == self.cursor.idx)
+            .cloned()
+            .collect()
+    }
+
+    fn get_selected_move(&self) -> Move {
+        *self
+            .get_moves_for_selected_piece()
+            .get(self.move_cursor)
+            .unwrap_or_else(|| {
+                panic!(
+                    "No move {} for square {}",
+                    self.move_cursor, self.cursor.idx
+                )
+            })
+    }
+
+    fn get_side_for_player(&self, player: Player) -> Side {
+        match player {
+            Player::Human => self.human,
+            Player::Computer => self.human.opposite(),
+        }
+    }
+}
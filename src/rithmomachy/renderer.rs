@@ -0,0 +1,200 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::constants::colors::{
+    BLACK, LIGHT_BLUE, LIGHT_GRAY, PIECE_COMPUTER, PIECE_HUMAN, RED, WHITE,
+};
+use crate::constants::Direction;
+use crate::rithmomachy::render_mode_selection::render_mode_selection;
+use crate::rithmomachy::{Move, Shape, Side, State, COLS, ROWS};
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::PlayState::ModeSelection;
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use ggez::graphics::DrawMode;
+use ggez::{Context, GameResult};
+
+pub(super) fn render(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    if state.play_state == ModeSelection {
+        render_mode_selection(ctx, mesh_helper, state)
+    } else {
+        render_game(ctx, mesh_helper, state)
+    }
+}
+
+fn square_pos(idx: usize, cell_size: f32, board_start: Point) -> Point {
+    Point::from(BoardCoord::from(idx))
+        .multiply(cell_size, cell_size)
+        .offset_point(board_start)
+}
+
+fn render_game(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State) -> GameResult<()> {
+    let cell_size = mesh_helper.calc_width(0.055);
+    let board_start = pt(mesh_helper.calc_width(0.06), mesh_helper.calc_height(0.1));
+    let board_width = cell_size * COLS as f32;
+    let board_height = cell_size * ROWS as f32;
+    let piece_size = cell_size * 0.8;
+    let inset = cell_size * 0.1;
+
+    let grid = mesh_helper.make_grid(
+        ctx,
+        board_width,
+        board_height,
+        COLS,
+        ROWS,
+        2.,
+        LIGHT_GRAY,
+        None,
+    )?;
+    let rect = mesh_helper.make_rect(ctx, board_width, board_height, DrawMode::stroke(2.))?;
+    let middle = mesh_helper.make_rect(ctx, 4., board_height, DrawMode::fill())?;
+    let circle = mesh_helper.make_circle(ctx, cell_size, piece_size * 0.5, DrawMode::fill())?;
+    let triangle = mesh_helper.make_triangle(ctx, piece_size, piece_size, Direction::Up)?;
+    let square = mesh_helper.make_rect(ctx, piece_size, piece_size, DrawMode::fill())?;
+    let pyramid_top =
+        mesh_helper.make_triangle(ctx, piece_size, piece_size * 0.5, Direction::Up)?;
+    let pyramid_base =
+        mesh_helper.make_rect(ctx, piece_size, piece_size * 0.5, DrawMode::fill())?;
+    let captured = mesh_helper.make_rect(ctx, cell_size, cell_size, DrawMode::stroke(2.))?;
+
+    mesh_helper.draw_mesh(ctx, grid.as_ref(), board_start);
+    mesh_helper.draw_mesh(ctx, rect.as_ref(), board_start);
+    mesh_helper.draw_coloured_mesh(
+        ctx,
+        middle.as_ref(),
+        board_start.offset(board_width * 0.5 - 2., 0.),
+        LIGHT_GRAY,
+    );
+
+    for (idx, square_piece) in state.position.board.iter().enumerate() {
+        if let Some(piece) = square_piece {
+            let pos = square_pos(idx, cell_size, board_start);
+            let (colour, text_colour) = match piece.side {
+                Side::Even => (PIECE_HUMAN, BLACK),
+                Side::Odd => (PIECE_COMPUTER, WHITE),
+            };
+            match piece.shape {
+                Shape::Circle => mesh_helper.draw_coloured_mesh(ctx, circle.as_ref(), pos, colour),
+                Shape::Triangle => mesh_helper.draw_coloured_mesh(
+                    ctx,
+                    triangle.as_ref(),
+                    pos.offset(inset, inset),
+                    colour,
+                ),
+                Shape::Square => mesh_helper.draw_coloured_mesh(
+                    ctx,
+                    square.as_ref(),
+                    pos.offset(inset, inset),
+                    colour,
+                ),
+                Shape::Pyramid => {
+                    mesh_helper.draw_coloured_mesh(
+                        ctx,
+                        pyramid_top.as_ref(),
+                        pos.offset(inset, inset),
+                        colour,
+                    );
+                    mesh_helper.draw_coloured_mesh(
+                        ctx,
+                        pyramid_base.as_ref(),
+                        pos.offset(inset, inset + piece_size * 0.5),
+                        colour,
+                    );
+                }
+            }
+            mesh_helper.draw_text(
+                ctx,
+                &piece.value.to_string(),
+                pos.offset(cell_size * 0.5, cell_size * 0.4),
+                text_colour,
+                18.,
+                true,
+            );
+        }
+    }
+
+    for idx in &state.last_captures {
+        mesh_helper.draw_coloured_mesh(
+            ctx,
+            captured.as_ref(),
+            square_pos(*idx, cell_size, board_start),
+            RED,
+        );
+    }
+
+    if state.play_state.is_human(SelectingPiece) {
+        state
+            .cursor
+            .render(ctx, mesh_helper, board_start, cell_size)?;
+
+        for mov in state.get_moves_for_selected_piece() {
+            draw_move(ctx, mesh_helper, cell_size, board_start, &mov, false)?;
+        }
+    } else if state.play_state.is_either(SelectingMove) {
+        state
+            .cursor
+            .render_dark(ctx, mesh_helper, board_start, cell_size)?;
+        draw_move(
+            ctx,
+            mesh_helper,
+            cell_size,
+            board_start,
+            &state.get_selected_move(),
+            true,
+        )?;
+    }
+
+    let info_start = board_start.offset(0., board_height + cell_size * 0.5);
+    let lines = [
+        (format!("Victory: {}", state.victory.desc()), LIGHT_BLUE),
+        (
+            format!(
+                "You have captured {} pieces worth {}",
+                state.position.captured[state.human.idx()].count,
+                state.position.captured[state.human.idx()].value
+            ),
+            WHITE,
+        ),
+        (
+            format!(
+                "Computer has captured {} pieces worth {}",
+                state.position.captured[state.human.opposite().idx()].count,
+                state.position.captured[state.human.opposite().idx()].value
+            ),
+            WHITE,
+        ),
+    ];
+    for (i, (line, colour)) in lines.iter().enumerate() {
+        mesh_helper.draw_text(
+            ctx,
+            line,
+            info_start.offset(0., 30. * i as f32),
+            *colour,
+            20.,
+            false,
+        );
+    }
+
+    Ok(())
+}
+
+fn draw_move(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    cell_size: f32,
+    board_start: Point,
+    mov: &Move,
+    highlight: bool,
+) -> GameResult<()> {
+    let move_mesh =
+        mesh_helper.make_circle(ctx, cell_size, cell_size * 0.1, DrawMode::stroke(1.))?;
+    mesh_helper.draw_coloured_mesh(
+        ctx,
+        move_mesh.as_ref(),
+        square_pos(mov.dest, cell_size, board_start),
+        if highlight { LIGHT_BLUE } else { WHITE },
+    );
+    Ok(())
+}
@@ -0,0 +1,407 @@
+use crate::rithmomachy::{
+    Board, Captured, Move, Piece, Position, Shape, Side, Victory, BODIES_TARGET, COLS,
+    GOODS_TARGET, ROWS, SQUARES,
+};
+
+const DIAGONALS: [(isize, isize); 4] = [(-1, -1), (1, -1), (-1, 1), (1, 1)];
+const ORTHOGONALS: [(isize, isize); 4] = [(0, -1), (-1, 0), (1, 0), (0, 1)];
+//Directions a line of pieces can run in for a proper victory
+const LINES: [(isize, isize); 4] = [(1, 0), (0, 1), (1, 1), (1, -1)];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Progression {
+    Arithmetic,
+    Geometric,
+    Harmonic,
+}
+
+//Shapes and values from the edge of the board inwards, top row first
+const EVEN_SQUARES: [u32; 8] = [15, 45, 153, 91, 289, 169, 81, 25];
+const EVEN_TRIANGLES: [u32; 8] = [6, 20, 42, 72, 81, 49, 25, 9];
+const EVEN_CIRCLES: [u32; 8] = [2, 4, 6, 8, 64, 36, 16, 4];
+const ODD_SQUARES: [u32; 8] = [28, 66, 120, 190, 361, 225, 121, 49];
+const ODD_TRIANGLES: [u32; 8] = [12, 30, 56, 90, 100, 64, 36, 16];
+const ODD_CIRCLES: [u32; 8] = [3, 5, 7, 9, 81, 49, 25, 9];
+//Row of the pyramid in the squares column
+const PYRAMID_ROW: usize = 3;
+
+pub(super) fn init_board() -> Board {
+    let mut board = [None; SQUARES];
+    let columns = [
+        (Side::Even, Shape::Square, 0, EVEN_SQUARES),
+        (Side::Even, Shape::Triangle, 1, EVEN_TRIANGLES),
+        (Side::Even, Shape::Circle, 2, EVEN_CIRCLES),
+        (Side::Odd, Shape::Circle, COLS - 3, ODD_CIRCLES),
+        (Side::Odd, Shape::Triangle, COLS - 2, ODD_TRIANGLES),
+        (Side::Odd, Shape::Square, COLS - 1, ODD_SQUARES),
+    ];
+    for (side, shape, x, values) in columns.iter() {
+        for (y, value) in values.iter().enumerate() {
+            let shape = if *shape == Shape::Square && y == PYRAMID_ROW {
+                Shape::Pyramid
+            } else {
+                *shape
+            };
+            board[y * COLS + x] = Some(Piece {
+                side: *side,
+                shape,
+                value: *value,
+            });
+        }
+    }
+    board
+}
+
+fn step(idx: usize, (dx, dy): (isize, isize), dist: isize) -> Option<usize> {
+    let x = (idx % COLS) as isize + dx * dist;
+    let y = (idx / COLS) as isize + dy * dist;
+    if x < 0 || y < 0 || x >= COLS as isize || y >= ROWS as isize {
+        None
+    } else {
+        Some(y as usize * COLS + x as usize)
+    }
+}
+
+//Squares 'dist' away in each direction, the squares passed over must be empty
+fn reach_in(board: &Board, idx: usize, dirs: &[(isize, isize)], dist: isize) -> Vec<usize> {
+    dirs.iter()
+        .filter(|dir| {
+            (1..dist).all(|i| matches!(step(idx, **dir, i), Some(sq) if board[sq].is_none()))
+        })
+        .filter_map(|dir| step(idx, *dir, dist))
+        .collect()
+}
+
+//Squares the piece on idx could move to if they were empty
+//Circles move 1 diagonally, triangles 2 orthogonally, squares 3 orthogonally
+pub(super) fn reach(board: &Board, idx: usize) -> Vec<usize> {
+    match board[idx].map(|piece| piece.shape) {
+        None => vec![],
+        Some(Shape::Circle) => reach_in(board, idx, &DIAGONALS, 1),
+        Some(Shape::Triangle) => reach_in(board, idx, &ORTHOGONALS, 2),
+        Some(Shape::Square) => reach_in(board, idx, &ORTHOGONALS, 3),
+        Some(Shape::Pyramid) => {
+            let mut squares = reach_in(board, idx, &DIAGONALS, 1);
+            squares.extend(reach_in(board, idx, &ORTHOGONALS, 2));
+            squares.extend(reach_in(board, idx, &ORTHOGONALS, 3));
+            squares
+        }
+    }
+}
+
+fn squares_of(board: &Board, side: Side) -> Vec<usize> {
+    (0..SQUARES)
+        .filter(|idx| matches!(board[*idx], Some(piece) if piece.side == side))
+        .collect()
+}
+
+pub(super) fn calc_valid_moves(board: &Board, side: Side) -> Vec<Move> {
+    squares_of(board, side)
+        .into_iter()
+        .flat_map(|origin| {
+            reach(board, origin)
+                .into_iter()
+                .filter(|dest| board[*dest].is_none())
+                .map(move |dest| Move { origin, dest })
+        })
+        .collect()
+}
+
+//Assault: the attacker's value times the number of empty squares between it and the enemy
+//equals the enemy's value
+fn is_assaulted(board: &Board, idx: usize, side: Side, value: u32) -> bool {
+    DIAGONALS.iter().chain(ORTHOGONALS.iter()).any(|dir| {
+        let mut dist = 1;
+        while let Some(sq) = step(idx, *dir, dist) {
+            if let Some(piece) = board[sq] {
+                let between = (dist - 1) as u32;
+                return piece.side == side && between > 0 && piece.value * between == value;
+            }
+            dist += 1;
+        }
+        false
+    })
+}
+
+//Siege: every orthogonal neighbour is the attacker's piece or the edge of the board
+fn is_besieged(board: &Board, idx: usize, side: Side) -> bool {
+    ORTHOGONALS.iter().all(|dir| match step(idx, *dir, 1) {
+        None => true,
+        Some(sq) => matches!(board[sq], Some(piece) if piece.side == side),
+    })
+}
+
+//Enemy pieces that 'side' can capture in this position
+pub(super) fn find_captures(board: &Board, side: Side) -> Vec<usize> {
+    let mut reached_by: Vec<Vec<u32>> = vec![vec![]; SQUARES];
+    for idx in squares_of(board, side) {
+        let value = board[idx].unwrap().value;
+        for sq in reach(board, idx) {
+            reached_by[sq].push(value);
+        }
+    }
+    squares_of(board, side.opposite())
+        .into_iter()
+        .filter(|idx| {
+            let value = board[*idx].unwrap().value;
+            let attackers = &reached_by[*idx];
+            //Meeting: a piece of equal value could move onto it
+            let meeting = attackers.contains(&value);
+            //Ambush: two pieces that could both move onto it add up to its value
+            let ambush = attackers
+                .iter()
+                .enumerate()
+                .any(|(i, a)| attackers[i + 1..].iter().any(|b| a + b == value));
+            meeting
+                || ambush
+                || is_assaulted(board, *idx, side, value)
+                || is_besieged(board, *idx, side)
+        })
+        .collect()
+}
+
+//Moves the piece then removes anything it captures, returns the captured squares
+pub(super) fn apply_move(position: &mut Position, mov: &Move) -> Vec<usize> {
+    let piece = position.board[mov.origin].expect("No piece to move");
+    position.board[mov.dest] = position.board[mov.origin].take();
+    let captures = find_captures(&position.board, piece.side);
+    let captured = &mut position.captured[piece.side.idx()];
+    for idx in &captures {
+        if let Some(taken) = position.board[*idx].take() {
+            captured.count += 1;
+            captured.value += taken.value;
+        }
+    }
+    captures
+}
+
+fn progressions(values: &[u32]) -> Vec<Progression> {
+    let mut sorted = values.to_vec();
+    sorted.sort_unstable();
+    let (a, b, c) = (sorted[0], sorted[1], sorted[2]);
+    let mut result = vec![];
+    if a == b || b == c {
+        return result;
+    }
+    if b - a == c - b {
+        result.push(Progression::Arithmetic);
+    }
+    if b * b == a * c {
+        result.push(Progression::Geometric);
+    }
+    if b * (a + c) == 2 * a * c {
+        result.push(Progression::Harmonic);
+    }
+    result
+}
+
+//A great victory needs 3 pieces in a progression, a greater victory needs 4 pieces with
+//two different progressions among them
+fn is_proper_victory(values: &[u32]) -> bool {
+    match values.len() {
+        3 => !progressions(values).is_empty(),
+        4 => {
+            let mut found: Vec<Progression> = vec![];
+            for skip in 0..4 {
+                let triple: Vec<u32> = (0..4).filter(|i| *i != skip).map(|i| values[i]).collect();
+                for prog in progressions(&triple) {
+                    if !found.contains(&prog) {
+                        found.push(prog);
+                    }
+                }
+            }
+            found.len() >= 2
+        }
+        _ => false,
+    }
+}
+
+//Whether the side has a line of 'len' pieces in the enemy's half forming a proper victory
+fn has_proper_victory(board: &Board, side: Side, len: usize) -> bool {
+    let half = side.enemy_half();
+    let in_half = |sq: usize| half.contains(&(sq % COLS));
+    squares_of(board, side)
+        .into_iter()
+        .filter(|idx| in_half(*idx))
+        .any(|idx| {
+            LINES.iter().any(|dir| {
+                let values: Option<Vec<u32>> = (0..len as isize)
+                    .map(|i| {
+                        step(idx, *dir, i)
+                            .filter(|sq| in_half(*sq))
+                            .and_then(|sq| board[sq])
+                            .filter(|piece| piece.side == side)
+                            .map(|piece| piece.value)
+                    })
+                    .collect();
+                values.is_some_and(|values| is_proper_victory(&values))
+            })
+        })
+}
+
+fn has_won(position: &Position, side: Side, victory: Victory) -> bool {
+    let Captured { count, value } = position.captured[side.idx()];
+    match victory {
+        Victory::Bodies => count >= BODIES_TARGET,
+        Victory::Goods => value >= GOODS_TARGET,
+        Victory::Great => has_proper_victory(&position.board, side, 3),
+        Victory::Greater => has_proper_victory(&position.board, side, 4),
+    }
+}
+
+//Checks the side that just moved first
+pub(super) fn winner(position: &Position, last_mover: Side, victory: Victory) -> Option<Side> {
+    [last_mover, last_mover.opposite()]
+        .iter()
+        .find(|side| has_won(position, **side, victory))
+        .copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn piece(side: Side, shape: Shape, value: u32) -> Option<Piece> {
+        Some(Piece { side, shape, value })
+    }
+
+    fn idx(x: usize, y: usize) -> usize {
+        y * COLS + x
+    }
+
+    fn empty() -> Position {
+        Position {
+            board: [None; SQUARES],
+            captured: [Captured::default(); 2],
+        }
+    }
+
+    #[test]
+    fn test_init_board() {
+        let board = init_board();
+        assert_eq!(squares_of(&board, Side::Even).len(), 24);
+        assert_eq!(squares_of(&board, Side::Odd).len(), 24);
+        assert_eq!(board[idx(0, 3)], piece(Side::Even, Shape::Pyramid, 91));
+        assert_eq!(board[idx(15, 3)], piece(Side::Odd, Shape::Pyramid, 190));
+        assert!(find_captures(&board, Side::Even).is_empty());
+        assert!(find_captures(&board, Side::Odd).is_empty());
+        //Only the circles can move at the start
+        assert_eq!(calc_valid_moves(&board, Side::Even).len(), 14);
+    }
+
+    #[test]
+    fn test_movement() {
+        let mut board = [None; SQUARES];
+        board[idx(5, 4)] = piece(Side::Even, Shape::Circle, 2);
+        assert_eq!(reach(&board, idx(5, 4)).len(), 4);
+        board[idx(5, 4)] = piece(Side::Even, Shape::Triangle, 6);
+        board[idx(5, 3)] = piece(Side::Even, Shape::Circle, 2);
+        //Up is blocked
+        let mut squares = reach(&board, idx(5, 4));
+        squares.sort_unstable();
+        assert_eq!(squares, vec![idx(3, 4), idx(7, 4), idx(5, 6)]);
+        board[idx(5, 4)] = piece(Side::Even, Shape::Square, 15);
+        let mut squares = reach(&board, idx(5, 4));
+        squares.sort_unstable();
+        assert_eq!(squares, vec![idx(2, 4), idx(8, 4), idx(5, 7)]);
+        board[idx(5, 4)] = piece(Side::Even, Shape::Pyramid, 91);
+        assert_eq!(reach(&board, idx(5, 4)).len(), 4 + 3 + 3);
+    }
+
+    #[test]
+    fn test_meeting_and_ambush() {
+        let mut position = empty();
+        position.board[idx(5, 4)] = piece(Side::Odd, Shape::Circle, 9);
+        position.board[idx(3, 4)] = piece(Side::Even, Shape::Triangle, 9);
+        assert_eq!(find_captures(&position.board, Side::Even), vec![idx(5, 4)]);
+        position.board[idx(3, 4)] = piece(Side::Even, Shape::Triangle, 6);
+        assert!(find_captures(&position.board, Side::Even).is_empty());
+        //6 + 3 from a circle diagonally adjacent
+        position.board[idx(5, 4)] = piece(Side::Odd, Shape::Circle, 9);
+        position.board[idx(4, 3)] = piece(Side::Even, Shape::Circle, 3);
+        assert_eq!(find_captures(&position.board, Side::Even), vec![idx(5, 4)]);
+        let captures = apply_move(
+            &mut position,
+            &Move {
+                origin: idx(4, 3),
+                dest: idx(3, 2),
+            },
+        );
+        //Moved out of reach
+        assert!(captures.is_empty());
+    }
+
+    #[test]
+    fn test_assault_and_siege() {
+        let mut position = empty();
+        position.board[idx(8, 2)] = piece(Side::Odd, Shape::Triangle, 12);
+        //4 x 3 empty squares between
+        position.board[idx(4, 2)] = piece(Side::Even, Shape::Circle, 4);
+        assert_eq!(find_captures(&position.board, Side::Even), vec![idx(8, 2)]);
+        position.board[idx(4, 2)] = piece(Side::Even, Shape::Circle, 5);
+        assert!(find_captures(&position.board, Side::Even).is_empty());
+
+        //Corner piece surrounded by two enemies
+        let mut position = empty();
+        position.board[0] = piece(Side::Odd, Shape::Square, 361);
+        position.board[1] = piece(Side::Even, Shape::Circle, 2);
+        position.board[idx(1, 2)] = piece(Side::Even, Shape::Circle, 4);
+        let captures = apply_move(
+            &mut position,
+            &Move {
+                origin: idx(1, 2),
+                dest: idx(0, 1),
+            },
+        );
+        assert_eq!(captures, vec![0]);
+        assert_eq!(position.board[0], None);
+        assert_eq!(
+            position.captured[Side::Even.idx()],
+            Captured {
+                count: 1,
+                value: 361
+            }
+        );
+        assert_eq!(winner(&position, Side::Even, Victory::Goods), None);
+        position.captured[Side::Even.idx()].value += 39;
+        assert_eq!(
+            winner(&position, Side::Even, Victory::Goods),
+            Some(Side::Even)
+        );
+    }
+
+    #[test]
+    fn test_progressions() {
+        assert_eq!(progressions(&[2, 4, 6]), vec![Progression::Arithmetic]);
+        assert_eq!(progressions(&[16, 4, 8]), vec![Progression::Geometric]);
+        assert_eq!(progressions(&[3, 4, 6]), vec![Progression::Harmonic]);
+        assert!(progressions(&[2, 5, 6]).is_empty());
+        //2, 3, 4 is arithmetic and 2, 3, 6 is harmonic
+        assert!(is_proper_victory(&[2, 3, 4, 6]));
+        assert!(!is_proper_victory(&[2, 4, 6, 10]));
+    }
+
+    #[test]
+    fn test_proper_victory() {
+        let mut position = empty();
+        for (i, value) in [2, 4, 6].iter().enumerate() {
+            position.board[idx(7 + i, 5)] = piece(Side::Even, Shape::Circle, *value);
+        }
+        //Not all in the enemy half
+        assert_eq!(winner(&position, Side::Even, Victory::Great), None);
+        position.board[idx(10, 5)] = piece(Side::Even, Shape::Circle, 8);
+        assert_eq!(
+            winner(&position, Side::Even, Victory::Great),
+            Some(Side::Even)
+        );
+        assert_eq!(winner(&position, Side::Even, Victory::Greater), None);
+        position.board[idx(10, 5)] = piece(Side::Even, Shape::Circle, 3);
+        position.board[idx(9, 5)] = piece(Side::Even, Shape::Circle, 6);
+        position.board[idx(8, 5)] = piece(Side::Even, Shape::Circle, 4);
+        position.board[idx(11, 5)] = piece(Side::Even, Shape::Circle, 2);
+        assert_eq!(
+            winner(&position, Side::Even, Victory::Greater),
+            Some(Side::Even)
+        );
+    }
+}
@@ -1,19 +1,159 @@
-use crate::senet::State;
+use crate::senet::init::INIT_BOARD;
+use crate::senet::rules::{
+    apply_move, calc_valid_moves, roll_sticks, winner, HOME, REPEAT_TURN_ROLL,
+};
+use crate::senet::{Board, State};
+use crate::system::mcts::{MctsGame, MctsSettings, Outcome, Search, FRAME_BUDGET};
+use crate::system::Player;
 use crate::system::TurnState::SelectingPiece;
+use rand::rngs::StdRng;
 use rand::{thread_rng, Rng};
+use std::time::Duration;
 
-pub(super) fn process(state: &mut State) {
+const PLAYOUTS: usize = 20_000;
+const THINKING_TIME: Duration = Duration::from_millis(1000);
+const MAX_PLAYOUT_MOVES: usize = 100;
+
+#[derive(Debug, Clone)]
+pub(super) struct Position {
+    board: Board,
+    to_move: Player,
+    roll: usize,
+}
+
+impl Position {
+    //Pieces that have been borne off count as being one past home
+    fn progress(&self, player: Player) -> usize {
+        let on_board: Vec<usize> = self
+            .board
+            .iter()
+            .enumerate()
+            .filter(|(_, square)| square.player() == Some(player))
+            .map(|(idx, _)| idx)
+            .collect();
+        let pieces = INIT_BOARD
+            .iter()
+            .filter(|square| square.player() == Some(player))
+            .count();
+        let borne_off = pieces - on_board.len();
+        on_board.iter().sum::<usize>() + borne_off * (HOME + 1)
+    }
+}
+
+//Moves are origin and destination, None is skipping a turn when the roll allows no moves
+impl MctsGame for Position {
+    type Move = Option<(usize, usize)>;
+
+    fn to_move(&self) -> Player {
+        self.to_move
+    }
+
+    fn legal_moves(&self) -> Vec<Option<(usize, usize)>> {
+        let moves: Vec<Option<(usize, usize)>> =
+            calc_valid_moves(&self.board, self.roll, self.to_move)
+                .iter()
+                .map(|mov| Some((mov.origin, mov.dest)))
+                .collect();
+        if moves.is_empty() {
+            vec![None]
+        } else {
+            moves
+        }
+    }
+
+    //Rolls the sticks for whoever moves next
+    fn apply(&mut self, mov: &Option<(usize, usize)>, rng: &mut StdRng) {
+        if let Some((origin, dest)) = mov {
+            let mov = calc_valid_moves(&self.board, self.roll, self.to_move)
+                .into_iter()
+                .find(|piece_mov| piece_mov.origin == *origin && piece_mov.dest == *dest)
+                .expect("Search played an illegal move");
+            apply_move(&mut self.board, &mov);
+        }
+        if mov.is_none() || !REPEAT_TURN_ROLL.contains(&self.roll) {
+            self.to_move = self.to_move.opposite();
+        }
+        self.roll = roll_sticks(rng);
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        winner(&self.board).map(Outcome::Win)
+    }
+
+    fn estimate(&self) -> Outcome {
+        let human = self.progress(Player::Human);
+        let computer = self.progress(Player::Computer);
+        if human > computer {
+            Outcome::Win(Player::Human)
+        } else if computer > human {
+            Outcome::Win(Player::Computer)
+        } else {
+            Outcome::Draw
+        }
+    }
+}
+
+fn start_search(board: &Board, roll: usize, seed: u64) -> Search<Position> {
+    let position = Position {
+        board: *board,
+        to_move: Player::Computer,
+        roll,
+    };
+    let settings = MctsSettings::new(PLAYOUTS, seed)
+        .with_time(THINKING_TIME)
+        .with_max_playout_moves(MAX_PLAYOUT_MOVES);
+    Search::new(position, settings)
+}
+
+//Searches for a frame, returns false until a move has been chosen
+pub(super) fn process(state: &mut State) -> bool {
     if state.play_state.is_computer(SelectingPiece) {
+        let board = state.board;
+        let roll = state.roll.expect("Computer hasn't rolled");
+        let search = state
+            .search
+            .get_or_insert_with(|| start_search(&board, roll, thread_rng().gen()));
+        if !search.run(FRAME_BUDGET) {
+            return false;
+        }
+        let mov = search.best_move().flatten();
+        state.search = None;
         state.last_human_cursor_pos = state.cursor.idx;
-        let piece_idx = state.valid_moves.keys().last().unwrap();
-        state.cursor.idx = *piece_idx;
-        let move_idx = thread_rng().gen_range(0.. state.get_moves_for_selected_piece().len());
-        state.move_cursor = move_idx;
-        debug_log!(
-            "Selecting {} from {}: {:?}",
-            move_idx,
-            piece_idx,
-            state.get_selected_move()
-        );
+        if let Some((origin, dest)) = mov {
+            state.cursor.idx = origin;
+            state.move_cursor = state
+                .get_moves_for_selected_piece()
+                .iter()
+                .position(|mov| mov.dest == dest)
+                .unwrap_or_default();
+            debug_log!("Selecting {:?}", state.get_selected_move());
+        }
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::senet::Square::{Computer, Empty, Human};
+    use serial_test::serial;
+
+    fn choose_move(board: &Board, roll: usize, seed: u64) -> Option<(usize, usize)> {
+        let mut search = start_search(board, roll, seed);
+        assert!(search.run(Duration::from_secs(60)));
+        search.best_move().flatten()
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_bears_off_last_piece() {
+        set_board_size((3, 10));
+        let mut board = [Empty; 30];
+        board[0] = Human;
+        board[1] = Human;
+        board[27] = Computer;
+        board[10] = Computer;
+        assert_eq!(choose_move(&board, 2, 1), Some((27, HOME)));
     }
 }
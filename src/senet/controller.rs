@@ -1,12 +1,15 @@
-use crate::boards::cursor::Cursor;
+use crate::boards::chessboard::ChessBoard;
+use crate::boards::cursor::{movable, Cursor};
 use crate::boards::idx_coord::BoardCoord;
+use crate::boards::move_entry::{EntryAction, MoveEntry};
 use crate::boards::set_board_size;
+use crate::boards::single_char_board_converter::SingleCharBoardConverter;
 use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
 use crate::senet::ai::process;
 use crate::senet::init::INIT_BOARD;
 use crate::senet::renderer::render;
-use crate::senet::rules::{calc_valid_moves, HOME, HOUSE_REBIRTH, HOUSE_WATER, REPEAT_TURN_ROLL};
-use crate::senet::{State, MAX_STICKS_UP};
+use crate::senet::rules::{apply_move, calc_valid_moves, roll_sticks, winner, REPEAT_TURN_ROLL};
+use crate::senet::{Move, State};
 use crate::system::find_nearest::find_nearest;
 use crate::system::ggez_ext::keycode_to_direction;
 use crate::system::mesh_helper::MeshHelper;
@@ -16,7 +19,7 @@ use crate::system::{PlayState, Player, Scene, Turn, TurnState, NEW_TURN_COMPUTER
 use ggez::event::KeyCode;
 use ggez::{Context, GameResult};
 use itertools::Itertools;
-use rand::{thread_rng, Rng};
+use rand::thread_rng;
 use std::collections::HashMap;
 
 pub struct Controller {
@@ -39,6 +42,9 @@ impl Controller {
                 next_move_time: 0.0,
                 last_human_cursor_pos: 0,
                 valid_moves: HashMap::new(),
+                coords: ChessBoard::new(Box::new(SingleCharBoardConverter::new(3, 10)), 3, 10),
+                entry: MoveEntry::new(),
+                search: None,
             },
         }
     }
@@ -79,21 +85,31 @@ impl Controller {
     }
 
     fn roll(&mut self, player: Player) {
-        let sticks = thread_rng().gen_range(0 ..MAX_STICKS_UP + 1);
-        self.state.roll = Some(if sticks == 0 { 5 } else { sticks });
-        debug_log!("Rolled {}", self.state.roll.unwrap_or(0));
+        let roll = roll_sticks(&mut thread_rng());
+        self.state.roll = Some(roll);
+        debug_log!("Rolled {}", roll);
         self.update_moves(player);
+        if self.state.valid_moves.is_empty() {
+            debug_log!("No moves, skipping turn");
+            self.start_new_turn(player.opposite(), false);
+            self.state.msg = Some(format!(
+                "No moves with a roll of {}. {}",
+                roll,
+                self.state.msg.as_deref().unwrap_or_default()
+            ));
+        }
     }
 
-    fn ai_update(&mut self) {
+    //Returns false while the computer is still choosing its move
+    fn ai_update(&mut self) -> bool {
         if self.state.play_state.is_computer(SelectingPiece) {
             if self.state.roll.is_none() {
                 self.roll(Player::Computer);
-            } else {
-                process(&mut self.state);
+            } else if !process(&mut self.state) {
+                return false;
             }
         } else if self.state.play_state.is_computer(SelectingMove) {
-            self.process_move();
+            self.process_move(self.state.get_selected_move());
             if REPEAT_TURN_ROLL.contains(&self.state.roll.unwrap()) {
                 self.start_new_turn(Player::Computer, true);
             } else {
@@ -101,36 +117,66 @@ impl Controller {
                 self.start_new_turn(Player::Human, false);
             }
         }
+        true
     }
 
-    fn process_move(&mut self) {
-        let mut mov = self.state.get_selected_move();
-        let origin = self.state.board[mov.origin];
-        self.state.board[mov.origin] = self.state.board[mov.dest];
-        if mov.dest == HOUSE_WATER {
-            mov.dest = HOUSE_REBIRTH;
+    //Return and Back while a move is being typed
+    fn handle_entry_key(&mut self, key: KeyCode) {
+        let options = self.state.entry_options();
+        if key == KeyCode::Back {
+            self.state.entry.pop(&options);
+            return;
         }
-        if mov.dest <= HOME {
-            self.state.board[mov.dest] = origin;
+        match self.state.entry.submit(&options) {
+            Some(EntryAction::SelectPiece(idx)) => {
+                self.state.cursor.idx = *idx;
+                self.state.move_cursor = 0;
+                self.state.play_state = PlayState::Playing(Turn::Human(SelectingMove));
+            }
+            Some(EntryAction::PlayMove(mov)) => {
+                self.state.cursor.idx = mov.origin;
+                self.process_move(mov.clone());
+            }
+            None => {}
+        }
+    }
+
+    fn process_move(&mut self, mov: Move) {
+        let player = self.state.board[mov.origin].player().unwrap();
+        apply_move(&mut self.state.board, &mov);
+        if let Some(winner) = winner(&self.state.board) {
+            debug_log!("{:?} has no pieces left", winner);
+            self.state.msg = None;
+            self.state.play_state = match winner {
+                Player::Human => PlayState::HumanWin,
+                Player::Computer => PlayState::ComputerWin,
+            };
+            return;
         }
         if REPEAT_TURN_ROLL.contains(&self.state.roll.unwrap()) {
-            self.start_new_turn(origin.player().unwrap(), true);
+            self.start_new_turn(player, true);
         } else {
-            self.start_new_turn(origin.player().unwrap().opposite(), false);
+            self.start_new_turn(player.opposite(), false);
         }
     }
 }
 
 impl Scene for Controller {
     fn on_key_down(&mut self, key: KeyCode) {
-        if self.state.play_state.is_human(SelectingPiece) {
+        if !self.state.entry.is_empty() && (key == KeyCode::Return || key == KeyCode::Back) {
+            self.handle_entry_key(key);
+        } else if self.state.play_state.is_human(SelectingPiece) {
             if self.state.roll.is_none() {
                 if key == KeyCode::Return {
-                    self.roll(Player::Human);
                     self.state.msg = Some(String::from("Your turn"));
+                    self.roll(Player::Human);
                 }
-            } else if !self.state.cursor.handle_input(key) {
-                if key == KeyCode::Return && !self.state.get_moves_for_selected_piece().is_empty() {
+            } else {
+                let movable = movable(&self.state.valid_moves);
+                if !self.state.cursor.handle_movable_input(key, &movable)
+                    && key == KeyCode::Return
+                    && !self.state.get_moves_for_selected_piece().is_empty()
+                {
                     self.state.play_state = PlayState::Playing(Turn::Human(SelectingMove));
                 }
             }
@@ -145,12 +191,16 @@ impl Scene for Controller {
                     self.state.move_cursor = new;
                 }
             } else if key == KeyCode::Return {
-                self.process_move();
+                self.process_move(self.state.get_selected_move());
             }
         }
     }
 
     fn on_key_up(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Escape && !self.state.entry.is_empty() {
+            self.state.entry.clear();
+            return true;
+        }
         if key == KeyCode::Escape && self.state.play_state.is_human(SelectingMove) {
             self.state.play_state = NEW_TURN_HUMAN;
             return true;
@@ -158,6 +208,16 @@ impl Scene for Controller {
         false
     }
 
+    fn on_keyboard_entry(&mut self, input: char) {
+        if self.state.roll.is_some()
+            && (self.state.play_state.is_human(SelectingPiece)
+                || self.state.play_state.is_human(SelectingMove))
+        {
+            let options = self.state.entry_options();
+            self.state.entry.push(input, &options);
+        }
+    }
+
     fn update(&mut self, delta: f64) -> GameResult<()> {
         if self.state.play_state == PlayState::Init {
             self.start_new_turn(Player::Human, false);
@@ -171,11 +231,15 @@ impl Scene for Controller {
                     self.roll(Player::Computer);
                     self.state.next_move_time = ANIMATION_DURATION;
                 } else if self.state.play_state.is_computer(SelectingPiece) {
-                    self.ai_update();
-                    self.state.next_move_time = ANIMATION_DURATION;
-                    self.state.play_state = PlayState::Playing(Computer(SelectingMove))
+                    if self.ai_update() {
+                        self.state.next_move_time = ANIMATION_DURATION;
+                        self.state.play_state = PlayState::Playing(Computer(SelectingMove))
+                    } else {
+                        //Carry on searching next frame
+                        self.state.next_move_time = 0.;
+                    }
                 } else if self.state.play_state.is_computer(SelectingMove) {
-                    self.process_move();
+                    self.process_move(self.state.get_selected_move());
                 }
             }
         }
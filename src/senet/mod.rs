@@ -1,4 +1,8 @@
-use crate::boards::cursor::Cursor;
+use crate::boards::chessboard::ChessBoard;
+use crate::boards::cursor::{movable, Cursor};
+use crate::boards::move_entry::{EntryAction, EntryOptions, MoveEntry};
+use crate::senet::ai::Position;
+use crate::system::mcts::Search;
 use crate::system::{PlayState, Player};
 use std::collections::HashMap;
 
@@ -69,9 +73,34 @@ struct State {
     next_move_time: f64,
     last_human_cursor_pos: usize,
     valid_moves: HashMap<usize, Vec<Move>>,
+    coords: ChessBoard,
+    entry: MoveEntry,
+    //Computer's move search, run a slice at a time
+    search: Option<Search<Position>>,
 }
 
 impl State {
+    //Squares and moves the player can type, e.g. c1, c1d1 or c1-d1
+    fn entry_options(&self) -> EntryOptions<Move> {
+        let name = |idx: usize| self.coords.idx_to_coord(idx).unwrap_or_default();
+        let mut options = vec![];
+        for origin in movable(&self.valid_moves) {
+            options.push((name(origin), EntryAction::SelectPiece(origin)));
+            for mov in &self.valid_moves[&origin] {
+                let (from, to) = (name(mov.origin), name(mov.dest));
+                options.push((
+                    format!("{}{}", from, to),
+                    EntryAction::PlayMove(mov.clone()),
+                ));
+                options.push((
+                    format!("{}-{}", from, to),
+                    EntryAction::PlayMove(mov.clone()),
+                ));
+            }
+        }
+        options
+    }
+
     //Safe, will return empty vec for empty/other players squares
     fn get_moves_for_selected_piece(&self) -> Vec<Move> {
         self.valid_moves
@@ -1,4 +1,6 @@
+use crate::boards::cursor::movable;
 use crate::boards::idx_coord::BoardCoord;
+use crate::boards::move_entry::render_coord_labels;
 use crate::boards::{board_cols, board_rows};
 use crate::constants::colors::{
     BROWN, CREAM, LIGHT_BLUE, LIGHT_GRAY, PIECE_COMPUTER, PIECE_HUMAN, RED, WHITE,
@@ -7,7 +9,7 @@ use crate::constants::Direction;
 use crate::senet::rules::{HOUSE_BEAUTY, HOUSE_HAPPINESS, HOUSE_REBIRTH, HOUSE_WATER};
 use crate::senet::{Move, Square, State};
 use crate::system::letter_mesh::make_letter_mesh;
-use crate::system::math::{Offset, OffsetTuple, Point, pt};
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
 use crate::system::mesh_helper::MeshHelper;
 use crate::system::TurnState::{SelectingMove, SelectingPiece};
 use ggez::graphics::DrawMode;
@@ -47,6 +49,7 @@ pub(super) fn render(
 
     mesh_helper.draw_mesh(ctx, grid.as_ref(), board_start);
     mesh_helper.draw_mesh(ctx, rect.as_ref(), board_start);
+    render_coord_labels(ctx, mesh_helper, &state.coords, board_start, cell_size);
 
     draw_cell_text(
         ctx,
@@ -111,6 +114,13 @@ pub(super) fn render(
     });
 
     if state.play_state.is_either(SelectingPiece) && state.roll.is_some() {
+        state.cursor.render_movable(
+            ctx,
+            mesh_helper,
+            board_start,
+            cell_size,
+            &movable(&state.valid_moves),
+        )?;
         state
             .cursor
             .render(ctx, mesh_helper, board_start, cell_size)?;
@@ -149,6 +159,14 @@ pub(super) fn render(
         mesh_helper.draw_white_text(ctx, msg, msg_start, 20., false);
     }
 
+    if state.roll.is_some()
+        && (state.play_state.is_human(SelectingPiece) || state.play_state.is_human(SelectingMove))
+    {
+        state
+            .entry
+            .render(ctx, mesh_helper, pt(8., mesh_helper.height - 72.));
+    }
+
     Ok(())
 }
 
@@ -1,5 +1,6 @@
-use crate::senet::{Board, Move, Square};
+use crate::senet::{Board, Move, Square, MAX_STICKS_UP};
 use crate::system::Player;
+use rand::Rng;
 
 pub(super) const REPEAT_TURN_ROLL: [usize; 3] = [1, 4, 5];
 pub(super) const HOUSE_HAPPINESS: usize = 27;
@@ -9,7 +10,6 @@ pub(super) const HOUSE_WATER: usize = 26;
 pub(super) const HOME: usize = 29;
 
 pub(super) fn calc_valid_moves(board: &Board, roll: usize, player: Player) -> Vec<Move> {
-    debug_log_start!("Calculating for {:?} with {}", player, roll);
     board
         .iter()
         .enumerate()
@@ -22,39 +22,69 @@ pub(super) fn calc_valid_moves(board: &Board, roll: usize, player: Player) -> Ve
         })
         .filter_map(|piece_idx| {
             let target = (piece_idx + roll).min(HOME);
-            debug_log_start!("Calculating for {} to {}", piece_idx, target);
-            let result = if can_jump(board, piece_idx, target, player) {
+            if can_jump(board, piece_idx, target, player) {
                 if piece_idx < HOUSE_BEAUTY && target > HOUSE_BEAUTY {
                     if board[HOUSE_BEAUTY] == Square::Empty
                         || board[HOUSE_BEAUTY] == player.opposite().into()
                     {
-                        debug_log!("Beauty blocking");
                         Some(Move::new(
                             piece_idx,
                             HOUSE_BEAUTY,
                             board[HOUSE_BEAUTY] == player.opposite().into(),
                         ))
                     } else {
-                        debug_log!("Beauty blocking but occupied");
                         None
                     }
                 } else if board[target] == player.opposite().into() {
-                    debug_log!("Exchange found");
                     Some(Move::new(piece_idx, target, true))
                 } else {
-                    debug_log!("Jump found");
                     Some(Move::new(piece_idx, target, false))
                 }
             } else {
-                debug_log!("No move");
                 None
-            };
-            debug_log_end!();
-            result
+            }
         })
         .collect()
 }
 
+//Number of sticks showing their light side, none counts as five
+pub(super) fn roll_sticks<R: Rng>(rng: &mut R) -> usize {
+    let sticks = rng.gen_range(0..MAX_STICKS_UP + 1);
+    if sticks == 0 {
+        5
+    } else {
+        sticks
+    }
+}
+
+//Exchanges swap the pieces, pieces reaching home leave the board and the house of water sends
+//the piece back to the house of rebirth (or the nearest empty square before it)
+pub(super) fn apply_move(board: &mut Board, mov: &Move) {
+    let piece = board[mov.origin];
+    board[mov.origin] = board[mov.dest];
+    if mov.dest == HOUSE_WATER {
+        board[mov.dest] = Square::Empty;
+        if let Some(idx) = (0..=HOUSE_REBIRTH)
+            .rev()
+            .find(|idx| board[*idx] == Square::Empty)
+        {
+            board[idx] = piece;
+        }
+    } else if mov.dest == HOME {
+        board[mov.dest] = Square::Empty;
+    } else {
+        board[mov.dest] = piece;
+    }
+}
+
+//The first player to bear off all their pieces wins
+pub(super) fn winner(board: &Board) -> Option<Player> {
+    [Player::Human, Player::Computer]
+        .iter()
+        .find(|player| !board.contains(&(**player).into()))
+        .copied()
+}
+
 fn can_jump(board: &Board, current: usize, target: usize, player: Player) -> bool {
     if board[target] != player.into() {
         let mut consecutive_count = 0;
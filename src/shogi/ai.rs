@@ -0,0 +1,363 @@
+use crate::shogi::game_types::GameType;
+use crate::shogi::rules::{apply_move, has_lost_royals, legal_moves};
+use crate::shogi::{Move, Piece, PieceKind, Position, Square};
+use crate::system::transposition_table::{Bound, TranspositionTable};
+use crate::system::zobrist::Zobrist;
+use crate::system::Player;
+use rand::seq::SliceRandom;
+use rand::thread_rng;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
+
+pub(super) const MATE: i32 = 1_000_000;
+
+pub(super) type Table = TranspositionTable<(i32, Option<Move>)>;
+
+//When to give up searching, the best move from the last completed depth is used
+#[derive(Debug, Default)]
+pub(super) struct Limits<'a> {
+    pub(super) deadline: Option<Instant>,
+    pub(super) stop: Option<&'a AtomicBool>,
+}
+
+impl Limits<'_> {
+    pub(super) fn reached(&self) -> bool {
+        self.deadline
+            .map(|deadline| Instant::now() >= deadline)
+            .unwrap_or(false)
+            || self
+                .stop
+                .map(|stop| stop.load(Ordering::Relaxed))
+                .unwrap_or(false)
+    }
+}
+
+//Result of each completed depth, score is from the point of view of the player to move
+#[derive(Debug, Copy, Clone)]
+pub(super) struct Iteration {
+    pub(super) depth: usize,
+    pub(super) score: i32,
+    pub(super) best: Option<Move>,
+}
+
+//Promoted pieces are valued as the kind they move as
+fn kind_value(kind: PieceKind) -> i32 {
+    match kind {
+        PieceKind::King => 0,
+        PieceKind::Rook => 1000,
+        PieceKind::DragonKing => 1300,
+        PieceKind::Bishop => 850,
+        PieceKind::DragonHorse => 1150,
+        PieceKind::Gold => 600,
+        PieceKind::Silver => 550,
+        PieceKind::Knight => 450,
+        PieceKind::Lance => 400,
+        PieceKind::Pawn => 100,
+        PieceKind::Copper => 350,
+        PieceKind::FerociousLeopard => 450,
+        PieceKind::BlindTiger => 500,
+        PieceKind::Kirin => 500,
+        PieceKind::Phoenix => 550,
+        PieceKind::DrunkElephant => 550,
+        PieceKind::ReverseChariot => 550,
+        PieceKind::SideMover => 650,
+        PieceKind::VerticalMover => 700,
+        PieceKind::Lion => 3000,
+        PieceKind::Queen => 2200,
+        PieceKind::GoBetween => 150,
+        PieceKind::FlyingStag => 950,
+        //A second royal, the game isn't lost until both are taken
+        PieceKind::CrownPrince => 2000,
+        PieceKind::WhiteHorse => 1100,
+        PieceKind::Whale => 1000,
+        PieceKind::FreeBoar => 1200,
+        PieceKind::FlyingOx => 1250,
+        PieceKind::HornedFalcon => 1900,
+        PieceKind::SoaringEagle => 2000,
+    }
+}
+
+fn piece_value(game_type: &GameType, piece: &Piece) -> i32 {
+    kind_value(game_type.moves_as(piece))
+}
+
+//Pieces in hand are slightly more valuable as they can be dropped anywhere
+fn hand_value(kind: PieceKind) -> i32 {
+    let value = kind_value(kind);
+    value + value / 10
+}
+
+//Material balance from the point of view of the player to move
+fn evaluate(game_type: &GameType, position: &Position) -> i32 {
+    let mut score = 0;
+    for square in &position.board {
+        match square {
+            Square::Empty => {}
+            Square::Human(piece) => score += piece_value(game_type, piece),
+            Square::Computer(piece) => score -= piece_value(game_type, piece),
+        }
+    }
+    for kind in &position.hand(Player::Human).pieces {
+        score += hand_value(*kind);
+    }
+    for kind in &position.hand(Player::Computer).pieces {
+        score -= hand_value(*kind);
+    }
+    match position.to_move {
+        Player::Human => score,
+        Player::Computer => -score,
+    }
+}
+
+fn order_moves(game_type: &GameType, position: &Position, moves: &mut [Move], best: Option<Move>) {
+    //Shuffled first so equal moves aren't always played in the same order
+    moves.shuffle(&mut thread_rng());
+    moves.sort_by_key(|mov| {
+        if Some(*mov) == best {
+            return i32::MIN;
+        }
+        let value = |idx: usize| {
+            position.board[idx]
+                .get_piece()
+                .filter(|_| Some(idx) != mov.from())
+                .map(|piece| piece_value(game_type, &piece))
+                .unwrap_or(0)
+        };
+        let capture = match mov {
+            Move::Step { to, .. } => value(*to),
+            Move::Drop { .. } => 0,
+            Move::Double { via, to, .. } => value(*via) + value(*to),
+        };
+        let promotion = match mov {
+            Move::Step { promote: true, .. } => 1,
+            _ => 0,
+        };
+        -(capture * 2 + promotion)
+    });
+}
+
+struct Search<'a> {
+    game_type: &'a GameType,
+    zobrist: &'a Zobrist,
+    table: &'a mut Table,
+    limits: Limits<'a>,
+    aborted: bool,
+}
+
+impl Search<'_> {
+    fn negamax(
+        &mut self,
+        position: &Position,
+        depth: usize,
+        mut alpha: i32,
+        beta: i32,
+        ply: i32,
+    ) -> (i32, Option<Move>) {
+        if has_lost_royals(self.game_type, &position.board, position.to_move) {
+            return (-MATE + ply, None);
+        }
+        if depth == 0 {
+            return (evaluate(self.game_type, position), None);
+        }
+        if self.aborted || self.limits.reached() {
+            self.aborted = true;
+            return (0, None);
+        }
+
+        let hash = position.hash(self.zobrist);
+        let mut best_move = None;
+        if let Some(entry) = self.table.get(hash) {
+            best_move = entry.value.1;
+            if entry.depth >= depth {
+                let (score, mov) = entry.value;
+                match entry.bound {
+                    Bound::Exact => return (score, mov),
+                    Bound::Lower if score >= beta => return (score, mov),
+                    Bound::Upper if score <= alpha => return (score, mov),
+                    _ => {}
+                }
+            }
+        }
+
+        let mut moves = legal_moves(self.game_type, position);
+        if moves.is_empty() {
+            //No moves is a loss in shogi, whether in check or not
+            return (-MATE + ply, None);
+        }
+        order_moves(self.game_type, position, &mut moves, best_move);
+
+        let original_alpha = alpha;
+        let mut best_score = i32::MIN;
+        for mov in moves {
+            let mut next = position.clone();
+            apply_move(self.game_type, &mut next, &mov);
+            let score = -self.negamax(&next, depth - 1, -beta, -alpha, ply + 1).0;
+            if self.aborted {
+                return (0, None);
+            }
+            if score > best_score {
+                best_score = score;
+                best_move = Some(mov);
+            }
+            alpha = alpha.max(score);
+            if alpha >= beta {
+                break;
+            }
+        }
+
+        let bound = if best_score <= original_alpha {
+            Bound::Upper
+        } else if best_score >= beta {
+            Bound::Lower
+        } else {
+            Bound::Exact
+        };
+        self.table
+            .insert(hash, depth, bound, (best_score, best_move));
+        (best_score, best_move)
+    }
+}
+
+//Iterative deepening so the table has good moves to try first at each depth
+//on_iteration is called after every completed depth
+pub(super) fn search(
+    game_type: &GameType,
+    zobrist: &Zobrist,
+    table: &mut Table,
+    position: &Position,
+    max_depth: usize,
+    limits: Limits,
+    on_iteration: &mut dyn FnMut(&Iteration),
+) -> Option<Move> {
+    table.new_search();
+    let mut search = Search {
+        game_type,
+        zobrist,
+        table,
+        limits,
+        aborted: false,
+    };
+    let mut best = None;
+    for depth in 1..=max_depth {
+        let (score, mov) = search.negamax(position, depth, -MATE - 1, MATE + 1, 0);
+        if search.aborted {
+            break;
+        }
+        best = mov.or(best);
+        on_iteration(&Iteration {
+            depth,
+            score,
+            best: mov,
+        });
+        if score.abs() >= MATE - depth as i32 {
+            break;
+        }
+    }
+    //Always play something if there was no time to finish the first depth
+    best.or_else(|| legal_moves(game_type, position).first().copied())
+}
+
+pub(super) fn choose_move(
+    game_type: &GameType,
+    zobrist: &Zobrist,
+    table: &mut Table,
+    position: &Position,
+    depth: usize,
+) -> Option<Move> {
+    search(
+        game_type,
+        zobrist,
+        table,
+        position,
+        depth,
+        Limits::default(),
+        &mut |iteration| {
+            debug_log!(
+                "Depth {}: {:?} scores {}",
+                iteration.depth,
+                iteration.best,
+                iteration.score
+            )
+        },
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::shogi::make_zobrist;
+    use serial_test::serial;
+
+    #[test]
+    #[serial(board_size)]
+    fn test_finds_mate_in_one() {
+        set_board_size((5, 5));
+        let h = |kind| Square::Human(Piece::new(kind));
+        let c = |kind| Square::Computer(Piece::new(kind));
+        let e = Square::Empty;
+        #[rustfmt::skip]
+        let position = Position::new(vec![
+            e, e, e, c(PieceKind::Pawn), c(PieceKind::King),
+            e, e, e, h(PieceKind::Gold), e,
+            e, e, e, h(PieceKind::Silver), e,
+            e, e, e, e, e,
+            h(PieceKind::King), e, e, e, e,
+        ]);
+        let mut table = TranspositionTable::new(1024);
+        let mov = choose_move(&GameType::Mini, &make_zobrist(25), &mut table, &position, 2);
+        assert_eq!(
+            mov,
+            Some(Move::Step {
+                from: 8,
+                to: 9,
+                promote: false
+            })
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_takes_free_piece() {
+        set_board_size((5, 5));
+        let h = |kind| Square::Human(Piece::new(kind));
+        let c = |kind| Square::Computer(Piece::new(kind));
+        let e = Square::Empty;
+        #[rustfmt::skip]
+        let mut position = Position::new(vec![
+            c(PieceKind::King), e, e, e, e,
+            e, e, e, e, e,
+            e, e, c(PieceKind::Rook), e, h(PieceKind::Bishop),
+            e, e, e, e, e,
+            e, e, e, e, h(PieceKind::King),
+        ]);
+        position.to_move = Player::Computer;
+        let mut table = TranspositionTable::new(1024);
+        let mov = choose_move(&GameType::Mini, &make_zobrist(25), &mut table, &position, 2);
+        assert_eq!(mov.map(|mov| mov.to()), Some(14));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_stopped_search_still_moves() {
+        set_board_size((5, 5));
+        let position = Position::new(GameType::Mini.get_init_board());
+        let stop = AtomicBool::new(true);
+        let mut table = TranspositionTable::new(1024);
+        let mut iterations = 0;
+        let mov = search(
+            &GameType::Mini,
+            &make_zobrist(25),
+            &mut table,
+            &position,
+            4,
+            Limits {
+                deadline: None,
+                stop: Some(&stop),
+            },
+            &mut |_| iterations += 1,
+        );
+        assert_eq!(iterations, 0);
+        assert!(mov.is_some());
+    }
+}
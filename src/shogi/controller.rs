@@ -0,0 +1,491 @@
+use crate::boards::cursor::Cursor;
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::set_board_size;
+use crate::boards::viewport::Viewport;
+use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
+use crate::shogi::ai::choose_move;
+use crate::shogi::game_types::get_shogi_game_type;
+use crate::shogi::records::{parse_record, to_csa, to_kif};
+use crate::shogi::renderer::{render, MAX_VISIBLE_CELLS};
+use crate::shogi::rules::{apply_move, has_lost_royals, impasse_result, is_in_check, legal_moves};
+use crate::shogi::{make_zobrist, Move, PastMove, Piece, Position, Selection, State};
+use crate::system::find_nearest::find_nearest;
+use crate::system::ggez_ext::keycode_to_direction;
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::transposition_table::TranspositionTable;
+use crate::system::zobrist::PositionHistory;
+use crate::system::PlayState::{ComputerWin, HumanWin};
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player, Scene, Turn, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
+use ggez::event::KeyCode;
+use ggez::{Context, GameResult};
+use std::fs;
+
+pub(super) const HAND_KEY: KeyCode = KeyCode::H;
+pub(super) const SAVE_KEY: KeyCode = KeyCode::S;
+pub(super) const DECLARE_KEY: KeyCode = KeyCode::D;
+//Sennichite, the same position four times
+const REPETITION_LIMIT: usize = 4;
+const AI_TABLE_SIZE: usize = 1 << 16;
+
+pub(crate) struct Controller {
+    state: State,
+}
+
+impl Controller {
+    pub fn new(game: &str) -> Self {
+        let game_type = get_shogi_game_type(game);
+        set_board_size(game_type.get_board_size());
+
+        debug_log!("Game board set to {:?}", game_type.get_board_size());
+
+        let zobrist = make_zobrist(game_type.get_board_cell_count());
+        let position = Position::new(game_type.get_init_board());
+        let mut position_history = PositionHistory::default();
+        position_history.push(position.hash(&zobrist));
+
+        Controller {
+            state: State {
+                play_state: PlayState::Init,
+                cursor: Cursor::new(),
+                viewport: Viewport::new(MAX_VISIBLE_CELLS),
+                selection: Selection::Board,
+                move_cursor: 0,
+                promotion_choice: None,
+                route_choice: vec![],
+                route_cursor: 0,
+                legal_moves: vec![],
+                move_history: vec![],
+                position,
+                game_type,
+                zobrist,
+                position_history,
+                check_history: vec![false],
+                ai_table: TranspositionTable::new(AI_TABLE_SIZE),
+                next_move_time: AI_MOVE_DELAY,
+                last_human_cursor_pos: 0,
+                computer_move: None,
+                message: None,
+            },
+        }
+    }
+
+    //Starts from the end of a KIF or CSA record
+    pub fn from_record(game: &str, record: &str) -> Result<Self, String> {
+        let mut controller = Controller::new(game);
+        let moves = parse_record(&controller.state.game_type, record)?;
+        debug_log!("Loaded {} moves", moves.len());
+        for mov in moves {
+            if !matches!(
+                controller.state.play_state,
+                PlayState::Init | PlayState::Playing(_)
+            ) {
+                break;
+            }
+            controller.process_move(mov);
+        }
+        Ok(controller)
+    }
+}
+
+impl Controller {
+    fn start_new_turn(&mut self) {
+        let player = self.state.position.to_move;
+        self.state.legal_moves = legal_moves(&self.state.game_type, &self.state.position);
+        self.state.move_cursor = 0;
+        self.state.selection = Selection::Board;
+        self.state.promotion_choice = None;
+        self.state.route_choice.clear();
+        self.state.next_move_time = AI_MOVE_DELAY;
+        let board = &self.state.position.board;
+        if has_lost_royals(&self.state.game_type, board, player) {
+            debug_log!("{:?} has no royal pieces left and loses", player);
+            self.state.play_state = Controller::win_for(player.opposite());
+            return;
+        }
+        if self.state.legal_moves.is_empty() {
+            debug_log!("{:?} has no moves and loses", player);
+            self.state.play_state = match player {
+                Player::Human => ComputerWin,
+                Player::Computer => HumanWin,
+            };
+            return;
+        }
+        self.state.play_state = match player {
+            Player::Human => NEW_TURN_HUMAN,
+            Player::Computer => NEW_TURN_COMPUTER,
+        };
+    }
+
+    fn process_move(&mut self, mov: Move) {
+        let player = self.state.position.to_move;
+        let piece = match mov {
+            Move::Step { from, .. } | Move::Double { from, .. } => self.state.position.board[from]
+                .get_piece()
+                .expect("Attempted to move from empty square"),
+            Move::Drop { kind, .. } => Piece::new(kind),
+        };
+        let game_type = &self.state.game_type;
+        let captured = apply_move(game_type, &mut self.state.position, &mov);
+        self.state.move_history.push(PastMove {
+            player,
+            mov,
+            piece,
+            capture: !captured.is_empty(),
+        });
+
+        let hash = self.state.position.hash(&self.state.zobrist);
+        self.state.position_history.push(hash);
+        self.state.check_history.push(is_in_check(
+            &self.state.game_type,
+            &self.state.position.board,
+            self.state.position.to_move,
+        ));
+
+        if self.state.position_history.is_repeated(REPETITION_LIMIT) {
+            self.state.play_state = self.repetition_result(hash);
+            debug_log!("Repetition: {:?}", self.state.play_state);
+            return;
+        }
+
+        self.start_new_turn();
+    }
+
+    //Ends the game on points if the player to move is allowed to declare an impasse
+    fn declare_impasse(&mut self) -> bool {
+        match impasse_result(&self.state.game_type, &self.state.position) {
+            Some(result) => {
+                debug_log!("Impasse declared: {:?}", result);
+                self.state.play_state = result;
+                true
+            }
+            None => false,
+        }
+    }
+
+    //Perpetual check loses for the checking player, otherwise it depends on the variant
+    fn repetition_result(&self, hash: u64) -> PlayState {
+        let first = self
+            .state
+            .position_history
+            .first_occurrence(hash)
+            .expect("Repeated position not in history");
+        let last = self.state.check_history.len() - 1;
+        //The player to move alternates, so every other position is the same player
+        let to_move = self.state.position.to_move;
+        let to_move_always_in_check = (first..=last)
+            .rev()
+            .step_by(2)
+            .all(|idx| self.state.check_history[idx]);
+        let other_always_in_check = (first..last)
+            .rev()
+            .step_by(2)
+            .all(|idx| self.state.check_history[idx]);
+        if to_move_always_in_check {
+            Controller::win_for(to_move)
+        } else if other_always_in_check {
+            Controller::win_for(to_move.opposite())
+        } else {
+            self.state.game_type.repetition_result(to_move.opposite())
+        }
+    }
+
+    fn win_for(player: Player) -> PlayState {
+        match player {
+            Player::Human => HumanWin,
+            Player::Computer => ComputerWin,
+        }
+    }
+
+    fn select_piece(&mut self) {
+        if !self.state.get_moves_for_selection().is_empty() {
+            self.state.move_cursor = 0;
+            self.state.play_state = PlayState::Playing(Turn::Human(SelectingMove));
+        }
+    }
+
+    fn select_move(&mut self) {
+        let target = self.state.get_selected_target();
+        let options: Vec<Move> = self
+            .state
+            .get_moves_for_selection()
+            .into_iter()
+            .filter(|mov| mov.to() == target.to())
+            .collect();
+        if options.iter().any(|mov| mov.via().is_some()) && options.len() > 1 {
+            self.state.route_choice = options;
+            self.state.route_cursor = 0;
+        } else if options.len() > 1 {
+            let promote = *options
+                .iter()
+                .find(|mov| matches!(mov, Move::Step { promote: true, .. }))
+                .unwrap();
+            let stay = *options
+                .iter()
+                .find(|mov| matches!(mov, Move::Step { promote: false, .. }))
+                .unwrap();
+            self.state.promotion_choice = Some((promote, stay));
+        } else {
+            self.process_move(target);
+        }
+    }
+
+    fn toggle_hand(&mut self) {
+        self.state.selection = match self.state.selection {
+            Selection::Board if !self.state.position.hand(Player::Human).is_empty() => {
+                Selection::Hand(0)
+            }
+            _ => Selection::Board,
+        };
+    }
+
+    fn move_hand_cursor(&mut self, key: KeyCode) {
+        if let Selection::Hand(idx) = self.state.selection {
+            let count = self.state.position.hand(Player::Human).kinds().len();
+            self.state.selection = match key {
+                KeyCode::Left | KeyCode::Up => Selection::Hand((idx + count - 1) % count),
+                KeyCode::Right | KeyCode::Down => Selection::Hand((idx + 1) % count),
+                _ => Selection::Hand(idx),
+            };
+        }
+    }
+
+    //Writes the game so far as KIF and CSA to the working directory
+    fn save_records(&mut self) {
+        if !self.state.game_type.has_records() {
+            self.state.message = Some(String::from("Records aren't supported for this variant"));
+            return;
+        }
+        let name = self.state.game_type.name();
+        let kif = format!("{}.kif", name);
+        let csa = format!("{}.csa", name);
+        let records = to_kif(&self.state.game_type, &self.state.move_history).and_then(|kif| {
+            to_csa(&self.state.game_type, &self.state.move_history).map(|csa| (kif, csa))
+        });
+        let result = match records {
+            Ok((kif_text, csa_text)) => fs::write(&kif, kif_text)
+                .and_then(|_| fs::write(&csa, csa_text))
+                .map_err(|err| err.to_string()),
+            Err(err) => Err(err),
+        };
+        self.state.message = Some(match result {
+            Ok(_) => format!("Saved {} and {}", kif, csa),
+            Err(err) => format!("Could not save: {}", err),
+        });
+    }
+
+    fn process_computer_turn(&mut self, delta: f64) {
+        if self.state.play_state.is_computer(SelectingPiece) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                //Only declares when it would win
+                let result = impasse_result(&self.state.game_type, &self.state.position);
+                if result == Some(ComputerWin) && self.declare_impasse() {
+                    return;
+                }
+                self.state.last_human_cursor_pos = self.state.cursor.idx;
+                let depth = self.state.game_type.ai_depth();
+                let mov = choose_move(
+                    &self.state.game_type,
+                    &self.state.zobrist,
+                    &mut self.state.ai_table,
+                    &self.state.position,
+                    depth,
+                )
+                .unwrap_or(self.state.legal_moves[0]);
+                debug_log!("Computer will play {:?}", mov);
+                self.state.viewport.follow(mov.to());
+                self.state.computer_move = Some(mov);
+                self.state.next_move_time = ANIMATION_DURATION;
+                self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove));
+            }
+        } else if self.state.play_state.is_computer(SelectingMove) {
+            self.state.next_move_time -= delta;
+            if self.state.next_move_time < 0. {
+                let mov = self
+                    .state
+                    .computer_move
+                    .take()
+                    .expect("No computer move chosen");
+                self.state.cursor.idx = self.state.last_human_cursor_pos;
+                self.process_move(mov);
+            }
+        }
+    }
+}
+
+impl Scene for Controller {
+    fn on_key_down(&mut self, key: KeyCode) {
+        let focus = if self.state.play_state.is_human(SelectingMove) {
+            self.state.get_selected_target().to()
+        } else {
+            self.state.cursor.idx
+        };
+        if self.state.viewport.handle_input(key, focus) {
+            return;
+        }
+        if !self.state.route_choice.is_empty() {
+            let count = self.state.route_choice.len();
+            match key {
+                KeyCode::Left | KeyCode::Up => {
+                    self.state.route_cursor = (self.state.route_cursor + count - 1) % count
+                }
+                KeyCode::Right | KeyCode::Down => {
+                    self.state.route_cursor = (self.state.route_cursor + 1) % count
+                }
+                KeyCode::Return => {
+                    let mov = self.state.route_choice[self.state.route_cursor];
+                    self.process_move(mov);
+                }
+                _ => {}
+            }
+            return;
+        }
+        if let Some((promote, stay)) = self.state.promotion_choice {
+            match key {
+                KeyCode::Y | KeyCode::Return => self.process_move(promote),
+                KeyCode::N => self.process_move(stay),
+                _ => {}
+            }
+            return;
+        }
+        if key == SAVE_KEY {
+            self.save_records();
+            return;
+        }
+        if self.state.play_state.is_human(SelectingPiece) {
+            if key == DECLARE_KEY && self.state.game_type.impasse_points().is_some() {
+                if !self.declare_impasse() {
+                    self.state.message = Some(String::from(
+                        "Both kings must be in the enemy camp, and yours not in check, to declare",
+                    ));
+                }
+            } else if key == HAND_KEY {
+                self.toggle_hand();
+            } else if key == KeyCode::Return {
+                self.select_piece();
+            } else if self.state.selection == Selection::Board {
+                self.state.cursor.handle_input(key);
+                self.state.viewport.follow(self.state.cursor.idx);
+            } else {
+                self.move_hand_cursor(key);
+            }
+        } else if self.state.play_state.is_human(SelectingMove) {
+            match key {
+                KeyCode::Up | KeyCode::Left | KeyCode::Down | KeyCode::Right => {
+                    let dir = keycode_to_direction(key).unwrap();
+                    let nearest = find_nearest(
+                        &self.state.get_targets_for_selection(),
+                        self.state.move_cursor,
+                        dir,
+                        &|mov| BoardCoord::from(mov.to()),
+                    );
+                    if let Some(nearest) = nearest {
+                        self.state.move_cursor = nearest;
+                        let to = self.state.get_selected_target().to();
+                        self.state.viewport.follow(to);
+                    }
+                }
+                KeyCode::Return => self.select_move(),
+                _ => {}
+            }
+        }
+    }
+
+    fn on_key_up(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Escape {
+            if !self.state.route_choice.is_empty() {
+                self.state.route_choice.clear();
+                return true;
+            }
+            if self.state.promotion_choice.is_some() {
+                self.state.promotion_choice = None;
+                return true;
+            }
+            if self.state.play_state.is_human(SelectingMove) {
+                self.state.play_state = NEW_TURN_HUMAN;
+                return true;
+            }
+        }
+        false
+    }
+
+    fn update(&mut self, delta: f64) -> GameResult<()> {
+        if self.state.play_state == PlayState::Init {
+            self.start_new_turn();
+        }
+        self.process_computer_turn(delta);
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut Context, mesh_helper: &mut MeshHelper) -> GameResult<()> {
+        render(ctx, mesh_helper, &self.state)
+    }
+
+    fn play_state(&self) -> PlayState {
+        self.state.play_state
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::constants::games;
+    use crate::shogi::{PieceKind, Square};
+    use serial_test::serial;
+
+    fn square(player: Player, kind: PieceKind) -> Square {
+        Square::new(player, Piece::new(kind))
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_mate_with_both_kings_entered() {
+        let mut controller = Controller::new(games::SHOGI_STANDARD);
+        let mut board = vec![Square::Empty; 81];
+        board[BoardCoord(0, 0).idx()] = square(Player::Human, PieceKind::King);
+        board[BoardCoord(0, 5).idx()] = square(Player::Human, PieceKind::Rook);
+        board[BoardCoord(7, 6).idx()] = square(Player::Human, PieceKind::Gold);
+        board[BoardCoord(8, 6).idx()] = square(Player::Human, PieceKind::Gold);
+        board[BoardCoord(8, 8).idx()] = square(Player::Computer, PieceKind::King);
+        let mut position = Position::new(board);
+        //None of these can be dropped on the last row to block
+        for _ in 0..18 {
+            position.hand_mut(Player::Computer).add(PieceKind::Pawn);
+        }
+        for _ in 0..4 {
+            position.hand_mut(Player::Computer).add(PieceKind::Lance);
+            position.hand_mut(Player::Computer).add(PieceKind::Knight);
+        }
+        controller.state.position = position;
+        controller.start_new_turn();
+
+        //The rook mates along the last row, on points the computer would win
+        controller.process_move(Move::Step {
+            from: BoardCoord(0, 5).idx(),
+            to: BoardCoord(0, 8).idx(),
+            promote: false,
+        });
+        assert_eq!(controller.state.play_state, HumanWin);
+        assert!(controller.state.legal_moves.is_empty());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_declare_impasse() {
+        let mut controller = Controller::new(games::SHOGI_STANDARD);
+        let mut board = vec![Square::Empty; 81];
+        board[BoardCoord(0, 1).idx()] = square(Player::Human, PieceKind::King);
+        board[BoardCoord(8, 8).idx()] = square(Player::Computer, PieceKind::King);
+        let mut position = Position::new(board);
+        for _ in 0..5 {
+            position.hand_mut(Player::Human).add(PieceKind::Rook);
+        }
+        controller.state.position = position;
+        controller.start_new_turn();
+        assert_eq!(controller.state.play_state, NEW_TURN_HUMAN);
+
+        controller.on_key_down(DECLARE_KEY);
+        assert_eq!(controller.state.play_state, HumanWin);
+    }
+}
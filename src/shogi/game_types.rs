@@ -0,0 +1,249 @@
+use crate::constants::games::*;
+use crate::shogi::{Board, Piece, PieceKind};
+use crate::system::{PlayState, Player};
+
+pub(super) fn get_shogi_game_type(game: &str) -> GameType {
+    match game {
+        SHOGI_STANDARD => GameType::Standard,
+        SHOGI_MEDIUM => GameType::Chu,
+        SHOGI_MINI => GameType::Mini,
+        _ => panic!("Unsupported game type: {}", game),
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) enum GameType {
+    Standard,
+    Mini,
+    Chu,
+}
+
+impl GameType {
+    pub(super) fn name(&self) -> &'static str {
+        match self {
+            GameType::Standard => SHOGI_STANDARD,
+            GameType::Mini => SHOGI_MINI,
+            GameType::Chu => SHOGI_MEDIUM,
+        }
+    }
+
+    pub(super) fn get_init_board(&self) -> Board {
+        match self {
+            GameType::Standard => init::SHOGI_STANDARD.clone(),
+            GameType::Mini => init::SHOGI_MINI.clone(),
+            GameType::Chu => init::SHOGI_CHU.clone(),
+        }
+    }
+
+    //(rows, cols)
+    pub(super) fn get_board_size(&self) -> (usize, usize) {
+        match self {
+            GameType::Standard => (9, 9),
+            GameType::Mini => (5, 5),
+            GameType::Chu => (12, 12),
+        }
+    }
+
+    pub(super) fn get_board_cell_count(&self) -> usize {
+        let (rows, cols) = self.get_board_size();
+        rows * cols
+    }
+
+    pub(super) fn promotion_zone_depth(&self) -> usize {
+        match self {
+            GameType::Standard => 3,
+            GameType::Mini => 1,
+            GameType::Chu => 4,
+        }
+    }
+
+    //Captured pieces go to the capturer's hand and can be dropped back on the board
+    pub(super) fn has_drops(&self) -> bool {
+        !matches!(self, GameType::Chu)
+    }
+
+    //Without check the game is won by capturing every royal piece (king and crown prince)
+    pub(super) fn uses_check(&self) -> bool {
+        !matches!(self, GameType::Chu)
+    }
+
+    //KIF and CSA only cover the standard piece set
+    pub(super) fn has_records(&self) -> bool {
+        !matches!(self, GameType::Chu)
+    }
+
+    //In Chu a piece can only promote when entering the zone or capturing in it,
+    //elsewhere any move into, out of or within the zone can promote
+    pub(super) fn promotion_needs_entry_or_capture(&self) -> bool {
+        matches!(self, GameType::Chu)
+    }
+
+    //None if the kind doesn't promote in this variant
+    pub(super) fn promotes_to(&self, kind: PieceKind) -> Option<PieceKind> {
+        match self {
+            GameType::Standard | GameType::Mini => match kind {
+                PieceKind::Rook => Some(PieceKind::DragonKing),
+                PieceKind::Bishop => Some(PieceKind::DragonHorse),
+                PieceKind::Silver | PieceKind::Knight | PieceKind::Lance | PieceKind::Pawn => {
+                    Some(PieceKind::Gold)
+                }
+                _ => None,
+            },
+            GameType::Chu => match kind {
+                PieceKind::Pawn => Some(PieceKind::Gold),
+                PieceKind::GoBetween => Some(PieceKind::DrunkElephant),
+                PieceKind::Copper => Some(PieceKind::SideMover),
+                PieceKind::Silver => Some(PieceKind::VerticalMover),
+                PieceKind::Gold => Some(PieceKind::Rook),
+                PieceKind::FerociousLeopard => Some(PieceKind::Bishop),
+                PieceKind::BlindTiger => Some(PieceKind::FlyingStag),
+                PieceKind::DrunkElephant => Some(PieceKind::CrownPrince),
+                PieceKind::Kirin => Some(PieceKind::Lion),
+                PieceKind::Phoenix => Some(PieceKind::Queen),
+                PieceKind::Lance => Some(PieceKind::WhiteHorse),
+                PieceKind::ReverseChariot => Some(PieceKind::Whale),
+                PieceKind::SideMover => Some(PieceKind::FreeBoar),
+                PieceKind::VerticalMover => Some(PieceKind::FlyingOx),
+                PieceKind::Bishop => Some(PieceKind::DragonHorse),
+                PieceKind::Rook => Some(PieceKind::DragonKing),
+                PieceKind::DragonHorse => Some(PieceKind::HornedFalcon),
+                PieceKind::DragonKing => Some(PieceKind::SoaringEagle),
+                _ => None,
+            },
+        }
+    }
+
+    //The kind whose movement the piece uses
+    pub(super) fn moves_as(&self, piece: &Piece) -> PieceKind {
+        if piece.promoted {
+            self.promotes_to(piece.kind).unwrap_or(piece.kind)
+        } else {
+            piece.kind
+        }
+    }
+
+    //Number of far rows a piece of this kind can't move to or be dropped on
+    //without promoting as it would have no moves afterwards
+    //Chu has no such rule, unpromoted pawns and lances may sit on the last row
+    pub(super) fn dead_rows(&self, kind: PieceKind) -> usize {
+        if matches!(self, GameType::Chu) {
+            return 0;
+        }
+        match kind {
+            PieceKind::Pawn | PieceKind::Lance => 1,
+            PieceKind::Knight => 2,
+            _ => 0,
+        }
+    }
+
+    //The result when a position repeats without perpetual check, mover made the repeating move
+    //In minishogi the first player (the human) loses, in Chu whoever repeats loses
+    pub(super) fn repetition_result(&self, mover: Player) -> PlayState {
+        match self {
+            GameType::Standard => PlayState::Draw,
+            GameType::Mini => PlayState::ComputerWin,
+            GameType::Chu => match mover {
+                Player::Human => PlayState::ComputerWin,
+                Player::Computer => PlayState::HumanWin,
+            },
+        }
+    }
+
+    //Points needed to avoid losing when both kings have entered the enemy camp (jishogi)
+    //Rooks and bishops are worth 5, other pieces except kings 1
+    pub(super) fn impasse_points(&self) -> Option<usize> {
+        match self {
+            GameType::Standard => Some(24),
+            GameType::Mini | GameType::Chu => None,
+        }
+    }
+
+    //How many plies the AI searches
+    pub(super) fn ai_depth(&self) -> usize {
+        match self {
+            GameType::Standard => 3,
+            GameType::Mini => 4,
+            GameType::Chu => 3,
+        }
+    }
+}
+
+mod init {
+    use crate::shogi::PieceKind::Bishop as B;
+    use crate::shogi::PieceKind::BlindTiger as BT;
+    use crate::shogi::PieceKind::Copper as C;
+    use crate::shogi::PieceKind::DragonHorse as DH;
+    use crate::shogi::PieceKind::DragonKing as DK;
+    use crate::shogi::PieceKind::DrunkElephant as DE;
+    use crate::shogi::PieceKind::FerociousLeopard as FL;
+    use crate::shogi::PieceKind::GoBetween as GB;
+    use crate::shogi::PieceKind::Gold as G;
+    use crate::shogi::PieceKind::King as K;
+    use crate::shogi::PieceKind::Kirin as Kr;
+    use crate::shogi::PieceKind::Knight as N;
+    use crate::shogi::PieceKind::Lance as L;
+    use crate::shogi::PieceKind::Lion as Ln;
+    use crate::shogi::PieceKind::Pawn as P;
+    use crate::shogi::PieceKind::Phoenix as Ph;
+    use crate::shogi::PieceKind::Queen as FK;
+    use crate::shogi::PieceKind::ReverseChariot as RC;
+    use crate::shogi::PieceKind::Rook as R;
+    use crate::shogi::PieceKind::SideMover as SM;
+    use crate::shogi::PieceKind::Silver as S;
+    use crate::shogi::PieceKind::VerticalMover as VM;
+    use crate::shogi::{Board, Piece, PieceKind, Square};
+
+    const E: Square = Square::Empty;
+
+    const fn h(kind: PieceKind) -> Square {
+        Square::Human(Piece::new(kind))
+    }
+
+    const fn c(kind: PieceKind) -> Square {
+        Square::Computer(Piece::new(kind))
+    }
+
+    //Half of the Chu setup from the human's side, the computer's half is the same rotated
+    #[rustfmt::skip]
+    const CHU_HUMAN: [Option<PieceKind>; 60] = [
+        None,     None,     None,     Some(GB), None,     None,     None,     None,     Some(GB), None,     None,     None,
+        Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),  Some(P),
+        Some(SM), Some(VM), Some(R),  Some(DH), Some(DK), Some(FK), Some(Ln), Some(DK), Some(DH), Some(R),  Some(VM), Some(SM),
+        Some(RC), None,     Some(B),  None,     Some(BT), Some(Ph), Some(Kr), Some(BT), None,     Some(B),  None,     Some(RC),
+        Some(L),  Some(FL), Some(C),  Some(S),  Some(G),  Some(K),  Some(DE), Some(G),  Some(S),  Some(C),  Some(FL), Some(L),
+    ];
+
+    fn chu_board() -> Board {
+        let mut board = vec![E; 144];
+        for (i, kind) in CHU_HUMAN.iter().enumerate() {
+            if let Some(kind) = kind {
+                board[84 + i] = h(*kind);
+                board[59 - i] = c(*kind);
+            }
+        }
+        board
+    }
+
+    #[rustfmt::skip]
+    lazy_static! {
+        pub(super) static ref SHOGI_STANDARD: Board = vec![
+            c(L), c(N), c(S), c(G), c(K), c(G), c(S), c(N), c(L),
+            E,    c(R), E,    E,    E,    E,    E,    c(B), E,
+            c(P), c(P), c(P), c(P), c(P), c(P), c(P), c(P), c(P),
+            E,    E,    E,    E,    E,    E,    E,    E,    E,
+            E,    E,    E,    E,    E,    E,    E,    E,    E,
+            E,    E,    E,    E,    E,    E,    E,    E,    E,
+            h(P), h(P), h(P), h(P), h(P), h(P), h(P), h(P), h(P),
+            E,    h(B), E,    E,    E,    E,    E,    h(R), E,
+            h(L), h(N), h(S), h(G), h(K), h(G), h(S), h(N), h(L),
+        ];
+        pub(super) static ref SHOGI_MINI: Board = vec![
+            c(R), c(B), c(S), c(G), c(K),
+            E,    E,    E,    E,    c(P),
+            E,    E,    E,    E,    E,
+            h(P), E,    E,    E,    E,
+            h(K), h(G), h(S), h(B), h(R),
+        ];
+        pub(super) static ref SHOGI_CHU: Board = chu_board();
+    }
+}
@@ -0,0 +1,180 @@
+use crate::shogi::ai::Limits;
+use crate::shogi::game_types::GameType;
+use crate::shogi::rules::{apply_move, is_in_check, legal_moves};
+use crate::shogi::{Move, Position};
+
+//Longest mate searched for, in plies
+pub(super) const MAX_MATE_PLIES: usize = 15;
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub(super) enum MateResult {
+    //Attacker and defender moves, ending with the mating move
+    Mate(Vec<Move>),
+    //No mate within MAX_MATE_PLIES where every attacking move is check
+    NoMate,
+    Timeout,
+}
+
+//Tsume search, the attacker has to give check with every move and the defender
+//picks whichever reply delays mate the longest
+struct MateSearch<'a> {
+    game_type: &'a GameType,
+    limits: Limits<'a>,
+    aborted: bool,
+}
+
+impl MateSearch<'_> {
+    fn attack(&mut self, position: &Position, plies: usize) -> Option<Vec<Move>> {
+        if self.aborted || self.limits.reached() {
+            self.aborted = true;
+            return None;
+        }
+        for mov in legal_moves(self.game_type, position) {
+            let mut next = position.clone();
+            apply_move(self.game_type, &mut next, &mov);
+            if !is_in_check(self.game_type, &next.board, next.to_move) {
+                continue;
+            }
+            let replies = legal_moves(self.game_type, &next);
+            if replies.is_empty() {
+                return Some(vec![mov]);
+            }
+            if plies >= 3 {
+                if let Some(line) = self.defend(&next, replies, plies - 1) {
+                    return Some([vec![mov], line].concat());
+                }
+            }
+        }
+        None
+    }
+
+    fn defend(
+        &mut self,
+        position: &Position,
+        replies: Vec<Move>,
+        plies: usize,
+    ) -> Option<Vec<Move>> {
+        let mut longest: Option<Vec<Move>> = None;
+        for reply in replies {
+            let mut next = position.clone();
+            apply_move(self.game_type, &mut next, &reply);
+            let line = self.attack(&next, plies - 1)?;
+            if longest
+                .as_ref()
+                .map(|other| line.len() + 1 > other.len())
+                .unwrap_or(true)
+            {
+                longest = Some([vec![reply], line].concat());
+            }
+        }
+        longest
+    }
+}
+
+//Searches for the shortest mate by the player to move
+pub(super) fn find_mate(game_type: &GameType, position: &Position, limits: Limits) -> MateResult {
+    let mut search = MateSearch {
+        game_type,
+        limits,
+        aborted: false,
+    };
+    for plies in (1..=MAX_MATE_PLIES).step_by(2) {
+        if let Some(line) = search.attack(position, plies) {
+            return MateResult::Mate(line);
+        }
+        if search.aborted {
+            return MateResult::Timeout;
+        }
+    }
+    MateResult::NoMate
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::shogi::{Piece, PieceKind, Square};
+    use crate::system::Player;
+    use serial_test::serial;
+
+    const E: Square = Square::Empty;
+
+    fn h(kind: PieceKind) -> Square {
+        Square::Human(Piece::new(kind))
+    }
+
+    fn c(kind: PieceKind) -> Square {
+        Square::Computer(Piece::new(kind))
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_mate_in_one() {
+        set_board_size((5, 5));
+        #[rustfmt::skip]
+        let position = Position::new(vec![
+            E, E, E, c(PieceKind::Pawn), c(PieceKind::King),
+            E, E, E, h(PieceKind::Gold), E,
+            E, E, E, h(PieceKind::Silver), E,
+            E, E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        assert_eq!(
+            find_mate(&GameType::Mini, &position, Limits::default()),
+            MateResult::Mate(vec![Move::Step {
+                from: 8,
+                to: 9,
+                promote: false
+            }])
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_mate_in_three() {
+        set_board_size((5, 5));
+        //Dragon checks from behind pushing the king down the file into the gold
+        #[rustfmt::skip]
+        let mut position = Position::new(vec![
+            E, E, E, Square::Human(Piece::new(PieceKind::Rook).promote()), E,
+            c(PieceKind::King), E, E, E, E,
+            E, E, h(PieceKind::Gold), E, E,
+            E, E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        position.hand_mut(Player::Human).add(PieceKind::Silver);
+        let line = match find_mate(&GameType::Mini, &position, Limits::default()) {
+            MateResult::Mate(line) => line,
+            other => panic!("Expected mate, got {:?}", other),
+        };
+        assert_eq!(line.len(), 3);
+        for mov in &line {
+            assert!(legal_moves(&GameType::Mini, &position).contains(mov));
+            apply_move(&GameType::Mini, &mut position, mov);
+        }
+        assert!(is_in_check(
+            &GameType::Mini,
+            &position.board,
+            Player::Computer
+        ));
+        assert!(legal_moves(&GameType::Mini, &position).is_empty());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_no_checks_is_no_mate() {
+        set_board_size((5, 5));
+        #[rustfmt::skip]
+        let position = Position::new(vec![
+            E, E, E, E, c(PieceKind::King),
+            E, E, E, E, E,
+            E, E, E, E, E,
+            E, E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        assert_eq!(
+            find_mate(&GameType::Mini, &position, Limits::default()),
+            MateResult::NoMate
+        );
+    }
+}
@@ -0,0 +1,512 @@
+use crate::boards::cursor::Cursor;
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::viewport::Viewport;
+use crate::boards::{board_cols, board_rows};
+use crate::shogi::game_types::GameType;
+use crate::system::math::idx_to_coord;
+use crate::system::transposition_table::TranspositionTable;
+use crate::system::zobrist::{PositionHistory, Zobrist, ZobristSquare};
+use crate::system::{PlayState, Player};
+use std::fmt;
+use std::fmt::{Display, Formatter};
+
+mod ai;
+pub mod controller;
+mod game_types;
+mod mate;
+mod movement;
+mod records;
+mod renderer;
+mod rules;
+pub mod usi;
+
+//Most of a single kind that can be held in hand, used for hashing
+const MAX_IN_HAND: usize = 18;
+
+type Board = Vec<Square>;
+
+//Kinds after Pawn are only used by Chu shogi, some only as promotions
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd, Hash)]
+enum PieceKind {
+    King,
+    Rook,
+    Bishop,
+    Gold,
+    Silver,
+    Knight,
+    Lance,
+    Pawn,
+    Copper,
+    FerociousLeopard,
+    BlindTiger,
+    Kirin,
+    Phoenix,
+    DrunkElephant,
+    ReverseChariot,
+    SideMover,
+    VerticalMover,
+    DragonHorse,
+    DragonKing,
+    Lion,
+    Queen,
+    GoBetween,
+    FlyingStag,
+    CrownPrince,
+    WhiteHorse,
+    Whale,
+    FreeBoar,
+    FlyingOx,
+    HornedFalcon,
+    SoaringEagle,
+}
+
+impl PieceKind {
+    const COUNT: usize = 30;
+
+    fn abbreviation(&self) -> &'static str {
+        match self {
+            PieceKind::King => "K",
+            PieceKind::Rook => "R",
+            PieceKind::Bishop => "B",
+            PieceKind::Gold => "G",
+            PieceKind::Silver => "S",
+            PieceKind::Knight => "N",
+            PieceKind::Lance => "L",
+            PieceKind::Pawn => "P",
+            PieceKind::Copper => "C",
+            PieceKind::FerociousLeopard => "FL",
+            PieceKind::BlindTiger => "BT",
+            PieceKind::Kirin => "Kr",
+            PieceKind::Phoenix => "Ph",
+            PieceKind::DrunkElephant => "DE",
+            PieceKind::ReverseChariot => "RC",
+            PieceKind::SideMover => "SM",
+            PieceKind::VerticalMover => "VM",
+            PieceKind::DragonHorse => "DH",
+            PieceKind::DragonKing => "DK",
+            PieceKind::Lion => "Ln",
+            PieceKind::Queen => "FK",
+            PieceKind::GoBetween => "GB",
+            PieceKind::FlyingStag => "FS",
+            PieceKind::CrownPrince => "CP",
+            PieceKind::WhiteHorse => "WH",
+            PieceKind::Whale => "Wh",
+            PieceKind::FreeBoar => "FB",
+            PieceKind::FlyingOx => "FO",
+            PieceKind::HornedFalcon => "HF",
+            PieceKind::SoaringEagle => "SE",
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
+struct Piece {
+    kind: PieceKind,
+    promoted: bool,
+}
+
+impl Piece {
+    const fn new(kind: PieceKind) -> Self {
+        Piece {
+            kind,
+            promoted: false,
+        }
+    }
+
+    fn promote(&self) -> Piece {
+        Piece {
+            kind: self.kind,
+            promoted: true,
+        }
+    }
+}
+
+impl Display for Piece {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        if self.promoted {
+            write!(f, "+{}", self.kind.abbreviation())
+        } else {
+            write!(f, "{}", self.kind.abbreviation())
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Square {
+    Empty,
+    Human(Piece),
+    Computer(Piece),
+}
+
+impl Square {
+    fn new(player: Player, piece: Piece) -> Self {
+        match player {
+            Player::Human => Square::Human(piece),
+            Player::Computer => Square::Computer(piece),
+        }
+    }
+
+    fn get_piece(&self) -> Option<Piece> {
+        match self {
+            Square::Empty => None,
+            Square::Human(piece) | Square::Computer(piece) => Some(*piece),
+        }
+    }
+
+    fn get_player(&self) -> Option<Player> {
+        match self {
+            Square::Empty => None,
+            Square::Human(_) => Some(Player::Human),
+            Square::Computer(_) => Some(Player::Computer),
+        }
+    }
+}
+
+impl ZobristSquare for Square {
+    const KINDS: usize = PieceKind::COUNT * 4;
+
+    fn zobrist_kind(&self) -> Option<usize> {
+        let (player, piece) = match self {
+            Square::Empty => return None,
+            Square::Human(piece) => (0, piece),
+            Square::Computer(piece) => (1, piece),
+        };
+        Some((player * PieceKind::COUNT + piece.kind as usize) * 2 + piece.promoted as usize)
+    }
+}
+
+//Captured pieces available to drop, kept sorted
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+struct Hand {
+    pieces: Vec<PieceKind>,
+}
+
+impl Hand {
+    fn add(&mut self, kind: PieceKind) {
+        let idx = self.pieces.partition_point(|other| other <= &kind);
+        self.pieces.insert(idx, kind);
+    }
+
+    fn remove(&mut self, kind: PieceKind) {
+        let idx = self
+            .pieces
+            .iter()
+            .position(|other| other == &kind)
+            .unwrap_or_else(|| panic!("No {:?} in hand", kind));
+        self.pieces.remove(idx);
+    }
+
+    fn count(&self, kind: PieceKind) -> usize {
+        self.pieces.iter().filter(|other| other == &&kind).count()
+    }
+
+    fn kinds(&self) -> Vec<PieceKind> {
+        let mut kinds = self.pieces.clone();
+        kinds.dedup();
+        kinds
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pieces.is_empty()
+    }
+}
+
+#[derive(Debug, Clone, Eq, PartialEq)]
+struct Position {
+    board: Board,
+    hands: [Hand; 2],
+    to_move: Player,
+    //Chu shogi, the last move captured a lion with another piece so only a lion may capture one now
+    lion_counterstrike: bool,
+}
+
+impl Position {
+    fn new(board: Board) -> Self {
+        Position {
+            board,
+            hands: [Hand::default(), Hand::default()],
+            to_move: Player::Human,
+            lion_counterstrike: false,
+        }
+    }
+
+    fn hand(&self, player: Player) -> &Hand {
+        &self.hands[player_idx(player)]
+    }
+
+    fn hand_mut(&mut self, player: Player) -> &mut Hand {
+        &mut self.hands[player_idx(player)]
+    }
+
+    fn hash(&self, zobrist: &Zobrist) -> u64 {
+        let mut hash = zobrist.hash(&self.board, self.to_move);
+        for player in [Player::Human, Player::Computer].iter() {
+            let hand = self.hand(*player);
+            for kind in hand.kinds() {
+                let count = hand.count(kind).min(MAX_IN_HAND);
+                let key = (player_idx(*player) * PieceKind::COUNT + kind as usize) * MAX_IN_HAND;
+                hash = zobrist.toggle_extra(hash, key + count - 1);
+            }
+        }
+        if self.lion_counterstrike {
+            hash = zobrist.toggle_extra(hash, COUNTERSTRIKE_KEY);
+        }
+        hash
+    }
+}
+
+fn player_idx(player: Player) -> usize {
+    match player {
+        Player::Human => 0,
+        Player::Computer => 1,
+    }
+}
+
+//Extra keys are the hand counts followed by the lion counterstrike flag
+const COUNTERSTRIKE_KEY: usize = 2 * PieceKind::COUNT * MAX_IN_HAND;
+
+fn make_zobrist(cell_count: usize) -> Zobrist {
+    Zobrist::with_extra_keys::<Square>(cell_count, COUNTERSTRIKE_KEY + 1)
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Move {
+    Step {
+        from: usize,
+        to: usize,
+        promote: bool,
+    },
+    Drop {
+        kind: PieceKind,
+        to: usize,
+    },
+    //Lion power, capturing on via then moving on to to, which can be from again
+    Double {
+        from: usize,
+        via: usize,
+        to: usize,
+    },
+}
+
+impl Move {
+    fn to(&self) -> usize {
+        match self {
+            Move::Step { to, .. } | Move::Drop { to, .. } | Move::Double { to, .. } => *to,
+        }
+    }
+
+    fn from(&self) -> Option<usize> {
+        match self {
+            Move::Step { from, .. } | Move::Double { from, .. } => Some(*from),
+            Move::Drop { .. } => None,
+        }
+    }
+
+    fn via(&self) -> Option<usize> {
+        match self {
+            Move::Double { via, .. } => Some(*via),
+            _ => None,
+        }
+    }
+}
+
+//Files are numbered right to left and ranks are lettered top to bottom, i.e. 1a is the top right
+fn square_name(idx: usize) -> String {
+    let (x, y) = idx_to_coord(idx, board_cols());
+    format!("{}{}", board_cols() - x, (b'a' + y as u8) as char)
+}
+
+//Reverse of square_name, only single digit files are supported
+fn parse_square_name(name: &str) -> Option<usize> {
+    let mut chars = name.chars();
+    let file = chars.next()?.to_digit(10)? as usize;
+    let rank = chars.next()?;
+    if chars.next().is_some() || !rank.is_ascii_lowercase() {
+        return None;
+    }
+    let y = (rank as u8 - b'a') as usize;
+    if file == 0 || file > board_cols() || y >= board_rows() {
+        return None;
+    }
+    Some(BoardCoord(board_cols() - file, y).idx())
+}
+
+#[derive(Debug)]
+struct PastMove {
+    player: Player,
+    mov: Move,
+    piece: Piece,
+    capture: bool,
+}
+
+fn capture_char(capture: bool) -> &'static str {
+    if capture {
+        "x"
+    } else {
+        "-"
+    }
+}
+
+impl Display for PastMove {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let player = match self.player {
+            Player::Human => "H",
+            Player::Computer => "C",
+        };
+        match self.mov {
+            Move::Step { from, to, promote } => write!(
+                f,
+                "{} {}{}{}{}{}",
+                player,
+                self.piece,
+                square_name(from),
+                capture_char(self.capture),
+                square_name(to),
+                if promote { "+" } else { "" }
+            ),
+            Move::Drop { to, .. } => write!(f, "{} {}*{}", player, self.piece, square_name(to)),
+            //The first step of a double move always captures
+            Move::Double { from, via, to } => write!(
+                f,
+                "{} {}{}x{}{}{}",
+                player,
+                self.piece,
+                square_name(from),
+                square_name(via),
+                capture_char(self.capture && to != from),
+                square_name(to)
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+enum Selection {
+    Board,
+    Hand(usize),
+}
+
+#[derive(Debug)]
+struct State {
+    play_state: PlayState,
+    game_type: GameType,
+    position: Position,
+    cursor: Cursor,
+    viewport: Viewport,
+    selection: Selection,
+    move_cursor: usize,
+    //Set when the human has picked a square that can be reached with or without promoting
+    promotion_choice: Option<(Move, Move)>,
+    //Set when the human has picked a square a lion can reach by more than one route
+    route_choice: Vec<Move>,
+    route_cursor: usize,
+    legal_moves: Vec<Move>,
+    move_history: Vec<PastMove>,
+    zobrist: Zobrist,
+    position_history: PositionHistory,
+    //Whether the player to move was in check, for each position in position_history
+    check_history: Vec<bool>,
+    ai_table: TranspositionTable<(i32, Option<Move>)>,
+    next_move_time: f64,
+    last_human_cursor_pos: usize,
+    computer_move: Option<Move>,
+    //Shown below the board, e.g. after saving the game
+    message: Option<String>,
+}
+
+impl State {
+    //Moves for the selected board piece or hand piece
+    fn get_moves_for_selection(&self) -> Vec<Move> {
+        match self.selection {
+            Selection::Board => self
+                .legal_moves
+                .iter()
+                .filter(|mov| mov.from() == Some(self.cursor.idx))
+                .cloned()
+                .collect(),
+            Selection::Hand(idx) => match self.position.hand(Player::Human).kinds().get(idx) {
+                Some(kind) => self
+                    .legal_moves
+                    .iter()
+                    .filter(|mov| matches!(mov, Move::Drop { kind: other, .. } if other == kind))
+                    .cloned()
+                    .collect(),
+                None => vec![],
+            },
+        }
+    }
+
+    //One move per destination, promotion is chosen after picking the destination
+    fn get_targets_for_selection(&self) -> Vec<Move> {
+        let mut targets: Vec<Move> = vec![];
+        for mov in self.get_moves_for_selection() {
+            if !targets.iter().any(|other| other.to() == mov.to()) {
+                targets.push(mov);
+            }
+        }
+        targets
+    }
+
+    fn get_selected_target(&self) -> Move {
+        *self
+            .get_targets_for_selection()
+            .get(self.move_cursor)
+            .unwrap_or_else(|| panic!("No move {} for selection", self.move_cursor))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    #[test]
+    fn test_hand() {
+        let mut hand = Hand::default();
+        hand.add(PieceKind::Pawn);
+        hand.add(PieceKind::Rook);
+        hand.add(PieceKind::Pawn);
+        assert_eq!(hand.kinds(), vec![PieceKind::Rook, PieceKind::Pawn]);
+        assert_eq!(hand.count(PieceKind::Pawn), 2);
+        hand.remove(PieceKind::Pawn);
+        assert_eq!(hand.count(PieceKind::Pawn), 1);
+        assert!(!hand.is_empty());
+    }
+
+    #[test]
+    fn test_hand_changes_hash() {
+        let zobrist = make_zobrist(4);
+        let mut position = Position::new(vec![Square::Empty; 4]);
+        let empty = position.hash(&zobrist);
+        position.hand_mut(Player::Human).add(PieceKind::Pawn);
+        let one = position.hash(&zobrist);
+        position.hand_mut(Player::Human).add(PieceKind::Pawn);
+        let two = position.hash(&zobrist);
+        position.hand_mut(Player::Human).remove(PieceKind::Pawn);
+        position.hand_mut(Player::Human).remove(PieceKind::Pawn);
+        position.hand_mut(Player::Computer).add(PieceKind::Pawn);
+        let other = position.hash(&zobrist);
+        assert_ne!(empty, one);
+        assert_ne!(one, two);
+        assert_ne!(one, other);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_square_name() {
+        set_board_size((5, 5));
+        assert_eq!(square_name(0), "5a");
+        assert_eq!(square_name(4), "1a");
+        assert_eq!(square_name(24), "1e");
+        set_board_size((9, 9));
+        assert_eq!(square_name(80), "1i");
+        assert_eq!(square_name(72), "9i");
+        for idx in 0..81 {
+            assert_eq!(parse_square_name(&square_name(idx)), Some(idx));
+        }
+        assert_eq!(parse_square_name("0a"), None);
+        assert_eq!(parse_square_name("1j"), None);
+        assert_eq!(parse_square_name("1a+"), None);
+    }
+}
@@ -0,0 +1,238 @@
+use crate::shogi::PieceKind;
+
+pub(super) type Offset = (isize, isize);
+
+//How a piece moves, all offsets are for the human (moving up the board) and are flipped for the computer
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub(super) struct Movement {
+    //Moves to an exact offset, jumping over anything in between
+    pub(super) steps: &'static [Offset],
+    //Slides any distance until blocked
+    pub(super) slides: &'static [Offset],
+    //Slides up to a limited number of squares, as used by the larger variants
+    pub(super) ranged: &'static [(Offset, usize)],
+    //Directions the piece may move one square then another, as with the Horned falcon
+    pub(super) lion_lines: &'static [Offset],
+    //Two king moves in one turn, capturing on either or both
+    pub(super) lion: bool,
+}
+
+const EMPTY: Movement = Movement {
+    steps: &[],
+    slides: &[],
+    ranged: &[],
+    lion_lines: &[],
+    lion: false,
+};
+
+pub(super) const KING: &[Offset] = &[
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (-1, 0),
+    (1, 0),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+];
+const GOLD: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (0, 1)];
+const SILVER: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (-1, 1), (1, 1)];
+const COPPER: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (0, 1)];
+const LEOPARD: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (-1, 1), (0, 1), (1, 1)];
+const TIGER: &[Offset] = &[(-1, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+const ELEPHANT: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (1, 1)];
+const KIRIN: &[Offset] = &[
+    (-1, -1),
+    (1, -1),
+    (-1, 1),
+    (1, 1),
+    (0, -2),
+    (-2, 0),
+    (2, 0),
+    (0, 2),
+];
+const PHOENIX: &[Offset] = &[
+    (0, -1),
+    (-1, 0),
+    (1, 0),
+    (0, 1),
+    (-2, -2),
+    (2, -2),
+    (-2, 2),
+    (2, 2),
+];
+const STAG: &[Offset] = &[(-1, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (1, 1)];
+const KNIGHT: &[Offset] = &[(-1, -2), (1, -2)];
+const FORWARD: &[Offset] = &[(0, -1)];
+const VERTICAL: &[Offset] = &[(0, -1), (0, 1)];
+const SIDEWAYS: &[Offset] = &[(-1, 0), (1, 0)];
+const ORTHOGONAL: &[Offset] = &[(0, -1), (-1, 0), (1, 0), (0, 1)];
+const DIAGONAL: &[Offset] = &[(-1, -1), (1, -1), (-1, 1), (1, 1)];
+const FORWARD_DIAGONAL: &[Offset] = &[(-1, -1), (1, -1)];
+const WHITE_HORSE: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (0, 1)];
+const WHALE: &[Offset] = &[(0, -1), (-1, 1), (0, 1), (1, 1)];
+const FREE_BOAR: &[Offset] = &[(-1, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (1, 1)];
+const FLYING_OX: &[Offset] = &[(-1, -1), (0, -1), (1, -1), (-1, 1), (0, 1), (1, 1)];
+const FALCON_SLIDES: &[Offset] = &[(-1, -1), (1, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+const FALCON_STEPS: &[Offset] = &[(0, -1), (0, -2)];
+const EAGLE_SLIDES: &[Offset] = &[(0, -1), (-1, 0), (1, 0), (-1, 1), (0, 1), (1, 1)];
+const EAGLE_STEPS: &[Offset] = &[(-1, -1), (1, -1), (-2, -2), (2, -2)];
+//Every square within two king moves
+const LION_AREA: &[Offset] = &[
+    (-2, -2),
+    (-1, -2),
+    (0, -2),
+    (1, -2),
+    (2, -2),
+    (-2, -1),
+    (-1, -1),
+    (0, -1),
+    (1, -1),
+    (2, -1),
+    (-2, 0),
+    (-1, 0),
+    (1, 0),
+    (2, 0),
+    (-2, 1),
+    (-1, 1),
+    (0, 1),
+    (1, 1),
+    (2, 1),
+    (-2, 2),
+    (-1, 2),
+    (0, 2),
+    (1, 2),
+    (2, 2),
+];
+
+//Promoted pieces use the movement of the kind they promote to, see GameType::promotes_to
+pub(super) fn movement(kind: PieceKind) -> Movement {
+    match kind {
+        PieceKind::King | PieceKind::CrownPrince => Movement {
+            steps: KING,
+            ..EMPTY
+        },
+        PieceKind::Rook => Movement {
+            slides: ORTHOGONAL,
+            ..EMPTY
+        },
+        PieceKind::Bishop => Movement {
+            slides: DIAGONAL,
+            ..EMPTY
+        },
+        PieceKind::Gold => Movement {
+            steps: GOLD,
+            ..EMPTY
+        },
+        PieceKind::Silver => Movement {
+            steps: SILVER,
+            ..EMPTY
+        },
+        PieceKind::Knight => Movement {
+            steps: KNIGHT,
+            ..EMPTY
+        },
+        PieceKind::Lance => Movement {
+            slides: FORWARD,
+            ..EMPTY
+        },
+        PieceKind::Pawn => Movement {
+            steps: FORWARD,
+            ..EMPTY
+        },
+        PieceKind::Copper => Movement {
+            steps: COPPER,
+            ..EMPTY
+        },
+        PieceKind::FerociousLeopard => Movement {
+            steps: LEOPARD,
+            ..EMPTY
+        },
+        PieceKind::BlindTiger => Movement {
+            steps: TIGER,
+            ..EMPTY
+        },
+        PieceKind::Kirin => Movement {
+            steps: KIRIN,
+            ..EMPTY
+        },
+        PieceKind::Phoenix => Movement {
+            steps: PHOENIX,
+            ..EMPTY
+        },
+        PieceKind::DrunkElephant => Movement {
+            steps: ELEPHANT,
+            ..EMPTY
+        },
+        PieceKind::ReverseChariot => Movement {
+            slides: VERTICAL,
+            ..EMPTY
+        },
+        PieceKind::SideMover => Movement {
+            steps: VERTICAL,
+            slides: SIDEWAYS,
+            ..EMPTY
+        },
+        PieceKind::VerticalMover => Movement {
+            steps: SIDEWAYS,
+            slides: VERTICAL,
+            ..EMPTY
+        },
+        PieceKind::DragonHorse => Movement {
+            steps: ORTHOGONAL,
+            slides: DIAGONAL,
+            ..EMPTY
+        },
+        PieceKind::DragonKing => Movement {
+            steps: DIAGONAL,
+            slides: ORTHOGONAL,
+            ..EMPTY
+        },
+        PieceKind::Lion => Movement {
+            steps: LION_AREA,
+            lion: true,
+            ..EMPTY
+        },
+        PieceKind::Queen => Movement {
+            slides: KING,
+            ..EMPTY
+        },
+        PieceKind::GoBetween => Movement {
+            steps: VERTICAL,
+            ..EMPTY
+        },
+        PieceKind::FlyingStag => Movement {
+            steps: STAG,
+            slides: VERTICAL,
+            ..EMPTY
+        },
+        PieceKind::WhiteHorse => Movement {
+            slides: WHITE_HORSE,
+            ..EMPTY
+        },
+        PieceKind::Whale => Movement {
+            slides: WHALE,
+            ..EMPTY
+        },
+        PieceKind::FreeBoar => Movement {
+            slides: FREE_BOAR,
+            ..EMPTY
+        },
+        PieceKind::FlyingOx => Movement {
+            slides: FLYING_OX,
+            ..EMPTY
+        },
+        PieceKind::HornedFalcon => Movement {
+            steps: FALCON_STEPS,
+            slides: FALCON_SLIDES,
+            lion_lines: FORWARD,
+            ..EMPTY
+        },
+        PieceKind::SoaringEagle => Movement {
+            steps: EAGLE_STEPS,
+            slides: EAGLE_SLIDES,
+            lion_lines: FORWARD_DIAGONAL,
+            ..EMPTY
+        },
+    }
+}
@@ -0,0 +1,489 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::{board_cols, board_rows};
+use crate::shogi::game_types::GameType;
+use crate::shogi::rules::{apply_move, legal_moves};
+use crate::shogi::{Move, PastMove, Piece, PieceKind, Position};
+use crate::system::math::idx_to_coord;
+use crate::system::Player;
+
+//Game records in KIF (Japanese notation, as UTF-8 rather than the traditional Shift-JIS)
+//and CSA formats. The human always plays sente (black/first) and the computer gote.
+
+const FULL_WIDTH_DIGITS: [char; 9] = ['１', '２', '３', '４', '５', '６', '７', '８', '９'];
+const KANJI_NUMERALS: [char; 9] = ['一', '二', '三', '四', '五', '六', '七', '八', '九'];
+//Moves in a KIF record that end the game rather than being played
+const KIF_ENDINGS: [&str; 8] = [
+    "投了",
+    "中断",
+    "千日手",
+    "詰み",
+    "持将棋",
+    "切れ負け",
+    "反則勝ち",
+    "反則負け",
+];
+
+fn csa_code(piece: &Piece) -> Result<&'static str, String> {
+    let code = match (piece.kind, piece.promoted) {
+        (PieceKind::King, _) => "OU",
+        (PieceKind::Rook, false) => "HI",
+        (PieceKind::Rook, true) => "RY",
+        (PieceKind::Bishop, false) => "KA",
+        (PieceKind::Bishop, true) => "UM",
+        (PieceKind::Gold, _) => "KI",
+        (PieceKind::Silver, false) => "GI",
+        (PieceKind::Silver, true) => "NG",
+        (PieceKind::Knight, false) => "KE",
+        (PieceKind::Knight, true) => "NK",
+        (PieceKind::Lance, false) => "KY",
+        (PieceKind::Lance, true) => "NY",
+        (PieceKind::Pawn, false) => "FU",
+        (PieceKind::Pawn, true) => "TO",
+        _ => return Err(format!("{} has no CSA code", piece)),
+    };
+    Ok(code)
+}
+
+fn kif_name(piece: &Piece) -> Result<&'static str, String> {
+    let name = match (piece.kind, piece.promoted) {
+        (PieceKind::King, _) => "玉",
+        (PieceKind::Rook, false) => "飛",
+        (PieceKind::Rook, true) => "龍",
+        (PieceKind::Bishop, false) => "角",
+        (PieceKind::Bishop, true) => "馬",
+        (PieceKind::Gold, _) => "金",
+        (PieceKind::Silver, false) => "銀",
+        (PieceKind::Silver, true) => "成銀",
+        (PieceKind::Knight, false) => "桂",
+        (PieceKind::Knight, true) => "成桂",
+        (PieceKind::Lance, false) => "香",
+        (PieceKind::Lance, true) => "成香",
+        (PieceKind::Pawn, false) => "歩",
+        (PieceKind::Pawn, true) => "と",
+        _ => return Err(format!("{} has no KIF name", piece)),
+    };
+    Ok(name)
+}
+
+//Alternative spellings are accepted when reading, longest names must come first
+const KIF_PIECE_NAMES: [(&str, PieceKind, bool); 19] = [
+    ("成銀", PieceKind::Silver, true),
+    ("成桂", PieceKind::Knight, true),
+    ("成香", PieceKind::Lance, true),
+    ("玉", PieceKind::King, false),
+    ("王", PieceKind::King, false),
+    ("飛", PieceKind::Rook, false),
+    ("龍", PieceKind::Rook, true),
+    ("竜", PieceKind::Rook, true),
+    ("角", PieceKind::Bishop, false),
+    ("馬", PieceKind::Bishop, true),
+    ("金", PieceKind::Gold, false),
+    ("銀", PieceKind::Silver, false),
+    ("全", PieceKind::Silver, true),
+    ("桂", PieceKind::Knight, false),
+    ("圭", PieceKind::Knight, true),
+    ("香", PieceKind::Lance, false),
+    ("杏", PieceKind::Lance, true),
+    ("歩", PieceKind::Pawn, false),
+    ("と", PieceKind::Pawn, true),
+];
+
+//(file, rank) both starting at 1, files count from the right
+fn square_coords(idx: usize) -> (usize, usize) {
+    let (x, y) = idx_to_coord(idx, board_cols());
+    (board_cols() - x, y + 1)
+}
+
+fn coords_square(file: usize, rank: usize) -> Option<usize> {
+    if (1..=board_cols()).contains(&file) && (1..=board_rows()).contains(&rank) {
+        Some(BoardCoord(board_cols() - file, rank - 1).idx())
+    } else {
+        None
+    }
+}
+
+fn kif_handicap(game_type: &GameType) -> Result<&'static str, String> {
+    match game_type {
+        GameType::Standard => Ok("平手"),
+        GameType::Mini => Ok("5五将棋"),
+        GameType::Chu => Err(String::from("Chu shogi has no KIF records")),
+    }
+}
+
+pub(super) fn to_kif(game_type: &GameType, history: &[PastMove]) -> Result<String, String> {
+    let mut lines = vec![
+        format!("手合割：{}", kif_handicap(game_type)?),
+        String::from("先手：Human"),
+        String::from("後手：Computer"),
+        String::from("手数----指手---------消費時間--"),
+    ];
+    let mut last_to = None;
+    for (i, past) in history.iter().enumerate() {
+        let to = past.mov.to();
+        let destination = if last_to == Some(to) {
+            String::from("同　")
+        } else {
+            let (file, rank) = square_coords(to);
+            format!(
+                "{}{}",
+                FULL_WIDTH_DIGITS[file - 1],
+                KANJI_NUMERALS[rank - 1]
+            )
+        };
+        let suffix = match past.mov {
+            Move::Step { from, promote, .. } => {
+                let (file, rank) = square_coords(from);
+                format!("{}({}{})", if promote { "成" } else { "" }, file, rank)
+            }
+            Move::Drop { .. } => String::from("打"),
+            Move::Double { .. } => {
+                return Err(String::from("Double moves can't be written as KIF"))
+            }
+        };
+        lines.push(format!(
+            "{: >4} {}{}{}",
+            i + 1,
+            destination,
+            kif_name(&past.piece)?,
+            suffix
+        ));
+        last_to = Some(to);
+    }
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+pub(super) fn to_csa(game_type: &GameType, history: &[PastMove]) -> Result<String, String> {
+    let mut lines = vec![
+        String::from("V2.2"),
+        String::from("N+Human"),
+        String::from("N-Computer"),
+    ];
+    for (y, row) in game_type.get_init_board().chunks(board_cols()).enumerate() {
+        let squares = row
+            .iter()
+            .map(|square| match (square.get_player(), square.get_piece()) {
+                (Some(Player::Human), Some(piece)) => Ok(format!("+{}", csa_code(&piece)?)),
+                (Some(Player::Computer), Some(piece)) => Ok(format!("-{}", csa_code(&piece)?)),
+                _ => Ok(String::from(" * ")),
+            })
+            .collect::<Result<String, String>>()?;
+        lines.push(format!("P{}{}", y + 1, squares));
+    }
+    lines.push(String::from("+"));
+    for past in history {
+        let sign = match past.player {
+            Player::Human => '+',
+            Player::Computer => '-',
+        };
+        let (to_file, to_rank) = square_coords(past.mov.to());
+        let (from, piece) = match past.mov {
+            Move::Step { from, promote, .. } => (
+                square_coords(from),
+                if promote {
+                    past.piece.promote()
+                } else {
+                    past.piece
+                },
+            ),
+            Move::Drop { .. } => ((0, 0), past.piece),
+            Move::Double { .. } => {
+                return Err(String::from("Double moves can't be written as CSA"))
+            }
+        };
+        lines.push(format!(
+            "{}{}{}{}{}{}",
+            sign,
+            from.0,
+            from.1,
+            to_file,
+            to_rank,
+            csa_code(&piece)?
+        ));
+    }
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+fn parse_digit(chr: char) -> Option<usize> {
+    FULL_WIDTH_DIGITS
+        .iter()
+        .position(|digit| *digit == chr)
+        .or_else(|| KANJI_NUMERALS.iter().position(|digit| *digit == chr))
+        .map(|idx| idx + 1)
+        .or_else(|| chr.to_digit(10).map(|digit| digit as usize))
+}
+
+//Reads a single KIF move and the piece it names, previous is the destination of the last move for 同
+fn parse_kif_move(text: &str, previous: Option<usize>) -> Result<(Move, Piece), String> {
+    let mut rest = text;
+    let to = if let Some(after) = rest.strip_prefix('同') {
+        rest = after.trim_start_matches(['　', ' ']);
+        previous.ok_or("同 used on the first move")?
+    } else {
+        let mut chars = rest.chars();
+        let file = chars.next().and_then(parse_digit);
+        let rank = chars.next().and_then(parse_digit);
+        rest = chars.as_str();
+        match (file, rank) {
+            (Some(file), Some(rank)) => {
+                coords_square(file, rank).ok_or(format!("Square off board: {}", text))?
+            }
+            _ => return Err(format!("Invalid destination: {}", text)),
+        }
+    };
+    let (name, kind, promoted) = KIF_PIECE_NAMES
+        .iter()
+        .find(|(name, _, _)| rest.starts_with(name))
+        .ok_or(format!("Unknown piece: {}", text))?;
+    let piece = Piece {
+        kind: *kind,
+        promoted: *promoted,
+    };
+    rest = &rest[name.len()..];
+    if rest.starts_with('打') {
+        return Ok((Move::Drop { kind: *kind, to }, piece));
+    }
+    let promote = !rest.starts_with("不成") && rest.starts_with('成');
+    let origin = rest
+        .find('(')
+        .map(|start| &rest[start + 1..])
+        .ok_or(format!("Missing origin: {}", text))?;
+    let mut digits = origin.chars().take(2).filter_map(|chr| chr.to_digit(10));
+    match (digits.next(), digits.next()) {
+        (Some(file), Some(rank)) => Ok((
+            Move::Step {
+                from: coords_square(file as usize, rank as usize)
+                    .ok_or(format!("Square off board: {}", text))?,
+                to,
+                promote,
+            },
+            piece,
+        )),
+        _ => Err(format!("Missing origin: {}", text)),
+    }
+}
+
+//KIF moves name the piece moved, which is checked against the position
+fn parse_kif(text: &str, game_type: &GameType) -> Result<Vec<Move>, String> {
+    let mut position = Position::new(game_type.get_init_board());
+    let mut moves = vec![];
+    for line in text.lines() {
+        let line = line.trim();
+        //Headers and comments are skipped, moves start with the move number
+        if !line.starts_with(|chr: char| chr.is_ascii_digit()) {
+            continue;
+        }
+        let mov = line
+            .trim_start_matches(|chr: char| chr.is_ascii_digit())
+            .trim_start();
+        if KIF_ENDINGS.iter().any(|ending| mov.starts_with(ending)) {
+            break;
+        }
+        let previous = moves.last().map(|mov: &Move| mov.to());
+        let (mov, piece) = parse_kif_move(mov, previous)?;
+        if let Move::Step { from, .. } = mov {
+            if position.board[from].get_piece() != Some(piece) {
+                return Err(format!("{} is not on the origin square: {}", piece, line));
+            }
+        }
+        play(game_type, &mut position, mov, moves.len())?;
+        moves.push(mov);
+    }
+    Ok(moves)
+}
+
+//CSA moves are identified by the piece after moving so promotion depends on the position
+fn parse_csa(text: &str, game_type: &GameType) -> Result<Vec<Move>, String> {
+    let mut position = Position::new(game_type.get_init_board());
+    let mut moves = vec![];
+    for statement in text.lines().flat_map(|line| line.split(',')) {
+        let statement = statement.trim();
+        if statement.starts_with('%') {
+            break;
+        }
+        if !is_csa_move(statement) {
+            continue;
+        }
+        let digits: Vec<usize> = statement[1..5]
+            .chars()
+            .map(|chr| chr.to_digit(10).unwrap() as usize)
+            .collect();
+        let to = coords_square(digits[2], digits[3])
+            .ok_or(format!("Square off board: {}", statement))?;
+        let (kind, promoted) =
+            csa_piece(&statement[5..7]).ok_or(format!("Unknown piece: {}", statement))?;
+        let mov = if digits[0] == 0 && digits[1] == 0 {
+            Move::Drop { kind, to }
+        } else {
+            let from = coords_square(digits[0], digits[1])
+                .ok_or(format!("Square off board: {}", statement))?;
+            let before = position.board[from]
+                .get_piece()
+                .ok_or(format!("No piece to move: {}", statement))?;
+            Move::Step {
+                from,
+                to,
+                promote: promoted && !before.promoted,
+            }
+        };
+        play(game_type, &mut position, mov, moves.len())?;
+        moves.push(mov);
+    }
+    Ok(moves)
+}
+
+fn is_csa_move(statement: &str) -> bool {
+    let bytes = statement.as_bytes();
+    bytes.len() >= 7
+        && (bytes[0] == b'+' || bytes[0] == b'-')
+        && bytes[1..5].iter().all(u8::is_ascii_digit)
+        && bytes[5..7].iter().all(u8::is_ascii_uppercase)
+}
+
+fn csa_piece(code: &str) -> Option<(PieceKind, bool)> {
+    [
+        PieceKind::King,
+        PieceKind::Rook,
+        PieceKind::Bishop,
+        PieceKind::Gold,
+        PieceKind::Silver,
+        PieceKind::Knight,
+        PieceKind::Lance,
+        PieceKind::Pawn,
+    ]
+    .iter()
+    .flat_map(|kind| vec![Piece::new(*kind), Piece::new(*kind).promote()])
+    .find(|piece| csa_code(piece) == Ok(code))
+    .map(|piece| (piece.kind, piece.promoted))
+}
+
+fn play(
+    game_type: &GameType,
+    position: &mut Position,
+    mov: Move,
+    idx: usize,
+) -> Result<(), String> {
+    if legal_moves(game_type, position).contains(&mov) {
+        apply_move(game_type, position, &mov);
+        Ok(())
+    } else {
+        Err(format!("Move {} is not legal: {:?}", idx + 1, mov))
+    }
+}
+
+//Reads a KIF or CSA record, every move must be legal from the start position
+pub(super) fn parse_record(game_type: &GameType, text: &str) -> Result<Vec<Move>, String> {
+    if text.lines().any(|line| is_csa_move(line.trim())) {
+        parse_csa(text, game_type)
+    } else {
+        parse_kif(text, game_type)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    fn step(from: (usize, usize), to: (usize, usize), promote: bool) -> Move {
+        Move::Step {
+            from: coords_square(from.0, from.1).unwrap(),
+            to: coords_square(to.0, to.1).unwrap(),
+            promote,
+        }
+    }
+
+    //Bishop exchange in the opening then dropping the bishop back
+    fn opening() -> Vec<Move> {
+        vec![
+            step((7, 7), (7, 6), false),
+            step((3, 3), (3, 4), false),
+            step((8, 8), (2, 2), true),
+            step((3, 1), (2, 2), false),
+            Move::Drop {
+                kind: PieceKind::Bishop,
+                to: coords_square(5, 5).unwrap(),
+            },
+        ]
+    }
+
+    fn history(game_type: &GameType, moves: &[Move]) -> Vec<PastMove> {
+        let mut position = Position::new(game_type.get_init_board());
+        moves
+            .iter()
+            .map(|mov| {
+                let player = position.to_move;
+                let piece = match mov {
+                    Move::Step { from, .. } | Move::Double { from, .. } => {
+                        position.board[*from].get_piece().unwrap()
+                    }
+                    Move::Drop { kind, .. } => Piece::new(*kind),
+                };
+                let capture = !apply_move(game_type, &mut position, mov).is_empty();
+                PastMove {
+                    player,
+                    mov: *mov,
+                    piece,
+                    capture,
+                }
+            })
+            .collect()
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_kif_round_trip() {
+        set_board_size((9, 9));
+        let kif = to_kif(
+            &GameType::Standard,
+            &history(&GameType::Standard, &opening()),
+        )
+        .unwrap();
+        assert!(kif.contains("   1 ７六歩(77)"));
+        assert!(kif.contains("   3 ２二角成(88)"));
+        assert!(kif.contains("   4 同　銀(31)"));
+        assert!(kif.contains("   5 ５五角打"));
+        assert_eq!(parse_record(&GameType::Standard, &kif), Ok(opening()));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_csa_round_trip() {
+        set_board_size((9, 9));
+        let csa = to_csa(
+            &GameType::Standard,
+            &history(&GameType::Standard, &opening()),
+        )
+        .unwrap();
+        assert!(csa.contains("P1-KY-KE-GI-KI-OU-KI-GI-KE-KY"));
+        assert!(csa.contains("+8822UM"));
+        assert!(csa.contains("-3122GI"));
+        assert!(csa.contains("+0055KA"));
+        assert_eq!(parse_record(&GameType::Standard, &csa), Ok(opening()));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_parse_kif_with_headers_and_ending() {
+        set_board_size((9, 9));
+        let kif = "# comment\n開始日時：2020/01/01\n手合割：平手\n手数----指手---------消費時間--\n   1 ７六歩(77)   ( 0:01/00:00:01)\n   2 ３四歩(33)   ( 0:02/00:00:02)\n   3 投了\n";
+        assert_eq!(
+            parse_record(&GameType::Standard, kif),
+            Ok(vec![
+                step((7, 7), (7, 6), false),
+                step((3, 3), (3, 4), false)
+            ])
+        );
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_illegal_record() {
+        set_board_size((9, 9));
+        assert!(parse_record(&GameType::Standard, "+7775FU\n").is_err());
+        assert!(parse_record(&GameType::Standard, "   1 ７六飛(77)\n").is_err());
+        assert!(parse_record(&GameType::Standard, "   1 ５五角打\n").is_err());
+    }
+}
@@ -0,0 +1,445 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::viewport::{ZOOM_IN_KEY, ZOOM_OUT_KEY};
+use crate::boards::{board_cols, board_rows};
+use crate::constants::colors::{
+    alpha, APRICOT, BLACK, BROWN, CREAM, DARK_CREAM, DARK_RED, LIGHT_BLUE, LIGHT_GRAY, RED, WHITE,
+};
+use crate::constants::Direction;
+use crate::shogi::controller::{DECLARE_KEY, HAND_KEY, SAVE_KEY};
+use crate::shogi::{square_name, Piece, Selection, State};
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::Player;
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use ggez::graphics::DrawMode;
+use ggez::{Context, GameResult};
+
+const MAX_CELL_SIZE: f32 = 110.;
+//Larger boards are scrolled so pieces stay readable
+pub(super) const MAX_VISIBLE_CELLS: usize = 16;
+const HAND_PIECE_SIZE: f32 = 44.;
+const HAND_PER_ROW: usize = 4;
+const HISTORY_LINES: usize = 34;
+
+pub(super) fn render(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let viewport = &state.viewport;
+    let cell_size = (mesh_helper.height * 0.8 / viewport.span() as f32).min(MAX_CELL_SIZE);
+    let board_width = cell_size * viewport.cols() as f32;
+    let board_height = cell_size * viewport.rows() as f32;
+    let view_start = pt(
+        mesh_helper.width - board_width - mesh_helper.calc_height(0.05),
+        (mesh_helper.height - board_height) * 0.5,
+    );
+    //Where the top left cell would be drawn, off screen when scrolled
+    let board_start = view_start.offset(
+        -(viewport.x as f32) * cell_size,
+        -(viewport.y as f32) * cell_size,
+    );
+
+    draw_board(ctx, mesh_helper, state, view_start, cell_size)?;
+
+    for (idx, square) in state.position.board.iter().enumerate() {
+        if let (Some(piece), Some(player)) = (square.get_piece(), square.get_player()) {
+            if viewport.contains(idx) {
+                let xy = cell_point(idx, board_start, cell_size);
+                draw_piece(ctx, mesh_helper, &piece, player, xy, cell_size)?;
+            }
+        }
+    }
+
+    draw_selection(ctx, mesh_helper, state, board_start, cell_size)?;
+
+    if state.game_type.has_drops() {
+        draw_hand(ctx, mesh_helper, state, Player::Computer, pt(8., 8.))?;
+        draw_hand(
+            ctx,
+            mesh_helper,
+            state,
+            Player::Human,
+            pt(8., mesh_helper.height - 190.),
+        )?;
+    }
+    draw_history(ctx, mesh_helper, state, pt(8., 150.));
+
+    if state.check_history.last() == Some(&true) && state.play_state.is_playing() {
+        mesh_helper.draw_text(
+            ctx,
+            "Check",
+            view_start.offset(board_width * 0.5, board_height + 8.),
+            RED,
+            24.,
+            true,
+        );
+    }
+
+    if let Some(mov) = state.route_choice.get(state.route_cursor) {
+        let route = match mov.via() {
+            Some(via) if via == mov.to() => String::from("pass"),
+            Some(via) => format!("via {}", square_name(via)),
+            None => String::from("jump"),
+        };
+        mesh_helper.draw_white_text(
+            ctx,
+            &format!(
+                "Route {}/{}: {} (arrows to change)",
+                state.route_cursor + 1,
+                state.route_choice.len(),
+                route
+            ),
+            view_start.offset(board_width * 0.5, -36.),
+            24.,
+            true,
+        );
+    }
+
+    if state.promotion_choice.is_some() {
+        mesh_helper.draw_white_text(
+            ctx,
+            "Promote? Y/N",
+            view_start.offset(board_width * 0.5, -36.),
+            24.,
+            true,
+        );
+    }
+
+    if let Some(message) = &state.message {
+        mesh_helper.draw_white_text(
+            ctx,
+            message,
+            view_start.offset(board_width * 0.5, board_height + 40.),
+            18.,
+            true,
+        );
+    }
+
+    let mut hint = format!("{:?}/{:?} - Zoom", ZOOM_IN_KEY, ZOOM_OUT_KEY);
+    if !viewport.is_whole_board() {
+        hint.push_str(", the board scrolls with the cursor");
+    }
+    if state.game_type.has_drops() {
+        hint = format!(
+            "{:?} - Select from hand  {:?} - Save KIF/CSA  {}",
+            HAND_KEY, SAVE_KEY, hint
+        );
+    }
+    if state.game_type.impasse_points().is_some() {
+        hint = format!("{:?} - Declare impasse  {}", DECLARE_KEY, hint);
+    }
+    mesh_helper.draw_text(
+        ctx,
+        &hint,
+        pt(8., mesh_helper.height - 24.),
+        LIGHT_GRAY,
+        14.,
+        false,
+    );
+
+    Ok(())
+}
+
+fn cell_point(idx: usize, board_start: Point, cell_size: f32) -> Point {
+    Point::from(BoardCoord::from(idx))
+        .multiply(cell_size, cell_size)
+        .offset_point(board_start)
+}
+
+//Visible part of the range start..end of rows or columns, relative to the viewport
+fn visible_range(start: usize, end: usize, view_start: usize, view_len: usize) -> (usize, usize) {
+    let first = start.max(view_start).min(view_start + view_len);
+    let last = end.min(view_start + view_len).max(first);
+    (first - view_start, last - first)
+}
+
+fn draw_board(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+    view_start: Point,
+    cell_size: f32,
+) -> GameResult<()> {
+    let viewport = &state.viewport;
+    let board_width = cell_size * viewport.cols() as f32;
+    let board_height = cell_size * viewport.rows() as f32;
+    let background = mesh_helper.make_rect(ctx, board_width, board_height, DrawMode::fill())?;
+    let grid = mesh_helper.make_grid(
+        ctx,
+        board_width,
+        board_height,
+        viewport.cols(),
+        viewport.rows(),
+        2.,
+        BROWN,
+        None,
+    )?;
+    let border = mesh_helper.make_rect(ctx, board_width, board_height, DrawMode::stroke(3.))?;
+
+    mesh_helper.draw_coloured_mesh(ctx, background.as_ref(), view_start, CREAM);
+    let depth = state.game_type.promotion_zone_depth();
+    for (start, end) in [(0, depth), (board_rows() - depth, board_rows())].iter() {
+        let (first, count) = visible_range(*start, *end, viewport.y, viewport.rows());
+        if count > 0 {
+            let zone = mesh_helper.make_rect(
+                ctx,
+                board_width,
+                cell_size * count as f32,
+                DrawMode::fill(),
+            )?;
+            mesh_helper.draw_coloured_mesh(
+                ctx,
+                zone.as_ref(),
+                view_start.offsety(cell_size * first as f32),
+                DARK_CREAM,
+            );
+        }
+    }
+    mesh_helper.draw_mesh(ctx, grid.as_ref(), view_start);
+    mesh_helper.draw_coloured_mesh(ctx, border.as_ref(), view_start, BROWN);
+
+    //Files are numbered from the right, ranks lettered from the top
+    for i in 0..viewport.cols() {
+        mesh_helper.draw_white_text(
+            ctx,
+            &format!("{}", board_cols() - viewport.x - i),
+            view_start.offset((i as f32 + 0.5) * cell_size, -24.),
+            18.,
+            true,
+        );
+    }
+    for i in 0..viewport.rows() {
+        mesh_helper.draw_white_text(
+            ctx,
+            &format!("{}", (b'a' + (viewport.y + i) as u8) as char),
+            view_start.offset(board_width + 14., (i as f32 + 0.4) * cell_size),
+            18.,
+            true,
+        );
+    }
+
+    if let Some(last) = state.move_history.last() {
+        if viewport.contains(last.mov.to()) {
+            let cell = mesh_helper.make_rect(ctx, cell_size, cell_size, DrawMode::fill())?;
+            let BoardCoord(x, y) = BoardCoord::from(last.mov.to());
+            mesh_helper.draw_coloured_mesh(
+                ctx,
+                cell.as_ref(),
+                view_start.offset(
+                    (x - viewport.x) as f32 * cell_size,
+                    (y - viewport.y) as f32 * cell_size,
+                ),
+                alpha(APRICOT, 0.6),
+            );
+        }
+    }
+
+    Ok(())
+}
+
+fn draw_piece(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    piece: &Piece,
+    player: Player,
+    xy: Point,
+    size: f32,
+) -> GameResult<()> {
+    let dir = match player {
+        Player::Human => Direction::Up,
+        Player::Computer => Direction::Down,
+    };
+    let width = size * 0.76;
+    let height = size * 0.84;
+    let fill = mesh_helper.make_shogi_piece(ctx, width, height, &dir, DrawMode::fill())?;
+    let outline = mesh_helper.make_shogi_piece(ctx, width, height, &dir, DrawMode::stroke(2.))?;
+    let piece_start = xy.offset((size - width) * 0.5, (size - height) * 0.5);
+    mesh_helper.draw_coloured_mesh(ctx, fill.as_ref(), piece_start, APRICOT);
+    mesh_helper.draw_coloured_mesh(ctx, outline.as_ref(), piece_start, BROWN);
+    let label = piece.to_string();
+    let text_size = if label.len() > 2 { 0.3 } else { 0.4 };
+    mesh_helper.draw_text(
+        ctx,
+        &label,
+        xy.offset(size * 0.5, size * (0.52 - text_size * 0.5)),
+        if piece.promoted { DARK_RED } else { BLACK },
+        size * text_size,
+        true,
+    );
+    Ok(())
+}
+
+fn draw_target(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+    idx: usize,
+    board_start: Point,
+    cell_size: f32,
+    highlight: bool,
+) -> GameResult<()> {
+    if !state.viewport.contains(idx) {
+        return Ok(());
+    }
+    let mesh = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.15, DrawMode::fill())?;
+    let colour = if highlight {
+        LIGHT_BLUE
+    } else {
+        alpha(LIGHT_BLUE, 0.4)
+    };
+    mesh_helper.draw_coloured_mesh(
+        ctx,
+        mesh.as_ref(),
+        cell_point(idx, board_start, cell_size),
+        colour,
+    );
+    Ok(())
+}
+
+fn draw_selection(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+    board_start: Point,
+    cell_size: f32,
+) -> GameResult<()> {
+    if state.play_state.is_human(SelectingPiece) {
+        if state.selection == Selection::Board {
+            state
+                .cursor
+                .render(ctx, mesh_helper, board_start, cell_size)?;
+        }
+        for mov in state.get_targets_for_selection() {
+            draw_target(
+                ctx,
+                mesh_helper,
+                state,
+                mov.to(),
+                board_start,
+                cell_size,
+                false,
+            )?;
+        }
+    } else if state.play_state.is_human(SelectingMove) {
+        if state.selection == Selection::Board && state.viewport.contains(state.cursor.idx) {
+            state
+                .cursor
+                .render_dark(ctx, mesh_helper, board_start, cell_size)?;
+        }
+        for (i, mov) in state.get_targets_for_selection().iter().enumerate() {
+            draw_target(
+                ctx,
+                mesh_helper,
+                state,
+                mov.to(),
+                board_start,
+                cell_size,
+                i == state.move_cursor,
+            )?;
+        }
+        let via = state
+            .route_choice
+            .get(state.route_cursor)
+            .and_then(|mov| mov.via())
+            .filter(|via| state.viewport.contains(*via));
+        if let Some(via) = via {
+            let cell = mesh_helper.make_rect(ctx, cell_size, cell_size, DrawMode::stroke(4.))?;
+            mesh_helper.draw_coloured_mesh(
+                ctx,
+                cell.as_ref(),
+                cell_point(via, board_start, cell_size),
+                LIGHT_BLUE,
+            );
+        }
+    } else if state.play_state.is_computer(SelectingMove) {
+        if let Some(mov) = state.computer_move {
+            let cell = mesh_helper.make_rect(ctx, cell_size, cell_size, DrawMode::stroke(4.))?;
+            let squares = mov
+                .from()
+                .iter()
+                .chain(mov.via().iter())
+                .copied()
+                .collect::<Vec<usize>>();
+            for idx in squares {
+                if state.viewport.contains(idx) {
+                    mesh_helper.draw_coloured_mesh(
+                        ctx,
+                        cell.as_ref(),
+                        cell_point(idx, board_start, cell_size),
+                        RED,
+                    );
+                }
+            }
+            draw_target(
+                ctx,
+                mesh_helper,
+                state,
+                mov.to(),
+                board_start,
+                cell_size,
+                true,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn draw_hand(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+    player: Player,
+    start: Point,
+) -> GameResult<()> {
+    let title = match player {
+        Player::Human => "Your hand",
+        Player::Computer => "Computer's hand",
+    };
+    mesh_helper.draw_white_text(ctx, title, start, 18., false);
+    let hand = state.position.hand(player);
+    if hand.is_empty() {
+        mesh_helper.draw_text(ctx, "Empty", start.offsety(28.), LIGHT_GRAY, 14., false);
+        return Ok(());
+    }
+    let selection_box =
+        mesh_helper.make_rect(ctx, HAND_PIECE_SIZE, HAND_PIECE_SIZE, DrawMode::stroke(3.))?;
+    for (i, kind) in hand.kinds().iter().enumerate() {
+        let xy = start.offset(
+            (i % HAND_PER_ROW) as f32 * (HAND_PIECE_SIZE + 32.),
+            28. + (i / HAND_PER_ROW) as f32 * (HAND_PIECE_SIZE + 8.),
+        );
+        draw_piece(
+            ctx,
+            mesh_helper,
+            &Piece::new(*kind),
+            player,
+            xy,
+            HAND_PIECE_SIZE,
+        )?;
+        mesh_helper.draw_white_text(
+            ctx,
+            &format!("x{}", hand.count(*kind)),
+            xy.offset(HAND_PIECE_SIZE + 2., HAND_PIECE_SIZE * 0.4),
+            16.,
+            false,
+        );
+        if player == Player::Human && state.selection == Selection::Hand(i) {
+            mesh_helper.draw_coloured_mesh(ctx, selection_box.as_ref(), xy, LIGHT_BLUE);
+        }
+    }
+    Ok(())
+}
+
+fn draw_history(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State, start: Point) {
+    let skip = state.move_history.len().saturating_sub(HISTORY_LINES);
+    let moves = state
+        .move_history
+        .iter()
+        .enumerate()
+        .skip(skip)
+        .map(|(idx, mov)| format!("{: >3}. {}", idx + 1, mov))
+        .collect::<Vec<String>>()
+        .join("\n");
+    mesh_helper.draw_text(ctx, &moves, start, WHITE, 16., false);
+}
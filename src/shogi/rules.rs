@@ -0,0 +1,902 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::boards::is_in_board::IsInBoard;
+use crate::boards::{board_cols, board_rows};
+use crate::shogi::game_types::GameType;
+use crate::shogi::movement::{movement, Offset, KING};
+use crate::shogi::{Move, Piece, PieceKind, Position, Square};
+use crate::system::math::idx_to_coord;
+use crate::system::{PlayState, Player};
+
+fn orientation(player: Player) -> isize {
+    match player {
+        Player::Human => 1,
+        Player::Computer => -1,
+    }
+}
+
+//0 for the far row from the player's side of the board
+fn rows_from_end(player: Player, idx: usize) -> usize {
+    let (_, y) = idx_to_coord(idx, board_cols());
+    match player {
+        Player::Human => y,
+        Player::Computer => board_rows() - 1 - y,
+    }
+}
+
+pub(super) fn is_in_promotion_zone(game_type: &GameType, player: Player, idx: usize) -> bool {
+    rows_from_end(player, idx) < game_type.promotion_zone_depth()
+}
+
+//True if a piece of this kind would have no moves from idx
+fn is_dead_square(game_type: &GameType, player: Player, kind: PieceKind, idx: usize) -> bool {
+    rows_from_end(player, idx) < game_type.dead_rows(kind)
+}
+
+fn target(origin: (isize, isize), (x, y): Offset, dir: isize) -> Option<usize> {
+    let target = (origin.0 + x, origin.1 + y * dir);
+    if target.is_in_board() {
+        Some(BoardCoord::from(target).idx())
+    } else {
+        None
+    }
+}
+
+//Every square the piece on origin attacks, ignoring whether the move is legal
+//Double moves are in double_moves, as everything they reach is also a step
+pub(super) fn attacked_squares(
+    game_type: &GameType,
+    board: &[Square],
+    origin: usize,
+) -> Vec<usize> {
+    let player = board[origin]
+        .get_player()
+        .expect("Checked attacks from empty square");
+    let piece = board[origin].get_piece().unwrap();
+    let dir = orientation(player);
+    let movement = movement(game_type.moves_as(&piece));
+    let origin_pos: (isize, isize) = BoardCoord::from(origin).into();
+    let mut results = vec![];
+    for offset in movement.steps {
+        if let Some(idx) = target(origin_pos, *offset, dir) {
+            if board[idx].get_player() != Some(player) {
+                results.push(idx);
+            }
+        }
+    }
+    let unlimited = movement.slides.iter().map(|offset| (*offset, usize::MAX));
+    for (offset, range) in unlimited.chain(movement.ranged.iter().copied()) {
+        let mut current = origin_pos;
+        for _ in 0..range {
+            let idx = match target(current, offset, dir) {
+                Some(idx) => idx,
+                None => break,
+            };
+            current = BoardCoord::from(idx).into();
+            match board[idx].get_player() {
+                None => results.push(idx),
+                Some(other) => {
+                    if other != player {
+                        results.push(idx);
+                    }
+                    break;
+                }
+            }
+        }
+    }
+    results
+}
+
+//Lion moves that capture on the first square then move on or return, or pass by moving out and back
+fn double_moves(game_type: &GameType, board: &[Square], origin: usize) -> Vec<Move> {
+    let player = board[origin].get_player().unwrap();
+    let piece = board[origin].get_piece().unwrap();
+    let dir = orientation(player);
+    let movement = movement(game_type.moves_as(&piece));
+    let firsts = if movement.lion {
+        KING
+    } else {
+        movement.lion_lines
+    };
+    let origin_pos: (isize, isize) = BoardCoord::from(origin).into();
+    let mut results = vec![];
+    let mut can_pass = false;
+    for first in firsts {
+        let via = match target(origin_pos, *first, dir) {
+            Some(via) => via,
+            None => continue,
+        };
+        match board[via].get_player() {
+            None => can_pass = true,
+            Some(other) if other == player => {}
+            Some(_) => {
+                let via_pos: (isize, isize) = BoardCoord::from(via).into();
+                let seconds = if movement.lion {
+                    KING.to_vec()
+                } else {
+                    vec![*first, (-first.0, -first.1)]
+                };
+                for second in seconds {
+                    if let Some(to) = target(via_pos, second, dir) {
+                        if to == origin || board[to].get_player() != Some(player) {
+                            results.push(Move::Double {
+                                from: origin,
+                                via,
+                                to,
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+    if can_pass {
+        let via = firsts
+            .iter()
+            .filter_map(|first| target(origin_pos, *first, dir))
+            .find(|via| board[*via] == Square::Empty)
+            .unwrap();
+        results.push(Move::Double {
+            from: origin,
+            via,
+            to: origin,
+        });
+    }
+    results
+}
+
+fn is_royal(game_type: &GameType, square: &Square) -> bool {
+    square
+        .get_piece()
+        .map(|piece| {
+            matches!(
+                game_type.moves_as(&piece),
+                PieceKind::King | PieceKind::CrownPrince
+            )
+        })
+        .unwrap_or(false)
+}
+
+fn find_king(board: &[Square], player: Player) -> Option<usize> {
+    board
+        .iter()
+        .position(|square| *square == Square::new(player, Piece::new(PieceKind::King)))
+}
+
+//Always false for variants where royals are captured rather than mated
+pub(super) fn is_in_check(game_type: &GameType, board: &[Square], player: Player) -> bool {
+    if !game_type.uses_check() {
+        return false;
+    }
+    match find_king(board, player) {
+        Some(king) => is_attacked_by(game_type, board, king, player.opposite()),
+        None => false,
+    }
+}
+
+fn is_attacked_by(game_type: &GameType, board: &[Square], idx: usize, player: Player) -> bool {
+    board
+        .iter()
+        .enumerate()
+        .filter(|(_, square)| square.get_player() == Some(player))
+        .any(|(origin, _)| attacked_squares(game_type, board, origin).contains(&idx))
+}
+
+//For variants without check, the player loses when their last royal piece is captured
+pub(super) fn has_lost_royals(game_type: &GameType, board: &[Square], player: Player) -> bool {
+    !game_type.uses_check()
+        && !board
+            .iter()
+            .any(|square| square.get_player() == Some(player) && is_royal(game_type, square))
+}
+
+fn may_promote(
+    game_type: &GameType,
+    player: Player,
+    kind: PieceKind,
+    (from, to): (usize, usize),
+    capture: bool,
+) -> bool {
+    let from_zone = is_in_promotion_zone(game_type, player, from);
+    let to_zone = is_in_promotion_zone(game_type, player, to);
+    if game_type.promotion_needs_entry_or_capture() {
+        //Entering the zone, capturing from inside it, or a pawn or lance reaching the last row
+        let enters = to_zone && !from_zone;
+        let last_row =
+            matches!(kind, PieceKind::Pawn | PieceKind::Lance) && rows_from_end(player, to) == 0;
+        enters || (capture && from_zone) || last_row
+    } else {
+        from_zone || to_zone
+    }
+}
+
+fn step_moves(game_type: &GameType, position: &Position, origin: usize) -> Vec<Move> {
+    let player = position.to_move;
+    let piece = position.board[origin].get_piece().unwrap();
+    let promotes = !piece.promoted && game_type.promotes_to(piece.kind).is_some();
+    let mut results = vec![];
+    for to in attacked_squares(game_type, &position.board, origin) {
+        let capture = position.board[to] != Square::Empty;
+        if promotes && may_promote(game_type, player, piece.kind, (origin, to), capture) {
+            results.push(Move::Step {
+                from: origin,
+                to,
+                promote: true,
+            });
+        }
+        if piece.promoted || !is_dead_square(game_type, player, piece.kind, to) {
+            results.push(Move::Step {
+                from: origin,
+                to,
+                promote: false,
+            });
+        }
+    }
+    results.append(&mut double_moves(game_type, &position.board, origin));
+    results
+}
+
+fn has_unpromoted_pawn_on_file(board: &[Square], player: Player, file: usize) -> bool {
+    (0..board_rows()).any(|y| {
+        board[BoardCoord(file, y).idx()] == Square::new(player, Piece::new(PieceKind::Pawn))
+    })
+}
+
+fn drop_moves(game_type: &GameType, position: &Position) -> Vec<Move> {
+    let player = position.to_move;
+    let mut results = vec![];
+    for kind in position.hand(player).kinds() {
+        for (to, square) in position.board.iter().enumerate() {
+            if *square != Square::Empty || is_dead_square(game_type, player, kind, to) {
+                continue;
+            }
+            //Nifu, two unpromoted pawns on the same file
+            if kind == PieceKind::Pawn {
+                let (file, _) = idx_to_coord(to, board_cols());
+                if has_unpromoted_pawn_on_file(&position.board, player, file) {
+                    continue;
+                }
+            }
+            results.push(Move::Drop { kind, to });
+        }
+    }
+    results
+}
+
+fn take(position: &mut Position, idx: usize) -> Option<Piece> {
+    let piece = position.board[idx].get_piece();
+    position.board[idx] = Square::Empty;
+    piece
+}
+
+//Updates the position, returns the captured pieces
+pub(super) fn apply_move(game_type: &GameType, position: &mut Position, mov: &Move) -> Vec<Piece> {
+    let player = position.to_move;
+    let mut captured = vec![];
+    let mut mover = None;
+    match mov {
+        Move::Step { from, to, promote } => {
+            let mut piece = take(position, *from).expect("Attempted to move from empty square");
+            captured.extend(take(position, *to));
+            if *promote {
+                piece = piece.promote();
+            }
+            position.board[*to] = Square::new(player, piece);
+            mover = Some(piece);
+        }
+        Move::Drop { kind, to } => {
+            position.hand_mut(player).remove(*kind);
+            position.board[*to] = Square::new(player, Piece::new(*kind));
+        }
+        Move::Double { from, via, to } => {
+            let piece = take(position, *from).expect("Attempted to move from empty square");
+            captured.extend(take(position, *via));
+            captured.extend(take(position, *to));
+            position.board[*to] = Square::new(player, piece);
+            mover = Some(piece);
+        }
+    }
+    if game_type.has_drops() {
+        for piece in &captured {
+            position.hand_mut(player).add(piece.kind);
+        }
+    }
+    let is_lion = |piece: &Piece| game_type.moves_as(piece) == PieceKind::Lion;
+    position.lion_counterstrike =
+        !mover.map(|piece| is_lion(&piece)).unwrap_or(false) && captured.iter().any(is_lion);
+    position.to_move = player.opposite();
+    captured
+}
+
+fn is_self_check(game_type: &GameType, position: &Position, mov: &Move) -> bool {
+    let mut next = position.clone();
+    apply_move(game_type, &mut next, mov);
+    is_in_check(game_type, &next.board, position.to_move)
+}
+
+fn pseudo_legal_moves(game_type: &GameType, position: &Position) -> Vec<Move> {
+    let mut moves: Vec<Move> = position
+        .board
+        .iter()
+        .enumerate()
+        .filter(|(_, square)| square.get_player() == Some(position.to_move))
+        .flat_map(|(idx, _)| step_moves(game_type, position, idx))
+        .collect();
+    if game_type.has_drops() {
+        moves.append(&mut drop_moves(game_type, position));
+    }
+    moves
+}
+
+//Ignores uchifuzume, only used to see if a player has been mated
+fn has_legal_move(game_type: &GameType, position: &Position) -> bool {
+    pseudo_legal_moves(game_type, position)
+        .iter()
+        .any(|mov| !is_self_check(game_type, position, mov))
+}
+
+//Uchifuzume, dropping a pawn to give immediate checkmate
+fn is_pawn_drop_mate(game_type: &GameType, position: &Position, mov: &Move) -> bool {
+    if let Move::Drop {
+        kind: PieceKind::Pawn,
+        ..
+    } = mov
+    {
+        let mut next = position.clone();
+        apply_move(game_type, &mut next, mov);
+        is_in_check(game_type, &next.board, next.to_move) && !has_legal_move(game_type, &next)
+    } else {
+        false
+    }
+}
+
+fn is_adjacent(a: usize, b: usize) -> bool {
+    let (ax, ay) = idx_to_coord(a, board_cols());
+    let (bx, by) = idx_to_coord(b, board_cols());
+    ax.max(bx) - ax.min(bx) <= 1 && ay.max(by) - ay.min(by) <= 1
+}
+
+//Chu's rules against trading lions
+//After a lion is taken by another piece, the reply can't take a lion except with a lion
+//A lion can't take a protected lion two squares away unless it first took something
+//other than a pawn or go-between on the way
+fn is_forbidden_lion_capture(game_type: &GameType, position: &Position, mov: &Move) -> bool {
+    let from = match mov.from() {
+        Some(from) => from,
+        None => return false,
+    };
+    let board = &position.board;
+    let is_lion = |idx: usize| {
+        board[idx]
+            .get_piece()
+            .map(|piece| game_type.moves_as(&piece) == PieceKind::Lion)
+            .unwrap_or(false)
+    };
+    let takes_lion = |idx: usize| idx != from && is_lion(idx);
+    let to = mov.to();
+    if !takes_lion(to) && !mov.via().map(takes_lion).unwrap_or(false) {
+        return false;
+    }
+    if !is_lion(from) {
+        return position.lion_counterstrike;
+    }
+    if !takes_lion(to) || is_adjacent(from, to) {
+        return false;
+    }
+    let bridge = mov.via().and_then(|via| board[via].get_piece());
+    if let Some(piece) = bridge {
+        if !matches!(
+            game_type.moves_as(&piece),
+            PieceKind::Pawn | PieceKind::GoBetween
+        ) {
+            return false;
+        }
+    }
+    let mut next = position.clone();
+    apply_move(game_type, &mut next, mov);
+    is_attacked_by(game_type, &next.board, to, next.to_move)
+}
+
+pub(super) fn legal_moves(game_type: &GameType, position: &Position) -> Vec<Move> {
+    let moves = pseudo_legal_moves(game_type, position);
+    if !game_type.uses_check() {
+        return moves
+            .into_iter()
+            .filter(|mov| !is_forbidden_lion_capture(game_type, position, mov))
+            .collect();
+    }
+    moves
+        .into_iter()
+        .filter(|mov| !is_self_check(game_type, position, mov))
+        .filter(|mov| !is_pawn_drop_mate(game_type, position, mov))
+        .collect()
+}
+
+fn impasse_value(kind: PieceKind) -> usize {
+    match kind {
+        PieceKind::King => 0,
+        PieceKind::Rook | PieceKind::Bishop => 5,
+        _ => 1,
+    }
+}
+
+fn impasse_points(position: &Position, player: Player) -> usize {
+    let on_board: usize = position
+        .board
+        .iter()
+        .filter(|square| square.get_player() == Some(player))
+        .filter_map(|square| square.get_piece())
+        .map(|piece| impasse_value(piece.kind))
+        .sum();
+    let in_hand: usize = position
+        .hand(player)
+        .pieces
+        .iter()
+        .map(|kind| impasse_value(*kind))
+        .sum();
+    on_board + in_hand
+}
+
+//Jishogi, once both kings have entered the enemy camp neither can realistically be mated
+//so the player to move may declare, unless in check, and the game is decided on points
+//None if the declaration isn't allowed, otherwise a player short of the target loses
+pub(super) fn impasse_result(game_type: &GameType, position: &Position) -> Option<PlayState> {
+    let needed = game_type.impasse_points()?;
+    if is_in_check(game_type, &position.board, position.to_move) {
+        return None;
+    }
+    let entered = |player| {
+        find_king(&position.board, player)
+            .map(|idx| is_in_promotion_zone(game_type, player, idx))
+            .unwrap_or(false)
+    };
+    if !entered(Player::Human) || !entered(Player::Computer) {
+        return None;
+    }
+    let human = impasse_points(position, Player::Human) >= needed;
+    let computer = impasse_points(position, Player::Computer) >= needed;
+    Some(match (human, computer) {
+        (true, false) => PlayState::HumanWin,
+        (false, true) => PlayState::ComputerWin,
+        _ => PlayState::Draw,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use serial_test::serial;
+
+    const E: Square = Square::Empty;
+
+    fn h(kind: PieceKind) -> Square {
+        Square::Human(Piece::new(kind))
+    }
+
+    fn c(kind: PieceKind) -> Square {
+        Square::Computer(Piece::new(kind))
+    }
+
+    fn mini_position(board: Vec<Square>) -> Position {
+        set_board_size((5, 5));
+        Position::new(board)
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_start_moves() {
+        set_board_size((5, 5));
+        let position = Position::new(GameType::Mini.get_init_board());
+        //Pawn 1, king 1, gold 2, silver 3, bishop 4, rook 3
+        assert_eq!(legal_moves(&GameType::Mini, &position).len(), 14);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_standard_start_moves() {
+        set_board_size((9, 9));
+        let position = Position::new(GameType::Standard.get_init_board());
+        assert_eq!(legal_moves(&GameType::Standard, &position).len(), 30);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_knight_and_lance() {
+        set_board_size((9, 9));
+        let mut board = vec![E; 81];
+        board[4] = c(PieceKind::King);
+        board[76] = h(PieceKind::King);
+        board[BoardCoord(2, 3).idx()] = h(PieceKind::Knight);
+        board[BoardCoord(8, 4).idx()] = h(PieceKind::Lance);
+        board[BoardCoord(8, 1).idx()] = c(PieceKind::Pawn);
+        let position = Position::new(board);
+        let moves = legal_moves(&GameType::Standard, &position);
+        let knight: Vec<&Move> = moves
+            .iter()
+            .filter(|mov| mov.from() == Some(BoardCoord(2, 3).idx()))
+            .collect();
+        //Knights can't stop on the last two rows without promoting
+        assert_eq!(knight.len(), 2);
+        assert!(knight
+            .iter()
+            .all(|mov| matches!(mov, Move::Step { promote: true, .. })));
+        let lance: Vec<usize> = moves
+            .iter()
+            .filter(|mov| mov.from() == Some(BoardCoord(8, 4).idx()))
+            .map(|mov| mov.to())
+            .collect();
+        //Lance slides forward up to and including the capture, with a choice in the zone
+        assert!(lance.contains(&BoardCoord(8, 3).idx()));
+        assert_eq!(
+            lance
+                .iter()
+                .filter(|to| **to == BoardCoord(8, 1).idx())
+                .count(),
+            2
+        );
+        assert!(!lance.contains(&BoardCoord(8, 0).idx()));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_impasse() {
+        set_board_size((9, 9));
+        let mut board = vec![E; 81];
+        board[BoardCoord(0, 1).idx()] = h(PieceKind::King);
+        board[BoardCoord(8, 8).idx()] = c(PieceKind::King);
+        let mut position = Position::new(board);
+        for _ in 0..5 {
+            position.hand_mut(Player::Human).add(PieceKind::Rook);
+        }
+        assert_eq!(
+            impasse_result(&GameType::Standard, &position),
+            Some(PlayState::HumanWin)
+        );
+        assert_eq!(impasse_result(&GameType::Mini, &position), None);
+        //Can't declare while in check
+        position.board[BoardCoord(0, 4).idx()] = c(PieceKind::Rook);
+        assert_eq!(impasse_result(&GameType::Standard, &position), None);
+        position.board[BoardCoord(0, 4).idx()] = E;
+        position.board[BoardCoord(8, 8).idx()] = E;
+        position.board[BoardCoord(8, 4).idx()] = c(PieceKind::King);
+        assert_eq!(impasse_result(&GameType::Standard, &position), None);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_promotion_options() {
+        #[rustfmt::skip]
+        let position = mini_position(vec![
+            E,            E, E, E, c(PieceKind::King),
+            h(PieceKind::Pawn), E, E, E, E,
+            E,            E, h(PieceKind::Silver), E, E,
+            E,            E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        let moves = legal_moves(&GameType::Mini, &position);
+        //Pawn must promote on the last row
+        assert!(moves.contains(&Move::Step {
+            from: 5,
+            to: 0,
+            promote: true
+        }));
+        assert!(!moves.contains(&Move::Step {
+            from: 5,
+            to: 0,
+            promote: false
+        }));
+        //Silver is not yet able to reach the zone
+        assert!(moves.contains(&Move::Step {
+            from: 12,
+            to: 7,
+            promote: false
+        }));
+        assert!(!moves.contains(&Move::Step {
+            from: 12,
+            to: 7,
+            promote: true
+        }));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_drops_nifu_and_dead_squares() {
+        #[rustfmt::skip]
+        let mut position = mini_position(vec![
+            E, E, E, E, c(PieceKind::King),
+            E, E, E, E, E,
+            E, E, E, E, E,
+            h(PieceKind::Pawn), E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        position.hand_mut(Player::Human).add(PieceKind::Pawn);
+        let drops: Vec<usize> = legal_moves(&GameType::Mini, &position)
+            .into_iter()
+            .filter_map(|mov| match mov {
+                Move::Drop { to, .. } => Some(to),
+                _ => None,
+            })
+            .collect();
+        //Not on the first file (nifu) and not on the last row
+        assert!(drops.iter().all(|to| to % 5 != 0));
+        assert!(drops.iter().all(|to| *to >= 5));
+        assert!(drops.contains(&6));
+        assert!(drops.contains(&23));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_uchifuzume() {
+        #[rustfmt::skip]
+        let mut position = mini_position(vec![
+            E, E, E, c(PieceKind::Pawn), c(PieceKind::King),
+            E, E, E, h(PieceKind::Gold), E,
+            E, E, E, h(PieceKind::Silver), E,
+            E, E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        position.hand_mut(Player::Human).add(PieceKind::Pawn);
+        let mate_drop = Move::Drop {
+            kind: PieceKind::Pawn,
+            to: 9,
+        };
+        //Dropping on 1b would be checkmate so isn't allowed
+        assert!(!legal_moves(&GameType::Mini, &position).contains(&mate_drop));
+
+        //Gold moving to the same square is checkmate and is allowed
+        let gold_mate = Move::Step {
+            from: 8,
+            to: 9,
+            promote: false,
+        };
+        assert!(legal_moves(&GameType::Mini, &position).contains(&gold_mate));
+        let mut next = position.clone();
+        apply_move(&GameType::Mini, &mut next, &gold_mate);
+        assert!(is_in_check(&GameType::Mini, &next.board, Player::Computer));
+        assert!(legal_moves(&GameType::Mini, &next).is_empty());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_capture_goes_to_hand_unpromoted() {
+        #[rustfmt::skip]
+        let mut position = mini_position(vec![
+            E, E, E, E, c(PieceKind::King),
+            E, E, E, E, E,
+            E, E, Square::Computer(Piece::new(PieceKind::Silver).promote()), E, E,
+            E, E, h(PieceKind::Rook), E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        let captured = apply_move(
+            &GameType::Mini,
+            &mut position,
+            &Move::Step {
+                from: 17,
+                to: 12,
+                promote: false,
+            },
+        );
+        assert_eq!(captured, vec![Piece::new(PieceKind::Silver).promote()]);
+        assert_eq!(position.hand(Player::Human).count(PieceKind::Silver), 1);
+        assert_eq!(position.to_move, Player::Computer);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_cant_move_into_check() {
+        #[rustfmt::skip]
+        let position = mini_position(vec![
+            E, E, E, E, c(PieceKind::King),
+            E, E, E, E, E,
+            E, c(PieceKind::Rook), E, E, E,
+            E, E, E, E, E,
+            h(PieceKind::King), E, E, E, E,
+        ]);
+        let moves = legal_moves(&GameType::Mini, &position);
+        assert!(!moves.iter().any(|mov| mov.to() == 21));
+        assert!(moves.contains(&Move::Step {
+            from: 20,
+            to: 15,
+            promote: false
+        }));
+    }
+
+    fn chu_position(pieces: &[((usize, usize), Square)]) -> Position {
+        set_board_size((12, 12));
+        let mut board = vec![E; 144];
+        board[BoardCoord(0, 0).idx()] = c(PieceKind::King);
+        board[BoardCoord(11, 11).idx()] = h(PieceKind::King);
+        for ((x, y), square) in pieces {
+            board[BoardCoord(*x, *y).idx()] = *square;
+        }
+        Position::new(board)
+    }
+
+    fn idx(x: usize, y: usize) -> usize {
+        BoardCoord(x, y).idx()
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_chu_setup() {
+        set_board_size((12, 12));
+        let board = GameType::Chu.get_init_board();
+        let count = |player| {
+            board
+                .iter()
+                .filter(|square| square.get_player() == Some(player))
+                .count()
+        };
+        assert_eq!(count(Player::Human), 46);
+        assert_eq!(count(Player::Computer), 46);
+        for (i, square) in board.iter().enumerate() {
+            assert_eq!(square.get_piece(), board[143 - i].get_piece());
+        }
+        let position = Position::new(board);
+        assert!(!legal_moves(&GameType::Chu, &position).is_empty());
+        assert!(!has_lost_royals(
+            &GameType::Chu,
+            &position.board,
+            Player::Human
+        ));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_lion_double_moves() {
+        let position = chu_position(&[((5, 6), h(PieceKind::Lion)), ((5, 5), c(PieceKind::Pawn))]);
+        let moves = legal_moves(&GameType::Chu, &position);
+        let from = idx(5, 6);
+        //Capturing without moving, capturing then moving on, passing and jumping
+        assert!(moves.contains(&Move::Double {
+            from,
+            via: idx(5, 5),
+            to: from
+        }));
+        assert!(moves.contains(&Move::Double {
+            from,
+            via: idx(5, 5),
+            to: idx(6, 4)
+        }));
+        assert!(moves.iter().any(
+            |mov| matches!(mov, Move::Double { via, to, .. } if *to == from && *via != idx(5, 5))
+        ));
+        assert!(moves.contains(&Move::Step {
+            from,
+            to: idx(3, 4),
+            promote: false
+        }));
+
+        let mut next = position.clone();
+        let captured = apply_move(
+            &GameType::Chu,
+            &mut next,
+            &Move::Double {
+                from,
+                via: idx(5, 5),
+                to: from,
+            },
+        );
+        assert_eq!(captured, vec![Piece::new(PieceKind::Pawn)]);
+        assert_eq!(next.board[from], h(PieceKind::Lion));
+        assert_eq!(next.board[idx(5, 5)], E);
+        //No drops in Chu
+        assert!(next.hand(Player::Human).is_empty());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_lion_trading() {
+        let jump = Move::Step {
+            from: idx(5, 7),
+            to: idx(5, 5),
+            promote: false,
+        };
+        let mut position =
+            chu_position(&[((5, 7), h(PieceKind::Lion)), ((5, 5), c(PieceKind::Lion))]);
+        assert!(legal_moves(&GameType::Chu, &position).contains(&jump));
+
+        //Protected by the gold so can't be taken from two squares away
+        position.board[idx(5, 4)] = c(PieceKind::Gold);
+        assert!(!legal_moves(&GameType::Chu, &position).contains(&jump));
+
+        //Unless something worth more than a pawn is taken on the way
+        let bridge = Move::Double {
+            from: idx(5, 7),
+            via: idx(5, 6),
+            to: idx(5, 5),
+        };
+        position.board[idx(5, 6)] = c(PieceKind::Pawn);
+        assert!(!legal_moves(&GameType::Chu, &position).contains(&bridge));
+        position.board[idx(5, 6)] = c(PieceKind::Silver);
+        assert!(legal_moves(&GameType::Chu, &position).contains(&bridge));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_lion_counterstrike() {
+        let mut position = chu_position(&[
+            ((5, 5), c(PieceKind::Lion)),
+            ((5, 9), h(PieceKind::Rook)),
+            ((4, 6), h(PieceKind::Lion)),
+        ]);
+        let rook_takes = Move::Step {
+            from: idx(5, 9),
+            to: idx(5, 5),
+            promote: false,
+        };
+        let lion_takes = Move::Step {
+            from: idx(4, 6),
+            to: idx(5, 5),
+            promote: false,
+        };
+        assert!(legal_moves(&GameType::Chu, &position).contains(&rook_takes));
+        position.lion_counterstrike = true;
+        let moves = legal_moves(&GameType::Chu, &position);
+        assert!(!moves.contains(&rook_takes));
+        assert!(moves.contains(&lion_takes));
+
+        //Set when a lion is taken by anything but a lion
+        position.lion_counterstrike = false;
+        apply_move(&GameType::Chu, &mut position, &rook_takes);
+        assert!(position.lion_counterstrike);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_chu_promotion() {
+        let position = chu_position(&[
+            ((3, 4), h(PieceKind::Silver)),
+            ((8, 3), h(PieceKind::Silver)),
+            ((7, 2), c(PieceKind::Pawn)),
+        ]);
+        let moves = legal_moves(&GameType::Chu, &position);
+        let promotes = |from, to| {
+            moves.contains(&Move::Step {
+                from,
+                to,
+                promote: true,
+            })
+        };
+        //Entering the zone
+        assert!(promotes(idx(3, 4), idx(3, 3)));
+        //Moving within the zone only with a capture
+        assert!(!promotes(idx(8, 3), idx(8, 2)));
+        assert!(promotes(idx(8, 3), idx(7, 2)));
+        //Never forced
+        assert!(moves.contains(&Move::Step {
+            from: idx(8, 3),
+            to: idx(7, 2),
+            promote: false
+        }));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_royal_capture() {
+        let mut position = chu_position(&[(
+            (4, 4),
+            Square::Human(Piece::new(PieceKind::DrunkElephant).promote()),
+        )]);
+        let king = idx(11, 11);
+        position.board[king] = E;
+        //The crown prince is enough to carry on
+        assert!(!has_lost_royals(
+            &GameType::Chu,
+            &position.board,
+            Player::Human
+        ));
+        assert!(!is_in_check(&GameType::Chu, &position.board, Player::Human));
+        position.board[idx(4, 4)] = E;
+        assert!(has_lost_royals(
+            &GameType::Chu,
+            &position.board,
+            Player::Human
+        ));
+        assert!(!has_lost_royals(
+            &GameType::Standard,
+            &position.board,
+            Player::Human
+        ));
+    }
+}
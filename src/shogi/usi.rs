@@ -0,0 +1,607 @@
+use crate::boards::set_board_size;
+use crate::shogi::ai::{search, Iteration, Limits, Table, MATE};
+use crate::shogi::game_types::GameType;
+use crate::shogi::mate::{find_mate, MateResult};
+use crate::shogi::rules::{apply_move, legal_moves};
+use crate::shogi::{
+    make_zobrist, parse_square_name, square_name, Move, Piece, PieceKind, Position, Square,
+};
+use crate::system::transposition_table::TranspositionTable;
+use crate::system::zobrist::Zobrist;
+use crate::system::Player;
+use std::io;
+use std::io::BufRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::thread;
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+//Universal Shogi Interface, lets GUIs and scripts use the rules and AI over stdin/stdout
+//The human is always sente (black, uppercase in SFEN) and the computer gote
+
+const MAX_DEPTH: usize = 64;
+const TABLE_SIZE: usize = 1 << 20;
+//Kept back from the time available for a move to cover output and scheduling
+const TIME_MARGIN: Duration = Duration::from_millis(100);
+const MIN_TIME: Duration = Duration::from_millis(10);
+//Share of the remaining time used for one move when there's no byoyomi or increment
+const MOVES_TO_GO: u32 = 40;
+const VARIANT_OPTION: &str = "UCI_Variant";
+const VARIANTS: [(&str, GameType); 2] =
+    [("shogi", GameType::Standard), ("minishogi", GameType::Mini)];
+
+fn sfen_piece(game_type: &GameType, chr: char, promoted: bool) -> Option<Square> {
+    let kind = piece_kind(chr.to_ascii_uppercase())?;
+    let piece = if promoted {
+        game_type
+            .promotes_to(kind)
+            .map(|_| Piece::new(kind).promote())?
+    } else {
+        Piece::new(kind)
+    };
+    Some(if chr.is_ascii_uppercase() {
+        Square::Human(piece)
+    } else {
+        Square::Computer(piece)
+    })
+}
+
+fn piece_kind(letter: char) -> Option<PieceKind> {
+    [
+        PieceKind::King,
+        PieceKind::Rook,
+        PieceKind::Bishop,
+        PieceKind::Gold,
+        PieceKind::Silver,
+        PieceKind::Knight,
+        PieceKind::Lance,
+        PieceKind::Pawn,
+    ]
+    .iter()
+    .find(|kind| kind.abbreviation() == letter.to_string())
+    .copied()
+}
+
+fn game_type_for_ranks(ranks: usize) -> Result<GameType, String> {
+    VARIANTS
+        .iter()
+        .map(|(_, game_type)| game_type)
+        .find(|game_type| game_type.get_board_size() == (ranks, ranks))
+        .cloned()
+        .ok_or(format!("Unsupported board size: {} ranks", ranks))
+}
+
+//e.g. lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1
+//Sets the board size as the variant is picked from the number of ranks
+fn parse_sfen(sfen: &[&str]) -> Result<(GameType, Position), String> {
+    let (rows, side, hands) = match sfen {
+        [rows, side, hands, ..] => (rows, side, hands),
+        _ => return Err(format!("Incomplete SFEN: {}", sfen.join(" "))),
+    };
+    let ranks: Vec<&str> = rows.split('/').collect();
+    let game_type = game_type_for_ranks(ranks.len())?;
+    let (_, cols) = game_type.get_board_size();
+    let mut board = vec![];
+    for rank in ranks {
+        let mut promoted = false;
+        let start = board.len();
+        for chr in rank.chars() {
+            if let Some(count) = chr.to_digit(10) {
+                board.extend(vec![Square::Empty; count as usize]);
+            } else if chr == '+' {
+                promoted = true;
+                continue;
+            } else {
+                board.push(
+                    sfen_piece(&game_type, chr, promoted)
+                        .ok_or(format!("Invalid piece: {}", chr))?,
+                );
+            }
+            promoted = false;
+        }
+        if board.len() - start != cols {
+            return Err(format!("Rank {} does not have {} files", rank, cols));
+        }
+    }
+    set_board_size(game_type.get_board_size());
+    let mut position = Position::new(board);
+    position.to_move = match *side {
+        "b" => Player::Human,
+        "w" => Player::Computer,
+        _ => return Err(format!("Invalid side to move: {}", side)),
+    };
+    if *hands != "-" {
+        //No more of a kind can be in hand than the variant starts with
+        let init_board = game_type.get_init_board();
+        let total = |kind: PieceKind| {
+            init_board
+                .iter()
+                .filter(|square| square.get_piece().map(|piece| piece.kind) == Some(kind))
+                .count()
+        };
+        let mut count = 0_usize;
+        for chr in hands.chars() {
+            if let Some(digit) = chr.to_digit(10) {
+                count = count
+                    .checked_mul(10)
+                    .and_then(|count| count.checked_add(digit as usize))
+                    .ok_or(format!("Invalid hand count in: {}", hands))?;
+                continue;
+            }
+            let kind = piece_kind(chr.to_ascii_uppercase())
+                .filter(|kind| *kind != PieceKind::King)
+                .ok_or(format!("Invalid piece in hand: {}", chr))?;
+            if count > total(kind) {
+                return Err(format!("Too many in hand: {}{}", count, chr));
+            }
+            let player = if chr.is_ascii_uppercase() {
+                Player::Human
+            } else {
+                Player::Computer
+            };
+            for _ in 0..count.max(1) {
+                position.hand_mut(player).add(kind);
+            }
+            count = 0;
+        }
+    }
+    Ok((game_type, position))
+}
+
+//e.g. 7g7f, 8h2b+ or P*5e
+fn format_move(mov: &Move) -> String {
+    match mov {
+        Move::Step { from, to, promote } => format!(
+            "{}{}{}",
+            square_name(*from),
+            square_name(*to),
+            if *promote { "+" } else { "" }
+        ),
+        Move::Drop { kind, to } => format!("{}*{}", kind.abbreviation(), square_name(*to)),
+        //Only reachable in Chu which has no SFEN support, written as both steps
+        Move::Double { from, via, to } => format!(
+            "{}{}{}",
+            square_name(*from),
+            square_name(*via),
+            square_name(*to)
+        ),
+    }
+}
+
+fn parse_move(text: &str) -> Option<Move> {
+    if let Some(drop) = text.get(1..2).filter(|chr| *chr == "*") {
+        let kind = piece_kind(text.chars().next()?)?;
+        let to = parse_square_name(text.get(drop.len() + 1..)?)?;
+        return Some(Move::Drop { kind, to });
+    }
+    let (squares, promote) = match text.strip_suffix('+') {
+        Some(squares) => (squares, true),
+        None => (text, false),
+    };
+    Some(Move::Step {
+        from: parse_square_name(squares.get(0..2)?)?,
+        to: parse_square_name(squares.get(2..)?)?,
+        promote,
+    })
+}
+
+fn format_score(score: i32) -> String {
+    if score.abs() >= MATE - MAX_DEPTH as i32 {
+        format!("mate {}", score.signum() * (MATE - score.abs()))
+    } else {
+        format!("cp {}", score)
+    }
+}
+
+#[derive(Debug, Default, Eq, PartialEq)]
+struct GoOptions {
+    depth: Option<usize>,
+    movetime: Option<u64>,
+    btime: u64,
+    wtime: u64,
+    binc: u64,
+    winc: u64,
+    byoyomi: u64,
+    infinite: bool,
+    //Some(None) is an unlimited mate search
+    mate: Option<Option<u64>>,
+}
+
+impl GoOptions {
+    fn parse(args: &[&str]) -> GoOptions {
+        let mut options = GoOptions::default();
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            let mut value = || iter.next().and_then(|value| value.parse::<u64>().ok());
+            match *arg {
+                "depth" => options.depth = value().map(|depth| depth as usize),
+                "movetime" => options.movetime = value(),
+                "btime" => options.btime = value().unwrap_or(0),
+                "wtime" => options.wtime = value().unwrap_or(0),
+                "binc" => options.binc = value().unwrap_or(0),
+                "winc" => options.winc = value().unwrap_or(0),
+                "byoyomi" => options.byoyomi = value().unwrap_or(0),
+                "infinite" => options.infinite = true,
+                "mate" => options.mate = Some(value()),
+                _ => {}
+            }
+        }
+        options
+    }
+
+    //None if there's no time limit
+    fn time_budget(&self, player: Player) -> Option<Duration> {
+        if self.infinite {
+            return None;
+        }
+        let (time, inc) = match player {
+            Player::Human => (self.btime, self.binc),
+            Player::Computer => (self.wtime, self.winc),
+        };
+        let total = match self.movetime {
+            Some(movetime) => Duration::from_millis(movetime),
+            None if time + inc + self.byoyomi > 0 => {
+                Duration::from_millis(time) / MOVES_TO_GO
+                    + Duration::from_millis(inc + self.byoyomi)
+            }
+            None => return None,
+        };
+        Some(total.saturating_sub(TIME_MARGIN).max(MIN_TIME))
+    }
+}
+
+struct Engine {
+    variant: GameType,
+    game_type: GameType,
+    position: Position,
+    zobrist: Arc<Zobrist>,
+    //Held by the search thread while searching
+    table: Option<Table>,
+    search: Option<(JoinHandle<Table>, Arc<AtomicBool>)>,
+}
+
+impl Engine {
+    fn new() -> Self {
+        let game_type = GameType::Standard;
+        set_board_size(game_type.get_board_size());
+        Engine {
+            variant: game_type.clone(),
+            position: Position::new(game_type.get_init_board()),
+            zobrist: Arc::new(make_zobrist(game_type.get_board_cell_count())),
+            table: Some(TranspositionTable::new(TABLE_SIZE)),
+            search: None,
+            game_type,
+        }
+    }
+
+    //Returns false when the engine should quit
+    fn handle(&mut self, line: &str) -> bool {
+        let tokens: Vec<&str> = line.split_whitespace().collect();
+        let (command, args) = match tokens.split_first() {
+            Some((command, args)) => (*command, args),
+            None => return true,
+        };
+        match command {
+            "isready" => println!("readyok"),
+            "stop" => self.finish_search(true),
+            "quit" => {
+                self.finish_search(true);
+                return false;
+            }
+            _ => {
+                self.finish_search(true);
+                match command {
+                    "usi" => self.identify(),
+                    "setoption" => self.set_option(args),
+                    "usinewgame" => {
+                        if let Some(table) = self.table.as_mut() {
+                            table.clear();
+                        }
+                    }
+                    "position" => {
+                        if let Err(err) = self.set_position(args) {
+                            println!("info string error: {}", err);
+                        }
+                    }
+                    "go" => self.go(args),
+                    "gameover" => {}
+                    _ => println!("info string unknown command: {}", command),
+                }
+            }
+        }
+        true
+    }
+
+    fn identify(&self) {
+        println!(
+            "id name {} {}",
+            env!("CARGO_PKG_NAME"),
+            env!("CARGO_PKG_VERSION")
+        );
+        println!("id author {}", env!("CARGO_PKG_AUTHORS"));
+        let vars: Vec<String> = VARIANTS
+            .iter()
+            .map(|(name, _)| format!("var {}", name))
+            .collect();
+        println!(
+            "option name {} type combo default {} {}",
+            VARIANT_OPTION,
+            VARIANTS[0].0,
+            vars.join(" ")
+        );
+        println!("usiok");
+    }
+
+    //setoption name <id> value <x>
+    fn set_option(&mut self, args: &[&str]) {
+        if let ["name", VARIANT_OPTION, "value", value] = args {
+            match VARIANTS.iter().find(|(name, _)| name == value) {
+                Some((_, game_type)) => self.variant = game_type.clone(),
+                None => println!("info string unknown variant: {}", value),
+            }
+        }
+    }
+
+    //position [startpos | sfen <sfen>] [moves <move> ...]
+    //The engine is only changed if the whole command is valid
+    fn set_position(&mut self, args: &[&str]) -> Result<(), String> {
+        match self.parse_position(args) {
+            Ok((game_type, position)) => {
+                if game_type != self.game_type {
+                    self.zobrist = Arc::new(make_zobrist(game_type.get_board_cell_count()));
+                    self.table = Some(TranspositionTable::new(TABLE_SIZE));
+                    self.game_type = game_type;
+                }
+                self.position = position;
+                Ok(())
+            }
+            Err(err) => {
+                //Parsing sets the board size for the new position
+                set_board_size(self.game_type.get_board_size());
+                Err(err)
+            }
+        }
+    }
+
+    fn parse_position(&self, args: &[&str]) -> Result<(GameType, Position), String> {
+        let moves_idx = args
+            .iter()
+            .position(|arg| *arg == "moves")
+            .unwrap_or(args.len());
+        let (game_type, mut position) = match args.first() {
+            Some(&"startpos") => {
+                set_board_size(self.variant.get_board_size());
+                (
+                    self.variant.clone(),
+                    Position::new(self.variant.get_init_board()),
+                )
+            }
+            Some(&"sfen") => parse_sfen(&args[1..moves_idx])?,
+            _ => return Err(String::from("Expected startpos or sfen")),
+        };
+        for text in args.iter().skip(moves_idx + 1) {
+            let mov = parse_move(text).ok_or(format!("Invalid move: {}", text))?;
+            if !legal_moves(&game_type, &position).contains(&mov) {
+                return Err(format!("Illegal move: {}", text));
+            }
+            apply_move(&game_type, &mut position, &mov);
+        }
+        Ok((game_type, position))
+    }
+
+    fn go(&mut self, args: &[&str]) {
+        let options = GoOptions::parse(args);
+        let stop = Arc::new(AtomicBool::new(false));
+        let game_type = self.game_type.clone();
+        let position = self.position.clone();
+        let zobrist = self.zobrist.clone();
+        let mut table = self.table.take().expect("Search table missing");
+        let thread_stop = stop.clone();
+        let handle = if let Some(mate_time) = options.mate {
+            let deadline = mate_time.map(|time| Instant::now() + Duration::from_millis(time));
+            thread::spawn(move || {
+                let limits = Limits {
+                    deadline,
+                    stop: Some(&thread_stop),
+                };
+                match find_mate(&game_type, &position, limits) {
+                    MateResult::Mate(line) => {
+                        let moves: Vec<String> = line.iter().map(format_move).collect();
+                        println!("checkmate {}", moves.join(" "));
+                    }
+                    MateResult::NoMate => println!("checkmate nomate"),
+                    MateResult::Timeout => println!("checkmate timeout"),
+                }
+                table
+            })
+        } else {
+            let deadline = options
+                .time_budget(position.to_move)
+                .map(|budget| Instant::now() + budget);
+            let depth = match (options.depth, deadline, options.infinite) {
+                (Some(depth), _, _) => depth,
+                (None, None, false) => game_type.ai_depth(),
+                _ => MAX_DEPTH,
+            };
+            thread::spawn(move || {
+                let start = Instant::now();
+                let limits = Limits {
+                    deadline,
+                    stop: Some(&thread_stop),
+                };
+                let mut on_iteration = |iteration: &Iteration| {
+                    println!(
+                        "info depth {} time {} score {}{}",
+                        iteration.depth,
+                        start.elapsed().as_millis(),
+                        format_score(iteration.score),
+                        iteration
+                            .best
+                            .map(|mov| format!(" pv {}", format_move(&mov)))
+                            .unwrap_or_default()
+                    );
+                };
+                let best = search(
+                    &game_type,
+                    &zobrist,
+                    &mut table,
+                    &position,
+                    depth,
+                    limits,
+                    &mut on_iteration,
+                );
+                match best {
+                    Some(mov) => println!("bestmove {}", format_move(&mov)),
+                    None => println!("bestmove resign"),
+                }
+                table
+            })
+        };
+        self.search = Some((handle, stop));
+    }
+
+    //Waits for the current search (if any) to finish, stopping it early if requested
+    fn finish_search(&mut self, stop: bool) {
+        if let Some((handle, flag)) = self.search.take() {
+            if stop {
+                flag.store(true, Ordering::Relaxed);
+            }
+            self.table = Some(handle.join().expect("Search thread failed"));
+        }
+    }
+}
+
+pub fn run() {
+    let mut engine = Engine::new();
+    for line in io::stdin().lock().lines() {
+        match line {
+            Ok(line) => {
+                if !engine.handle(&line) {
+                    return;
+                }
+            }
+            Err(_) => break,
+        }
+    }
+    engine.finish_search(false);
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::{board_cols, board_rows};
+    use serial_test::serial;
+
+    const START: &str = "lnsgkgsnl/1r5b1/ppppppppp/9/9/9/PPPPPPPPP/1B5R1/LNSGKGSNL b - 1";
+
+    fn sfen(text: &str) -> Result<(GameType, Position), String> {
+        parse_sfen(&text.split_whitespace().collect::<Vec<&str>>())
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_parse_start_sfen() {
+        let (game_type, position) = sfen(START).unwrap();
+        assert_eq!(game_type, GameType::Standard);
+        assert_eq!(position, Position::new(GameType::Standard.get_init_board()));
+        let (game_type, position) = sfen("rbsgk/4p/5/P4/KGSBR b - 1").unwrap();
+        assert_eq!(game_type, GameType::Mini);
+        assert_eq!(position, Position::new(GameType::Mini.get_init_board()));
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_parse_sfen_hands_and_promotions() {
+        let (_, position) = sfen("4k4/9/4+P4/9/9/9/9/9/4K4 w S2Pb10p 42").unwrap();
+        assert_eq!(position.to_move, Player::Computer);
+        assert_eq!(
+            position.board[22],
+            Square::Human(Piece::new(PieceKind::Pawn).promote())
+        );
+        assert_eq!(position.hand(Player::Human).count(PieceKind::Silver), 1);
+        assert_eq!(position.hand(Player::Human).count(PieceKind::Pawn), 2);
+        assert_eq!(position.hand(Player::Computer).count(PieceKind::Bishop), 1);
+        assert_eq!(position.hand(Player::Computer).count(PieceKind::Pawn), 10);
+        assert!(sfen("4k4/9/9 b - 1").is_err());
+        assert!(sfen("4k3/9/9/9/9/9/9/9/4K4 b - 1").is_err());
+        assert!(sfen("4k4/9/9/9/9/9/9/9/4+K4 b - 1").is_err());
+        assert!(sfen("4k4/9/9/9/9/9/9/9/4K4 b 19P 1").is_err());
+        assert!(sfen("4k4/9/9/9/9/9/9/9/4K4 b 99999999999999999999999p 1").is_err());
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_moves() {
+        set_board_size((9, 9));
+        for text in ["7g7f", "8h2b+", "P*5e"].iter() {
+            assert_eq!(format_move(&parse_move(text).unwrap()), *text);
+        }
+        assert_eq!(parse_move("7g7"), None);
+        assert_eq!(parse_move("K*0a"), None);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_position_command() {
+        let mut engine = Engine::new();
+        engine
+            .set_position(&["startpos", "moves", "7g7f", "3c3d", "8h2b+"])
+            .unwrap();
+        assert_eq!(
+            engine.position.hand(Player::Human).count(PieceKind::Bishop),
+            1
+        );
+        assert_eq!(engine.position.to_move, Player::Computer);
+        assert!(engine.set_position(&["startpos", "moves", "7g7e"]).is_err());
+        engine.set_option(&["name", VARIANT_OPTION, "value", "minishogi"]);
+        engine.set_position(&["startpos"]).unwrap();
+        assert_eq!(engine.game_type, GameType::Mini);
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_invalid_position_keeps_engine() {
+        let mut engine = Engine::new();
+        engine.set_position(&["startpos", "moves", "7g7f"]).unwrap();
+        let position = engine.position.clone();
+        //Minishogi position with an illegal king move
+        let args: Vec<&str> = "sfen rbsgk/4p/5/P4/KGSBR b - 1 moves 5e5a"
+            .split_whitespace()
+            .collect();
+        assert!(engine.set_position(&args).is_err());
+        let args: Vec<&str> = "sfen 4k4/9/9/9/9/9/9/9/4K4 b 18446744073709551615p 1"
+            .split_whitespace()
+            .collect();
+        assert!(engine.set_position(&args).is_err());
+        assert_eq!(engine.game_type, GameType::Standard);
+        assert_eq!(engine.position, position);
+        assert_eq!((board_rows(), board_cols()), (9, 9));
+        assert_eq!(
+            engine.position.hash(&engine.zobrist),
+            position.hash(&make_zobrist(81))
+        );
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let go = GoOptions::parse(&["btime", "0", "wtime", "0", "byoyomi", "1000"]);
+        assert_eq!(go.byoyomi, 1000);
+        assert_eq!(
+            go.time_budget(Player::Human),
+            Some(Duration::from_millis(900))
+        );
+        let go = GoOptions::parse(&["btime", "40000", "wtime", "0", "binc", "500"]);
+        assert_eq!(
+            go.time_budget(Player::Human),
+            Some(Duration::from_millis(1400))
+        );
+        assert_eq!(go.time_budget(Player::Computer), None);
+        assert_eq!(
+            GoOptions::parse(&["depth", "3"]).time_budget(Player::Human),
+            None
+        );
+        assert_eq!(GoOptions::parse(&["mate", "infinite"]).mate, Some(None));
+        assert_eq!(GoOptions::parse(&["mate", "500"]).mate, Some(Some(500)));
+    }
+}
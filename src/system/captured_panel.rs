@@ -0,0 +1,62 @@
+use crate::constants::colors::LIGHT_GRAY;
+use crate::system::math::{pt, Offset, Point};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::Player;
+use ggez::graphics::{Color, DrawMode};
+use ggez::{Context, GameResult};
+use std::cmp::Ordering;
+
+pub const CAPTURED_PIECE_SIZE: f32 = 28.;
+const CAPTURED_PER_ROW: usize = 9;
+
+//Draws the pieces each player has captured, below the move list, and who is ahead on material
+//rows are the title and number of captures for the human then the computer
+//draw_piece is passed the capturing player, the capture's index and where to draw it
+//Returns where the next line of text can be drawn
+pub fn draw_captured_panel<F>(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    rows: [(String, usize); 2],
+    background: Option<Color>,
+    balance: isize,
+    mut draw_piece: F,
+) -> GameResult<Point>
+where
+    F: FnMut(&mut Context, &mut MeshHelper, Player, usize, Point) -> GameResult<()>,
+{
+    let panel_start = pt(8., mesh_helper.calc_height(0.74));
+    mesh_helper.draw_white_text(ctx, "Captured", panel_start, 16., false);
+
+    let mut row_start = panel_start.offsety(22.);
+    for (player, (title, count)) in [Player::Human, Player::Computer].iter().zip(rows.iter()) {
+        mesh_helper.draw_text(ctx, title, row_start, LIGHT_GRAY, 14., false);
+        let pieces_start = row_start.offsety(18.);
+        let lines = ((*count as f32) / CAPTURED_PER_ROW as f32).ceil().max(1.);
+        if let Some(colour) = background {
+            let rect = mesh_helper.make_rect(
+                ctx,
+                CAPTURED_PIECE_SIZE * CAPTURED_PER_ROW as f32,
+                CAPTURED_PIECE_SIZE * lines,
+                DrawMode::fill(),
+            )?;
+            mesh_helper.draw_coloured_mesh(ctx, rect.as_ref(), pieces_start, colour);
+        }
+        for i in 0..*count {
+            let xy = pieces_start.offset(
+                (i % CAPTURED_PER_ROW) as f32 * CAPTURED_PIECE_SIZE,
+                (i / CAPTURED_PER_ROW) as f32 * CAPTURED_PIECE_SIZE,
+            );
+            draw_piece(ctx, mesh_helper, *player, i, xy)?;
+        }
+        row_start = pieces_start.offsety(CAPTURED_PIECE_SIZE * lines + 8.);
+    }
+
+    let text = match balance.cmp(&0) {
+        Ordering::Greater => format!("Material: +{} (You)", balance),
+        Ordering::Less => format!("Material: +{} (Computer)", -balance),
+        Ordering::Equal => String::from("Material: even"),
+    };
+    mesh_helper.draw_white_text(ctx, &text, row_start, 16., false);
+
+    Ok(row_start.offsety(22.))
+}
@@ -15,7 +15,16 @@ pub fn find_nearest<T: Debug>(
         from,
         list
     );
-    let from = transform(&list[from]);
+    find_nearest_to(list, transform(&list[from]), search, transform)
+}
+
+//Return the index of the nearest item in the list to the coord in the direction of 'search'
+pub fn find_nearest_to<T: Debug>(
+    list: &[T],
+    from: BoardCoord,
+    search: Direction,
+    transform: &dyn Fn(&T) -> BoardCoord,
+) -> Option<usize> {
     let nearest = list
         .iter()
         .enumerate()
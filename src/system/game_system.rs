@@ -1,18 +1,19 @@
 use crate::constants::colors::{BLACK, BLUE, DARK_RED, GREEN, LIGHT_BLUE, RED, WHITE};
 use crate::constants::games;
-use crate::system::math::{Offset, pt};
+use crate::system::math::{pt, Offset};
 use crate::system::mesh_helper::MeshHelper;
 use crate::system::PlayState::*;
 use crate::system::{PlayState, Scene};
 use crate::{
-    chess, draughts, graphics_testing, mancala, menu, orderchaos, senet, tablut, tictactoe,
-    FPS_ENABLED, SCREEN_WIDTH,
+    blackhole, chess, draughts, go, graphics_testing, mancala, menu, orderchaos, rithmomachy,
+    senet, shogi, tablut, tictactoe, ur, FPS_ENABLED, SCREEN_WIDTH,
 };
 use ggez::event::{EventHandler, KeyCode};
 use ggez::graphics::{Color, DrawMode};
 use ggez::input::keyboard::KeyMods;
 use ggez::{graphics, timer, Context, GameResult};
 use std::collections::HashMap;
+use std::fs;
 
 pub struct GameSystem {
     mesh_helper: MeshHelper,
@@ -61,10 +62,26 @@ impl GameSystem {
             games::DRAUGHTS_CANADIAN
             | games::DRAUGHTS_BRAZILIAN
             | games::DRAUGHTS_INTERNATIONAL
-            | games::DRAUGHTS_ENGLISH => Box::new(draughts::controller::Controller::new(game)),
-            games::MANCALA => Box::new(mancala::controller::Controller::new()),
+            | games::DRAUGHTS_ENGLISH
+            | games::DRAUGHTS_RUSSIAN
+            | games::DRAUGHTS_ITALIAN
+            | games::DRAUGHTS_SPANISH
+            | games::DRAUGHTS_TURKISH
+            | games::DRAUGHTS_FRISIAN => Box::new(draughts::controller::Controller::new(game)),
+            games::MANCALA_KALAH | games::MANCALA_OWARE | games::MANCALA_CONGKAK => {
+                Box::new(mancala::controller::Controller::new(game))
+            }
             games::ORDERCHAOS => Box::new(orderchaos::controller::Controller::new()),
             games::SENET => Box::new(senet::controller::Controller::new()),
+            games::UR => Box::new(ur::controller::Controller::new()),
+            games::BLACKHOLE => Box::new(blackhole::controller::Controller::new()),
+            games::RITHMOMANCHY => Box::new(rithmomachy::controller::Controller::new()),
+            games::SHOGI_MINI | games::SHOGI_STANDARD | games::SHOGI_MEDIUM => {
+                Box::new(shogi::controller::Controller::new(game))
+            }
+            games::GO_SMALL | games::GO_MEDIUM | games::GO_STANDARD => {
+                Box::new(go::controller::Controller::new(game))
+            }
             games::CHESS_STANDARD
             | games::CHESS_MINI
             | games::CHESS_GRAND
@@ -78,6 +95,56 @@ impl GameSystem {
         }
     }
 
+    //Starts a game from a saved record, only supported by some games
+    pub fn load_game(&mut self, game: &str, path: &str) -> Result<(), String> {
+        let record =
+            fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+        self.active = match game {
+            games::SHOGI_MINI | games::SHOGI_STANDARD => {
+                Box::new(shogi::controller::Controller::from_record(game, &record)?)
+            }
+            games::GO_SMALL | games::GO_MEDIUM | games::GO_STANDARD => {
+                Box::new(go::controller::Controller::from_record(game, &record)?)
+            }
+            games::DRAUGHTS_ENGLISH
+            | games::DRAUGHTS_INTERNATIONAL
+            | games::DRAUGHTS_CANADIAN
+            | games::DRAUGHTS_BRAZILIAN
+            | games::DRAUGHTS_RUSSIAN
+            | games::DRAUGHTS_ITALIAN
+            | games::DRAUGHTS_SPANISH
+            | games::DRAUGHTS_TURKISH
+            | games::DRAUGHTS_FRISIAN => Box::new(draughts::controller::Controller::from_record(
+                game, &record,
+            )?),
+            _ => return Err(format!("Loading records is not supported for {}", game)),
+        };
+        self.active_name = Some(game.to_string());
+        Ok(())
+    }
+
+    //Starts solving problems from a file, only supported by draughts
+    pub fn load_problems(&mut self, game: &str, path: &str) -> Result<(), String> {
+        let text =
+            fs::read_to_string(path).map_err(|err| format!("Could not read {}: {}", path, err))?;
+        self.active = match game {
+            games::DRAUGHTS_ENGLISH
+            | games::DRAUGHTS_INTERNATIONAL
+            | games::DRAUGHTS_CANADIAN
+            | games::DRAUGHTS_BRAZILIAN
+            | games::DRAUGHTS_RUSSIAN
+            | games::DRAUGHTS_ITALIAN
+            | games::DRAUGHTS_SPANISH
+            | games::DRAUGHTS_TURKISH
+            | games::DRAUGHTS_FRISIAN => Box::new(draughts::controller::Controller::from_problems(
+                game, &text,
+            )?),
+            _ => return Err(format!("Problems are not supported for {}", game)),
+        };
+        self.active_name = Some(game.to_string());
+        Ok(())
+    }
+
     fn handle_game_over(&mut self, ctx: &mut Context) -> GameResult {
         match self.active.play_state() {
             ModeSelection | Init | Playing(_) => {}
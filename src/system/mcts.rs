@@ -0,0 +1,436 @@
+use crate::system::Player;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::SeedableRng;
+use std::fmt::Debug;
+use std::time::{Duration, Instant};
+
+//Monte Carlo tree search using UCT, optionally with RAVE (rapid action value estimation)
+//The tree is open loop, nodes are move sequences rather than positions, so games with dice
+//can be searched by rolling in apply
+//Searches are run a slice at a time so the game can keep drawing frames while the computer thinks
+
+//How long a search runs each frame, short enough that the game keeps drawing smoothly
+pub const FRAME_BUDGET: Duration = Duration::from_millis(10);
+
+const DEFAULT_EXPLORATION: f64 = std::f64::consts::SQRT_2;
+const DEFAULT_MAX_PLAYOUT_MOVES: usize = 1000;
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Outcome {
+    Win(Player),
+    Draw,
+}
+
+impl Outcome {
+    fn reward(&self, player: Player) -> f64 {
+        match self {
+            Outcome::Win(winner) if *winner == player => 1.,
+            Outcome::Win(_) => 0.,
+            Outcome::Draw => 0.5,
+        }
+    }
+}
+
+//Implemented by game states that can be searched, Player only tells the two sides apart
+pub trait MctsGame: Clone + Debug {
+    type Move: Clone + PartialEq + Debug;
+
+    fn to_move(&self) -> Player;
+
+    //Only used while outcome is None, games where a player can't move should offer a pass
+    fn legal_moves(&self) -> Vec<Self::Move>;
+
+    //rng is for anything random after the move, such as the next dice roll
+    fn apply(&mut self, mov: &Self::Move, rng: &mut StdRng);
+
+    //None while the game is still going
+    fn outcome(&self) -> Option<Outcome>;
+
+    //Picks moves during random playouts, games can override this to skip hopeless moves
+    fn playout_move(&self, rng: &mut StdRng) -> Option<Self::Move> {
+        self.legal_moves().choose(rng).cloned()
+    }
+
+    //Result used when a playout is cut short
+    fn estimate(&self) -> Outcome {
+        Outcome::Draw
+    }
+
+    //(visits, wins) a move starts with when it's added to the tree, as if it had already been
+    //played out that many times, so moves that are usually good are searched first
+    fn prior(&self, _: &Self::Move) -> (f64, f64) {
+        (0., 0.)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MctsSettings {
+    //Search stops after this many playouts or when time runs out, whichever is first
+    pub playouts: usize,
+    pub time: Option<Duration>,
+    pub exploration: f64,
+    //RAVE equivalence, roughly the visits at which a move's own results count as much as its
+    //results from anywhere in playouts
+    pub rave: Option<f64>,
+    pub seed: u64,
+    pub max_playout_moves: usize,
+}
+
+impl MctsSettings {
+    pub fn new(playouts: usize, seed: u64) -> Self {
+        MctsSettings {
+            playouts,
+            time: None,
+            exploration: DEFAULT_EXPLORATION,
+            rave: None,
+            seed,
+            max_playout_moves: DEFAULT_MAX_PLAYOUT_MOVES,
+        }
+    }
+
+    pub fn with_time(self, time: Duration) -> Self {
+        MctsSettings {
+            time: Some(time),
+            ..self
+        }
+    }
+
+    pub fn with_rave(self, equivalence: f64) -> Self {
+        MctsSettings {
+            rave: Some(equivalence),
+            ..self
+        }
+    }
+
+    pub fn with_max_playout_moves(self, max_playout_moves: usize) -> Self {
+        MctsSettings {
+            max_playout_moves,
+            ..self
+        }
+    }
+}
+
+#[derive(Debug)]
+struct Node<M> {
+    //None for the root
+    mov: Option<M>,
+    visits: f64,
+    //From the point of view of the player who made mov
+    wins: f64,
+    rave_visits: f64,
+    rave_wins: f64,
+    children: Vec<usize>,
+}
+
+impl<M> Node<M> {
+    fn new(mov: Option<M>) -> Self {
+        Node {
+            mov,
+            visits: 0.,
+            wins: 0.,
+            rave_visits: 0.,
+            rave_wins: 0.,
+            children: vec![],
+        }
+    }
+
+    fn value(&self, parent_visits: f64, settings: &MctsSettings) -> f64 {
+        let mut mean = self.wins / self.visits;
+        if let Some(equivalence) = settings.rave {
+            if self.rave_visits > 0. {
+                let beta = (equivalence / (3. * self.visits + equivalence)).sqrt();
+                mean = (1. - beta) * mean + beta * self.rave_wins / self.rave_visits;
+            }
+        }
+        mean + settings.exploration * (parent_visits.ln() / self.visits).sqrt()
+    }
+}
+
+#[derive(Debug)]
+pub struct Search<G: MctsGame> {
+    root: G,
+    settings: MctsSettings,
+    rng: StdRng,
+    deadline: Option<Instant>,
+    tree: Vec<Node<G::Move>>,
+    playouts: usize,
+    finished: bool,
+    //Set when there is only one legal move, so nothing needs searching
+    only_move: Option<G::Move>,
+}
+
+impl<G: MctsGame> Search<G> {
+    //The thinking time in settings starts now, not when the search is first run
+    pub fn new(root: G, settings: MctsSettings) -> Self {
+        let mut only_move = None;
+        let mut finished = root.outcome().is_some();
+        if !finished {
+            let moves = root.legal_moves();
+            if moves.len() <= 1 {
+                only_move = moves.into_iter().next();
+                finished = true;
+            }
+        }
+        Search {
+            rng: StdRng::seed_from_u64(settings.seed),
+            deadline: settings.time.map(|time| Instant::now() + time),
+            root,
+            settings,
+            tree: vec![Node::new(None)],
+            playouts: 0,
+            finished,
+            only_move,
+        }
+    }
+
+    //Runs playouts for up to budget, returns true once the search has finished
+    pub fn run(&mut self, budget: Duration) -> bool {
+        let stop = Instant::now() + budget;
+        while !self.finished {
+            let now = Instant::now();
+            if self.playouts >= self.settings.playouts
+                || self.deadline.map(|end| now >= end).unwrap_or(false)
+            {
+                self.finished = true;
+                debug_log!(
+                    "MCTS ran {} playouts, {} nodes",
+                    self.playouts,
+                    self.tree.len()
+                );
+            } else if now >= stop {
+                break;
+            } else {
+                iterate(&mut self.tree, &self.root, &self.settings, &mut self.rng);
+                self.playouts += 1;
+            }
+        }
+        self.finished
+    }
+
+    //The most visited move so far, None if the game is over
+    pub fn best_move(&self) -> Option<G::Move> {
+        self.tree[0]
+            .children
+            .iter()
+            .map(|idx| &self.tree[*idx])
+            .max_by(|lhs, rhs| {
+                (lhs.visits, lhs.wins)
+                    .partial_cmp(&(rhs.visits, rhs.wins))
+                    .unwrap()
+            })
+            .and_then(|node| node.mov.clone())
+            .or_else(|| self.only_move.clone())
+    }
+}
+
+fn iterate<G: MctsGame>(
+    tree: &mut Vec<Node<G::Move>>,
+    root: &G,
+    settings: &MctsSettings,
+    rng: &mut StdRng,
+) {
+    let mut state = root.clone();
+    let mut path = vec![0];
+    let mut played: Vec<(Player, G::Move)> = vec![];
+    let mut node = 0;
+
+    //Selection and expansion
+    while state.outcome().is_none() {
+        let legal = state.legal_moves();
+        if legal.is_empty() {
+            break;
+        }
+        let player = state.to_move();
+        let untried: Vec<&G::Move> = legal
+            .iter()
+            .filter(|mov| {
+                !tree[node]
+                    .children
+                    .iter()
+                    .any(|child| tree[*child].mov.as_ref() == Some(mov))
+            })
+            .collect();
+        let (child, expanded) = match untried.choose(rng) {
+            Some(mov) => {
+                let mut new_node = Node::new(Some((*mov).clone()));
+                let (visits, wins) = state.prior(mov);
+                new_node.visits = visits;
+                new_node.wins = wins;
+                tree.push(new_node);
+                let child = tree.len() - 1;
+                tree[node].children.push(child);
+                (child, true)
+            }
+            None => {
+                let parent_visits = tree[node].visits;
+                let child = *tree[node]
+                    .children
+                    .iter()
+                    .filter(|child| {
+                        legal
+                            .iter()
+                            .any(|mov| tree[**child].mov.as_ref() == Some(mov))
+                    })
+                    .max_by(|lhs, rhs| {
+                        let lhs = tree[**lhs].value(parent_visits, settings);
+                        let rhs = tree[**rhs].value(parent_visits, settings);
+                        lhs.partial_cmp(&rhs).unwrap()
+                    })
+                    .expect("No child for a legal move");
+                (child, false)
+            }
+        };
+        let mov = tree[child].mov.clone().expect("Child without a move");
+        state.apply(&mov, rng);
+        played.push((player, mov));
+        path.push(child);
+        node = child;
+        if expanded {
+            break;
+        }
+    }
+
+    //Playout
+    let mut length = 0;
+    let outcome = loop {
+        if let Some(outcome) = state.outcome() {
+            break outcome;
+        }
+        if length >= settings.max_playout_moves {
+            break state.estimate();
+        }
+        let player = state.to_move();
+        match state.playout_move(rng) {
+            Some(mov) => {
+                state.apply(&mov, rng);
+                if settings.rave.is_some() {
+                    played.push((player, mov));
+                }
+            }
+            None => break state.estimate(),
+        }
+        length += 1;
+    };
+
+    //Backpropagation, moves are credited to whoever made them in this playout as with dice the
+    //same node can be reached with either player to move
+    for (depth, idx) in path.iter().enumerate() {
+        tree[*idx].visits += 1.;
+        if depth > 0 {
+            tree[*idx].wins += outcome.reward(played[depth - 1].0);
+        }
+        if settings.rave.is_some() {
+            if let Some((mover, _)) = played.get(depth) {
+                let later = &played[depth..];
+                for child in tree[*idx].children.clone() {
+                    let seen = later.iter().any(|(player, mov)| {
+                        player == mover && tree[child].mov.as_ref() == Some(mov)
+                    });
+                    if seen {
+                        tree[child].rave_visits += 1.;
+                        tree[child].rave_wins += outcome.reward(*mover);
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    //Players take one to three counters, taking the last one wins
+    #[derive(Debug, Clone)]
+    struct Nim {
+        counters: usize,
+        to_move: Player,
+    }
+
+    impl MctsGame for Nim {
+        type Move = usize;
+
+        fn to_move(&self) -> Player {
+            self.to_move
+        }
+
+        fn legal_moves(&self) -> Vec<usize> {
+            (1..=self.counters.min(3)).collect()
+        }
+
+        fn apply(&mut self, mov: &usize, _: &mut StdRng) {
+            self.counters -= mov;
+            self.to_move = self.to_move.opposite();
+        }
+
+        fn outcome(&self) -> Option<Outcome> {
+            if self.counters == 0 {
+                Some(Outcome::Win(self.to_move.opposite()))
+            } else {
+                None
+            }
+        }
+    }
+
+    //Runs the whole search in one go
+    fn search(root: &Nim, settings: &MctsSettings) -> Option<usize> {
+        let mut search = Search::new(root.clone(), settings.clone());
+        assert!(search.run(Duration::from_secs(60)));
+        search.best_move()
+    }
+
+    fn nim(counters: usize) -> Nim {
+        Nim {
+            counters,
+            to_move: Player::Computer,
+        }
+    }
+
+    #[test]
+    fn test_finds_winning_moves() {
+        let settings = MctsSettings::new(2000, 1);
+        //Leaving a multiple of four wins
+        assert_eq!(search(&nim(5), &settings), Some(1));
+        assert_eq!(search(&nim(7), &settings), Some(3));
+        assert_eq!(search(&nim(10), &settings), Some(2));
+        assert_eq!(search(&nim(10), &settings.with_rave(100.)), Some(2));
+        assert_eq!(search(&nim(1), &MctsSettings::new(10, 1)), Some(1));
+        assert_eq!(search(&nim(0), &MctsSettings::new(10, 1)), None);
+    }
+
+    #[test]
+    fn test_deterministic() {
+        //With few playouts the choice depends on the seed but is repeatable
+        for seed in 0..10 {
+            let settings = MctsSettings::new(12, seed).with_rave(10.);
+            let first = search(&nim(20), &settings);
+            assert_eq!(search(&nim(20), &settings), first);
+        }
+    }
+
+    #[test]
+    fn test_runs_in_slices() {
+        let settings = MctsSettings::new(2000, 1);
+        let mut search = Search::new(nim(10), settings.clone());
+        let mut slices = 1;
+        while !search.run(Duration::from_micros(100)) {
+            slices += 1;
+        }
+        assert!(slices > 1);
+        assert_eq!(search.playouts, 2000);
+        assert_eq!(search.best_move(), Some(2));
+        //Slicing doesn't change the result
+        let mut whole = Search::new(nim(10), settings);
+        assert!(whole.run(Duration::from_secs(60)));
+        assert_eq!(whole.best_move(), search.best_move());
+    }
+
+    #[test]
+    fn test_limits() {
+        let settings = MctsSettings::new(usize::MAX, 1).with_time(Duration::from_millis(50));
+        let start = Instant::now();
+        assert!(search(&nim(21), &settings).is_some());
+        assert!(start.elapsed() < Duration::from_secs(2));
+    }
+}
@@ -1,7 +1,9 @@
 use crate::constants::colors::WHITE;
 use crate::constants::{Direction, TOLERANCE};
 use crate::system::math::{pt, Point};
-use ggez::graphics::{Color, DrawMode, Drawable, Mesh, MeshBuilder, Rect, Text, TextFragment, PxScale};
+use ggez::graphics::{
+    Color, DrawMode, Drawable, Mesh, MeshBuilder, PxScale, Rect, Text, TextFragment,
+};
 use ggez::{graphics, Context, GameResult};
 use std::collections::HashMap;
 use std::rc::Rc;
@@ -25,8 +27,7 @@ impl MeshHelper {
 
 impl MeshHelper {
     pub fn get_screen_size(ctx: &mut Context) -> (f32, f32) {
-        let inner_size = graphics::window(ctx)
-            .inner_size();
+        let inner_size = graphics::window(ctx).inner_size();
         (inner_size.width as f32, inner_size.height as f32)
     }
 }
@@ -221,7 +222,7 @@ impl MeshHelper {
         });
         let mut xy = position;
         if centered {
-            xy = pt(position.x - (text.width(ctx) as f32 / 2.), position.y);
+            xy = pt(position.x - (text.width(ctx) / 2.), position.y);
         }
         self.draw_mesh(ctx, &text, xy);
     }
@@ -247,7 +248,7 @@ impl MeshHelper {
     }
 
     pub fn draw_mesh<D: Drawable>(&mut self, ctx: &mut Context, mesh: &D, xy: Point) {
-        graphics::draw(ctx, mesh, (xy, )).expect("couldn't draw");
+        graphics::draw(ctx, mesh, (xy,)).expect("couldn't draw");
     }
 
     pub fn draw_coloured_mesh<D: Drawable>(
@@ -260,5 +261,36 @@ impl MeshHelper {
         graphics::draw(ctx, mesh, (xy, new_colour)).expect("couldn't draw");
     }
 
-    // pub fn make_shogi_piece(&mut self, ctx: &mut Context, width: f32, height: f32) -> GameResult<Rc<Mesh>> {}
+    //Pentagon with the point facing dir (only Up and Down are supported)
+    pub fn make_shogi_piece(
+        &mut self,
+        ctx: &mut Context,
+        width: f32,
+        height: f32,
+        dir: &Direction,
+        mode: DrawMode,
+    ) -> GameResult<Rc<Mesh>> {
+        self.make_mesh(
+            ctx,
+            format!("shogi_{}_{}_{:?}_{:?}", width, height, dir, mode),
+            &(|builder| {
+                let points = [
+                    pt(width * 0.5, 0.),
+                    pt(width * 0.85, height * 0.2),
+                    pt(width, height),
+                    pt(0., height),
+                    pt(width * 0.15, height * 0.2),
+                ];
+                let points: Vec<Point> = match dir {
+                    Direction::Down => points
+                        .iter()
+                        .map(|point| pt(point.x, height - point.y))
+                        .collect(),
+                    _ => points.to_vec(),
+                };
+                builder.polygon(mode, &points, WHITE)?;
+                Ok(())
+            }),
+        )
+    }
 }
@@ -5,13 +5,18 @@ use crate::system::TurnState::SelectingPiece;
 use ggez::event::KeyCode;
 use ggez::{Context, GameResult};
 
+pub mod captured_panel;
 pub mod find_nearest;
 pub mod game_system;
 pub mod ggez_ext;
 pub mod letter_mesh;
 pub mod math;
+pub mod mcts;
 pub mod mesh_helper;
 pub mod neighbours;
+pub mod resources;
+pub mod transposition_table;
+pub mod zobrist;
 
 pub const NEW_TURN_HUMAN: PlayState = Playing(Human(SelectingPiece));
 pub const NEW_TURN_COMPUTER: PlayState = Playing(Computer(SelectingPiece));
@@ -0,0 +1,25 @@
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+//Same folder ggez is given, so files can be read before a Context exists
+pub fn resources_dir() -> PathBuf {
+    if let Ok(manifest_dir) = env::var("CARGO_MANIFEST_DIR") {
+        let mut path = PathBuf::from(manifest_dir);
+        path.push("resources");
+        path
+    } else {
+        PathBuf::from("./resources")
+    }
+}
+
+pub fn read_resource(name: &str) -> Option<String> {
+    let path = resources_dir().join(name);
+    match fs::read_to_string(&path) {
+        Ok(text) => Some(text),
+        Err(err) => {
+            debug_log!("Unable to read {:?}: {}", path, err);
+            None
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//Fixed size cache of search results keyed by zobrist hash
+//Each hash maps to a single slot, when two positions share a slot:
+// - entries from an earlier search are always replaced
+// - otherwise the entry searched to the greater (or equal) depth is kept
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum Bound {
+    Exact,
+    Lower,
+    Upper,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Entry<T> {
+    pub hash: u64,
+    pub depth: usize,
+    pub bound: Bound,
+    pub value: T,
+    generation: u8,
+}
+
+#[derive(Debug)]
+pub struct TranspositionTable<T> {
+    slots: Vec<Option<Entry<T>>>,
+    generation: u8,
+}
+
+impl<T> TranspositionTable<T> {
+    //Capacity is rounded up to a power of two
+    pub fn new(capacity: usize) -> Self {
+        let size = capacity.max(1).next_power_of_two();
+        TranspositionTable {
+            slots: (0..size).map(|_| None).collect(),
+            generation: 0,
+        }
+    }
+
+    fn slot(&self, hash: u64) -> usize {
+        (hash as usize) & (self.slots.len() - 1)
+    }
+
+    //Call before each new search so old results are replaced first
+    pub fn new_search(&mut self) {
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    pub fn clear(&mut self) {
+        self.slots.iter_mut().for_each(|slot| *slot = None);
+    }
+
+    pub fn get(&self, hash: u64) -> Option<&Entry<T>> {
+        self.slots[self.slot(hash)]
+            .as_ref()
+            .filter(|entry| entry.hash == hash)
+    }
+
+    //Returns true if the entry was stored
+    pub fn insert(&mut self, hash: u64, depth: usize, bound: Bound, value: T) -> bool {
+        let idx = self.slot(hash);
+        let replace = match &self.slots[idx] {
+            None => true,
+            Some(existing) => {
+                existing.generation != self.generation
+                    || existing.hash == hash
+                    || depth >= existing.depth
+            }
+        };
+        if replace {
+            self.slots[idx] = Some(Entry {
+                hash,
+                depth,
+                bound,
+                value,
+                generation: self.generation,
+            });
+        }
+        replace
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_capacity() {
+        assert_eq!(TranspositionTable::<i32>::new(0).slots.len(), 1);
+        assert_eq!(TranspositionTable::<i32>::new(1000).slots.len(), 1024);
+        assert_eq!(TranspositionTable::<i32>::new(64).slots.len(), 64);
+    }
+
+    #[test]
+    fn test_get_checks_hash() {
+        let mut table = TranspositionTable::new(4);
+        assert!(table.insert(5, 1, Bound::Exact, 10));
+        assert_eq!(table.get(5).map(|entry| entry.value), Some(10));
+        //Same slot, different position
+        assert_eq!(table.get(1), None);
+        assert_eq!(table.get(6), None);
+    }
+
+    #[test]
+    fn test_replacement() {
+        let mut table = TranspositionTable::new(4);
+        table.insert(1, 4, Bound::Exact, 10);
+
+        //Shallower search for a different position in the same slot is rejected
+        assert!(!table.insert(5, 2, Bound::Exact, 20));
+        assert_eq!(table.get(1).map(|entry| entry.value), Some(10));
+
+        //Same position is always updated
+        assert!(table.insert(1, 1, Bound::Upper, 11));
+        assert_eq!(table.get(1).map(|entry| entry.value), Some(11));
+
+        //Deeper search replaces
+        assert!(table.insert(5, 2, Bound::Exact, 20));
+        assert_eq!(table.get(5).map(|entry| entry.value), Some(20));
+
+        //Entries from old searches are replaced
+        table.new_search();
+        assert!(table.insert(9, 0, Bound::Exact, 30));
+        assert_eq!(table.get(9).map(|entry| entry.value), Some(30));
+        assert_eq!(table.get(5), None);
+
+        table.clear();
+        assert_eq!(table.get(9), None);
+    }
+}
@@ -0,0 +1,197 @@
+use crate::system::Player;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+//Fixed so that hashes are the same for every table and every run
+const ZOBRIST_SEED: u64 = 0x5EE_D0FB_0A2D;
+
+//Implemented by the square types of boards that can be hashed
+//Each piece kind (including which player owns it) needs a unique index below KINDS
+pub trait ZobristSquare {
+    const KINDS: usize;
+
+    //None for empty squares
+    fn zobrist_kind(&self) -> Option<usize>;
+}
+
+#[derive(Debug, Clone)]
+pub struct Zobrist {
+    keys: Vec<u64>,
+    kinds: usize,
+    computer_to_move: u64,
+    extra_keys: Vec<u64>,
+}
+
+impl Zobrist {
+    pub fn new<S: ZobristSquare>(cell_count: usize) -> Self {
+        Zobrist::with_extra_keys::<S>(cell_count, 0)
+    }
+
+    //Extra keys are for state that isn't on the board, such as pieces in hand
+    pub fn with_extra_keys<S: ZobristSquare>(cell_count: usize, extra_count: usize) -> Self {
+        let mut rng = StdRng::seed_from_u64(ZOBRIST_SEED);
+        Zobrist {
+            keys: (0..cell_count * S::KINDS).map(|_| rng.gen()).collect(),
+            kinds: S::KINDS,
+            computer_to_move: rng.gen(),
+            extra_keys: (0..extra_count).map(|_| rng.gen()).collect(),
+        }
+    }
+
+    pub fn hash<S: ZobristSquare>(&self, board: &[S], to_move: Player) -> u64 {
+        let hash = board.iter().enumerate().fold(0, |hash, (idx, square)| {
+            self.toggle_square(hash, idx, square)
+        });
+        match to_move {
+            Player::Human => hash,
+            Player::Computer => self.toggle_player(hash),
+        }
+    }
+
+    //Adds or removes square from the hash, so a move can be applied by
+    //toggling the old and new contents of each cell it changes
+    pub fn toggle_square<S: ZobristSquare>(&self, hash: u64, idx: usize, square: &S) -> u64 {
+        match square.zobrist_kind() {
+            Some(kind) => {
+                debug_assert!(kind < self.kinds, "Invalid zobrist kind {}", kind);
+                hash ^ self.keys[idx * self.kinds + kind]
+            }
+            None => hash,
+        }
+    }
+
+    pub fn toggle_player(&self, hash: u64) -> u64 {
+        hash ^ self.computer_to_move
+    }
+
+    pub fn toggle_extra(&self, hash: u64, idx: usize) -> u64 {
+        hash ^ self.extra_keys[idx]
+    }
+}
+
+//Hashes of every position reached in a game, in order
+#[derive(Debug, Default, Clone)]
+pub struct PositionHistory {
+    hashes: Vec<u64>,
+}
+
+impl PositionHistory {
+    pub fn push(&mut self, hash: u64) {
+        self.hashes.push(hash);
+    }
+
+    pub fn clear(&mut self) {
+        self.hashes.clear();
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.hashes.is_empty()
+    }
+
+    pub fn first_occurrence(&self, hash: u64) -> Option<usize> {
+        self.hashes.iter().position(|&other| other == hash)
+    }
+
+    pub fn count(&self, hash: u64) -> usize {
+        self.hashes.iter().filter(|&&other| other == hash).count()
+    }
+
+    //True if the latest position has now occurred at least times times
+    pub fn is_repeated(&self, times: usize) -> bool {
+        match self.hashes.last() {
+            Some(hash) => self.count(*hash) >= times,
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Debug, Copy, Clone, PartialEq)]
+    enum TestSquare {
+        Empty,
+        Human,
+        Computer,
+    }
+
+    impl ZobristSquare for TestSquare {
+        const KINDS: usize = 2;
+
+        fn zobrist_kind(&self) -> Option<usize> {
+            match self {
+                TestSquare::Empty => None,
+                TestSquare::Human => Some(0),
+                TestSquare::Computer => Some(1),
+            }
+        }
+    }
+
+    use TestSquare::{Computer as C, Empty as E, Human as H};
+
+    #[test]
+    fn test_hash_is_stable() {
+        let board = [H, E, C, E];
+        let first = Zobrist::new::<TestSquare>(4);
+        let second = Zobrist::new::<TestSquare>(4);
+        assert_eq!(
+            first.hash(&board, Player::Human),
+            second.hash(&board, Player::Human)
+        );
+        assert_eq!(first.hash(&[E, E, E, E], Player::Human), 0);
+    }
+
+    #[test]
+    fn test_hash_differs() {
+        let zobrist = Zobrist::new::<TestSquare>(4);
+        let hash = zobrist.hash(&[H, E, C, E], Player::Human);
+        assert_ne!(hash, zobrist.hash(&[E, H, C, E], Player::Human));
+        assert_ne!(hash, zobrist.hash(&[C, E, H, E], Player::Human));
+        assert_ne!(hash, zobrist.hash(&[H, E, C, E], Player::Computer));
+    }
+
+    #[test]
+    fn test_extra_keys() {
+        let zobrist = Zobrist::with_extra_keys::<TestSquare>(4, 2);
+        let hash = zobrist.hash(&[H, E, C, E], Player::Human);
+        assert_ne!(hash, zobrist.toggle_extra(hash, 0));
+        assert_ne!(zobrist.toggle_extra(hash, 0), zobrist.toggle_extra(hash, 1));
+        assert_eq!(hash, zobrist.toggle_extra(zobrist.toggle_extra(hash, 1), 1));
+    }
+
+    #[test]
+    fn test_incremental_matches_full() {
+        let zobrist = Zobrist::new::<TestSquare>(4);
+        let mut board = vec![H, E, C, E];
+        let mut hash = zobrist.hash(&board, Player::Human);
+
+        //Human moves 0 -> 2 capturing
+        hash = zobrist.toggle_square(hash, 0, &board[0]);
+        hash = zobrist.toggle_square(hash, 2, &board[2]);
+        hash = zobrist.toggle_square(hash, 2, &H);
+        hash = zobrist.toggle_player(hash);
+        board[0] = E;
+        board[2] = H;
+
+        assert_eq!(hash, zobrist.hash(&board, Player::Computer));
+    }
+
+    #[test]
+    fn test_position_history() {
+        let mut history = PositionHistory::default();
+        assert!(!history.is_repeated(1));
+        history.push(1);
+        history.push(2);
+        history.push(1);
+        assert_eq!(history.count(1), 2);
+        assert_eq!(history.first_occurrence(2), Some(1));
+        assert_eq!(history.hashes.len(), 3);
+        assert!(history.is_repeated(2));
+        assert!(!history.is_repeated(3));
+        history.push(2);
+        assert!(!history.is_repeated(3));
+        history.clear();
+        assert_eq!(history.count(2), 0);
+    }
+}
@@ -1,23 +1,141 @@
-use crate::tablut::State;
-use itertools::Itertools;
+use crate::system::mcts::{MctsGame, MctsSettings, Outcome, Search, FRAME_BUDGET};
+use crate::system::Player;
+use crate::tablut::rules::{apply_move, calc_valid_moves, moves_for_square, winner};
+use crate::tablut::{Board, Mode, Square, State, CORNERS};
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
 use rand::{thread_rng, Rng};
+use std::time::Duration;
 
-pub(super) fn process(state: &mut State) {
-    let moves = state.valid_moves.iter().into_group_map_by(|mov| mov.value);
-    let highest_value = moves.keys().sorted().last().unwrap();
-    debug_log!("Highest value: {}", highest_value);
-    let best_moves = moves.get(highest_value).unwrap();
-
-    debug_log!("{} best moves available", best_moves.len());
-
-    let idx = thread_rng().gen_range(0.. best_moves.len());
-    let mov = best_moves[idx];
-    debug_log!("Playing {:?}", mov);
-    state.cursor.idx = mov.origin;
-    for (i, piece_mov) in state.get_moves_for_selected_piece().iter().enumerate() {
-        if piece_mov == mov {
-            state.move_cursor = i;
-            break;
-        }
+const PLAYOUTS: usize = 20_000;
+const THINKING_TIME: Duration = Duration::from_millis(1500);
+const MAX_PLAYOUT_MOVES: usize = 150;
+
+#[derive(Debug, Clone)]
+pub(super) struct Position {
+    board: Board,
+    to_move: Mode,
+}
+
+//The search only needs to tell the sides apart
+fn side(mode: Mode) -> Player {
+    match mode {
+        Mode::Attacker => Player::Human,
+        Mode::Defender => Player::Computer,
+    }
+}
+
+//Moves are searched as origin and destination as the move values include some randomness
+impl MctsGame for Position {
+    type Move = (usize, usize);
+
+    fn to_move(&self) -> Player {
+        side(self.to_move)
+    }
+
+    //Running out of moves is a draw, which is what the search assumes when it can't continue
+    fn legal_moves(&self) -> Vec<(usize, usize)> {
+        calc_valid_moves(&self.board, self.to_move)
+            .iter()
+            .map(|mov| (mov.origin, mov.dest))
+            .collect()
+    }
+
+    fn apply(&mut self, mov: &(usize, usize), _: &mut StdRng) {
+        let mov = moves_for_square(&self.board, mov.0)
+            .into_iter()
+            .find(|piece_mov| piece_mov.dest == mov.1)
+            .expect("Search played an illegal move");
+        apply_move(&mut self.board, &mov);
+        self.to_move = self.to_move.opposite();
+    }
+
+    fn outcome(&self) -> Option<Outcome> {
+        winner(&self.board).map(|mode| Outcome::Win(side(mode)))
+    }
+
+    //Always captures or takes the king to a corner when possible so playouts are less aimless
+    fn playout_move(&self, rng: &mut StdRng) -> Option<(usize, usize)> {
+        let moves = calc_valid_moves(&self.board, self.to_move);
+        let urgent: Vec<_> = moves
+            .iter()
+            .filter(|mov| {
+                !mov.capturing.is_empty()
+                    || (self.board[mov.origin] == Square::King && CORNERS.contains(&mov.dest))
+            })
+            .collect();
+        let mov = if urgent.is_empty() {
+            moves.choose(rng)
+        } else {
+            urgent.choose(rng).copied()
+        };
+        mov.map(|mov| (mov.origin, mov.dest))
+    }
+}
+
+fn start_search(board: &Board, mode: Mode, seed: u64) -> Search<Position> {
+    let position = Position {
+        board: *board,
+        to_move: mode,
+    };
+    let settings = MctsSettings::new(PLAYOUTS, seed)
+        .with_time(THINKING_TIME)
+        .with_max_playout_moves(MAX_PLAYOUT_MOVES);
+    Search::new(position, settings)
+}
+
+//Searches for a frame, returns false until a move has been chosen
+pub(super) fn process(state: &mut State) -> bool {
+    let mode = state.get_mode_for_player(Player::Computer);
+    let board = state.board;
+    let search = state
+        .search
+        .get_or_insert_with(|| start_search(&board, mode, thread_rng().gen()));
+    if !search.run(FRAME_BUDGET) {
+        return false;
+    }
+    let mov = search.best_move();
+    state.search = None;
+    let (origin, dest) = match mov {
+        Some(mov) => mov,
+        None => return true,
+    };
+    debug_log!("Playing {}-{}", origin, dest);
+    state.cursor.idx = origin;
+    if let Some(idx) = state
+        .get_moves_for_selected_piece()
+        .iter()
+        .position(|mov| mov.dest == dest)
+    {
+        state.move_cursor = idx;
+    }
+    true
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::boards::set_board_size;
+    use crate::tablut::Square::{Attacker, Empty, King};
+    use serial_test::serial;
+
+    fn choose_move(board: &Board, mode: Mode, seed: u64) -> Option<(usize, usize)> {
+        let mut search = start_search(board, mode, seed);
+        assert!(search.run(Duration::from_secs(60)));
+        search.best_move()
+    }
+
+    #[test]
+    #[serial(board_size)]
+    fn test_king_escapes() {
+        set_board_size((9, 9));
+        let mut board = [Empty; 81];
+        board[4] = King;
+        board[31] = Attacker;
+        board[60] = Attacker;
+        assert!(matches!(
+            choose_move(&board, Mode::Defender, 1),
+            Some((4, 0)) | Some((4, 8))
+        ));
     }
 }
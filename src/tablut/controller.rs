@@ -1,6 +1,9 @@
+use crate::boards::chessboard::ChessBoard;
 use crate::boards::cursor::Cursor;
 use crate::boards::idx_coord::BoardCoord;
+use crate::boards::move_entry::{EntryAction, MoveEntry};
 use crate::boards::set_board_size;
+use crate::boards::single_char_board_converter::SingleCharBoardConverter;
 use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
 use crate::system::find_nearest::find_nearest;
 use crate::system::ggez_ext::keycode_to_direction;
@@ -11,8 +14,8 @@ use crate::system::{PlayState, Player, Scene, Turn, NEW_TURN_COMPUTER, NEW_TURN_
 use crate::tablut::ai::process;
 use crate::tablut::init::INIT_BOARD;
 use crate::tablut::renderer::render;
-use crate::tablut::rules::calc_valid_moves;
-use crate::tablut::{Mode, Square, State, CORNERS};
+use crate::tablut::rules::{apply_move, calc_valid_moves, winner};
+use crate::tablut::{Mode, Move, State};
 use ggez::event::KeyCode;
 use ggez::{Context, GameResult};
 
@@ -33,6 +36,9 @@ impl Controller {
                 move_cursor: 0,
                 player_mode: Mode::Attacker,
                 next_move_time: AI_MOVE_DELAY,
+                coords: ChessBoard::new(Box::new(SingleCharBoardConverter::new(9, 9)), 9, 9),
+                entry: MoveEntry::new(),
+                search: None,
             },
         }
     }
@@ -53,36 +59,12 @@ impl Controller {
     }
 
     fn check_for_game_over(&mut self) {
-        for corner in CORNERS.iter() {
-            if self.state.board[*corner] == Square::King {
-                self.state.play_state = match self.state.player_mode {
-                    Mode::Attacker => {
-                        debug_log!("King in corner, human was attacker: Computer wins!");
-                        ComputerWin
-                    }
-                    Mode::Defender => {
-                        debug_log!("King in corner, human was defender: Human wins!");
-                        HumanWin
-                    }
-                };
-                return;
-            }
-        }
-        if !self
-            .state
-            .board
-            .iter()
-            .any(|square| square == &Square::King)
-        {
-            self.state.play_state = match self.state.player_mode {
-                Mode::Attacker => {
-                    debug_log!("No king on board, human was attacker: Human wins!");
-                    HumanWin
-                }
-                Mode::Defender => {
-                    debug_log!("No king on board, human was defender: Computer wins!");
-                    ComputerWin
-                }
+        if let Some(winner) = winner(&self.state.board) {
+            debug_log!("{:?} won, human was {:?}", winner, self.state.player_mode);
+            self.state.play_state = if winner == self.state.player_mode {
+                HumanWin
+            } else {
+                ComputerWin
             };
             return;
         }
@@ -96,19 +78,38 @@ impl Controller {
         self.state.valid_moves = calc_valid_moves(&self.state.board, mode);
     }
 
-    fn process_move(&mut self) {
-        let mov = self.state.get_selected_move();
-        self.state.board[mov.dest] = self.state.board[mov.origin];
-        self.state.board[mov.origin] = Square::Empty;
-        for capture in mov.capturing {
-            self.state.board[capture] = Square::Empty;
+    fn process_move(&mut self, mov: Move) {
+        apply_move(&mut self.state.board, &mov);
+    }
+
+    //Return and Back while a move is being typed
+    fn handle_entry_key(&mut self, key: KeyCode) {
+        let options = self.state.entry_options();
+        if key == KeyCode::Back {
+            self.state.entry.pop(&options);
+            return;
+        }
+        match self.state.entry.submit(&options) {
+            Some(EntryAction::SelectPiece(idx)) => {
+                self.state.cursor.idx = *idx;
+                self.state.move_cursor = 0;
+                self.state.play_state = PlayState::Playing(Turn::Human(SelectingMove));
+            }
+            Some(EntryAction::PlayMove(mov)) => {
+                self.state.cursor.idx = mov.origin;
+                self.process_move(mov.clone());
+                self.start_new_turn(Player::Computer);
+            }
+            None => {}
         }
     }
 }
 
 impl Scene for Controller {
     fn on_key_down(&mut self, key: KeyCode) {
-        if self.state.play_state == PlayState::ModeSelection {
+        if !self.state.entry.is_empty() && (key == KeyCode::Return || key == KeyCode::Back) {
+            self.handle_entry_key(key);
+        } else if self.state.play_state == PlayState::ModeSelection {
             match key {
                 KeyCode::Left | KeyCode::Right => {
                     if self.state.player_mode == Mode::Attacker {
@@ -121,7 +122,8 @@ impl Scene for Controller {
                 _ => {}
             }
         } else if self.state.play_state.is_human(SelectingPiece) {
-            if !self.state.cursor.handle_input(key)
+            let movable = self.state.movable_squares();
+            if !self.state.cursor.handle_movable_input(key, &movable)
                 && key == KeyCode::Return
                 && !self.state.get_moves_for_selected_piece().is_empty()
             {
@@ -142,7 +144,7 @@ impl Scene for Controller {
                     }
                 }
                 KeyCode::Return => {
-                    self.process_move();
+                    self.process_move(self.state.get_selected_move());
                     self.start_new_turn(Player::Computer);
                 }
                 _ => {}
@@ -151,6 +153,10 @@ impl Scene for Controller {
     }
 
     fn on_key_up(&mut self, key: KeyCode) -> bool {
+        if key == KeyCode::Escape && !self.state.entry.is_empty() {
+            self.state.entry.clear();
+            return true;
+        }
         if self.state.play_state.is_human(SelectingMove) && key == KeyCode::Escape {
             self.state.play_state = NEW_TURN_HUMAN;
             return true;
@@ -158,6 +164,15 @@ impl Scene for Controller {
         false
     }
 
+    fn on_keyboard_entry(&mut self, input: char) {
+        if self.state.play_state.is_human(SelectingPiece)
+            || self.state.play_state.is_human(SelectingMove)
+        {
+            let options = self.state.entry_options();
+            self.state.entry.push(input, &options);
+        }
+    }
+
     fn update(&mut self, delta: f64) -> GameResult<()> {
         if self.state.play_state == PlayState::Init {
             match self.state.player_mode {
@@ -168,14 +183,16 @@ impl Scene for Controller {
             self.state.next_move_time -= delta;
             if self.state.next_move_time < 0. {
                 self.state.last_human_cursor_pos = self.state.cursor.idx;
-                process(&mut self.state);
-                self.state.next_move_time = ANIMATION_DURATION;
-                self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove));
+                //The search carries on each frame until it has chosen a move
+                if process(&mut self.state) {
+                    self.state.next_move_time = ANIMATION_DURATION;
+                    self.state.play_state = PlayState::Playing(Turn::Computer(SelectingMove));
+                }
             }
         } else if self.state.play_state.is_computer(SelectingMove) {
             self.state.next_move_time -= delta;
             if self.state.next_move_time < 0. {
-                self.process_move();
+                self.process_move(self.state.get_selected_move());
                 self.state.cursor.idx = self.state.last_human_cursor_pos;
                 self.start_new_turn(Player::Human);
             }
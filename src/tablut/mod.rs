@@ -4,8 +4,12 @@ mod render_mode_selection;
 mod renderer;
 mod rules;
 
-use crate::boards::cursor::Cursor;
+use crate::boards::chessboard::ChessBoard;
+use crate::boards::cursor::{movable, Cursor};
+use crate::boards::move_entry::{EntryAction, EntryOptions, MoveEntry};
+use crate::system::mcts::Search;
 use crate::system::{PlayState, Player};
+use crate::tablut::ai::Position;
 use itertools::Itertools;
 use std::fmt;
 use std::fmt::{Display, Formatter};
@@ -18,6 +22,15 @@ enum Mode {
     Defender,
 }
 
+impl Mode {
+    fn opposite(&self) -> Mode {
+        match self {
+            Mode::Attacker => Mode::Defender,
+            Mode::Defender => Mode::Attacker,
+        }
+    }
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Hash)]
 enum Square {
     Empty,
@@ -59,9 +72,40 @@ struct State {
     player_mode: Mode,
     next_move_time: f64,
     move_cursor: usize,
+    coords: ChessBoard,
+    entry: MoveEntry,
+    //Computer's move search, run a slice at a time
+    search: Option<Search<Position>>,
 }
 
 impl State {
+    //Squares and moves the player can type, e.g. e1, e1e4 or e1-e4
+    fn entry_options(&self) -> EntryOptions<Move> {
+        let name = |idx: usize| self.coords.idx_to_coord(idx).unwrap_or_default();
+        let mut options: EntryOptions<Move> = self
+            .movable_squares()
+            .into_iter()
+            .map(|origin| (name(origin), EntryAction::SelectPiece(origin)))
+            .collect();
+        for mov in &self.valid_moves {
+            let (from, to) = (name(mov.origin), name(mov.dest));
+            options.push((
+                format!("{}{}", from, to),
+                EntryAction::PlayMove(mov.clone()),
+            ));
+            options.push((
+                format!("{}-{}", from, to),
+                EntryAction::PlayMove(mov.clone()),
+            ));
+        }
+        options
+    }
+
+    //Moves aren't grouped by square here so they're grouped for cursor::movable
+    fn movable_squares(&self) -> Vec<usize> {
+        movable(&self.valid_moves.iter().into_group_map_by(|mov| mov.origin))
+    }
+
     fn get_moves_for_selected_piece(&self) -> Vec<Move> {
         self.valid_moves
             .iter()
@@ -85,13 +129,7 @@ impl State {
     fn get_mode_for_player(&self, player: Player) -> Mode {
         match player {
             Player::Human => self.player_mode,
-            Player::Computer => {
-                if self.player_mode == Mode::Attacker {
-                    Mode::Defender
-                } else {
-                    Mode::Attacker
-                }
-            }
+            Player::Computer => self.player_mode.opposite(),
         }
     }
 }
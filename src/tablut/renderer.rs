@@ -1,10 +1,11 @@
 use crate::boards::idx_coord::BoardCoord;
+use crate::boards::move_entry::render_coord_labels;
 use crate::boards::{board_cols, board_rows};
 use crate::constants::colors::{
     DARK_GRAY, DARK_GREEN, FAINT_BLUE, FAINT_RED, LIGHT_BLUE, LIGHT_GRAY, RED, WHITE,
 };
 use crate::system::letter_mesh::make_letter_mesh;
-use crate::system::math::{pt, Point, Offset, OffsetTuple};
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
 use crate::system::mesh_helper::MeshHelper;
 use crate::system::PlayState::ModeSelection;
 use crate::system::TurnState::{SelectingMove, SelectingPiece};
@@ -49,6 +50,7 @@ fn render_game(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State) -
 
     mesh_helper.draw_mesh(ctx, grid.as_ref(), board_start);
     mesh_helper.draw_mesh(ctx, rect.as_ref(), board_start);
+    render_coord_labels(ctx, mesh_helper, &state.coords, board_start, cell_size);
     mesh_helper.draw_coloured_mesh(ctx, cell.as_ref(), board_start, DARK_GREEN);
     mesh_helper.draw_coloured_mesh(
         ctx,
@@ -97,6 +99,13 @@ fn render_game(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State) -
     });
 
     if state.play_state.is_human(SelectingPiece) {
+        state.cursor.render_movable(
+            ctx,
+            mesh_helper,
+            board_start,
+            cell_size,
+            &state.movable_squares(),
+        )?;
         state
             .cursor
             .render(ctx, mesh_helper, board_start, cell_size)?;
@@ -118,6 +127,12 @@ fn render_game(ctx: &mut Context, mesh_helper: &mut MeshHelper, state: &State) -
         )?;
     }
 
+    if state.play_state.is_human(SelectingPiece) || state.play_state.is_human(SelectingMove) {
+        state
+            .entry
+            .render(ctx, mesh_helper, pt(8., mesh_helper.height - 72.));
+    }
+
     Ok(())
 }
 
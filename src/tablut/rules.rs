@@ -43,7 +43,6 @@ lazy_static! {
 }
 
 pub(super) fn calc_valid_moves(board: &Board, mode: Mode) -> Vec<Move> {
-    debug_log_start!("Calcing valid moves for {:?}", mode);
     let valid_pieces = match mode {
         Mode::Defender => vec![Square::Defender, Square::King],
         Mode::Attacker => vec![Square::Attacker],
@@ -59,19 +58,15 @@ pub(super) fn calc_valid_moves(board: &Board, mode: Mode) -> Vec<Move> {
             }
         })
         .collect();
-    debug_log!("{} pieces available", pieces.len());
-    let result = pieces
+    pieces
         .iter()
         .map(|idx| moves_for_square(board, *idx))
         .flatten()
-        .collect();
-    debug_log_end!();
-    result
+        .collect()
 }
 
-fn moves_for_square(board: &Board, origin: usize) -> Vec<Move> {
+pub(super) fn moves_for_square(board: &Board, origin: usize) -> Vec<Move> {
     let mut moves = vec![];
-    debug_log_start!("Finding moves for {}", origin);
     let allies = &ALLY[&board[origin]];
     get_neighbours(origin, true, false)
         .iter()
@@ -113,13 +108,6 @@ fn moves_for_square(board: &Board, origin: usize) -> Vec<Move> {
 
                     if !FORBIDDEN[&board[origin]].contains(&current) {
                         let value = calc_value_of_move(board, origin, current, &captures);
-                        debug_log!(
-                            "Found move from {} to {} with {} captures (score: {})",
-                            origin,
-                            current,
-                            captures.len(),
-                            value
-                        );
                         moves.push(Move {
                             origin,
                             dest: current,
@@ -138,12 +126,29 @@ fn moves_for_square(board: &Board, origin: usize) -> Vec<Move> {
                 }
             }
         });
-    debug_log_end!("Found {} moves for {}", moves.len(), origin);
     moves
 }
 
+pub(super) fn apply_move(board: &mut Board, mov: &Move) {
+    board[mov.dest] = board[mov.origin];
+    board[mov.origin] = Square::Empty;
+    for capture in &mov.capturing {
+        board[*capture] = Square::Empty;
+    }
+}
+
+//The king reaching a corner wins for the defenders, capturing it wins for the attackers
+pub(super) fn winner(board: &Board) -> Option<Mode> {
+    if CORNERS.iter().any(|corner| board[*corner] == Square::King) {
+        Some(Mode::Defender)
+    } else if !board.contains(&Square::King) {
+        Some(Mode::Attacker)
+    } else {
+        None
+    }
+}
+
 fn calc_value_of_move(board: &Board, origin: usize, current: usize, captures: &[usize]) -> usize {
-    debug_log_start!("Calculating value");
     let king_coord = board
         .iter()
         .enumerate()
@@ -160,7 +165,6 @@ fn calc_value_of_move(board: &Board, origin: usize, current: usize, captures: &[
     let castle_coord = BoardCoord::from(CASTLE);
     let mut value = 0;
     if CORNERS.contains(&current) {
-        debug_log!("Corner in one move +10000");
         value += 10000;
     }
     if board[origin] == Square::King
@@ -169,26 +173,21 @@ fn calc_value_of_move(board: &Board, origin: usize, current: usize, captures: &[
             || dest_coord.0 == board_cols()
             || dest_coord.1 == board_rows())
     {
-        debug_log!("Edge in one move +1000");
         value += 1000;
     }
-    debug_log!("Captures +{}", captures.len() * 10);
     value += captures.len() * 10;
     for capture in captures.iter() {
         if board[*capture] == Square::King {
-            debug_log!("Capture king +10000");
             value += 10000;
         }
     }
     for neighbour in get_neighbours(current, true, false) {
         if board[neighbour] == Square::King && board[origin] == Square::Attacker {
-            debug_log!("Move next to king +20");
             value += 20;
         }
         if CORNERS.contains(&neighbour) {
             let neighbour_coord = BoardCoord::from(neighbour);
             value += if board[origin] == Square::King {
-                debug_log!("Move next to corner (king) +100");
                 100
             } else if let Some(king_coord) = king_coord {
                 if (king_coord.0 < castle_coord.0 && neighbour_coord.0 < castle_coord.0)
@@ -196,10 +195,8 @@ fn calc_value_of_move(board: &Board, origin: usize, current: usize, captures: &[
                     && (king_coord.1 < castle_coord.1 && neighbour_coord.1 < castle_coord.1)
                     && (king_coord.1 > castle_coord.1 && neighbour_coord.1 > castle_coord.1)
                 {
-                    debug_log!("Move next to corner (attacker) +10");
                     10
                 } else {
-                    debug_log!("Move next to corner (attacker) +1");
                     1
                 }
             } else {
@@ -210,17 +207,14 @@ fn calc_value_of_move(board: &Board, origin: usize, current: usize, captures: &[
     if board[origin] == Square::King {
         let dist = (origin_coord.dist(dest_coord) as f32 * thread_rng().gen::<f32>()) as usize;
         value += dist;
-        debug_log!("Distance +{}", dist);
         if (origin_coord.1 < castle_coord.1 && dest_coord.1 < origin_coord.1)
             || (origin_coord.1 > castle_coord.1 && dest_coord.1 > origin_coord.1)
             || (origin_coord.0 < castle_coord.0 && dest_coord.0 < origin_coord.0)
             || (origin_coord.0 > castle_coord.0 && dest_coord.0 > origin_coord.0)
         {
-            debug_log!("Moving away from castle +1");
             value += 1;
         }
     }
 
-    debug_log_end!();
     value
 }
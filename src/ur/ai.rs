@@ -0,0 +1,126 @@
+use crate::system::Player;
+use crate::ur::rules::{
+    apply_move, calc_valid_moves, winner, DICE, ROLL_WEIGHTS, ROSETTES, SAFE, SHARED_END,
+    SHARED_START,
+};
+use crate::ur::{Board, Move, FINISH};
+
+//Number of moves looked ahead, each with a chance node for the dice before it
+const DEPTH: usize = 3;
+const WIN: f64 = 10000.;
+const FINISHED_VALUE: f64 = 20.;
+const ROSETTE_VALUE: f64 = 3.;
+const SAFE_VALUE: f64 = 4.;
+//Pieces on the shared row can be captured
+const EXPOSED_VALUE: f64 = 1.5;
+
+//Progress of a player's pieces, from their point of view
+fn progress(board: &Board, player: Player) -> f64 {
+    board
+        .track(player)
+        .iter()
+        .enumerate()
+        .map(|(step, count)| {
+            let mut value = match step {
+                FINISH => FINISHED_VALUE,
+                _ => step as f64,
+            };
+            if step == SAFE {
+                value += SAFE_VALUE;
+            } else if ROSETTES.contains(&step) {
+                value += ROSETTE_VALUE;
+            } else if (SHARED_START..=SHARED_END).contains(&step) {
+                value -= EXPOSED_VALUE;
+            }
+            value * *count as f64
+        })
+        .sum()
+}
+
+fn evaluate(board: &Board, player: Player) -> f64 {
+    progress(board, player) - progress(board, player.opposite())
+}
+
+//Expected value of the position for player before to_move throws the dice
+fn chance(board: &Board, to_move: Player, player: Player, depth: usize) -> f64 {
+    if let Some(winner) = winner(board) {
+        return if winner == player { WIN } else { -WIN };
+    }
+    if depth == 0 {
+        return evaluate(board, player);
+    }
+    let total = (1 << DICE) as f64;
+    (0..=DICE)
+        .map(|roll| {
+            let weight = ROLL_WEIGHTS[roll] as f64 / total;
+            weight * decide(board, roll, to_move, player, depth).1
+        })
+        .sum()
+}
+
+//Best move for to_move with the roll, maximising for player and minimising for the opponent
+fn decide(
+    board: &Board,
+    roll: usize,
+    to_move: Player,
+    player: Player,
+    depth: usize,
+) -> (Option<Move>, f64) {
+    let moves = calc_valid_moves(board, roll, to_move);
+    if moves.is_empty() {
+        return (None, chance(board, to_move.opposite(), player, depth - 1));
+    }
+    let results = moves.into_iter().map(|mov| {
+        let mut next = *board;
+        let again = apply_move(&mut next, &mov, to_move);
+        let next_player = if again { to_move } else { to_move.opposite() };
+        (Some(mov), chance(&next, next_player, player, depth - 1))
+    });
+    let best = if to_move == player {
+        results.max_by(|lhs, rhs| lhs.1.partial_cmp(&rhs.1).unwrap())
+    } else {
+        results.min_by(|lhs, rhs| lhs.1.partial_cmp(&rhs.1).unwrap())
+    };
+    best.expect("No moves")
+}
+
+pub(super) fn choose_move(board: &Board, roll: usize, player: Player) -> Option<Move> {
+    let (mov, value) = decide(board, roll, player, player, DEPTH);
+    debug_log!("Chose {:?} with value {:.2}", mov, value);
+    mov
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ur::PIECES;
+
+    #[test]
+    fn test_prefers_capture_and_rosettes() {
+        let mut board = Board::new();
+        board.track_mut(Player::Computer)[0] = 0;
+        board.track_mut(Player::Computer)[3] = 1;
+        board.track_mut(Player::Computer)[9] = 1;
+        board.track_mut(Player::Computer)[FINISH] = PIECES - 2;
+        board.track_mut(Player::Human)[0] = PIECES - 1;
+        board.track_mut(Player::Human)[7] = 1;
+        //Capturing the piece at 7 beats moving the front piece on
+        let mov = choose_move(&board, 4, Player::Computer).unwrap();
+        assert_eq!((mov.origin, mov.dest, mov.capture), (3, 7, true));
+        //With a one, landing on the first rosette for another turn beats moving along
+        let mov = choose_move(&board, 1, Player::Computer).unwrap();
+        assert_eq!(mov.dest, 4);
+    }
+
+    #[test]
+    fn test_bears_off() {
+        let mut board = Board::new();
+        board.track_mut(Player::Computer)[0] = 0;
+        board.track_mut(Player::Computer)[13] = 1;
+        board.track_mut(Player::Computer)[FINISH] = PIECES - 1;
+        let mov = choose_move(&board, 2, Player::Computer).unwrap();
+        assert_eq!((mov.origin, mov.dest), (13, FINISH));
+        assert_eq!(choose_move(&board, 3, Player::Computer), None);
+        assert_eq!(choose_move(&board, 0, Player::Computer), None);
+    }
+}
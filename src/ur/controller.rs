@@ -0,0 +1,161 @@
+use crate::constants::{AI_MOVE_DELAY, ANIMATION_DURATION};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::Turn::Computer;
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::system::{PlayState, Player, Scene, NEW_TURN_COMPUTER, NEW_TURN_HUMAN};
+use crate::ur::ai::choose_move;
+use crate::ur::renderer::render;
+use crate::ur::rules::{apply_move, calc_valid_moves, roll_dice, winner};
+use crate::ur::{Board, State};
+use ggez::event::KeyCode;
+use ggez::{Context, GameResult};
+use rand::thread_rng;
+
+pub struct Controller {
+    state: State,
+}
+
+impl Controller {
+    pub fn new() -> Self {
+        Controller {
+            state: State {
+                play_state: PlayState::Init,
+                msg: None,
+                board: Board::new(),
+                roll: None,
+                valid_moves: vec![],
+                move_cursor: 0,
+                next_move_time: 0.,
+            },
+        }
+    }
+}
+
+impl Controller {
+    fn start_new_turn(&mut self, player: Player, repeat_turn: bool) {
+        debug_log!("Starting new turn for {:?}", player);
+        self.state.roll = None;
+        self.state.valid_moves.clear();
+        self.state.move_cursor = 0;
+        self.state.next_move_time = AI_MOVE_DELAY;
+        let again = if repeat_turn { " again" } else { "" };
+        match player {
+            Player::Human => {
+                self.state.msg = Some(format!(
+                    "Your turn{}, press return to throw the dice",
+                    again
+                ));
+                self.state.play_state = NEW_TURN_HUMAN;
+            }
+            Player::Computer => {
+                self.state.msg = Some(format!("Computers turn{}", again));
+                self.state.play_state = NEW_TURN_COMPUTER;
+            }
+        }
+    }
+
+    //Passes the turn straight away if the roll allows no moves
+    fn roll(&mut self, player: Player) {
+        let roll = roll_dice(&mut thread_rng());
+        debug_log!("Rolled {}", roll);
+        self.state.roll = Some(roll);
+        self.state.valid_moves = calc_valid_moves(&self.state.board, roll, player);
+        if self.state.valid_moves.is_empty() {
+            self.start_new_turn(player.opposite(), false);
+            self.state.msg = Some(format!(
+                "No moves with a roll of {}. {}",
+                roll,
+                self.state.msg.as_deref().unwrap_or_default()
+            ));
+        }
+    }
+
+    fn process_move(&mut self, player: Player) {
+        let mov = self.state.get_selected_move();
+        let again = apply_move(&mut self.state.board, &mov, player);
+        if let Some(winner) = winner(&self.state.board) {
+            debug_log!("{:?} has borne off all their pieces", winner);
+            self.state.msg = None;
+            self.state.play_state = match winner {
+                Player::Human => PlayState::HumanWin,
+                Player::Computer => PlayState::ComputerWin,
+            };
+            return;
+        }
+        if again {
+            self.start_new_turn(player, true);
+        } else {
+            self.start_new_turn(player.opposite(), false);
+        }
+    }
+
+    fn process_computer_turn(&mut self, delta: f64) {
+        self.state.next_move_time -= delta;
+        if self.state.next_move_time >= 0. {
+            return;
+        }
+        self.state.next_move_time = ANIMATION_DURATION;
+        if self.state.play_state.is_computer(SelectingPiece) {
+            if let Some(roll) = self.state.roll {
+                let mov = choose_move(&self.state.board, roll, Player::Computer)
+                    .expect("Computer has no move");
+                self.state.move_cursor = self
+                    .state
+                    .valid_moves
+                    .iter()
+                    .position(|valid| *valid == mov)
+                    .unwrap_or_default();
+                self.state.play_state = PlayState::Playing(Computer(SelectingMove));
+            } else {
+                self.roll(Player::Computer);
+            }
+        } else if self.state.play_state.is_computer(SelectingMove) {
+            self.process_move(Player::Computer);
+        }
+    }
+}
+
+impl Scene for Controller {
+    fn on_key_down(&mut self, key: KeyCode) {
+        if !self.state.play_state.is_human(SelectingPiece) {
+            return;
+        }
+        if self.state.roll.is_none() {
+            if key == KeyCode::Return {
+                self.state.msg = Some(String::from(
+                    "Your turn, left/right to choose a move, return to play it",
+                ));
+                self.roll(Player::Human);
+            }
+            return;
+        }
+        let count = self.state.valid_moves.len();
+        match key {
+            KeyCode::Left | KeyCode::Up => {
+                self.state.move_cursor = (self.state.move_cursor + count - 1) % count
+            }
+            KeyCode::Right | KeyCode::Down => {
+                self.state.move_cursor = (self.state.move_cursor + 1) % count
+            }
+            KeyCode::Return => self.process_move(Player::Human),
+            _ => {}
+        }
+    }
+
+    fn update(&mut self, delta: f64) -> GameResult<()> {
+        if self.state.play_state == PlayState::Init {
+            self.start_new_turn(Player::Human, false);
+        } else if self.state.play_state.player() == Some(Player::Computer) {
+            self.process_computer_turn(delta);
+        }
+        Ok(())
+    }
+
+    fn render(&mut self, ctx: &mut Context, mesh_helper: &mut MeshHelper) -> GameResult<()> {
+        render(ctx, mesh_helper, &self.state)
+    }
+
+    fn play_state(&self) -> PlayState {
+        self.state.play_state
+    }
+}
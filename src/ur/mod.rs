@@ -0,0 +1,68 @@
+use crate::system::{PlayState, Player};
+
+mod ai;
+pub mod controller;
+mod renderer;
+mod rules;
+
+const PIECES: usize = 7;
+//Steps along a player's track, 0 is waiting to enter and FINISH is borne off
+const FINISH: usize = 15;
+const TRACK_LEN: usize = FINISH + 1;
+
+//Number of pieces at each step, indexed by Board::idx
+type Track = [usize; TRACK_LEN];
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Board {
+    tracks: [Track; 2],
+}
+
+impl Board {
+    fn new() -> Self {
+        let mut track = [0; TRACK_LEN];
+        track[0] = PIECES;
+        Board { tracks: [track; 2] }
+    }
+
+    fn idx(player: Player) -> usize {
+        match player {
+            Player::Human => 0,
+            Player::Computer => 1,
+        }
+    }
+
+    fn track(&self, player: Player) -> &Track {
+        &self.tracks[Board::idx(player)]
+    }
+
+    fn track_mut(&mut self, player: Player) -> &mut Track {
+        &mut self.tracks[Board::idx(player)]
+    }
+}
+
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+struct Move {
+    origin: usize,
+    dest: usize,
+    capture: bool,
+}
+
+struct State {
+    play_state: PlayState,
+    msg: Option<String>,
+    board: Board,
+    roll: Option<usize>,
+    valid_moves: Vec<Move>,
+    move_cursor: usize,
+    next_move_time: f64,
+}
+
+impl State {
+    fn get_selected_move(&self) -> Move {
+        *self
+            .valid_moves
+            .get(self.move_cursor)
+            .unwrap_or_else(|| panic!("No move {}", self.move_cursor))
+    }
+}
@@ -0,0 +1,186 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::constants::colors::{
+    BROWN, CREAM, LIGHT_BLUE, LIGHT_GRAY, PIECE_COMPUTER, PIECE_HUMAN, RED, WHITE,
+};
+use crate::constants::Direction;
+use crate::system::letter_mesh::make_letter_mesh;
+use crate::system::math::{pt, Offset, OffsetTuple, Point};
+use crate::system::mesh_helper::MeshHelper;
+use crate::system::Player;
+use crate::system::TurnState::{SelectingMove, SelectingPiece};
+use crate::ur::rules::{player_row, track_coord, BOARD_COLS, BOARD_ROWS, ROSETTES};
+use crate::ur::{Move, State, FINISH};
+use ggez::graphics::DrawMode;
+use ggez::{Context, GameResult};
+
+//Columns in the gap of each player's row, used for pieces waiting and finished
+const WAITING_COL: usize = 4;
+const FINISHED_COL: usize = 5;
+
+//Where a step is drawn, pieces off the board are in the gap of the player's row
+fn step_coord(player: Player, step: usize) -> BoardCoord {
+    track_coord(player, step).unwrap_or_else(|| {
+        let col = if step == FINISH {
+            FINISHED_COL
+        } else {
+            WAITING_COL
+        };
+        BoardCoord(col, player_row(player))
+    })
+}
+
+fn is_gap(coord: BoardCoord) -> bool {
+    coord.1 != 1 && (coord.0 == WAITING_COL || coord.0 == FINISHED_COL)
+}
+
+pub(super) fn render(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    state: &State,
+) -> GameResult<()> {
+    let cell_size = mesh_helper.calc_width(0.09);
+    let dice_size = mesh_helper.calc_height(0.05);
+    let msg_start = pt(mesh_helper.calc_width(0.05), mesh_helper.calc_height(0.45));
+    let dice_start = pt(mesh_helper.calc_width(0.05), mesh_helper.calc_height(0.5));
+    let board_start = pt(mesh_helper.calc_width(0.05), mesh_helper.calc_width(0.05));
+    let cell_pos = |coord: BoardCoord| {
+        Point::from(coord)
+            .multiply(cell_size, cell_size)
+            .offset_point(board_start)
+    };
+
+    let cell = mesh_helper.make_rect(ctx, cell_size, cell_size, DrawMode::stroke(2.))?;
+    let rosette = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.4, DrawMode::stroke(2.))?;
+    let human = mesh_helper.make_circle(ctx, cell_size, cell_size * 0.3, DrawMode::fill())?;
+    let computer =
+        mesh_helper.make_triangle(ctx, cell_size * 0.6, cell_size * 0.6, Direction::Up)?;
+
+    for y in 0..BOARD_ROWS {
+        for x in 0..BOARD_COLS {
+            let coord = BoardCoord(x, y);
+            if !is_gap(coord) {
+                mesh_helper.draw_coloured_mesh(ctx, cell.as_ref(), cell_pos(coord), LIGHT_GRAY);
+            }
+        }
+    }
+    for step in ROSETTES.iter() {
+        for player in [Player::Human, Player::Computer] {
+            let coord = step_coord(player, *step);
+            mesh_helper.draw_coloured_mesh(ctx, rosette.as_ref(), cell_pos(coord), LIGHT_GRAY);
+        }
+    }
+
+    for player in [Player::Human, Player::Computer] {
+        let (mesh, colour, offset) = match player {
+            Player::Human => (human.as_ref(), PIECE_HUMAN, (0., 0.)),
+            Player::Computer => (
+                computer.as_ref(),
+                PIECE_COMPUTER,
+                (cell_size * 0.2, cell_size * 0.2),
+            ),
+        };
+        for (step, count) in state.board.track(player).iter().enumerate() {
+            if *count == 0 {
+                continue;
+            }
+            let pos = cell_pos(step_coord(player, step));
+            mesh_helper.draw_coloured_mesh(ctx, mesh, pos.offset(offset.0, offset.1), colour);
+            if track_coord(player, step).is_none() {
+                mesh_helper.draw_white_text(
+                    ctx,
+                    &count.to_string(),
+                    pos.offset(cell_size * 0.85, cell_size * 0.05),
+                    16.,
+                    true,
+                );
+            }
+        }
+        let row = player_row(player) as f32;
+        let labels = [(WAITING_COL, "START"), (FINISHED_COL, "HOME")];
+        for (col, label) in labels.iter() {
+            mesh_helper.draw_white_text(
+                ctx,
+                label,
+                board_start.offset(cell_size * (*col as f32 + 0.5), cell_size * row + 4.),
+                12.,
+                true,
+            );
+        }
+    }
+
+    if state.play_state.is_either(SelectingPiece) && state.roll.is_some() {
+        let player = state.play_state.player().unwrap();
+        for mov in &state.valid_moves {
+            draw_move(ctx, mesh_helper, cell_size, board_start, player, mov, false)?;
+        }
+        if !state.valid_moves.is_empty() && state.play_state.is_human(SelectingPiece) {
+            let mov = state.get_selected_move();
+            draw_move(ctx, mesh_helper, cell_size, board_start, player, &mov, true)?;
+        }
+    } else if state.play_state.is_either(SelectingMove) {
+        let player = state.play_state.player().unwrap();
+        let mov = state.get_selected_move();
+        draw_move(ctx, mesh_helper, cell_size, board_start, player, &mov, true)?;
+    }
+
+    //Tetrahedral dice, a light tip counts as one
+    if let Some(roll) = state.roll {
+        let die = mesh_helper.make_triangle(ctx, dice_size, dice_size, Direction::Up)?;
+        for i in 0..4 {
+            let colour = if i < roll { CREAM } else { BROWN };
+            mesh_helper.draw_coloured_mesh(
+                ctx,
+                die.as_ref(),
+                dice_start.offset(dice_size * 1.5 * i as f32, 0.),
+                colour,
+            );
+        }
+    }
+
+    if let Some(msg) = &state.msg {
+        mesh_helper.draw_white_text(ctx, msg, msg_start, 20., false);
+    }
+
+    Ok(())
+}
+
+//Outlines the piece to move and marks where it lands, highlighted if it's the chosen move
+fn draw_move(
+    ctx: &mut Context,
+    mesh_helper: &mut MeshHelper,
+    cell_size: f32,
+    board_start: Point,
+    player: Player,
+    mov: &Move,
+    highlight: bool,
+) -> GameResult<()> {
+    let move_mesh =
+        mesh_helper.make_circle(ctx, cell_size, cell_size * 0.1, DrawMode::stroke(1.))?;
+    let capture_mesh = make_letter_mesh(ctx, mesh_helper, cell_size * 0.3, 'x')?;
+    let outline = mesh_helper.make_rect(ctx, cell_size, cell_size, DrawMode::stroke(3.))?;
+    let pos = |step: usize| {
+        Point::from(step_coord(player, step))
+            .multiply(cell_size, cell_size)
+            .offset_point(board_start)
+    };
+
+    if highlight {
+        mesh_helper.draw_coloured_mesh(ctx, outline.as_ref(), pos(mov.origin), LIGHT_BLUE);
+    }
+    let (mesh, colour, point) = if mov.capture {
+        (
+            capture_mesh.as_ref(),
+            RED,
+            pos(mov.dest).offset(cell_size * 0.35, cell_size * 0.35),
+        )
+    } else {
+        (move_mesh.as_ref(), WHITE, pos(mov.dest))
+    };
+    mesh_helper.draw_coloured_mesh(
+        ctx,
+        mesh,
+        point,
+        if highlight { LIGHT_BLUE } else { colour },
+    );
+    Ok(())
+}
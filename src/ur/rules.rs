@@ -0,0 +1,184 @@
+use crate::boards::idx_coord::BoardCoord;
+use crate::system::Player;
+use crate::ur::{Board, Move, FINISH, PIECES};
+use rand::Rng;
+
+pub(super) const DICE: usize = 4;
+//Landing on a rosette gives another turn
+pub(super) const ROSETTES: [usize; 3] = [4, 8, 14];
+//Both tracks run along the middle row for these steps, the middle rosette is safe from capture
+pub(super) const SHARED_START: usize = 5;
+pub(super) const SHARED_END: usize = 12;
+pub(super) const SAFE: usize = 8;
+
+// 3  2  1  0  -  -  14 13   computer
+// 5  6  7  8  9  10 11 12   both
+// 3  2  1  0  -  -  14 13   human
+pub(super) const BOARD_COLS: usize = 8;
+pub(super) const BOARD_ROWS: usize = 3;
+
+//Four binary dice, the roll is the number of marked tips showing
+pub(super) fn roll_dice<R: Rng>(rng: &mut R) -> usize {
+    (0..DICE).filter(|_| rng.gen::<bool>()).count()
+}
+
+//Chance of each roll out of 16
+pub(super) const ROLL_WEIGHTS: [usize; DICE + 1] = [1, 4, 6, 4, 1];
+
+fn is_shared(step: usize) -> bool {
+    (SHARED_START..=SHARED_END).contains(&step)
+}
+
+//Row with the player's private squares, pieces waiting and finished are shown in its gap
+pub(super) fn player_row(player: Player) -> usize {
+    match player {
+        Player::Computer => 0,
+        Player::Human => BOARD_ROWS - 1,
+    }
+}
+
+//Square on the board for a step on a player's track, None for pieces off the board
+pub(super) fn track_coord(player: Player, step: usize) -> Option<BoardCoord> {
+    let row = player_row(player);
+    match step {
+        1..=4 => Some(BoardCoord(4 - step, row)),
+        SHARED_START..=SHARED_END => Some(BoardCoord(step - SHARED_START, 1)),
+        13..=14 => Some(BoardCoord(20 - step, row)),
+        _ => None,
+    }
+}
+
+pub(super) fn calc_valid_moves(board: &Board, roll: usize, player: Player) -> Vec<Move> {
+    if roll == 0 {
+        return vec![];
+    }
+    let own = board.track(player);
+    let other = board.track(player.opposite());
+    (0..FINISH)
+        .filter(|origin| own[*origin] > 0)
+        .filter_map(|origin| {
+            let dest = origin + roll;
+            if dest > FINISH || (dest < FINISH && own[dest] > 0) {
+                return None;
+            }
+            let capture = is_shared(dest) && other[dest] > 0;
+            if capture && dest == SAFE {
+                return None;
+            }
+            Some(Move {
+                origin,
+                dest,
+                capture,
+            })
+        })
+        .collect()
+}
+
+//Returns true if the player gets another turn
+pub(super) fn apply_move(board: &mut Board, mov: &Move, player: Player) -> bool {
+    let own = board.track_mut(player);
+    own[mov.origin] -= 1;
+    own[mov.dest] += 1;
+    if mov.capture {
+        let other = board.track_mut(player.opposite());
+        other[mov.dest] -= 1;
+        other[0] += 1;
+    }
+    ROSETTES.contains(&mov.dest)
+}
+
+pub(super) fn winner(board: &Board) -> Option<Player> {
+    [Player::Human, Player::Computer]
+        .iter()
+        .find(|player| board.track(**player)[FINISH] == PIECES)
+        .copied()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_track() {
+        assert_eq!(track_coord(Player::Human, 0), None);
+        assert_eq!(track_coord(Player::Human, 1), Some(BoardCoord(3, 2)));
+        assert_eq!(track_coord(Player::Computer, 4), Some(BoardCoord(0, 0)));
+        assert_eq!(track_coord(Player::Human, 8), Some(BoardCoord(3, 1)));
+        assert_eq!(
+            track_coord(Player::Computer, 8),
+            track_coord(Player::Human, 8)
+        );
+        assert_eq!(track_coord(Player::Human, 13), Some(BoardCoord(7, 2)));
+        assert_eq!(track_coord(Player::Human, 14), Some(BoardCoord(6, 2)));
+        assert_eq!(track_coord(Player::Human, FINISH), None);
+        for step in ROSETTES.iter() {
+            let coord = track_coord(Player::Human, *step).unwrap();
+            assert!(coord.0 == 0 || coord.0 == 3 || coord.0 == 6);
+        }
+    }
+
+    #[test]
+    fn test_moves() {
+        let mut board = Board::new();
+        assert!(calc_valid_moves(&board, 0, Player::Human).is_empty());
+        let moves = calc_valid_moves(&board, 4, Player::Human);
+        assert_eq!(
+            moves,
+            vec![Move {
+                origin: 0,
+                dest: 4,
+                capture: false
+            }]
+        );
+        assert!(apply_move(&mut board, &moves[0], Player::Human));
+
+        //Blocked by own piece but not by the opponent on their private squares
+        board.track_mut(Player::Computer)[4] = 1;
+        board.track_mut(Player::Computer)[0] -= 1;
+        assert!(calc_valid_moves(&board, 4, Player::Human)
+            .iter()
+            .all(|mov| mov.origin != 0));
+        let moves = calc_valid_moves(&board, 4, Player::Computer);
+        assert!(moves.iter().all(|mov| !mov.capture));
+
+        //Captures on the shared row except on the middle rosette
+        board.track_mut(Player::Computer)[6] = 1;
+        board.track_mut(Player::Computer)[0] -= 1;
+        let moves = calc_valid_moves(&board, 2, Player::Human);
+        let capture = moves.iter().find(|mov| mov.origin == 4).unwrap();
+        assert_eq!(capture.dest, 6);
+        assert!(capture.capture);
+        assert!(!apply_move(&mut board, capture, Player::Human));
+        assert_eq!(board.track(Player::Computer)[6], 0);
+        assert_eq!(board.track(Player::Computer)[0], PIECES - 1);
+
+        board.track_mut(Player::Computer)[SAFE] = 1;
+        assert!(calc_valid_moves(&board, 2, Player::Human)
+            .iter()
+            .all(|mov| mov.dest != SAFE));
+    }
+
+    #[test]
+    fn test_bearing_off() {
+        let mut board = Board::new();
+        board.track_mut(Player::Human)[0] = 0;
+        board.track_mut(Player::Human)[13] = 1;
+        board.track_mut(Player::Human)[FINISH] = PIECES - 1;
+        //Needs the exact roll
+        assert!(calc_valid_moves(&board, 3, Player::Human).is_empty());
+        let moves = calc_valid_moves(&board, 2, Player::Human);
+        assert_eq!(moves[0].dest, FINISH);
+        assert_eq!(winner(&board), None);
+        apply_move(&mut board, &moves[0], Player::Human);
+        assert_eq!(winner(&board), Some(Player::Human));
+    }
+
+    #[test]
+    fn test_roll_distribution() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..100 {
+            assert!(roll_dice(&mut rng) <= DICE);
+        }
+        assert_eq!(ROLL_WEIGHTS.iter().sum::<usize>(), 1 << DICE);
+    }
+}